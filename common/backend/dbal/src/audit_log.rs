@@ -0,0 +1,77 @@
+use chrono::Utc;
+use sea_orm::prelude::*;
+use sea_orm::{NotSet, PaginatorTrait, QueryOrder, Set};
+
+use bamboo_common_core::entities::*;
+use bamboo_common_core::error::*;
+
+pub const AUDIT_LOG_PAGE_SIZE: u64 = 50;
+
+/// Entries can only be written and read, there is no way to change or remove them besides deleting the grove.
+pub async fn create_audit_log_entry(
+    grove_id: i32,
+    actor: &User,
+    action: AuditAction,
+    target: Option<&User>,
+    db: &DatabaseConnection,
+) -> BambooErrorResult {
+    audit_log_entry::ActiveModel {
+        id: NotSet,
+        grove_id: Set(grove_id),
+        actor_id: Set(actor.id),
+        actor_name: Set(actor.display_name.clone()),
+        target_id: Set(target.map(|target| target.id)),
+        target_name: Set(target.map(|target| target.display_name.clone())),
+        action: Set(action),
+        created_at: Set(Utc::now().naive_utc()),
+    }
+    .insert(db)
+    .await
+    .map_err(|err| {
+        log::error!("{err}");
+        BambooError::database("audit_log_entry", "Failed to create audit log entry")
+    })
+    .map(|_| ())
+}
+
+/// Pages start at zero, the newest entries come first.
+pub async fn get_audit_log_entries(
+    grove_id: i32,
+    action: Option<AuditAction>,
+    actor_id: Option<i32>,
+    target_id: Option<i32>,
+    page: u64,
+    db: &DatabaseConnection,
+) -> BambooResult<AuditLogPage> {
+    let mut query =
+        audit_log_entry::Entity::find().filter(audit_log_entry::Column::GroveId.eq(grove_id));
+    if let Some(action) = action {
+        query = query.filter(audit_log_entry::Column::Action.eq(action));
+    }
+    if let Some(actor_id) = actor_id {
+        query = query.filter(audit_log_entry::Column::ActorId.eq(actor_id));
+    }
+    if let Some(target_id) = target_id {
+        query = query.filter(audit_log_entry::Column::TargetId.eq(target_id));
+    }
+
+    let paginator = query
+        .order_by_desc(audit_log_entry::Column::CreatedAt)
+        .order_by_desc(audit_log_entry::Column::Id)
+        .paginate(db, AUDIT_LOG_PAGE_SIZE);
+    let counts = paginator.num_items_and_pages().await.map_err(|err| {
+        log::error!("{err}");
+        BambooError::database("audit_log_entry", "Failed to count audit log entries")
+    })?;
+    let entries = paginator.fetch_page(page).await.map_err(|err| {
+        log::error!("{err}");
+        BambooError::database("audit_log_entry", "Failed to load audit log entries")
+    })?;
+
+    Ok(AuditLogPage {
+        entries,
+        page,
+        page_count: counts.number_of_pages,
+        total_count: counts.number_of_items,
+    })
+}
@@ -1,4 +1,7 @@
+use std::str::FromStr;
+
 use base64::Engine;
+use chrono::Utc;
 use rand::distributions::Uniform;
 use rand::Rng;
 use sea_orm::prelude::Expr;
@@ -6,30 +9,102 @@ use sea_orm::ActiveValue::Set;
 use sea_orm::{
     ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, NotSet, QueryFilter,
 };
+use webauthn_rs::prelude::PublicKeyCredential;
+use webauthn_rs::Webauthn;
 
 use bamboo_common_core::entities::*;
 use bamboo_common_core::error::*;
 
 use crate as dbal;
 use crate::user::get_users;
-use crate::{decrypt_string, encrypt_string};
+use crate::{decrypt_secret, encrypt_secret};
+
+const DEFAULT_TWO_FACTOR_CODE_VALID_MINUTES: i64 = 10;
+const DEFAULT_TWO_FACTOR_CODE_MAX_ATTEMPTS: i32 = 5;
+const DEFAULT_TWO_FACTOR_CODE_RESEND_COOLDOWN_SECONDS: i64 = 60;
+
+fn get_env_or<T: FromStr>(key: &str, default: T) -> T {
+    std::env::var(key)
+        .ok()
+        .and_then(|value| value.parse::<T>().ok())
+        .unwrap_or(default)
+}
+
+/// The minutes a two factor code from the mail is valid, configured with `TWO_FACTOR_CODE_VALID_MINUTES`.
+fn get_two_factor_code_valid_minutes() -> i64 {
+    get_env_or(
+        "TWO_FACTOR_CODE_VALID_MINUTES",
+        DEFAULT_TWO_FACTOR_CODE_VALID_MINUTES,
+    )
+}
+
+/// The failed attempts before a two factor code is invalidated, configured with `TWO_FACTOR_CODE_MAX_ATTEMPTS`.
+fn get_two_factor_code_max_attempts() -> i32 {
+    get_env_or(
+        "TWO_FACTOR_CODE_MAX_ATTEMPTS",
+        DEFAULT_TWO_FACTOR_CODE_MAX_ATTEMPTS,
+    )
+}
 
-pub async fn create_google_auth_token(
+/// The seconds before a new two factor code can be requested, configured with `TWO_FACTOR_CODE_RESEND_COOLDOWN_SECONDS`.
+fn get_two_factor_code_resend_cooldown_seconds() -> i64 {
+    get_env_or(
+        "TWO_FACTOR_CODE_RESEND_COOLDOWN_SECONDS",
+        DEFAULT_TWO_FACTOR_CODE_RESEND_COOLDOWN_SECONDS,
+    )
+}
+
+/// Replaces bcrypt hashes and Argon2id hashes with outdated parameters, a failure doesn't fail the login.
+async fn rehash_password_if_needed(user: &User, password: &str, db: &DatabaseConnection) {
+    if !user.password_needs_rehash() {
+        return;
+    }
+
+    let hashed_password = match user::hash_password(password) {
+        Ok(hashed_password) => hashed_password,
+        Err(err) => {
+            log::error!("Failed to rehash the password of user {}: {err}", user.id);
+            return;
+        }
+    };
+
+    if let Err(err) = user::Entity::update_many()
+        .col_expr(user::Column::Password, Expr::value(hashed_password))
+        .filter(user::Column::Id.eq(user.id))
+        .exec(db)
+        .await
+    {
+        log::error!(
+            "Failed to store the rehashed password of user {}: {err}",
+            user.id
+        );
+    }
+}
+
+/// Logs in the configured demo account with its password only. Any other account is rejected,
+/// so the two factor bypass cannot be used for regular users.
+pub async fn validate_demo_auth_and_create_token(
+    demo_email: String,
+    username: String,
     password: String,
     db: &DatabaseConnection,
 ) -> BambooResult<LoginResult> {
-    let user =
-        crate::user::get_user_by_email_or_username("playstore@google.bambushain".to_string(), db)
-            .await
-            .map_err(|err| {
-                log::error!("Failed to load user playstore@google.bambushain: {err}");
-                BambooError::not_found("user", "User not found")
-            })?;
+    let user = crate::user::get_user_by_email_or_username(username.clone(), db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to load user {username}: {err}");
+            BambooError::not_found("user", "User not found")
+        })?;
 
-    if !user.validate_password(password) {
+    if !user.email.eq_ignore_ascii_case(demo_email.as_str()) {
         return Err(BambooError::unauthorized("user", "Invalid login data"));
     }
 
+    if !user.validate_password(password.clone()) {
+        return Err(BambooError::unauthorized("user", "Invalid login data"));
+    }
+    rehash_password_if_needed(&user, password.as_str(), db).await;
+
     token::ActiveModel {
         id: NotSet,
         token: Set(uuid::Uuid::new_v4().to_string()),
@@ -78,18 +153,112 @@ pub async fn validate_auth_and_create_token(
         BambooError::database("token", "Failed to create token")
     });
 
-    let _ = bamboo_common_core::entities::user::Entity::update_many()
-        .col_expr(
-            bamboo_common_core::entities::user::Column::TwoFactorCode,
-            Expr::value::<Option<String>>(None),
-        )
-        .filter(bamboo_common_core::entities::user::Column::Id.eq(user.id))
-        .exec(db)
-        .await;
+    let _ = clear_two_factor_code(user.id, db).await;
 
     result
 }
 
+pub async fn validate_passkey_and_create_token(
+    username: String,
+    challenge_id: String,
+    credential: PublicKeyCredential,
+    webauthn: &Webauthn,
+    db: &DatabaseConnection,
+) -> BambooResult<LoginResult> {
+    let user = crate::user::get_user_by_email_or_username(username.clone(), db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to load user {username}: {err}");
+            BambooError::not_found("user", "User not found")
+        })?;
+
+    validate_passkey_login(user.id, challenge_id, credential, webauthn, db).await?;
+
+    token::ActiveModel {
+        id: NotSet,
+        token: Set(uuid::Uuid::new_v4().to_string()),
+        user_id: Set(user.id),
+    }
+    .insert(db)
+    .await
+    .map(|token| LoginResult {
+        token: token.token,
+        user: user.clone().into(),
+    })
+    .map_err(|err| {
+        log::error!("{err}");
+        BambooError::database("token", "Failed to create token")
+    })
+}
+
+pub async fn validate_external_identity_and_create_token(
+    issuer: String,
+    subject: String,
+    email: Option<String>,
+    email_verified: bool,
+    allowed_groves: Option<Vec<i32>>,
+    db: &DatabaseConnection,
+) -> BambooResult<LoginResult> {
+    let linked_user =
+        dbal::get_user_by_external_identity(issuer.clone(), subject.clone(), db).await?;
+    let is_linked = linked_user.is_some();
+    let user = if let Some(user) = linked_user {
+        user
+    } else {
+        let email = email
+            .filter(|_| email_verified)
+            .ok_or(BambooError::unauthorized(
+                "user",
+                "A verified email is required to link the account",
+            ))?;
+        let user = crate::user::get_user_by_email_or_username(email.clone(), db)
+            .await
+            .map_err(|err| {
+                log::error!("Failed to load user {email}: {err}");
+                BambooError::not_found("user", "User not found")
+            })?;
+        if user.email != email {
+            return Err(BambooError::not_found("user", "User not found"));
+        }
+
+        user
+    };
+
+    dbal::check_grove_allows_login(&user, db).await?;
+    if let Some(allowed_groves) = allowed_groves {
+        let groves = dbal::get_groves_by_user_id(user.id, db).await?;
+        if !groves
+            .iter()
+            .any(|grove| allowed_groves.contains(&grove.id))
+        {
+            return Err(BambooError::unauthorized(
+                "user",
+                "The login is not allowed for this grove",
+            ));
+        }
+    }
+
+    if !is_linked {
+        dbal::link_external_identity(user.id, issuer, subject, db).await?;
+    }
+
+    token::ActiveModel {
+        id: NotSet,
+        token: Set(uuid::Uuid::new_v4().to_string()),
+        user_id: Set(user.id),
+    }
+    .insert(db)
+    .await
+    .map(|token| LoginResult {
+        token: token.token,
+        user: user.clone().into(),
+    })
+    .map_err(|err| {
+        log::error!("{err}");
+        BambooError::database("token", "Failed to create token")
+    })
+}
+
 pub async fn validate_auth_and_set_two_factor_code(
     username: String,
     password: String,
@@ -114,6 +283,18 @@ pub async fn validate_auth_and_set_two_factor_code(
         });
     }
 
+    if let Some(issued_at) = user.two_factor_code_issued_at {
+        let seconds_since_issued = (Utc::now().naive_utc() - issued_at).num_seconds();
+        if user.two_factor_code.is_some()
+            && seconds_since_issued < get_two_factor_code_resend_cooldown_seconds()
+        {
+            return Err(BambooError::too_many_requests(
+                "user",
+                "Please wait before requesting a new two factor code",
+            ));
+        }
+    }
+
     let two_factor_code = rand::thread_rng()
         .sample_iter(&Uniform::new(0, 10))
         .take(6)
@@ -121,13 +302,25 @@ pub async fn validate_auth_and_set_two_factor_code(
         .collect::<Vec<String>>()
         .join("");
 
-    let encrypted_code = encrypt_string(two_factor_code.clone().into_bytes(), password)?;
+    let (encrypted_code, key_id) = encrypt_secret(two_factor_code.clone().into_bytes())?;
 
     bamboo_common_core::entities::user::Entity::update_many()
         .col_expr(
             bamboo_common_core::entities::user::Column::TwoFactorCode,
             Expr::value(base64::prelude::BASE64_STANDARD.encode(encrypted_code)),
         )
+        .col_expr(
+            bamboo_common_core::entities::user::Column::TwoFactorCodeKeyId,
+            Expr::value(key_id),
+        )
+        .col_expr(
+            bamboo_common_core::entities::user::Column::TwoFactorCodeIssuedAt,
+            Expr::value(Utc::now().naive_utc()),
+        )
+        .col_expr(
+            bamboo_common_core::entities::user::Column::TwoFactorCodeAttempts,
+            Expr::value(0),
+        )
         .filter(bamboo_common_core::entities::user::Column::Id.eq(user.id))
         .exec(db)
         .await
@@ -181,11 +374,36 @@ pub async fn validate_login(
         return Err(BambooError::unauthorized("user", "Invalid login data"));
     }
 
-    if initial_validation || user.totp_validated.unwrap_or(false) {
-        validate_totp_token(code, password, user, db).await
+    let result = if initial_validation {
+        validate_totp_token(code, password.clone(), user.clone(), db).await
+    } else if user.totp_validated.unwrap_or(false) {
+        if validate_totp_token(code.clone(), password.clone(), user.clone(), db)
+            .await
+            .is_ok()
+        {
+            Ok(())
+        } else {
+            dbal::use_totp_recovery_code(user.id, code, db).await
+        }
     } else {
-        validate_email_token(code, password, user)
+        validate_email_token(code, password.clone(), user.clone(), db).await
+    };
+
+    if result.is_ok() {
+        rehash_password_if_needed(&user, password.as_str(), db).await;
     }
+
+    result
+}
+
+pub async fn validate_passkey_login(
+    id: i32,
+    challenge_id: String,
+    credential: PublicKeyCredential,
+    webauthn: &Webauthn,
+    db: &DatabaseConnection,
+) -> BambooErrorResult {
+    dbal::finish_passkey_authentication(id, challenge_id, credential, webauthn, db).await
 }
 
 async fn validate_totp_token(
@@ -195,30 +413,40 @@ async fn validate_totp_token(
     db: &DatabaseConnection,
 ) -> BambooErrorResult {
     let totp_secret = if user.totp_secret_encrypted {
-        decrypt_string(user.totp_secret.unwrap(), password.clone())?
+        decrypt_secret(
+            user.totp_secret.unwrap(),
+            user.totp_secret_key_id.clone(),
+            password.clone(),
+        )?
     } else {
-        let decrypted_secret = user.totp_secret.unwrap();
-        let encrypted_secret = encrypt_string(decrypted_secret.clone(), password.clone())?;
+        user.totp_secret.unwrap()
+    };
+
+    // Plain secrets and legacy secrets encrypted with the password move to the server side key once the password is known
+    if !user.totp_secret_encrypted || user.totp_secret_key_id.is_none() {
+        let (encrypted_secret, key_id) = encrypt_secret(totp_secret.clone())?;
 
         user::Entity::update_many()
             .col_expr(user::Column::TotpSecretEncrypted, Expr::value(true))
             .col_expr(user::Column::TotpSecret, Expr::value(encrypted_secret))
+            .col_expr(user::Column::TotpSecretKeyId, Expr::value(key_id))
             .filter(user::Column::Id.eq(user.id))
             .exec(db)
             .await
             .map_err(|_| BambooError::database("user", "Failed to validate"))?;
+    }
 
-        decrypted_secret
-    };
+    check_totp_code(totp_secret, code, user.display_name)
+}
 
+pub(crate) fn check_totp_code(
+    totp_secret: Vec<u8>,
+    code: String,
+    display_name: String,
+) -> BambooErrorResult {
     let is_totp_valid = totp_rs::TOTP::from_rfc6238(
-        totp_rs::Rfc6238::new(
-            6,
-            totp_secret.clone(),
-            Some("Bambushain".to_string()),
-            user.display_name.clone(),
-        )
-        .map_err(|_| BambooError::crypto("user", "Failed to validate"))?,
+        totp_rs::Rfc6238::new(6, totp_secret, Some("Bambushain".to_string()), display_name)
+            .map_err(|_| BambooError::crypto("user", "Failed to validate"))?,
     )
     .map_err(|err| {
         log::error!("Failed to create totp url {err}");
@@ -238,11 +466,60 @@ async fn validate_totp_token(
     }
 }
 
-fn validate_email_token(code: String, password: String, user: User) -> BambooErrorResult {
-    let two_factor_code = String::from_utf8_lossy(&decrypt_string(
+pub(crate) async fn clear_two_factor_code(
+    user_id: i32,
+    db: &DatabaseConnection,
+) -> BambooErrorResult {
+    user::Entity::update_many()
+        .col_expr(
+            user::Column::TwoFactorCode,
+            Expr::value::<Option<String>>(None),
+        )
+        .col_expr(
+            user::Column::TwoFactorCodeIssuedAt,
+            Expr::value::<Option<chrono::NaiveDateTime>>(None),
+        )
+        .col_expr(user::Column::TwoFactorCodeAttempts, Expr::value(0))
+        .col_expr(
+            user::Column::TwoFactorCodeKeyId,
+            Expr::value::<Option<String>>(None),
+        )
+        .filter(user::Column::Id.eq(user_id))
+        .exec(db)
+        .await
+        .map_err(|err| {
+            log::error!("{err}");
+            BambooError::database("user", "Failed to clear two factor code")
+        })
+        .map(|_| ())
+}
+
+async fn validate_email_token(
+    code: String,
+    password: String,
+    user: User,
+    db: &DatabaseConnection,
+) -> BambooErrorResult {
+    let invalid = BambooError::unauthorized("user", "Failed to validate");
+    let (Some(encrypted_code), Some(issued_at)) =
+        (user.two_factor_code.clone(), user.two_factor_code_issued_at)
+    else {
+        return Err(invalid);
+    };
+
+    let max_attempts = get_two_factor_code_max_attempts();
+    let expired =
+        (Utc::now().naive_utc() - issued_at).num_minutes() >= get_two_factor_code_valid_minutes();
+    if expired || user.two_factor_code_attempts >= max_attempts {
+        clear_two_factor_code(user.id, db).await?;
+        return Err(invalid);
+    }
+
+    let two_factor_code = String::from_utf8_lossy(&decrypt_secret(
         base64::prelude::BASE64_STANDARD
-            .decode(user.two_factor_code.unwrap())
-            .map_err(|_| BambooError::unauthorized("user", "Failed to validate"))?,
+            .decode(encrypted_code)
+            .map_err(|_| invalid.clone())?,
+        user.two_factor_code_key_id.clone(),
         password.clone(),
     )?)
     .into_owned();
@@ -250,6 +527,23 @@ fn validate_email_token(code: String, password: String, user: User) -> BambooErr
     if two_factor_code.eq(&code) {
         Ok(())
     } else {
-        Err(BambooError::unauthorized("user", "Failed to validate"))
+        if user.two_factor_code_attempts + 1 >= max_attempts {
+            clear_two_factor_code(user.id, db).await?;
+        } else {
+            user::Entity::update_many()
+                .col_expr(
+                    user::Column::TwoFactorCodeAttempts,
+                    Expr::col(user::Column::TwoFactorCodeAttempts).add(1),
+                )
+                .filter(user::Column::Id.eq(user.id))
+                .exec(db)
+                .await
+                .map_err(|err| {
+                    log::error!("{err}");
+                    BambooError::database("user", "Failed to count two factor attempt")
+                })?;
+        }
+
+        Err(invalid)
     }
 }
@@ -0,0 +1,138 @@
+use base64::Engine;
+use chrono::{Duration, Utc};
+use rand::RngCore;
+use sea_orm::prelude::*;
+use sea_orm::sea_query::Expr;
+use sea_orm::{NotSet, Set};
+use sha2::{Digest, Sha256};
+
+use bamboo_common_core::entities::*;
+use bamboo_common_core::error::*;
+
+use crate as dbal;
+
+const EMAIL_CHANGE_TOKEN_VALID_HOURS: i64 = 24;
+
+fn hash_email_change_token(token: &str) -> String {
+    format!("{:x}", Sha256::digest(token.as_bytes()))
+}
+
+async fn email_exists(id: i32, email: String, db: &DatabaseConnection) -> BambooResult<bool> {
+    user::Entity::find()
+        .filter(user::Column::Id.ne(id))
+        .filter(user::Column::Email.eq(email))
+        .count(db)
+        .await
+        .map(|count| count > 0)
+        .map_err(|err| {
+            log::error!("Failed to load users {err}");
+            BambooError::database("user", "Failed to load users")
+        })
+}
+
+pub async fn check_email_available(
+    user_id: i32,
+    email: String,
+    db: &DatabaseConnection,
+) -> BambooErrorResult {
+    if email_exists(user_id, email, db).await? {
+        Err(BambooError::exists_already(
+            "user",
+            "A user with that email or name exists already",
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// Stores the new email as pending, it only replaces the current email once the returned token is confirmed.
+/// Requesting another change replaces the pending one.
+pub async fn create_email_change_token(
+    user_id: i32,
+    new_email: String,
+    db: &DatabaseConnection,
+) -> BambooResult<String> {
+    check_email_available(user_id, new_email.clone(), db).await?;
+
+    let mut token = [0_u8; 32];
+    rand::thread_rng().fill_bytes(&mut token);
+    let token = base64::prelude::BASE64_URL_SAFE_NO_PAD.encode(token);
+
+    email_change_token::Entity::delete_many()
+        .filter(email_change_token::Column::UserId.eq(user_id))
+        .exec(db)
+        .await
+        .map_err(|err| {
+            log::error!("{err}");
+            BambooError::database("user", "Failed to create email change token")
+        })?;
+
+    email_change_token::ActiveModel {
+        id: NotSet,
+        user_id: Set(user_id),
+        new_email: Set(new_email),
+        token_hash: Set(hash_email_change_token(token.as_str())),
+        expires_at: Set(Utc::now().naive_utc() + Duration::hours(EMAIL_CHANGE_TOKEN_VALID_HOURS)),
+    }
+    .insert(db)
+    .await
+    .map_err(|err| {
+        log::error!("{err}");
+        BambooError::database("user", "Failed to create email change token")
+    })
+    .map(|_| token)
+}
+
+/// Replaces the email of the user with the pending email and returns the user with the new email.
+pub async fn confirm_email_change(token: String, db: &DatabaseConnection) -> BambooResult<User> {
+    let change_token = email_change_token::Entity::find()
+        .filter(email_change_token::Column::TokenHash.eq(hash_email_change_token(token.as_str())))
+        .one(db)
+        .await
+        .map_err(|err| {
+            log::error!("{err}");
+            BambooError::database("user", "Failed to load email change token")
+        })?
+        .ok_or(BambooError::validation(
+            "user",
+            "The email confirmation link is invalid or expired",
+        ))?;
+
+    email_change_token::Entity::delete_by_id(change_token.id)
+        .exec(db)
+        .await
+        .map_err(|err| {
+            log::error!("{err}");
+            BambooError::database("user", "Failed to invalidate email change token")
+        })?;
+
+    if change_token.expires_at < Utc::now().naive_utc() {
+        return Err(BambooError::validation(
+            "user",
+            "The email confirmation link is invalid or expired",
+        ));
+    }
+
+    if email_exists(change_token.user_id, change_token.new_email.clone(), db).await? {
+        return Err(BambooError::exists_already(
+            "user",
+            "A user with that email or name exists already",
+        ));
+    }
+
+    user::Entity::update_many()
+        .col_expr(
+            user::Column::Email,
+            Expr::value(change_token.new_email.clone()),
+        )
+        .filter(user::Column::Id.eq(change_token.user_id))
+        .exec(db)
+        .await
+        .map_err(|err| {
+            log::error!("{err}");
+            BambooError::database("user", "Failed to update user")
+        })?;
+
+    dbal::clear_two_factor_code(change_token.user_id, db).await?;
+    dbal::get_user_by_id_only(change_token.user_id, db).await
+}
@@ -0,0 +1,256 @@
+use base64::Engine;
+use chacha20poly1305::aead::{Aead, OsRng};
+use chacha20poly1305::{AeadCore, ChaCha20Poly1305, Key, KeyInit, Nonce};
+use sea_orm::prelude::*;
+use sea_orm::sea_query::Expr;
+use sea_orm::Condition;
+
+use bamboo_common_core::entities::*;
+use bamboo_common_core::error::*;
+
+use crate::decrypt_string;
+
+struct EncryptionKey {
+    id: String,
+    key: Key,
+}
+
+pub struct EncryptionKeyRotation {
+    pub rotated_secrets: usize,
+    pub legacy_secrets: u64,
+}
+
+fn invalid_key_configuration() -> BambooError {
+    BambooError::crypto(
+        "encryption",
+        "The encryption keys are not configured correctly",
+    )
+}
+
+/// Reads the server side keys from `ENCRYPTION_KEYS`, a comma separated list of `id:base64 encoded 32 byte key`.
+/// Old keys have to stay in the list until `rotate_encryption_key` moved all secrets to the current key.
+fn get_encryption_keys() -> BambooResult<Vec<EncryptionKey>> {
+    let keys = std::env::var("ENCRYPTION_KEYS").unwrap_or_default();
+    let mut encryption_keys = Vec::<EncryptionKey>::new();
+
+    for entry in keys
+        .split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+    {
+        let (id, key) = entry.split_once(':').ok_or_else(|| {
+            log::error!("The encryption key entry {entry} has no id");
+            invalid_key_configuration()
+        })?;
+        let id = id.trim().to_string();
+        let key = base64::prelude::BASE64_STANDARD
+            .decode(key.trim())
+            .ok()
+            .and_then(|key| <[u8; 32]>::try_from(key).ok())
+            .ok_or_else(|| {
+                log::error!("The encryption key {id} must be a base64 encoded 32 byte key");
+                invalid_key_configuration()
+            })?;
+        if id.is_empty() || encryption_keys.iter().any(|existing| existing.id == id) {
+            log::error!("The encryption key id {id} is empty or used twice");
+            return Err(invalid_key_configuration());
+        }
+
+        encryption_keys.push(EncryptionKey {
+            id,
+            key: Key::from(key),
+        });
+    }
+
+    Ok(encryption_keys)
+}
+
+/// The current key is selected with `ENCRYPTION_CURRENT_KEY_ID` and defaults to the last configured key.
+fn get_current_encryption_key() -> BambooResult<Option<EncryptionKey>> {
+    let mut keys = get_encryption_keys()?;
+    match std::env::var("ENCRYPTION_CURRENT_KEY_ID") {
+        Ok(current_key_id) => keys
+            .into_iter()
+            .find(|key| key.id == current_key_id)
+            .map(Some)
+            .ok_or_else(|| {
+                log::error!("The current encryption key {current_key_id} is not configured");
+                invalid_key_configuration()
+            }),
+        Err(_) => Ok(keys.pop()),
+    }
+}
+
+fn get_encryption_key(key_id: &str) -> BambooResult<EncryptionKey> {
+    get_encryption_keys()?
+        .into_iter()
+        .find(|key| key.id == key_id)
+        .ok_or_else(|| {
+            log::error!("The encryption key {key_id} is not configured anymore");
+            BambooError::crypto("encryption", "Failed to decrypt")
+        })
+}
+
+fn encrypt_with_key(plain: &[u8], key: &EncryptionKey) -> BambooResult<Vec<u8>> {
+    let cipher = ChaCha20Poly1305::new(&key.key);
+    let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+
+    let encrypted = cipher
+        .encrypt(&nonce, plain)
+        .map_err(|_| BambooError::crypto("encryption", "Failed to encrypt"))?;
+
+    let mut data = vec![];
+    data.extend_from_slice(&nonce);
+    data.extend(encrypted);
+
+    Ok(data)
+}
+
+fn decrypt_with_key(encrypted: &[u8], key: &EncryptionKey) -> BambooResult<Vec<u8>> {
+    if encrypted.len() < 12 {
+        return Err(BambooError::crypto("encryption", "Failed to decrypt"));
+    }
+
+    let cipher = ChaCha20Poly1305::new(&key.key);
+    let nonce = Nonce::from_slice(&encrypted[..12]);
+
+    cipher
+        .decrypt(nonce, encrypted[12..].as_ref())
+        .map_err(|_| BambooError::crypto("encryption", "Failed to decrypt"))
+}
+
+/// The server refuses to start without a server side key, new secrets are never encrypted with the password anymore.
+pub fn check_encryption_key() -> BambooErrorResult {
+    get_current_encryption_key()?.map(|_| ()).ok_or_else(|| {
+        log::error!("ENCRYPTION_KEYS has to contain at least one key");
+        invalid_key_configuration()
+    })
+}
+
+/// Encrypts with the current server side key and returns its id.
+pub(crate) fn encrypt_secret(plain: Vec<u8>) -> BambooResult<(Vec<u8>, String)> {
+    let key = get_current_encryption_key()?.ok_or_else(invalid_key_configuration)?;
+
+    encrypt_with_key(plain.as_slice(), &key).map(|encrypted| (encrypted, key.id))
+}
+
+/// Decrypts with the server side key of the given id. Secrets without a key id are legacy rows encrypted with a key
+/// derived from the password and the `DATABASE_URL`, they stay on that scheme until the password is known again.
+pub(crate) fn decrypt_secret(
+    encrypted: Vec<u8>,
+    key_id: Option<String>,
+    password: String,
+) -> BambooResult<Vec<u8>> {
+    if let Some(key_id) = key_id {
+        decrypt_with_key(encrypted.as_slice(), &get_encryption_key(key_id.as_str())?)
+    } else {
+        decrypt_string(encrypted, password)
+    }
+}
+
+fn reencrypt_two_factor_code(
+    code: String,
+    key_id: &str,
+    current_key: &EncryptionKey,
+) -> BambooResult<String> {
+    let encrypted = base64::prelude::BASE64_STANDARD
+        .decode(code)
+        .map_err(|_| BambooError::crypto("encryption", "Failed to decrypt"))?;
+    let decrypted = decrypt_with_key(encrypted.as_slice(), &get_encryption_key(key_id)?)?;
+
+    encrypt_with_key(decrypted.as_slice(), current_key)
+        .map(|encrypted| base64::prelude::BASE64_STANDARD.encode(encrypted))
+}
+
+/// Encrypts all secrets stored with an older server side key with the current key. Legacy secrets that are still
+/// encrypted with the password can't be rotated here, they move to the current key when the user logs in or changes
+/// the password. Until then they stay on the old scheme and are only counted.
+pub async fn rotate_encryption_key(db: &DatabaseConnection) -> BambooResult<EncryptionKeyRotation> {
+    let current_key = get_current_encryption_key()?.ok_or_else(|| {
+        BambooError::crypto("encryption", "There is no encryption key configured")
+    })?;
+
+    let users = user::Entity::find()
+        .filter(
+            Condition::any()
+                .add(user::Column::TotpSecretKeyId.ne(current_key.id.clone()))
+                .add(user::Column::TwoFactorCodeKeyId.ne(current_key.id.clone())),
+        )
+        .all(db)
+        .await
+        .map_err(|err| {
+            log::error!("{err}");
+            BambooError::database("user", "Failed to load users")
+        })?;
+
+    let mut rotated_secrets = 0;
+    for user in users {
+        let mut update = user::Entity::update_many().filter(user::Column::Id.eq(user.id));
+
+        if let (Some(secret), Some(key_id)) = (user.totp_secret, user.totp_secret_key_id) {
+            if key_id != current_key.id {
+                let decrypted =
+                    decrypt_with_key(secret.as_slice(), &get_encryption_key(key_id.as_str())?)?;
+                update = update
+                    .col_expr(
+                        user::Column::TotpSecret,
+                        Expr::value(encrypt_with_key(decrypted.as_slice(), &current_key)?),
+                    )
+                    .col_expr(
+                        user::Column::TotpSecretKeyId,
+                        Expr::value(current_key.id.clone()),
+                    );
+                rotated_secrets += 1;
+            }
+        }
+        if let (Some(code), Some(key_id)) = (user.two_factor_code, user.two_factor_code_key_id) {
+            if key_id != current_key.id {
+                update = update
+                    .col_expr(
+                        user::Column::TwoFactorCode,
+                        Expr::value(reencrypt_two_factor_code(
+                            code,
+                            key_id.as_str(),
+                            &current_key,
+                        )?),
+                    )
+                    .col_expr(
+                        user::Column::TwoFactorCodeKeyId,
+                        Expr::value(current_key.id.clone()),
+                    );
+                rotated_secrets += 1;
+            }
+        }
+
+        update.exec(db).await.map_err(|err| {
+            log::error!("{err}");
+            BambooError::database("user", "Failed to rotate the encryption key")
+        })?;
+    }
+
+    let legacy_secrets = user::Entity::find()
+        .filter(
+            Condition::any()
+                .add(
+                    Condition::all()
+                        .add(user::Column::TotpSecretEncrypted.eq(true))
+                        .add(user::Column::TotpSecretKeyId.is_null()),
+                )
+                .add(
+                    Condition::all()
+                        .add(user::Column::TwoFactorCode.is_not_null())
+                        .add(user::Column::TwoFactorCodeKeyId.is_null()),
+                ),
+        )
+        .count(db)
+        .await
+        .map_err(|err| {
+            log::error!("{err}");
+            BambooError::database("user", "Failed to count users")
+        })?;
+
+    Ok(EncryptionKeyRotation {
+        rotated_secrets,
+        legacy_secrets,
+    })
+}
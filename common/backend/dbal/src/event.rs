@@ -81,6 +81,7 @@ pub async fn create_event(
     let mut model = event.clone().into_active_model();
     model.id = NotSet;
     model.grove_id = Set(grove_id);
+    model.created_by_id = Set(Some(user_id));
     if event.is_private {
         model.user_id = Set(Some(user_id));
     }
@@ -126,3 +127,25 @@ pub async fn delete_event(grove_id: i32, id: i32, db: &DatabaseConnection) -> Ba
         })
         .map(|_| ())
 }
+
+/// Deletes the events the user created and their private events, events of other pandas stay.
+pub async fn delete_events_of_user(
+    grove_id: i32,
+    user_id: i32,
+    db: &DatabaseConnection,
+) -> BambooErrorResult {
+    event::Entity::delete_many()
+        .filter(event::Column::GroveId.eq(grove_id))
+        .filter(
+            Condition::any()
+                .add(event::Column::CreatedById.eq(user_id))
+                .add(event::Column::UserId.eq(user_id)),
+        )
+        .exec(db)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to delete events {err}");
+            BambooError::database("event", "Failed to delete events")
+        })
+        .map(|_| ())
+}
@@ -0,0 +1,126 @@
+use chrono::{Duration, Utc};
+use sea_orm::prelude::*;
+use sea_orm::{NotSet, Set};
+
+use bamboo_common_core::entities::*;
+use bamboo_common_core::error::*;
+
+use crate as dbal;
+
+const OIDC_LOGIN_STATE_VALID_MINUTES: i64 = 10;
+
+pub async fn create_oidc_login_state(
+    state: String,
+    nonce: String,
+    pkce_verifier: String,
+    db: &DatabaseConnection,
+) -> BambooErrorResult {
+    let now = Utc::now().naive_utc();
+    oidc_login_state::Entity::delete_many()
+        .filter(oidc_login_state::Column::ExpiresAt.lt(now))
+        .exec(db)
+        .await
+        .map_err(|err| {
+            log::error!("{err}");
+            BambooError::database("oidc", "Failed to clean up login states")
+        })?;
+
+    oidc_login_state::ActiveModel {
+        id: NotSet,
+        state: Set(state),
+        nonce: Set(nonce),
+        pkce_verifier: Set(pkce_verifier),
+        expires_at: Set(now + Duration::minutes(OIDC_LOGIN_STATE_VALID_MINUTES)),
+    }
+    .insert(db)
+    .await
+    .map_err(|err| {
+        log::error!("{err}");
+        BambooError::database("oidc", "Failed to create login state")
+    })
+    .map(|_| ())
+}
+
+pub async fn take_oidc_login_state(
+    state: String,
+    db: &DatabaseConnection,
+) -> BambooResult<OidcLoginState> {
+    let login_state = oidc_login_state::Entity::find()
+        .filter(oidc_login_state::Column::State.eq(state))
+        .one(db)
+        .await
+        .map_err(|err| {
+            log::error!("{err}");
+            BambooError::database("oidc", "Failed to load login state")
+        })?
+        .ok_or(BambooError::unauthorized(
+            "oidc",
+            "The login state is invalid or expired",
+        ))?;
+
+    oidc_login_state::Entity::delete_by_id(login_state.id)
+        .exec(db)
+        .await
+        .map_err(|err| {
+            log::error!("{err}");
+            BambooError::database("oidc", "Failed to invalidate login state")
+        })?;
+
+    if login_state.expires_at < Utc::now().naive_utc() {
+        Err(BambooError::unauthorized(
+            "oidc",
+            "The login state is invalid or expired",
+        ))
+    } else {
+        Ok(login_state)
+    }
+}
+
+pub(crate) async fn get_user_by_external_identity(
+    issuer: String,
+    subject: String,
+    db: &DatabaseConnection,
+) -> BambooResult<Option<User>> {
+    user::Entity::find()
+        .filter(external_identity::Column::Issuer.eq(issuer))
+        .filter(external_identity::Column::Subject.eq(subject))
+        .inner_join(external_identity::Entity)
+        .one(db)
+        .await
+        .map_err(|err| {
+            log::error!("{err}");
+            BambooError::database("oidc", "Failed to load external identity")
+        })
+}
+
+pub(crate) async fn link_external_identity(
+    user_id: i32,
+    issuer: String,
+    subject: String,
+    db: &DatabaseConnection,
+) -> BambooErrorResult {
+    external_identity::ActiveModel {
+        id: NotSet,
+        user_id: Set(user_id),
+        issuer: Set(issuer),
+        subject: Set(subject),
+    }
+    .insert(db)
+    .await
+    .map_err(|err| {
+        log::error!("{err}");
+        BambooError::database("oidc", "Failed to link external identity")
+    })
+    .map(|_| ())
+}
+
+pub(crate) async fn check_grove_allows_login(
+    user: &User,
+    db: &DatabaseConnection,
+) -> BambooErrorResult {
+    if dbal::has_usable_grove(user.id, db).await? {
+        Ok(())
+    } else {
+        Err(BambooError::unauthorized("user", "Invalid login data"))
+    }
+}
@@ -1,3 +1,4 @@
+use chrono::{Days, NaiveDateTime, Utc};
 use sea_orm::prelude::*;
 use sea_orm::{IntoActiveModel, NotSet, QueryOrder};
 
@@ -86,6 +87,19 @@ pub async fn migrate_between_groves(
     new_grove_id: i32,
     db: &DatabaseConnection,
 ) -> BambooErrorResult {
+    let users_without_grove = if old_grove_id.is_none() {
+        user::Entity::find()
+            .filter(user::Column::GroveId.is_null())
+            .all(db)
+            .await
+            .map_err(|err| {
+                log::error!("Failed to load users without grove {err}");
+                BambooError::database("grove", "Failed to create grove")
+            })?
+    } else {
+        vec![]
+    };
+
     if let Some(id) = old_grove_id {
         user::Entity::update_many().filter(user::Column::GroveId.eq(id))
     } else {
@@ -102,6 +116,23 @@ pub async fn migrate_between_groves(
     })
     .map(|_| ())?;
 
+    if let Some(id) = old_grove_id {
+        grove_membership::Entity::update_many()
+            .col_expr(grove_membership::Column::GroveId, Expr::value(new_grove_id))
+            .filter(grove_membership::Column::GroveId.eq(id))
+            .exec(db)
+            .await
+            .map_err(|err| {
+                log::error!(
+                    "Failed to migrate memberships from grove {id} to {new_grove_id} grove {err}"
+                );
+                BambooError::database("grove", "Failed to create grove")
+            })?;
+    }
+    for user in users_without_grove {
+        crate::create_grove_membership(new_grove_id, user.id, false, None, db).await?;
+    }
+
     if let Some(id) = old_grove_id {
         event::Entity::update_many().filter(event::Column::GroveId.eq(id))
     } else {
@@ -155,7 +186,75 @@ pub async fn enable_grove(id: i32, db: &DatabaseConnection) -> BambooErrorResult
         .map(|_| ())
 }
 
-pub async fn delete_grove(id: i32, db: &DatabaseConnection) -> BambooErrorResult {
+/// Locks the grove right away, the grove stays restorable until the purge job removes it after the grace period.
+pub async fn schedule_grove_deletion(
+    id: i32,
+    grace_period_days: u64,
+    db: &DatabaseConnection,
+) -> BambooResult<Grove> {
+    let now = Utc::now().naive_utc();
+    let purge_at = now + Days::new(grace_period_days);
+
+    let result = grove::Entity::update_many()
+        .filter(grove::Column::Id.eq(id))
+        .filter(grove::Column::DeletedAt.is_null())
+        .col_expr(grove::Column::DeletedAt, Expr::value(now))
+        .col_expr(grove::Column::PurgeAt, Expr::value(purge_at))
+        .exec(db)
+        .await
+        .map_err(|err| {
+            log::error!("{err}");
+            BambooError::database("grove", "Failed to delete grove")
+        })?;
+    if result.rows_affected == 0 {
+        return Err(BambooError::exists_already(
+            "grove",
+            "The grove is already scheduled for deletion",
+        ));
+    }
+
+    get_grove_by_id(id, db).await
+}
+
+pub async fn restore_grove(id: i32, db: &DatabaseConnection) -> BambooErrorResult {
+    let result = grove::Entity::update_many()
+        .filter(grove::Column::Id.eq(id))
+        .filter(grove::Column::DeletedAt.is_not_null())
+        .col_expr(grove::Column::DeletedAt, Expr::value(None::<NaiveDateTime>))
+        .col_expr(grove::Column::PurgeAt, Expr::value(None::<NaiveDateTime>))
+        .exec(db)
+        .await
+        .map_err(|err| {
+            log::error!("{err}");
+            BambooError::database("grove", "Failed to restore grove")
+        })?;
+
+    if result.rows_affected == 0 {
+        Err(BambooError::not_found(
+            "grove",
+            "The grove is not scheduled for deletion",
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+pub async fn get_groves_due_for_purge(db: &DatabaseConnection) -> BambooResult<Vec<Grove>> {
+    grove::Entity::find()
+        .filter(grove::Column::PurgeAt.lte(Utc::now().naive_utc()))
+        .order_by_asc(grove::Column::Id)
+        .all(db)
+        .await
+        .map_err(|err| {
+            log::error!("{err}");
+            BambooError::database("grove", "Failed to load groves")
+        })
+}
+
+/// Removes the grove and all its data for good, only the purge job should call this.
+pub async fn purge_grove(id: i32, db: &DatabaseConnection) -> BambooErrorResult {
+    crate::move_default_grove_away(id, db).await?;
+
     grove::Entity::delete_by_id(id)
         .exec(db)
         .await
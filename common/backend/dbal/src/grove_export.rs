@@ -0,0 +1,324 @@
+use std::collections::BTreeMap;
+
+use chrono::{Duration, Utc};
+use sea_orm::prelude::*;
+use sea_orm::{NotSet, QueryOrder, Set};
+
+use bamboo_common_core::entities::*;
+use bamboo_common_core::entities::{
+    character, character_housing, crafter, custom_character_field, custom_character_field_option,
+    custom_character_field_value, fighter, free_company, grove_export,
+};
+use bamboo_common_core::error::*;
+
+use crate::grove::get_grove_by_id;
+use crate::grove_role::get_grove_roles;
+use crate::grove_settings::get_grove_settings;
+use crate::user::get_users;
+
+/// Exports run in the background of a single instance, an export that is still running after this gets lost in a restart.
+const EXPORT_TIMEOUT_MINUTES: i64 = 60;
+
+/// Marks exports that didn't finish in time as failed, otherwise they would block new exports forever.
+async fn fail_stale_grove_exports(grove_id: i32, db: &DatabaseConnection) -> BambooErrorResult {
+    let now = Utc::now().naive_utc();
+    grove_export::Entity::update_many()
+        .set(grove_export::ActiveModel {
+            status: Set(GroveExportStatus::Failed),
+            finished_at: Set(Some(now)),
+            ..Default::default()
+        })
+        .filter(grove_export::Column::GroveId.eq(grove_id))
+        .filter(grove_export::Column::Status.eq(GroveExportStatus::Running))
+        .filter(grove_export::Column::CreatedAt.lt(now - Duration::minutes(EXPORT_TIMEOUT_MINUTES)))
+        .exec(db)
+        .await
+        .map_err(|err| {
+            log::error!("{err}");
+            BambooError::database("grove_export", "Failed to update exports")
+        })
+        .map(|_| ())
+}
+
+pub async fn get_grove_exports(
+    grove_id: i32,
+    db: &DatabaseConnection,
+) -> BambooResult<Vec<GroveExport>> {
+    fail_stale_grove_exports(grove_id, db).await?;
+
+    grove_export::Entity::find()
+        .filter(grove_export::Column::GroveId.eq(grove_id))
+        .order_by_desc(grove_export::Column::CreatedAt)
+        .all(db)
+        .await
+        .map_err(|err| {
+            log::error!("{err}");
+            BambooError::database("grove_export", "Failed to load exports")
+        })
+}
+
+pub async fn get_grove_export(
+    grove_id: i32,
+    id: i32,
+    db: &DatabaseConnection,
+) -> BambooResult<GroveExport> {
+    grove_export::Entity::find_by_id(id)
+        .filter(grove_export::Column::GroveId.eq(grove_id))
+        .one(db)
+        .await
+        .map_err(|err| {
+            log::error!("{err}");
+            BambooError::database("grove_export", "Failed to load export")
+        })?
+        .ok_or(BambooError::not_found(
+            "grove_export",
+            "The export was not found",
+        ))
+}
+
+pub async fn create_grove_export(
+    grove_id: i32,
+    requested_by: &User,
+    db: &DatabaseConnection,
+) -> BambooResult<GroveExport> {
+    fail_stale_grove_exports(grove_id, db).await?;
+
+    let running = grove_export::Entity::find()
+        .filter(grove_export::Column::GroveId.eq(grove_id))
+        .filter(grove_export::Column::Status.eq(GroveExportStatus::Running))
+        .count(db)
+        .await
+        .map_err(|err| {
+            log::error!("{err}");
+            BambooError::database("grove_export", "Failed to load exports")
+        })?;
+    if running > 0 {
+        return Err(BambooError::exists_already(
+            "grove_export",
+            "An export is already running",
+        ));
+    }
+
+    grove_export::ActiveModel {
+        id: NotSet,
+        grove_id: Set(grove_id),
+        requested_by_name: Set(requested_by.display_name.clone()),
+        status: Set(GroveExportStatus::Running),
+        created_at: Set(Utc::now().naive_utc()),
+        finished_at: Set(None),
+    }
+    .insert(db)
+    .await
+    .map_err(|err| {
+        log::error!("{err}");
+        BambooError::database("grove_export", "Failed to create export")
+    })
+}
+
+/// Only running exports get finished, an export that was marked as failed because it took too long stays failed.
+pub async fn finish_grove_export(
+    id: i32,
+    status: GroveExportStatus,
+    db: &DatabaseConnection,
+) -> BambooErrorResult {
+    let result = grove_export::Entity::update_many()
+        .set(grove_export::ActiveModel {
+            status: Set(status),
+            finished_at: Set(Some(Utc::now().naive_utc())),
+            ..Default::default()
+        })
+        .filter(grove_export::Column::Id.eq(id))
+        .filter(grove_export::Column::Status.eq(GroveExportStatus::Running))
+        .exec(db)
+        .await
+        .map_err(|err| {
+            log::error!("{err}");
+            BambooError::database("grove_export", "Failed to update export")
+        })?;
+
+    if result.rows_affected == 0 {
+        Err(BambooError::not_found(
+            "grove_export",
+            "The export is no longer running",
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+fn map_database_error(entity: &'static str) -> impl Fn(DbErr) -> BambooError {
+    move |err| {
+        log::error!("{err}");
+        BambooError::database(entity, "Failed to load grove data")
+    }
+}
+
+/// Collects all data of the grove, the profile pictures are added by the caller since they live in the object storage.
+/// Private events only belong to the panda that created them, so they are not part of the export.
+pub async fn get_grove_export_archive(
+    grove_id: i32,
+    db: &DatabaseConnection,
+) -> BambooResult<GroveExportArchive> {
+    let grove = get_grove_by_id(grove_id, db).await?;
+    let settings = get_grove_settings(grove_id, db).await?;
+    let roles = get_grove_roles(grove_id, db).await?;
+    let users = get_users(grove_id, db).await?;
+    let user_ids = users.iter().map(|user| user.id).collect::<Vec<i32>>();
+
+    let characters = character::Entity::find()
+        .filter(character::Column::UserId.is_in(user_ids.clone()))
+        .order_by_asc(character::Column::Id)
+        .all(db)
+        .await
+        .map_err(map_database_error("character"))?;
+    let character_ids = characters
+        .iter()
+        .map(|character| character.id)
+        .collect::<Vec<i32>>();
+
+    let mut fighters = BTreeMap::<i32, Vec<Fighter>>::new();
+    for fighter in fighter::Entity::find()
+        .filter(fighter::Column::CharacterId.is_in(character_ids.clone()))
+        .order_by_asc(fighter::Column::Id)
+        .all(db)
+        .await
+        .map_err(map_database_error("fighter"))?
+    {
+        fighters
+            .entry(fighter.character_id)
+            .or_default()
+            .push(fighter);
+    }
+    let mut crafters = BTreeMap::<i32, Vec<Crafter>>::new();
+    for crafter in crafter::Entity::find()
+        .filter(crafter::Column::CharacterId.is_in(character_ids.clone()))
+        .order_by_asc(crafter::Column::Id)
+        .all(db)
+        .await
+        .map_err(map_database_error("crafter"))?
+    {
+        crafters
+            .entry(crafter.character_id)
+            .or_default()
+            .push(crafter);
+    }
+    let mut housings = BTreeMap::<i32, Vec<CharacterHousing>>::new();
+    for housing in character_housing::Entity::find()
+        .filter(character_housing::Column::CharacterId.is_in(character_ids.clone()))
+        .order_by_asc(character_housing::Column::Id)
+        .all(db)
+        .await
+        .map_err(map_database_error("character_housing"))?
+    {
+        housings
+            .entry(housing.character_id)
+            .or_default()
+            .push(housing);
+    }
+    let mut custom_field_values = BTreeMap::<i32, Vec<i32>>::new();
+    for value in custom_character_field_value::Entity::find()
+        .filter(custom_character_field_value::Column::CharacterId.is_in(character_ids))
+        .order_by_asc(custom_character_field_value::Column::Id)
+        .all(db)
+        .await
+        .map_err(map_database_error("custom_character_field_value"))?
+    {
+        custom_field_values
+            .entry(value.character_id)
+            .or_default()
+            .push(value.custom_character_field_option_id);
+    }
+
+    let free_companies = free_company::Entity::find()
+        .filter(free_company::Column::UserId.is_in(user_ids.clone()))
+        .order_by_asc(free_company::Column::Id)
+        .all(db)
+        .await
+        .map_err(map_database_error("free_company"))?;
+
+    let custom_fields = custom_character_field::Entity::find()
+        .filter(custom_character_field::Column::UserId.is_in(user_ids))
+        .order_by_asc(custom_character_field::Column::Position)
+        .all(db)
+        .await
+        .map_err(map_database_error("custom_character_field"))?;
+    let mut custom_field_options = BTreeMap::<i32, Vec<CustomCharacterFieldOption>>::new();
+    for option in custom_character_field_option::Entity::find()
+        .filter(
+            custom_character_field_option::Column::CustomCharacterFieldId
+                .is_in(custom_fields.iter().map(|field| field.id)),
+        )
+        .order_by_asc(custom_character_field_option::Column::Id)
+        .all(db)
+        .await
+        .map_err(map_database_error("custom_character_field_option"))?
+    {
+        custom_field_options
+            .entry(option.custom_character_field_id)
+            .or_default()
+            .push(option);
+    }
+
+    let events = event::Entity::find()
+        .filter(event::Column::GroveId.eq(grove_id))
+        .filter(event::Column::UserId.is_null())
+        .order_by_asc(event::Column::StartDate)
+        .all(db)
+        .await
+        .map_err(map_database_error("event"))?
+        .into_iter()
+        .map(|event| ExportedEvent {
+            user_id: event.user_id,
+            created_by_id: event.created_by_id,
+            event,
+        })
+        .collect();
+
+    let users = users
+        .into_iter()
+        .map(|user| ExportedUser {
+            characters: characters
+                .iter()
+                .filter(|character| character.user_id == user.id)
+                .map(|character| ExportedCharacter {
+                    id: character.id,
+                    race: character.race,
+                    name: character.name.clone(),
+                    world: character.world.clone(),
+                    free_company_id: character.free_company_id,
+                    fighters: fighters.remove(&character.id).unwrap_or_default(),
+                    crafters: crafters.remove(&character.id).unwrap_or_default(),
+                    housings: housings.remove(&character.id).unwrap_or_default(),
+                    custom_field_option_ids: custom_field_values
+                        .remove(&character.id)
+                        .unwrap_or_default(),
+                })
+                .collect(),
+            free_companies: free_companies
+                .iter()
+                .filter(|free_company| free_company.user_id == user.id)
+                .cloned()
+                .collect(),
+            custom_fields: custom_fields
+                .iter()
+                .filter(|field| field.user_id == user.id)
+                .map(|field| CustomCharacterField {
+                    options: custom_field_options.remove(&field.id).unwrap_or_default(),
+                    ..field.clone()
+                })
+                .collect(),
+            profile_picture: None,
+            user: user.into(),
+        })
+        .collect();
+
+    Ok(GroveExportArchive {
+        version: GROVE_EXPORT_VERSION,
+        exported_at: Utc::now().naive_utc(),
+        grove_name: grove.name,
+        settings,
+        roles,
+        users,
+        events,
+    })
+}
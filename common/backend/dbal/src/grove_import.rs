@@ -0,0 +1,317 @@
+use std::collections::BTreeMap;
+
+use base64::Engine;
+use rand::RngCore;
+use sea_orm::prelude::*;
+use sea_orm::{ConnectionTrait, IntoActiveModel, NotSet, Set};
+
+use bamboo_common_core::entities::*;
+use bamboo_common_core::entities::{
+    character, custom_character_field, custom_character_field_option, custom_character_field_value,
+    grove_role, grove_settings,
+};
+use bamboo_common_core::error::*;
+
+use crate as dbal;
+
+fn map_database_error(entity: &'static str) -> impl Fn(DbErr) -> BambooError {
+    move |err| {
+        log::error!("{err}");
+        BambooError::database(entity, "Failed to import grove data")
+    }
+}
+
+/// Nobody knows the password of imported accounts, the pandas set their own with the link in their invitation mail.
+fn get_unknown_password() -> String {
+    let mut password = [0_u8; 32];
+    rand::thread_rng().fill_bytes(&mut password);
+
+    base64::prelude::BASE64_STANDARD.encode(password)
+}
+
+/// The display name has to be unique across all groves, so taken names get a number appended.
+async fn get_free_display_name(
+    display_name: String,
+    db: &impl ConnectionTrait,
+) -> BambooResult<String> {
+    let mut candidate = display_name.clone();
+    let mut counter = 2;
+    while dbal::display_name_exists(candidate.clone(), db).await? {
+        candidate = format!("{display_name} ({counter})");
+        counter += 1;
+    }
+
+    Ok(candidate)
+}
+
+async fn import_user(
+    grove_id: i32,
+    exported_user: &ExportedUser,
+    role_id: Option<i32>,
+    db: &impl ConnectionTrait,
+) -> BambooResult<ImportedUser> {
+    let exported = &exported_user.user;
+    let existing_user = user::Entity::find()
+        .filter(user::Column::Email.eq(exported.email.clone()))
+        .one(db)
+        .await
+        .map_err(map_database_error("user"))?;
+
+    let imported_user = if let Some(existing_user) = existing_user {
+        ImportedUser {
+            id: existing_user.id,
+            exported_id: exported.id,
+            display_name: existing_user.display_name,
+            email: existing_user.email,
+            has_existing_account: true,
+            was_renamed: false,
+        }
+    } else {
+        let display_name = get_free_display_name(exported.display_name.clone(), db).await?;
+        let mut model = User::new(
+            exported.email.clone(),
+            display_name.clone(),
+            exported.discord_name.clone(),
+            exported.is_mod,
+        )
+        .into_active_model();
+        model.id = NotSet;
+        model.grove_id = Set(grove_id);
+        model.set_password(&get_unknown_password()).map_err(|err| {
+            log::error!("{err}");
+            BambooError::database("user", "Failed to hash password user")
+        })?;
+        let user = model.insert(db).await.map_err(map_database_error("user"))?;
+
+        ImportedUser {
+            id: user.id,
+            exported_id: exported.id,
+            was_renamed: display_name != exported.display_name,
+            display_name,
+            email: user.email,
+            has_existing_account: false,
+        }
+    };
+
+    dbal::create_grove_membership(grove_id, imported_user.id, exported.is_mod, role_id, db).await?;
+
+    Ok(imported_user)
+}
+
+/// Characters, free companies and custom fields belong to the account, so they are only imported for new accounts.
+async fn import_user_data(
+    user_id: i32,
+    exported_user: &ExportedUser,
+    db: &impl ConnectionTrait,
+) -> BambooErrorResult {
+    let mut free_company_ids = BTreeMap::<i32, i32>::new();
+    for exported_free_company in exported_user.free_companies.iter() {
+        let mut model = exported_free_company.clone().into_active_model();
+        model.id = NotSet;
+        model.user_id = Set(user_id);
+        let free_company = model
+            .insert(db)
+            .await
+            .map_err(map_database_error("free_company"))?;
+        free_company_ids.insert(exported_free_company.id, free_company.id);
+    }
+
+    let mut custom_field_option_ids = BTreeMap::<i32, (i32, i32)>::new();
+    for exported_field in exported_user.custom_fields.iter() {
+        let field = custom_character_field::ActiveModel {
+            id: NotSet,
+            label: Set(exported_field.label.clone()),
+            user_id: Set(user_id),
+            position: Set(exported_field.position),
+        }
+        .insert(db)
+        .await
+        .map_err(map_database_error("custom_character_field"))?;
+
+        for exported_option in exported_field.options.iter() {
+            let option = custom_character_field_option::ActiveModel {
+                id: NotSet,
+                custom_character_field_id: Set(field.id),
+                label: Set(exported_option.label.clone()),
+            }
+            .insert(db)
+            .await
+            .map_err(map_database_error("custom_character_field_option"))?;
+            custom_field_option_ids.insert(exported_option.id, (field.id, option.id));
+        }
+    }
+
+    for exported_character in exported_user.characters.iter() {
+        let character = character::ActiveModel {
+            id: NotSet,
+            race: Set(exported_character.race),
+            name: Set(exported_character.name.clone()),
+            world: Set(exported_character.world.clone()),
+            user_id: Set(user_id),
+            free_company_id: Set(exported_character
+                .free_company_id
+                .and_then(|id| free_company_ids.get(&id).copied())),
+        }
+        .insert(db)
+        .await
+        .map_err(map_database_error("character"))?;
+
+        for exported_fighter in exported_character.fighters.iter() {
+            let mut model = exported_fighter.clone().into_active_model();
+            model.id = NotSet;
+            model.character_id = Set(character.id);
+            model
+                .insert(db)
+                .await
+                .map_err(map_database_error("fighter"))?;
+        }
+        for exported_crafter in exported_character.crafters.iter() {
+            let mut model = exported_crafter.clone().into_active_model();
+            model.id = NotSet;
+            model.character_id = Set(character.id);
+            model
+                .insert(db)
+                .await
+                .map_err(map_database_error("crafter"))?;
+        }
+        for exported_housing in exported_character.housings.iter() {
+            let mut model = exported_housing.clone().into_active_model();
+            model.id = NotSet;
+            model.character_id = Set(character.id);
+            model
+                .insert(db)
+                .await
+                .map_err(map_database_error("character_housing"))?;
+        }
+
+        let values = exported_character
+            .custom_field_option_ids
+            .iter()
+            .filter_map(|id| custom_field_option_ids.get(id))
+            .map(
+                |(field_id, option_id)| custom_character_field_value::ActiveModel {
+                    id: NotSet,
+                    character_id: Set(character.id),
+                    custom_character_field_id: Set(*field_id),
+                    custom_character_field_option_id: Set(*option_id),
+                },
+            )
+            .collect::<Vec<custom_character_field_value::ActiveModel>>();
+        if !values.is_empty() {
+            custom_character_field_value::Entity::insert_many(values)
+                .exec(db)
+                .await
+                .map_err(map_database_error("custom_character_field_value"))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Recreates the grove from the archive in one transaction, the ids of the archive are remapped to the new entries.
+/// Pandas that already have an account on this instance join the grove with it, new pandas get an account without a known password.
+pub async fn import_grove(
+    archive: GroveExportArchive,
+    db: &DatabaseConnection,
+) -> BambooResult<GroveImport> {
+    if archive.version != GROVE_EXPORT_VERSION {
+        return Err(BambooError::validation(
+            "grove_import",
+            format!(
+                "The archive has version {}, only version {GROVE_EXPORT_VERSION} is supported",
+                archive.version
+            ),
+        ));
+    }
+    let grove_name = archive.grove_name.trim().to_string();
+    if grove_name.is_empty() {
+        return Err(BambooError::validation(
+            "grove_import",
+            "The grove name must not be empty",
+        ));
+    }
+    dbal::validate_grove_settings(&archive.settings)?;
+
+    let txn = dbal::begin_transaction(db).await?;
+    if grove::Entity::find()
+        .filter(grove::Column::Name.eq(grove_name.clone()))
+        .count(&txn)
+        .await
+        .map_err(map_database_error("grove"))?
+        > 0
+    {
+        return Err(BambooError::exists_already(
+            "grove",
+            "A grove with that name exists already",
+        ));
+    }
+
+    let mut grove = Grove::new(grove_name, false, true).into_active_model();
+    grove.id = NotSet;
+    let grove = grove
+        .insert(&txn)
+        .await
+        .map_err(map_database_error("grove"))?;
+
+    let mut settings = GroveSettings {
+        grove_id: grove.id,
+        has_logo: false,
+        ..archive.settings
+    }
+    .into_active_model();
+    settings.id = NotSet;
+    grove_settings::Entity::insert(settings)
+        .exec(&txn)
+        .await
+        .map_err(map_database_error("grove_settings"))?;
+
+    let mut role_ids = BTreeMap::<i32, i32>::new();
+    for exported_role in archive.roles.iter() {
+        let role = grove_role::ActiveModel {
+            id: NotSet,
+            grove_id: Set(grove.id),
+            name: Set(exported_role.name.clone()),
+            permissions: Set(exported_role.permissions.clone()),
+        }
+        .insert(&txn)
+        .await
+        .map_err(map_database_error("grove_role"))?;
+        role_ids.insert(exported_role.id, role.id);
+    }
+
+    let mut users = vec![];
+    let mut user_ids = BTreeMap::<i32, i32>::new();
+    for exported_user in archive.users.iter() {
+        let role_id = exported_user
+            .user
+            .role_id
+            .and_then(|id| role_ids.get(&id).copied());
+        let imported_user = import_user(grove.id, exported_user, role_id, &txn).await?;
+        if !imported_user.has_existing_account {
+            import_user_data(imported_user.id, exported_user, &txn).await?;
+        }
+
+        user_ids.insert(exported_user.user.id, imported_user.id);
+        users.push(imported_user);
+    }
+
+    for exported_event in archive.events.into_iter() {
+        let mut event = exported_event.event.into_active_model();
+        event.id = NotSet;
+        event.grove_id = Set(grove.id);
+        event.user_id = Set(exported_event
+            .user_id
+            .and_then(|id| user_ids.get(&id).copied()));
+        event.created_by_id = Set(exported_event
+            .created_by_id
+            .and_then(|id| user_ids.get(&id).copied()));
+        event
+            .insert(&txn)
+            .await
+            .map_err(map_database_error("event"))?;
+    }
+
+    dbal::commit_transaction(txn).await?;
+
+    Ok(GroveImport { grove, users })
+}
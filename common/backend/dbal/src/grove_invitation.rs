@@ -0,0 +1,293 @@
+use base64::Engine;
+use chrono::{Duration, Utc};
+use rand::RngCore;
+use sea_orm::prelude::*;
+use sea_orm::sea_query::Expr;
+use sea_orm::{Condition, NotSet, QueryOrder, Set};
+use sha2::{Digest, Sha256};
+
+use bamboo_common_core::entities::*;
+use bamboo_common_core::error::*;
+
+use crate as dbal;
+
+const EXISTING_USER_INVITATION_DAYS: i64 = 7;
+
+fn hash_invitation_token(token: &str) -> String {
+    format!("{:x}", Sha256::digest(token.as_bytes()))
+}
+
+fn invalid_invitation() -> BambooError {
+    BambooError::not_found("grove_invitation", "The invitation is invalid or expired")
+}
+
+pub async fn get_grove_invitations(
+    grove_id: i32,
+    db: &DatabaseConnection,
+) -> BambooResult<Vec<GroveInvitation>> {
+    grove_invitation::Entity::find()
+        .filter(grove_invitation::Column::GroveId.eq(grove_id))
+        .order_by_desc(grove_invitation::Column::CreatedAt)
+        .all(db)
+        .await
+        .map_err(|err| {
+            log::error!("{err}");
+            BambooError::database("grove_invitation", "Failed to load invitations")
+        })
+}
+
+/// Creates the invitation and returns it together with the plain token, only the hash of the token is stored.
+pub async fn create_grove_invitation(
+    grove_id: i32,
+    data: CreateGroveInvitation,
+    db: &DatabaseConnection,
+) -> BambooResult<(GroveInvitation, String)> {
+    let now = Utc::now().naive_utc();
+    if data.expires_at <= now {
+        return Err(BambooError::validation(
+            "grove_invitation",
+            "The expiry date must be in the future",
+        ));
+    }
+    if data.max_uses.is_some_and(|max_uses| max_uses < 1) {
+        return Err(BambooError::validation(
+            "grove_invitation",
+            "The invitation must be usable at least once",
+        ));
+    }
+
+    let email = data
+        .email
+        .map(|email| email.trim().to_string())
+        .filter(|email| !email.is_empty());
+
+    let mut token = [0_u8; 32];
+    rand::thread_rng().fill_bytes(&mut token);
+    let token = base64::prelude::BASE64_URL_SAFE_NO_PAD.encode(token);
+
+    grove_invitation::ActiveModel {
+        id: NotSet,
+        grove_id: Set(grove_id),
+        token_hash: Set(hash_invitation_token(token.as_str())),
+        email: Set(email.clone()),
+        max_uses: Set(if email.is_some() {
+            Some(1)
+        } else {
+            data.max_uses
+        }),
+        use_count: Set(0),
+        created_at: Set(now),
+        expires_at: Set(data.expires_at),
+    }
+    .insert(db)
+    .await
+    .map_err(|err| {
+        log::error!("{err}");
+        BambooError::database("grove_invitation", "Failed to create invitation")
+    })
+    .map(|invitation| (invitation, token))
+}
+
+pub async fn delete_grove_invitation(
+    grove_id: i32,
+    id: i32,
+    db: &DatabaseConnection,
+) -> BambooErrorResult {
+    grove_invitation::Entity::delete_many()
+        .filter(grove_invitation::Column::GroveId.eq(grove_id))
+        .filter(grove_invitation::Column::Id.eq(id))
+        .exec(db)
+        .await
+        .map_err(|err| {
+            log::error!("{err}");
+            BambooError::database("grove_invitation", "Failed to delete invitation")
+        })
+        .map(|_| ())
+}
+
+/// Existing accounts are never added to a grove directly, they get a single use invitation for their email and join
+/// once they accept it with their password. Returns `None` if there is no account with the email.
+pub async fn invite_existing_user(
+    grove_id: i32,
+    email: String,
+    db: &DatabaseConnection,
+) -> BambooResult<Option<(User, String)>> {
+    let Some(user) = user::Entity::find()
+        .filter(user::Column::Email.eq(email.trim()))
+        .one(db)
+        .await
+        .map_err(|err| {
+            log::error!("{err}");
+            BambooError::database("user", "Failed to load user")
+        })?
+    else {
+        return Ok(None);
+    };
+
+    if dbal::get_grove_membership(grove_id, user.id, db)
+        .await
+        .is_ok()
+    {
+        return Err(BambooError::exists_already(
+            "user",
+            "The user is already a member of the grove",
+        ));
+    }
+
+    let (_, token) = create_grove_invitation(
+        grove_id,
+        CreateGroveInvitation {
+            email: Some(user.email.clone()),
+            max_uses: Some(1),
+            expires_at: Utc::now().naive_utc() + Duration::days(EXISTING_USER_INVITATION_DAYS),
+        },
+        db,
+    )
+    .await?;
+
+    Ok(Some((user, token)))
+}
+
+fn usable_invitation_condition() -> Condition {
+    Condition::all()
+        .add(grove_invitation::Column::ExpiresAt.gt(Utc::now().naive_utc()))
+        .add(
+            Condition::any()
+                .add(grove_invitation::Column::MaxUses.is_null())
+                .add(
+                    Expr::col(grove_invitation::Column::UseCount)
+                        .lt(Expr::col(grove_invitation::Column::MaxUses)),
+                ),
+        )
+}
+
+async fn get_usable_invitation(
+    token: String,
+    db: &DatabaseConnection,
+) -> BambooResult<GroveInvitation> {
+    grove_invitation::Entity::find()
+        .filter(grove_invitation::Column::TokenHash.eq(hash_invitation_token(token.as_str())))
+        .filter(usable_invitation_condition())
+        .one(db)
+        .await
+        .map_err(|err| {
+            log::error!("{err}");
+            BambooError::database("grove_invitation", "Failed to load invitation")
+        })?
+        .ok_or_else(invalid_invitation)
+}
+
+pub async fn get_grove_invitation_details(
+    token: String,
+    db: &DatabaseConnection,
+) -> BambooResult<GroveInvitationDetails> {
+    let invitation = get_usable_invitation(token, db).await?;
+    let grove = dbal::get_grove_by_id(invitation.grove_id, db).await?;
+
+    Ok(GroveInvitationDetails {
+        grove_name: grove.name,
+        email: invitation.email,
+    })
+}
+
+async fn change_invitation_use_count(
+    invitation: &GroveInvitation,
+    change: i32,
+    db: &DatabaseConnection,
+) -> BambooResult<bool> {
+    let mut update = grove_invitation::Entity::update_many()
+        .col_expr(
+            grove_invitation::Column::UseCount,
+            Expr::col(grove_invitation::Column::UseCount).add(change),
+        )
+        .filter(grove_invitation::Column::Id.eq(invitation.id));
+    if change > 0 {
+        update = update.filter(usable_invitation_condition());
+    }
+
+    update
+        .exec(db)
+        .await
+        .map(|result| result.rows_affected > 0)
+        .map_err(|err| {
+            log::error!("{err}");
+            BambooError::database("grove_invitation", "Failed to update invitation")
+        })
+}
+
+/// Creates a new user in the grove of the invitation. Invitations with a preset email can only be accepted with that email.
+/// Pandas that already have an account join the grove with their existing account after entering its password.
+pub async fn accept_grove_invitation(
+    data: AcceptGroveInvitation,
+    db: &DatabaseConnection,
+) -> BambooResult<User> {
+    let invitation = get_usable_invitation(data.token.clone(), db).await?;
+    let email = invitation
+        .email
+        .clone()
+        .unwrap_or_else(|| data.email.trim().to_string());
+    let display_name = data.display_name.trim().to_string();
+    if email.is_empty() || display_name.is_empty() {
+        return Err(BambooError::validation(
+            "user",
+            "The email and the name must not be empty",
+        ));
+    }
+
+    if let Some(user) = user::Entity::find()
+        .filter(user::Column::Email.eq(email.clone()))
+        .one(db)
+        .await
+        .map_err(|err| {
+            log::error!("{err}");
+            BambooError::database("user", "Failed to load user")
+        })?
+    {
+        if !user.validate_password(data.password.clone()) {
+            return Err(BambooError::exists_already(
+                "user",
+                "A user with that email or name exists already",
+            ));
+        }
+        if !change_invitation_use_count(&invitation, 1, db).await? {
+            return Err(invalid_invitation());
+        }
+
+        let result = dbal::add_user_to_grove(invitation.grove_id, email, false, None, db)
+            .await
+            .and_then(|user| user.ok_or(BambooError::not_found("user", "The user was not found")));
+        if result.is_err() {
+            change_invitation_use_count(&invitation, -1, db).await?;
+        }
+
+        return result;
+    }
+
+    dbal::check_password_policy(
+        data.password.as_str(),
+        &[email.clone(), display_name.clone()],
+    )
+    .await?;
+
+    if !change_invitation_use_count(&invitation, 1, db).await? {
+        return Err(invalid_invitation());
+    }
+
+    let result = dbal::create_user(
+        invitation.grove_id,
+        User::new(
+            email,
+            display_name,
+            data.discord_name.trim().to_string(),
+            false,
+        ),
+        data.password,
+        db,
+    )
+    .await;
+    if result.is_err() {
+        change_invitation_use_count(&invitation, -1, db).await?;
+    }
+
+    result
+}
@@ -0,0 +1,461 @@
+use std::collections::BTreeMap;
+
+use chrono::{Duration, Utc};
+use sea_orm::prelude::*;
+use sea_orm::sea_query::Expr;
+use sea_orm::{
+    Condition, ConnectionTrait, DatabaseTransaction, NotSet, QueryOrder, QuerySelect, Set,
+    TransactionTrait,
+};
+
+use bamboo_common_core::entities::*;
+use bamboo_common_core::error::*;
+
+const ACTIVITY_RESOLUTION_MINUTES: i64 = 5;
+
+/// Mods can still login into disabled or deleted groves, suspended groves are closed for everyone.
+pub(crate) fn usable_grove_condition() -> Condition {
+    Condition::all()
+        .add(grove::Column::IsSuspended.eq(false))
+        .add(
+            Condition::any()
+                .add(
+                    Condition::all()
+                        .add(grove::Column::IsEnabled.eq(true))
+                        .add(grove::Column::DeletedAt.is_null()),
+                )
+                .add(grove_membership::Column::IsMod.eq(true)),
+        )
+}
+
+pub async fn get_grove_membership(
+    grove_id: i32,
+    user_id: i32,
+    db: &DatabaseConnection,
+) -> BambooResult<GroveMembership> {
+    grove_membership::Entity::find()
+        .filter(grove_membership::Column::GroveId.eq(grove_id))
+        .filter(grove_membership::Column::UserId.eq(user_id))
+        .one(db)
+        .await
+        .map_err(|err| {
+            log::error!("{err}");
+            BambooError::database("grove_membership", "Failed to load membership")
+        })?
+        .ok_or(BambooError::not_found(
+            "grove_membership",
+            "The user is not a member of the grove",
+        ))
+}
+
+/// Accounts can be shared between groves, the mods of a grove may only change the account of pandas that are in no other grove.
+pub async fn is_only_member_of_grove(
+    grove_id: i32,
+    user_id: i32,
+    db: &DatabaseConnection,
+) -> BambooResult<bool> {
+    grove_membership::Entity::find()
+        .filter(grove_membership::Column::UserId.eq(user_id))
+        .filter(grove_membership::Column::GroveId.ne(grove_id))
+        .count(db)
+        .await
+        .map(|count| count == 0)
+        .map_err(|err| {
+            log::error!("{err}");
+            BambooError::database("grove_membership", "Failed to load memberships")
+        })
+}
+
+pub async fn get_groves_by_user_id(
+    user_id: i32,
+    db: &DatabaseConnection,
+) -> BambooResult<Vec<Grove>> {
+    grove::Entity::find()
+        .filter(grove_membership::Column::UserId.eq(user_id))
+        .inner_join(grove_membership::Entity)
+        .order_by_asc(grove::Column::Name)
+        .all(db)
+        .await
+        .map_err(|err| {
+            log::error!("{err}");
+            BambooError::database("grove", "Failed to load groves")
+        })
+}
+
+/// Resolves the grove the user works in, without a requested grove the default grove of the user is used.
+/// While the default grove is locked for the user the first usable grove takes over, the default grove itself stays
+/// untouched so it is active again once the grove gets restored or enabled.
+pub async fn get_active_grove(
+    user: &User,
+    grove_id: Option<i32>,
+    db: &DatabaseConnection,
+) -> BambooResult<(Grove, GroveMembership)> {
+    let membership = get_grove_membership(grove_id.unwrap_or(user.grove_id), user.id, db).await?;
+    let grove = grove::Entity::find_by_id(membership.grove_id)
+        .one(db)
+        .await
+        .map_err(|err| {
+            log::error!("{err}");
+            BambooError::database("grove", "Failed to load grove")
+        })?
+        .ok_or(BambooError::not_found("grove", "The grove was not found"))?;
+
+    let is_locked =
+        ((!grove.is_enabled || grove.is_deleted()) && !membership.is_mod) || grove.is_suspended;
+    if grove_id.is_none() && is_locked {
+        if let Some((fallback_membership, Some(fallback_grove))) = grove_membership::Entity::find()
+            .filter(grove_membership::Column::UserId.eq(user.id))
+            .filter(usable_grove_condition())
+            .find_also_related(grove::Entity)
+            .order_by_asc(grove_membership::Column::Id)
+            .one(db)
+            .await
+            .map_err(|err| {
+                log::error!("{err}");
+                BambooError::database("grove_membership", "Failed to load memberships")
+            })?
+        {
+            return Ok((fallback_grove, fallback_membership));
+        }
+    }
+
+    Ok((grove, membership))
+}
+
+pub async fn has_usable_grove(user_id: i32, db: &DatabaseConnection) -> BambooResult<bool> {
+    grove_membership::Entity::find()
+        .filter(grove_membership::Column::UserId.eq(user_id))
+        .filter(usable_grove_condition())
+        .inner_join(grove::Entity)
+        .count(db)
+        .await
+        .map(|count| count > 0)
+        .map_err(|err| {
+            log::error!("{err}");
+            BambooError::database("grove_membership", "Failed to load memberships")
+        })
+}
+
+/// The activity is only written every few minutes, so not every request ends up in a database write.
+/// The already loaded membership is checked first, the database only gets asked once the activity is outdated.
+pub async fn mark_grove_membership_active(
+    membership: &GroveMembership,
+    db: &DatabaseConnection,
+) -> BambooErrorResult {
+    let now = Utc::now().naive_utc();
+    let outdated_before = now - Duration::minutes(ACTIVITY_RESOLUTION_MINUTES);
+    if membership
+        .last_active_at
+        .is_some_and(|last_active_at| last_active_at >= outdated_before)
+    {
+        return Ok(());
+    }
+
+    grove_membership::Entity::update_many()
+        .col_expr(grove_membership::Column::LastActiveAt, Expr::value(now))
+        .filter(grove_membership::Column::Id.eq(membership.id))
+        .filter(
+            Condition::any()
+                .add(grove_membership::Column::LastActiveAt.is_null())
+                .add(grove_membership::Column::LastActiveAt.lt(outdated_before)),
+        )
+        .exec(db)
+        .await
+        .map_err(|err| {
+            log::error!("{err}");
+            BambooError::database("grove_membership", "Failed to update membership")
+        })
+        .map(|_| ())
+}
+
+pub(crate) async fn create_grove_membership(
+    grove_id: i32,
+    user_id: i32,
+    is_mod: bool,
+    role_id: Option<i32>,
+    db: &impl ConnectionTrait,
+) -> BambooResult<GroveMembership> {
+    grove_membership::ActiveModel {
+        id: NotSet,
+        user_id: Set(user_id),
+        grove_id: Set(grove_id),
+        is_mod: Set(is_mod),
+        role_id: Set(role_id),
+        last_active_at: Set(None),
+    }
+    .insert(db)
+    .await
+    .map_err(|err| {
+        log::error!("{err}");
+        BambooError::database("grove_membership", "Failed to create membership")
+    })
+}
+
+/// The default grove is used for requests that don't select a grove, it is remembered across devices.
+pub async fn change_default_grove(
+    user_id: i32,
+    grove_id: i32,
+    db: &DatabaseConnection,
+) -> BambooErrorResult {
+    get_grove_membership(grove_id, user_id, db).await?;
+
+    user::Entity::update_many()
+        .col_expr(user::Column::GroveId, Expr::value(grove_id))
+        .filter(user::Column::Id.eq(user_id))
+        .exec(db)
+        .await
+        .map_err(|err| {
+            log::error!("{err}");
+            BambooError::database("user", "Failed to update user")
+        })
+        .map(|_| ())
+}
+
+/// Users whose default grove is the given grove move to the oldest of their other groves,
+/// so that deleting the grove only deletes the users that aren't members anywhere else.
+pub(crate) async fn move_default_grove_away(
+    grove_id: i32,
+    db: &DatabaseConnection,
+) -> BambooErrorResult {
+    let user_ids = user::Entity::find()
+        .filter(user::Column::GroveId.eq(grove_id))
+        .all(db)
+        .await
+        .map_err(|err| {
+            log::error!("{err}");
+            BambooError::database("user", "Failed to load users")
+        })?
+        .into_iter()
+        .map(|user| user.id)
+        .collect::<Vec<i32>>();
+
+    let mut other_groves = BTreeMap::<i32, i32>::new();
+    for membership in grove_membership::Entity::find()
+        .filter(grove_membership::Column::UserId.is_in(user_ids))
+        .filter(grove_membership::Column::GroveId.ne(grove_id))
+        .order_by_asc(grove_membership::Column::Id)
+        .all(db)
+        .await
+        .map_err(|err| {
+            log::error!("{err}");
+            BambooError::database("grove_membership", "Failed to load memberships")
+        })?
+    {
+        other_groves
+            .entry(membership.user_id)
+            .or_insert(membership.grove_id);
+    }
+
+    for (user_id, other_grove_id) in other_groves {
+        user::Entity::update_many()
+            .col_expr(user::Column::GroveId, Expr::value(other_grove_id))
+            .filter(user::Column::Id.eq(user_id))
+            .exec(db)
+            .await
+            .map_err(|err| {
+                log::error!("{err}");
+                BambooError::database("user", "Failed to update user")
+            })?;
+    }
+
+    Ok(())
+}
+
+pub(crate) async fn begin_transaction(
+    db: &DatabaseConnection,
+) -> BambooResult<DatabaseTransaction> {
+    db.begin().await.map_err(|err| {
+        log::error!("{err}");
+        BambooError::database("grove_membership", "Failed to start transaction")
+    })
+}
+
+pub(crate) async fn commit_transaction(txn: DatabaseTransaction) -> BambooErrorResult {
+    txn.commit().await.map_err(|err| {
+        log::error!("{err}");
+        BambooError::database("grove_membership", "Failed to commit transaction")
+    })
+}
+
+/// Every grove needs at least one mod, the mod memberships stay locked until the transaction ends,
+/// so two mods can't take each other's rights away at the same time.
+pub(crate) async fn ensure_grove_keeps_mod(
+    grove_id: i32,
+    user_id: i32,
+    db: &impl ConnectionTrait,
+) -> BambooErrorResult {
+    let mods = grove_membership::Entity::find()
+        .filter(grove_membership::Column::GroveId.eq(grove_id))
+        .filter(grove_membership::Column::IsMod.eq(true))
+        .lock_exclusive()
+        .all(db)
+        .await
+        .map_err(|err| {
+            log::error!("{err}");
+            BambooError::database("grove_membership", "Failed to load memberships")
+        })?;
+
+    let is_mod = mods.iter().any(|membership| membership.user_id == user_id);
+    let has_other_mod = mods.iter().any(|membership| membership.user_id != user_id);
+    if is_mod && !has_other_mod {
+        Err(BambooError::validation(
+            "grove_membership",
+            "The grove needs at least one mod",
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+async fn remove_from_grove(
+    grove_id: i32,
+    user_id: i32,
+    db: &impl ConnectionTrait,
+) -> BambooErrorResult {
+    ensure_grove_keeps_mod(grove_id, user_id, db).await?;
+
+    let memberships = grove_membership::Entity::find()
+        .filter(grove_membership::Column::UserId.eq(user_id))
+        .order_by_asc(grove_membership::Column::Id)
+        .all(db)
+        .await
+        .map_err(|err| {
+            log::error!("{err}");
+            BambooError::database("grove_membership", "Failed to load memberships")
+        })?;
+    if !memberships
+        .iter()
+        .any(|membership| membership.grove_id == grove_id)
+    {
+        return Err(BambooError::not_found(
+            "grove_membership",
+            "The user is not a member of the grove",
+        ));
+    }
+
+    if let Some(other_membership) = memberships
+        .iter()
+        .find(|membership| membership.grove_id != grove_id)
+    {
+        user::Entity::update_many()
+            .col_expr(
+                user::Column::GroveId,
+                Expr::value(other_membership.grove_id),
+            )
+            .filter(user::Column::Id.eq(user_id))
+            .filter(user::Column::GroveId.eq(grove_id))
+            .exec(db)
+            .await
+            .map_err(|err| {
+                log::error!("{err}");
+                BambooError::database("user", "Failed to update user")
+            })?;
+
+        grove_membership::Entity::delete_many()
+            .filter(grove_membership::Column::GroveId.eq(grove_id))
+            .filter(grove_membership::Column::UserId.eq(user_id))
+            .exec(db)
+            .await
+            .map_err(|err| {
+                log::error!("{err}");
+                BambooError::database("grove_membership", "Failed to delete membership")
+            })
+            .map(|_| ())
+    } else {
+        user::Entity::delete_by_id(user_id)
+            .exec(db)
+            .await
+            .map_err(|err| {
+                log::error!("{err}");
+                BambooError::database("user", "Failed to delete user")
+            })
+            .map(|_| ())
+    }
+}
+
+/// The demo grove deliberately has no mod, so unlike `change_mod_status` this doesn't require another mod to stay.
+pub async fn revoke_mod_status_of_demo_account(
+    grove_id: i32,
+    user_id: i32,
+    db: &DatabaseConnection,
+) -> BambooErrorResult {
+    grove_membership::Entity::update_many()
+        .filter(grove_membership::Column::GroveId.eq(grove_id))
+        .filter(grove_membership::Column::UserId.eq(user_id))
+        .col_expr(grove_membership::Column::IsMod, Expr::value(false))
+        .exec(db)
+        .await
+        .map_err(|err| {
+            log::error!("{err}");
+            BambooError::database("grove_membership", "Failed to update membership")
+        })
+        .map(|_| ())
+}
+
+/// Removes the user from the grove, users without any other grove are deleted.
+/// The last mod of a grove can't leave, they have to transfer their mod rights first.
+pub async fn leave_grove(
+    grove_id: i32,
+    user_id: i32,
+    db: &DatabaseConnection,
+) -> BambooErrorResult {
+    let txn = begin_transaction(db).await?;
+    remove_from_grove(grove_id, user_id, &txn).await?;
+
+    commit_transaction(txn).await
+}
+
+/// Makes the successor mod and removes the user from the grove in one go, only mods can hand over their rights.
+pub async fn transfer_mod_rights_and_leave(
+    grove_id: i32,
+    user_id: i32,
+    successor_id: i32,
+    db: &DatabaseConnection,
+) -> BambooErrorResult {
+    if successor_id == user_id {
+        return Err(BambooError::validation(
+            "grove_membership",
+            "You cannot transfer your mod rights to yourself",
+        ));
+    }
+
+    let txn = begin_transaction(db).await?;
+    let is_mod = grove_membership::Entity::find()
+        .filter(grove_membership::Column::GroveId.eq(grove_id))
+        .filter(grove_membership::Column::UserId.eq(user_id))
+        .lock_exclusive()
+        .one(&txn)
+        .await
+        .map_err(|err| {
+            log::error!("{err}");
+            BambooError::database("grove_membership", "Failed to load membership")
+        })?
+        .is_some_and(|membership| membership.is_mod);
+    if !is_mod {
+        return Err(BambooError::insufficient_rights(
+            "grove_membership",
+            "Only mods can transfer their mod rights",
+        ));
+    }
+
+    let result = grove_membership::Entity::update_many()
+        .col_expr(grove_membership::Column::IsMod, Expr::value(true))
+        .filter(grove_membership::Column::GroveId.eq(grove_id))
+        .filter(grove_membership::Column::UserId.eq(successor_id))
+        .exec(&txn)
+        .await
+        .map_err(|err| {
+            log::error!("{err}");
+            BambooError::database("grove_membership", "Failed to update membership")
+        })?;
+    if result.rows_affected == 0 {
+        return Err(BambooError::not_found(
+            "grove_membership",
+            "The successor is not a member of the grove",
+        ));
+    }
+
+    remove_from_grove(grove_id, user_id, &txn).await?;
+
+    commit_transaction(txn).await
+}
@@ -0,0 +1,244 @@
+use sea_orm::prelude::*;
+use sea_orm::sea_query::Expr;
+use sea_orm::{IntoActiveModel, NotSet, QueryOrder, Set};
+
+use bamboo_common_core::entities::*;
+use bamboo_common_core::error::*;
+
+async fn role_name_exists(
+    grove_id: i32,
+    id: i32,
+    name: String,
+    db: &DatabaseConnection,
+) -> BambooResult<bool> {
+    grove_role::Entity::find()
+        .filter(grove_role::Column::GroveId.eq(grove_id))
+        .filter(grove_role::Column::Id.ne(id))
+        .filter(grove_role::Column::Name.eq(name))
+        .count(db)
+        .await
+        .map(|count| count > 0)
+        .map_err(|err| {
+            log::error!("Failed to load roles {err}");
+            BambooError::database("grove_role", "Failed to load roles")
+        })
+}
+
+fn validate_role(role: &GroveRole) -> BambooErrorResult {
+    if role.name.trim().is_empty() {
+        Err(BambooError::validation(
+            "grove_role",
+            "The name must not be empty",
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+pub async fn get_grove_roles(
+    grove_id: i32,
+    db: &DatabaseConnection,
+) -> BambooResult<Vec<GroveRole>> {
+    grove_role::Entity::find()
+        .filter(grove_role::Column::GroveId.eq(grove_id))
+        .order_by_asc(grove_role::Column::Name)
+        .all(db)
+        .await
+        .map_err(|err| {
+            log::error!("{err}");
+            BambooError::database("grove_role", "Failed to load roles")
+        })
+}
+
+pub async fn get_grove_role(
+    grove_id: i32,
+    id: i32,
+    db: &DatabaseConnection,
+) -> BambooResult<GroveRole> {
+    grove_role::Entity::find_by_id(id)
+        .filter(grove_role::Column::GroveId.eq(grove_id))
+        .one(db)
+        .await
+        .map_err(|err| {
+            log::error!("{err}");
+            BambooError::database("grove_role", "Failed to load role")
+        })?
+        .ok_or(BambooError::not_found(
+            "grove_role",
+            "The role was not found",
+        ))
+}
+
+pub async fn create_grove_role(
+    grove_id: i32,
+    role: GroveRole,
+    db: &DatabaseConnection,
+) -> BambooResult<GroveRole> {
+    validate_role(&role)?;
+    if role_name_exists(grove_id, -1, role.name.trim().to_string(), db).await? {
+        return Err(BambooError::exists_already(
+            "grove_role",
+            "A role with that name exists already",
+        ));
+    }
+
+    let mut model = role.clone().into_active_model();
+    model.id = NotSet;
+    model.grove_id = Set(grove_id);
+    model.name = Set(role.name.trim().to_string());
+
+    model.insert(db).await.map_err(|err| {
+        log::error!("{err}");
+        BambooError::database("grove_role", "Failed to create role")
+    })
+}
+
+pub async fn update_grove_role(
+    grove_id: i32,
+    id: i32,
+    role: GroveRole,
+    db: &DatabaseConnection,
+) -> BambooErrorResult {
+    validate_role(&role)?;
+    get_grove_role(grove_id, id, db).await?;
+    if role_name_exists(grove_id, id, role.name.trim().to_string(), db).await? {
+        return Err(BambooError::exists_already(
+            "grove_role",
+            "A role with that name exists already",
+        ));
+    }
+
+    grove_role::Entity::update_many()
+        .col_expr(
+            grove_role::Column::Name,
+            Expr::value(role.name.trim().to_string()),
+        )
+        .col_expr(
+            grove_role::Column::Permissions,
+            Expr::value(role.permissions),
+        )
+        .filter(grove_role::Column::GroveId.eq(grove_id))
+        .filter(grove_role::Column::Id.eq(id))
+        .exec(db)
+        .await
+        .map_err(|err| {
+            log::error!("{err}");
+            BambooError::database("grove_role", "Failed to update role")
+        })
+        .map(|_| ())
+}
+
+/// Pandas with the deleted role keep their account but lose the permissions of the role.
+pub async fn delete_grove_role(
+    grove_id: i32,
+    id: i32,
+    db: &DatabaseConnection,
+) -> BambooErrorResult {
+    grove_role::Entity::delete_many()
+        .filter(grove_role::Column::GroveId.eq(grove_id))
+        .filter(grove_role::Column::Id.eq(id))
+        .exec(db)
+        .await
+        .map_err(|err| {
+            log::error!("{err}");
+            BambooError::database("grove_role", "Failed to delete role")
+        })
+        .map(|_| ())
+}
+
+pub async fn change_user_role(
+    grove_id: i32,
+    user_id: i32,
+    role_id: Option<i32>,
+    db: &DatabaseConnection,
+) -> BambooErrorResult {
+    if let Some(role_id) = role_id {
+        get_grove_role(grove_id, role_id, db).await?;
+    }
+
+    grove_membership::Entity::update_many()
+        .col_expr(grove_membership::Column::RoleId, Expr::value(role_id))
+        .filter(grove_membership::Column::GroveId.eq(grove_id))
+        .filter(grove_membership::Column::UserId.eq(user_id))
+        .exec(db)
+        .await
+        .map_err(|err| {
+            log::error!("{err}");
+            BambooError::database("user", "Failed to update user")
+        })
+        .map(|_| ())
+}
+
+/// Mods have all permissions, everyone else has the permissions of their role.
+/// The user needs the membership of the grove applied, like the authenticated user of a request.
+pub async fn get_user_permissions(
+    user: &User,
+    db: &DatabaseConnection,
+) -> BambooResult<GrovePermissions> {
+    if user.is_mod {
+        return Ok(GrovePermissions::all());
+    }
+
+    if let Some(role_id) = user.role_id {
+        get_grove_role(user.grove_id, role_id, db)
+            .await
+            .map(|role| role.permissions)
+    } else {
+        Ok(GrovePermissions::default())
+    }
+}
+
+pub async fn has_permission(
+    user: &User,
+    permission: GrovePermission,
+    db: &DatabaseConnection,
+) -> BambooResult<bool> {
+    get_user_permissions(user, db)
+        .await
+        .map(|permissions| permissions.contains(permission))
+}
+
+/// Only mods can manage other mods, everyone else can only manage pandas whose permissions they have themselves.
+pub async fn can_manage_user(
+    actor: &User,
+    target: &User,
+    db: &DatabaseConnection,
+) -> BambooResult<bool> {
+    if actor.is_mod {
+        return Ok(true);
+    }
+    if target.is_mod {
+        return Ok(false);
+    }
+
+    let actor_permissions = get_user_permissions(actor, db).await?;
+    get_user_permissions(target, db).await.map(|permissions| {
+        permissions
+            .0
+            .into_iter()
+            .all(|permission| actor_permissions.contains(permission))
+    })
+}
+
+/// A role can only be given by someone who has all of its permissions, mods can give every role.
+pub async fn can_assign_role(
+    actor: &User,
+    grove_id: i32,
+    role_id: Option<i32>,
+    db: &DatabaseConnection,
+) -> BambooResult<bool> {
+    let Some(role_id) = role_id else {
+        return Ok(true);
+    };
+    if actor.is_mod {
+        return Ok(true);
+    }
+
+    let actor_permissions = get_user_permissions(actor, db).await?;
+    get_grove_role(grove_id, role_id, db).await.map(|role| {
+        role.permissions
+            .0
+            .into_iter()
+            .all(|permission| actor_permissions.contains(permission))
+    })
+}
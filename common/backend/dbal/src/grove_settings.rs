@@ -0,0 +1,125 @@
+use sea_orm::prelude::*;
+use sea_orm::{IntoActiveModel, NotSet, Set};
+
+use bamboo_common_core::entities::*;
+use bamboo_common_core::error::*;
+
+use crate::grove::get_grove_by_id;
+
+pub(crate) fn validate_grove_settings(settings: &GroveSettings) -> BambooErrorResult {
+    let accent_color = settings.accent_color.trim_start_matches('#');
+    if settings.display_name.trim().is_empty() {
+        Err(BambooError::validation(
+            "grove_settings",
+            "The display name must not be empty",
+        ))
+    } else if !TIMEZONES.contains(&settings.timezone.as_str()) {
+        Err(BambooError::validation(
+            "grove_settings",
+            "The timezone is not supported",
+        ))
+    } else if !settings.accent_color.starts_with('#')
+        || accent_color.len() != 6
+        || !accent_color.chars().all(|c| c.is_ascii_hexdigit())
+    {
+        Err(BambooError::validation(
+            "grove_settings",
+            "The accent color must be a hex color",
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+async fn find_grove_settings(
+    grove_id: i32,
+    db: &DatabaseConnection,
+) -> BambooResult<Option<GroveSettings>> {
+    grove_settings::Entity::find()
+        .filter(grove_settings::Column::GroveId.eq(grove_id))
+        .one(db)
+        .await
+        .map_err(|err| {
+            log::error!("{err}");
+            BambooError::database("grove_settings", "Failed to load grove settings")
+        })
+}
+
+/// Groves get their settings row on the first save, until then the defaults based on the grove are returned.
+pub async fn get_grove_settings(
+    grove_id: i32,
+    db: &DatabaseConnection,
+) -> BambooResult<GroveSettings> {
+    if let Some(settings) = find_grove_settings(grove_id, db).await? {
+        Ok(settings)
+    } else {
+        let grove = get_grove_by_id(grove_id, db).await?;
+
+        Ok(GroveSettings {
+            grove_id,
+            display_name: grove.name,
+            ..GroveSettings::default()
+        })
+    }
+}
+
+pub async fn update_grove_settings(
+    grove_id: i32,
+    settings: GroveSettings,
+    db: &DatabaseConnection,
+) -> BambooErrorResult {
+    validate_grove_settings(&settings)?;
+
+    if let Some(existing) = find_grove_settings(grove_id, db).await? {
+        let mut active_model = existing.into_active_model();
+        active_model.display_name = Set(settings.display_name.trim().to_string());
+        active_model.description = Set(settings.description);
+        active_model.language = Set(settings.language);
+        active_model.timezone = Set(settings.timezone);
+        active_model.accent_color = Set(settings.accent_color.to_lowercase());
+
+        active_model.update(db).await.map(|_| ())
+    } else {
+        let mut active_model = GroveSettings {
+            grove_id,
+            display_name: settings.display_name.trim().to_string(),
+            accent_color: settings.accent_color.to_lowercase(),
+            has_logo: false,
+            ..settings
+        }
+        .into_active_model();
+        active_model.id = NotSet;
+
+        active_model.insert(db).await.map(|_| ())
+    }
+    .map_err(|err| {
+        log::error!("{err}");
+        BambooError::database("grove_settings", "Failed to update grove settings")
+    })
+}
+
+pub async fn set_grove_has_logo(
+    grove_id: i32,
+    has_logo: bool,
+    db: &DatabaseConnection,
+) -> BambooErrorResult {
+    if let Some(existing) = find_grove_settings(grove_id, db).await? {
+        let mut active_model = existing.into_active_model();
+        active_model.has_logo = Set(has_logo);
+
+        active_model.update(db).await.map(|_| ())
+    } else {
+        let mut active_model = GroveSettings {
+            has_logo,
+            ..get_grove_settings(grove_id, db).await?
+        }
+        .into_active_model();
+        active_model.id = NotSet;
+
+        active_model.insert(db).await.map(|_| ())
+    }
+    .map_err(|err| {
+        log::error!("{err}");
+        BambooError::database("grove_settings", "Failed to update grove logo")
+    })
+}
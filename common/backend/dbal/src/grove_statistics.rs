@@ -0,0 +1,76 @@
+use chrono::{Duration, Utc};
+use sea_orm::prelude::*;
+
+use bamboo_common_core::entities::*;
+use bamboo_common_core::error::*;
+
+use crate::grove::get_grove_by_id;
+
+/// The storage for pictures lives in the object storage, the caller has to add it.
+pub async fn get_grove_statistics(
+    grove_id: i32,
+    db: &DatabaseConnection,
+) -> BambooResult<GroveStatistics> {
+    get_grove_by_id(grove_id, db).await?;
+
+    let memberships = grove_membership::Entity::find()
+        .filter(grove_membership::Column::GroveId.eq(grove_id))
+        .all(db)
+        .await
+        .map_err(|err| {
+            log::error!("{err}");
+            BambooError::database("grove_membership", "Failed to load memberships")
+        })?;
+    let event_count = event::Entity::find()
+        .filter(event::Column::GroveId.eq(grove_id))
+        .count(db)
+        .await
+        .map_err(|err| {
+            log::error!("{err}");
+            BambooError::database("event", "Failed to count events")
+        })?;
+    let character_count = character::Entity::find()
+        .filter(
+            character::Column::UserId.is_in(
+                memberships
+                    .iter()
+                    .map(|membership| membership.user_id)
+                    .collect::<Vec<i32>>(),
+            ),
+        )
+        .count(db)
+        .await
+        .map_err(|err| {
+            log::error!("{err}");
+            BambooError::database("character", "Failed to count characters")
+        })?;
+
+    let now = Utc::now().naive_utc();
+    let active_since = |days: i64| {
+        memberships
+            .iter()
+            .filter(|membership| {
+                membership
+                    .last_active_at
+                    .is_some_and(|last_active_at| last_active_at >= now - Duration::days(days))
+            })
+            .count() as u64
+    };
+
+    Ok(GroveStatistics {
+        member_count: memberships.len() as u64,
+        mod_count: memberships
+            .iter()
+            .filter(|membership| membership.is_mod)
+            .count() as u64,
+        active_users_last_7_days: active_since(7),
+        active_users_last_30_days: active_since(30),
+        event_count,
+        character_count,
+        picture_storage_bytes: 0,
+        last_activity_at: memberships
+            .iter()
+            .filter_map(|membership| membership.last_active_at)
+            .max(),
+    })
+}
@@ -1,32 +1,66 @@
-use chacha20poly1305::aead::{Aead, OsRng};
-use chacha20poly1305::{AeadCore, ChaCha20Poly1305, Key, KeyInit, Nonce};
+use chacha20poly1305::aead::Aead;
+use chacha20poly1305::{ChaCha20Poly1305, Key, KeyInit, Nonce};
 use pbkdf2::hmac::Hmac;
 use sha2::Sha512;
 
 use bamboo_common_core::error::*;
 
+pub use crate::audit_log::*;
 pub use crate::authentication::*;
 pub use crate::character::*;
 pub use crate::character_housing::*;
 pub use crate::crafter::*;
 pub use crate::custom_field::*;
+pub use crate::email_change::*;
+pub use crate::encryption::*;
 pub use crate::event::*;
+pub use crate::external_identity::*;
 pub use crate::fighter::*;
 pub use crate::free_company::*;
 pub use crate::grove::*;
+pub use crate::grove_export::*;
+pub use crate::grove_import::*;
+pub use crate::grove_invitation::*;
+pub use crate::grove_membership::*;
+pub use crate::grove_role::*;
+pub use crate::grove_settings::*;
+pub use crate::grove_statistics::*;
+pub use crate::login_lockout::*;
 pub use crate::my::*;
+pub use crate::passkey::*;
+pub use crate::password_policy::*;
+pub use crate::password_reset::*;
+pub use crate::personal_access_token::*;
+pub use crate::totp_recovery_code::*;
 pub use crate::user::*;
 
+mod audit_log;
 mod authentication;
 mod character;
 mod character_housing;
 mod crafter;
 mod custom_field;
+mod email_change;
+mod encryption;
 mod event;
+mod external_identity;
 mod fighter;
 mod free_company;
 mod grove;
+mod grove_export;
+mod grove_import;
+mod grove_invitation;
+mod grove_membership;
+mod grove_role;
+mod grove_settings;
+mod grove_statistics;
+mod login_lockout;
 mod my;
+mod passkey;
+mod password_policy;
+mod password_reset;
+mod personal_access_token;
+mod totp_recovery_code;
 mod user;
 
 fn get_passphrase(passphrase: &[u8]) -> BambooResult<Key> {
@@ -54,18 +88,3 @@ pub(crate) fn decrypt_string(encrypted: Vec<u8>, passphrase: String) -> BambooRe
 
     Ok(decrypted)
 }
-
-pub(crate) fn encrypt_string(plain: Vec<u8>, passphrase: String) -> BambooResult<Vec<u8>> {
-    let cipher = ChaCha20Poly1305::new(&get_passphrase(passphrase.as_bytes())?);
-    let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
-
-    let encrypted = cipher
-        .encrypt(&nonce, plain.as_ref())
-        .map_err(|_| BambooError::crypto("encryption", "Failed to encrypt"))?;
-
-    let mut data = vec![];
-    data.extend_from_slice(&nonce);
-    data.extend(encrypted);
-
-    Ok(data)
-}
@@ -0,0 +1,222 @@
+use chrono::{Duration, NaiveDateTime, Utc};
+use sea_orm::prelude::*;
+use sea_orm::{IntoActiveModel, JoinType, NotSet, QueryOrder, QuerySelect, RelationTrait, Set};
+
+use bamboo_common_core::entities::*;
+use bamboo_common_core::error::*;
+
+const USER_ATTEMPTS_BEFORE_LOCKOUT: i32 = 5;
+const IP_ATTEMPTS_BEFORE_LOCKOUT: i32 = 20;
+const LOCKOUT_BASE_SECONDS: i64 = 60;
+const LOCKOUT_MAX_SECONDS: i64 = 60 * 60 * 24;
+const FAILED_ATTEMPTS_FORGET_AFTER_SECONDS: i64 = 60 * 60 * 24;
+
+fn now() -> NaiveDateTime {
+    Utc::now().naive_utc()
+}
+
+fn lockout_duration(failed_attempts: i32, attempts_before_lockout: i32) -> Option<Duration> {
+    if failed_attempts < attempts_before_lockout {
+        return None;
+    }
+
+    let exponent = (failed_attempts - attempts_before_lockout).min(16) as u32;
+    let seconds = LOCKOUT_BASE_SECONDS
+        .saturating_mul(2_i64.saturating_pow(exponent))
+        .min(LOCKOUT_MAX_SECONDS);
+
+    Some(Duration::seconds(seconds))
+}
+
+async fn get_lockout_by_user_id(
+    user_id: i32,
+    db: &DatabaseConnection,
+) -> BambooResult<Option<LoginLockout>> {
+    login_lockout::Entity::find()
+        .filter(login_lockout::Column::UserId.eq(user_id))
+        .filter(login_lockout::Column::IpAddress.is_null())
+        .one(db)
+        .await
+        .map_err(|err| {
+            log::error!("{err}");
+            BambooError::database("login_lockout", "Failed to load login lockout")
+        })
+}
+
+async fn get_lockout_by_ip_address(
+    ip_address: String,
+    db: &DatabaseConnection,
+) -> BambooResult<Option<LoginLockout>> {
+    login_lockout::Entity::find()
+        .filter(login_lockout::Column::IpAddress.eq(ip_address))
+        .filter(login_lockout::Column::UserId.is_null())
+        .one(db)
+        .await
+        .map_err(|err| {
+            log::error!("{err}");
+            BambooError::database("login_lockout", "Failed to load login lockout")
+        })
+}
+
+async fn increase_failed_attempts(
+    lockout: Option<LoginLockout>,
+    user_id: Option<i32>,
+    ip_address: Option<String>,
+    attempts_before_lockout: i32,
+    db: &DatabaseConnection,
+) -> BambooResult<(LoginLockout, bool)> {
+    let now = now();
+    let (was_locked, failed_attempts) = if let Some(lockout) = &lockout {
+        if (now - lockout.last_failed_at).num_seconds() > FAILED_ATTEMPTS_FORGET_AFTER_SECONDS {
+            (false, 1)
+        } else {
+            (lockout.is_locked(now), lockout.failed_attempts + 1)
+        }
+    } else {
+        (false, 1)
+    };
+
+    let locked_until =
+        lockout_duration(failed_attempts, attempts_before_lockout).map(|duration| now + duration);
+
+    let result = if let Some(lockout) = lockout {
+        let mut active_model = lockout.into_active_model();
+        active_model.failed_attempts = Set(failed_attempts);
+        active_model.last_failed_at = Set(now);
+        active_model.locked_until = Set(locked_until);
+
+        active_model.update(db).await
+    } else {
+        login_lockout::ActiveModel {
+            id: NotSet,
+            user_id: Set(user_id),
+            ip_address: Set(ip_address),
+            failed_attempts: Set(failed_attempts),
+            last_failed_at: Set(now),
+            locked_until: Set(locked_until),
+        }
+        .insert(db)
+        .await
+    };
+
+    result
+        .map(|lockout| (lockout.clone(), !was_locked && lockout.is_locked(now)))
+        .map_err(|err| {
+            log::error!("{err}");
+            BambooError::database("login_lockout", "Failed to save login lockout")
+        })
+}
+
+pub async fn check_login_lockout(
+    user_id: Option<i32>,
+    ip_address: Option<String>,
+    db: &DatabaseConnection,
+) -> BambooErrorResult {
+    let now = now();
+    let too_many_requests = BambooError::too_many_requests(
+        "login_lockout",
+        "Too many failed login attempts, please try again later",
+    );
+
+    if let Some(user_id) = user_id {
+        if let Some(lockout) = get_lockout_by_user_id(user_id, db).await? {
+            if lockout.is_locked(now) {
+                return Err(too_many_requests);
+            }
+        }
+    }
+
+    if let Some(ip_address) = ip_address {
+        if let Some(lockout) = get_lockout_by_ip_address(ip_address, db).await? {
+            if lockout.is_locked(now) {
+                return Err(too_many_requests);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+pub async fn register_failed_login(
+    user_id: Option<i32>,
+    ip_address: Option<String>,
+    db: &DatabaseConnection,
+) -> BambooResult<Option<LoginLockout>> {
+    if let Some(ip_address) = ip_address {
+        let lockout = get_lockout_by_ip_address(ip_address.clone(), db).await?;
+        increase_failed_attempts(
+            lockout,
+            None,
+            Some(ip_address),
+            IP_ATTEMPTS_BEFORE_LOCKOUT,
+            db,
+        )
+        .await?;
+    }
+
+    if let Some(user_id) = user_id {
+        let lockout = get_lockout_by_user_id(user_id, db).await?;
+        let (lockout, newly_locked) = increase_failed_attempts(
+            lockout,
+            Some(user_id),
+            None,
+            USER_ATTEMPTS_BEFORE_LOCKOUT,
+            db,
+        )
+        .await?;
+
+        if newly_locked {
+            return Ok(Some(lockout));
+        }
+    }
+
+    Ok(None)
+}
+
+pub async fn reset_failed_logins(user_id: i32, db: &DatabaseConnection) -> BambooErrorResult {
+    login_lockout::Entity::delete_many()
+        .filter(login_lockout::Column::UserId.eq(user_id))
+        .exec(db)
+        .await
+        .map_err(|err| {
+            log::error!("{err}");
+            BambooError::database("login_lockout", "Failed to reset login lockout")
+        })
+        .map(|_| ())
+}
+
+pub async fn get_login_lockouts(
+    grove_id: i32,
+    db: &DatabaseConnection,
+) -> BambooResult<Vec<LoginLockout>> {
+    login_lockout::Entity::find()
+        .filter(grove_membership::Column::GroveId.eq(grove_id))
+        .filter(login_lockout::Column::LockedUntil.gt(now()))
+        .join(JoinType::InnerJoin, login_lockout::Relation::User.def())
+        .join(JoinType::InnerJoin, user::Relation::GroveMembership.def())
+        .order_by_asc(login_lockout::Column::LockedUntil)
+        .all(db)
+        .await
+        .map_err(|err| {
+            log::error!("{err}");
+            BambooError::database("login_lockout", "Failed to load login lockouts")
+        })
+}
+
+pub async fn clear_login_lockout(
+    grove_id: i32,
+    user_id: i32,
+    db: &DatabaseConnection,
+) -> BambooErrorResult {
+    crate::get_user(grove_id, user_id, db).await?;
+
+    login_lockout::Entity::delete_many()
+        .filter(login_lockout::Column::UserId.eq(user_id))
+        .exec(db)
+        .await
+        .map_err(|err| {
+            log::error!("{err}");
+            BambooError::database("login_lockout", "Failed to clear login lockout")
+        })
+        .map(|_| ())
+}
@@ -5,7 +5,7 @@ use bamboo_common_core::entities::*;
 use bamboo_common_core::error::*;
 
 use crate as dbal;
-use crate::{decrypt_string, encrypt_string};
+use crate::{decrypt_secret, encrypt_secret};
 
 pub async fn change_my_password(
     id: i32,
@@ -13,7 +13,7 @@ pub async fn change_my_password(
     new_password: String,
     db: &DatabaseConnection,
 ) -> Result<(), PasswordError> {
-    let hashed_password = bcrypt::hash(new_password.clone(), 12).map_err(|err| {
+    let hashed_password = user::hash_password(new_password.as_str()).map_err(|err| {
         log::error!("{err}");
         PasswordError::Unknown
     })?;
@@ -27,21 +27,36 @@ pub async fn change_my_password(
         return Err(PasswordError::WrongPassword);
     }
 
-    let (totp_secret, totp_secret_encrypted) = if user.totp_validated.unwrap_or(false) {
-        let decrypted_totp_secret = if user.totp_secret_encrypted {
-            decrypt_string(user.totp_secret.clone().unwrap(), old_password)
-                .map_err(|_| PasswordError::Unknown)?
+    dbal::check_password_policy(
+        new_password.as_str(),
+        &[user.email.clone(), user.display_name.clone()],
+    )
+    .await
+    .map_err(PasswordError::PolicyViolation)?;
+
+    let (totp_secret, totp_secret_encrypted, totp_secret_key_id) =
+        if !user.totp_validated.unwrap_or(false) {
+            (None, false, None)
+        } else if user.totp_secret_key_id.is_some() {
+            // Secrets encrypted with a server side key don't depend on the password
+            (
+                user.totp_secret.clone(),
+                true,
+                user.totp_secret_key_id.clone(),
+            )
         } else {
-            user.totp_secret.clone().unwrap()
-        };
+            let decrypted_totp_secret = if user.totp_secret_encrypted {
+                decrypt_secret(user.totp_secret.clone().unwrap(), None, old_password)
+                    .map_err(|_| PasswordError::Unknown)?
+            } else {
+                user.totp_secret.clone().unwrap()
+            };
 
-        let encrypted_totp_secret = encrypt_string(decrypted_totp_secret, new_password.clone())
-            .map_err(|_| PasswordError::Unknown)?;
+            let (encrypted_totp_secret, key_id) =
+                encrypt_secret(decrypted_totp_secret).map_err(|_| PasswordError::Unknown)?;
 
-        (Some(encrypted_totp_secret), true)
-    } else {
-        (None, false)
-    };
+            (Some(encrypted_totp_secret), true, Some(key_id))
+        };
 
     user::Entity::update_many()
         .col_expr(user::Column::Password, Expr::value(hashed_password))
@@ -50,6 +65,10 @@ pub async fn change_my_password(
             Expr::value(totp_secret_encrypted),
         )
         .col_expr(user::Column::TotpSecret, Expr::value(totp_secret))
+        .col_expr(
+            user::Column::TotpSecretKeyId,
+            Expr::value(totp_secret_key_id),
+        )
         .filter(user::Column::Id.eq(id))
         .exec(db)
         .await
@@ -68,6 +87,10 @@ pub async fn enable_my_totp(
     user::Entity::update_many()
         .col_expr(user::Column::TotpSecret, Expr::value(secret))
         .col_expr(user::Column::TotpSecretEncrypted, Expr::value(false))
+        .col_expr(
+            user::Column::TotpSecretKeyId,
+            Expr::value::<Option<String>>(None),
+        )
         .col_expr(user::Column::TotpValidated, Expr::value(false))
         .filter(user::Column::Id.eq(id))
         .exec(db)
@@ -77,6 +100,8 @@ pub async fn enable_my_totp(
 }
 
 pub async fn disable_my_totp(id: i32, db: &DatabaseConnection) -> BambooErrorResult {
+    dbal::delete_totp_recovery_codes(id, db).await?;
+
     user::Entity::update_many()
         .col_expr(
             user::Column::TotpSecret,
@@ -84,6 +109,10 @@ pub async fn disable_my_totp(id: i32, db: &DatabaseConnection) -> BambooErrorRes
         )
         .col_expr(user::Column::TotpValidated, Expr::value(false))
         .col_expr(user::Column::TotpSecretEncrypted, Expr::value(false))
+        .col_expr(
+            user::Column::TotpSecretKeyId,
+            Expr::value::<Option<String>>(None),
+        )
         .filter(user::Column::Id.eq(id))
         .exec(db)
         .await
@@ -96,32 +125,46 @@ pub async fn validate_my_totp(
     password: String,
     code: String,
     db: &DatabaseConnection,
-) -> BambooResult<bool> {
-    let user = dbal::get_user_by_id_only(id, db).await?;
+) -> BambooResult<Option<TotpRecoveryCodes>> {
     let valid = dbal::validate_login(id, code, password.clone(), true, db)
         .await
         .is_ok();
-    let totp_secret = encrypt_string(user.totp_secret.unwrap(), password)?;
+    let user = dbal::get_user_by_id_only(id, db).await?;
+    let totp_secret = if user.totp_secret_encrypted {
+        decrypt_secret(
+            user.totp_secret.unwrap(),
+            user.totp_secret_key_id,
+            password.clone(),
+        )?
+    } else {
+        user.totp_secret.unwrap()
+    };
+    let (totp_secret, key_id) = encrypt_secret(totp_secret)?;
 
     user::Entity::update_many()
         .col_expr(user::Column::TotpSecret, Expr::value(totp_secret))
         .col_expr(user::Column::TotpSecretEncrypted, Expr::value(true))
+        .col_expr(user::Column::TotpSecretKeyId, Expr::value(key_id))
         .col_expr(user::Column::TotpValidated, Expr::value(Some(valid)))
         .filter(user::Column::Id.eq(id))
         .exec(db)
         .await
-        .map_err(|_| BambooError::database("user", "Totp could not be validated"))
-        .map(|_| valid)
+        .map_err(|_| BambooError::database("user", "Totp could not be validated"))?;
+
+    if valid {
+        dbal::create_totp_recovery_codes(id, db).await.map(Some)
+    } else {
+        Ok(None)
+    }
 }
 
 pub async fn update_my_profile(
     id: i32,
-    email: String,
     display_name: String,
     discord_name: String,
     db: &DatabaseConnection,
 ) -> BambooErrorResult {
-    if dbal::user_exists_by_id(id, email.clone(), display_name.clone(), db).await? {
+    if dbal::display_name_exists_by_id(id, display_name.clone(), db).await? {
         return Err(BambooError::exists_already(
             "user",
             "A user with that email or name exists already",
@@ -129,7 +172,6 @@ pub async fn update_my_profile(
     }
 
     user::Entity::update_many()
-        .col_expr(user::Column::Email, Expr::value(email))
         .col_expr(user::Column::DisplayName, Expr::value(display_name))
         .col_expr(user::Column::DiscordName, Expr::value(discord_name))
         .filter(user::Column::Id.eq(id))
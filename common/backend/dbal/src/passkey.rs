@@ -0,0 +1,305 @@
+use base64::Engine;
+use chrono::{Duration, Utc};
+use sea_orm::prelude::*;
+use sea_orm::{IntoActiveModel, NotSet, QueryOrder, Set};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use webauthn_rs::prelude::{
+    AuthenticationResult, PasskeyAuthentication, PasskeyRegistration, PublicKeyCredential,
+    RegisterPublicKeyCredential, Uuid,
+};
+use webauthn_rs::Webauthn;
+
+use bamboo_common_core::entities::*;
+use bamboo_common_core::error::*;
+
+use crate as dbal;
+
+const WEBAUTHN_CHALLENGE_VALID_MINUTES: i64 = 5;
+
+fn encode_credential_id(credential_id: &[u8]) -> String {
+    base64::prelude::BASE64_URL_SAFE_NO_PAD.encode(credential_id)
+}
+
+fn user_unique_id(user_id: i32) -> Uuid {
+    Uuid::from_u64_pair(0, user_id as u64)
+}
+
+fn parse_passkey(passkey: &Passkey) -> BambooResult<webauthn_rs::prelude::Passkey> {
+    serde_json::from_str(passkey.credential.as_str()).map_err(|err| {
+        log::error!("Failed to parse passkey {err}");
+        BambooError::crypto("passkey", "Failed to load passkey")
+    })
+}
+
+async fn save_webauthn_challenge(
+    user_id: i32,
+    state: &impl Serialize,
+    db: &DatabaseConnection,
+) -> BambooResult<String> {
+    let now = Utc::now().naive_utc();
+    webauthn_challenge::Entity::delete_many()
+        .filter(webauthn_challenge::Column::ExpiresAt.lt(now))
+        .exec(db)
+        .await
+        .map_err(|err| {
+            log::error!("{err}");
+            BambooError::database("passkey", "Failed to clean up passkey challenges")
+        })?;
+
+    let state = serde_json::to_string(state).map_err(|err| {
+        log::error!("Failed to serialize passkey challenge {err}");
+        BambooError::crypto("passkey", "Failed to create passkey challenge")
+    })?;
+
+    webauthn_challenge::ActiveModel {
+        id: NotSet,
+        challenge_id: Set(uuid::Uuid::new_v4().to_string()),
+        user_id: Set(user_id),
+        state: Set(state),
+        expires_at: Set(now + Duration::minutes(WEBAUTHN_CHALLENGE_VALID_MINUTES)),
+    }
+    .insert(db)
+    .await
+    .map(|challenge| challenge.challenge_id)
+    .map_err(|err| {
+        log::error!("{err}");
+        BambooError::database("passkey", "Failed to create passkey challenge")
+    })
+}
+
+async fn take_webauthn_challenge<T: DeserializeOwned>(
+    challenge_id: String,
+    user_id: i32,
+    db: &DatabaseConnection,
+) -> BambooResult<T> {
+    let challenge = webauthn_challenge::Entity::find()
+        .filter(webauthn_challenge::Column::ChallengeId.eq(challenge_id))
+        .filter(webauthn_challenge::Column::UserId.eq(user_id))
+        .one(db)
+        .await
+        .map_err(|err| {
+            log::error!("{err}");
+            BambooError::database("passkey", "Failed to load passkey challenge")
+        })?
+        .ok_or(BambooError::unauthorized(
+            "passkey",
+            "The passkey challenge is invalid or expired",
+        ))?;
+
+    webauthn_challenge::Entity::delete_by_id(challenge.id)
+        .exec(db)
+        .await
+        .map_err(|err| {
+            log::error!("{err}");
+            BambooError::database("passkey", "Failed to invalidate passkey challenge")
+        })?;
+
+    if challenge.expires_at < Utc::now().naive_utc() {
+        return Err(BambooError::unauthorized(
+            "passkey",
+            "The passkey challenge is invalid or expired",
+        ));
+    }
+
+    serde_json::from_str(challenge.state.as_str()).map_err(|err| {
+        log::error!("Failed to parse passkey challenge {err}");
+        BambooError::crypto("passkey", "Failed to load passkey challenge")
+    })
+}
+
+pub async fn get_passkeys(user_id: i32, db: &DatabaseConnection) -> BambooResult<Vec<Passkey>> {
+    passkey::Entity::find()
+        .filter(passkey::Column::UserId.eq(user_id))
+        .order_by_asc(passkey::Column::CreatedAt)
+        .all(db)
+        .await
+        .map_err(|err| {
+            log::error!("{err}");
+            BambooError::database("passkey", "Failed to load passkeys")
+        })
+}
+
+pub async fn start_passkey_registration(
+    user_id: i32,
+    webauthn: &Webauthn,
+    db: &DatabaseConnection,
+) -> BambooResult<PasskeyRegistrationChallenge> {
+    let user = dbal::get_user_by_id_only(user_id, db).await?;
+    let exclude_credentials = get_passkeys(user_id, db)
+        .await?
+        .iter()
+        .map(parse_passkey)
+        .collect::<BambooResult<Vec<_>>>()?
+        .iter()
+        .map(|passkey| passkey.cred_id().clone())
+        .collect::<Vec<_>>();
+
+    let (options, state) = webauthn
+        .start_passkey_registration(
+            user_unique_id(user.id),
+            user.email.as_str(),
+            user.display_name.as_str(),
+            Some(exclude_credentials),
+        )
+        .map_err(|err| {
+            log::error!("Failed to start passkey registration {err}");
+            BambooError::crypto("passkey", "Failed to start passkey registration")
+        })?;
+
+    let challenge_id = save_webauthn_challenge(user.id, &state, db).await?;
+
+    Ok(PasskeyRegistrationChallenge {
+        challenge_id,
+        options,
+    })
+}
+
+pub async fn finish_passkey_registration(
+    user_id: i32,
+    challenge_id: String,
+    name: String,
+    credential: RegisterPublicKeyCredential,
+    webauthn: &Webauthn,
+    db: &DatabaseConnection,
+) -> BambooResult<Passkey> {
+    let state = take_webauthn_challenge::<PasskeyRegistration>(challenge_id, user_id, db).await?;
+    let passkey = webauthn
+        .finish_passkey_registration(&credential, &state)
+        .map_err(|err| {
+            log::error!("Failed to finish passkey registration {err}");
+            BambooError::validation("passkey", "The passkey could not be registered")
+        })?;
+
+    let credential = serde_json::to_string(&passkey).map_err(|err| {
+        log::error!("Failed to serialize passkey {err}");
+        BambooError::crypto("passkey", "Failed to save passkey")
+    })?;
+
+    passkey::ActiveModel {
+        id: NotSet,
+        user_id: Set(user_id),
+        name: Set(name),
+        credential_id: Set(encode_credential_id(passkey.cred_id().as_ref())),
+        credential: Set(credential),
+        created_at: Set(Utc::now().naive_utc()),
+        last_used_at: Set(None),
+    }
+    .insert(db)
+    .await
+    .map_err(|err| {
+        log::error!("{err}");
+        BambooError::database("passkey", "Failed to save passkey")
+    })
+}
+
+pub async fn delete_passkey(
+    user_id: i32,
+    passkey_id: i32,
+    db: &DatabaseConnection,
+) -> BambooErrorResult {
+    let result = passkey::Entity::delete_many()
+        .filter(passkey::Column::Id.eq(passkey_id))
+        .filter(passkey::Column::UserId.eq(user_id))
+        .exec(db)
+        .await
+        .map_err(|err| {
+            log::error!("{err}");
+            BambooError::database("passkey", "Failed to delete passkey")
+        })?;
+
+    if result.rows_affected == 0 {
+        Err(BambooError::not_found(
+            "passkey",
+            "The passkey was not found",
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+pub async fn start_passkey_authentication(
+    user_id: i32,
+    webauthn: &Webauthn,
+    db: &DatabaseConnection,
+) -> BambooResult<PasskeyLoginChallenge> {
+    let passkeys = get_passkeys(user_id, db)
+        .await?
+        .iter()
+        .map(parse_passkey)
+        .collect::<BambooResult<Vec<_>>>()?;
+    if passkeys.is_empty() {
+        return Err(BambooError::not_found(
+            "passkey",
+            "The user has no passkeys registered",
+        ));
+    }
+
+    let (options, state) = webauthn
+        .start_passkey_authentication(passkeys.as_slice())
+        .map_err(|err| {
+            log::error!("Failed to start passkey authentication {err}");
+            BambooError::crypto("passkey", "Failed to start passkey authentication")
+        })?;
+
+    let challenge_id = save_webauthn_challenge(user_id, &state, db).await?;
+
+    Ok(PasskeyLoginChallenge {
+        challenge_id,
+        options,
+    })
+}
+
+pub(crate) async fn finish_passkey_authentication(
+    user_id: i32,
+    challenge_id: String,
+    credential: PublicKeyCredential,
+    webauthn: &Webauthn,
+    db: &DatabaseConnection,
+) -> BambooErrorResult {
+    let state = take_webauthn_challenge::<PasskeyAuthentication>(challenge_id, user_id, db).await?;
+    let result = webauthn
+        .finish_passkey_authentication(&credential, &state)
+        .map_err(|err| {
+            log::error!("Failed to finish passkey authentication {err}");
+            BambooError::unauthorized("passkey", "Invalid login data")
+        })?;
+
+    update_used_passkey(user_id, result, db).await
+}
+
+async fn update_used_passkey(
+    user_id: i32,
+    result: AuthenticationResult,
+    db: &DatabaseConnection,
+) -> BambooErrorResult {
+    let stored_passkey = passkey::Entity::find()
+        .filter(passkey::Column::UserId.eq(user_id))
+        .filter(passkey::Column::CredentialId.eq(encode_credential_id(result.cred_id().as_ref())))
+        .one(db)
+        .await
+        .map_err(|err| {
+            log::error!("{err}");
+            BambooError::database("passkey", "Failed to load passkey")
+        })?
+        .ok_or(BambooError::unauthorized("passkey", "Invalid login data"))?;
+
+    let mut passkey = parse_passkey(&stored_passkey)?;
+    passkey.update_credential(&result);
+    let credential = serde_json::to_string(&passkey).map_err(|err| {
+        log::error!("Failed to serialize passkey {err}");
+        BambooError::crypto("passkey", "Failed to save passkey")
+    })?;
+
+    let mut active_model = stored_passkey.into_active_model();
+    active_model.credential = Set(credential);
+    active_model.last_used_at = Set(Some(Utc::now().naive_utc()));
+    active_model
+        .update(db)
+        .await
+        .map_err(|err| {
+            log::error!("{err}");
+            BambooError::database("passkey", "Failed to update passkey")
+        })
+        .map(|_| ())
+}
@@ -0,0 +1,478 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use rand::distributions::Alphanumeric;
+use rand::Rng;
+use sha1::{Digest, Sha1};
+
+use bamboo_common_core::error::*;
+
+const BUNDLED_BREACHED_PASSWORDS: &str = include_str!("../data/breached_passwords.txt");
+const HASH_PREFIX_LENGTH: usize = 5;
+const BREACHED_PASSWORDS_API_TIMEOUT: Duration = Duration::from_secs(3);
+const GENERATED_PASSWORD_MIN_LENGTH: usize = 16;
+const GENERATED_PASSWORD_MAX_ATTEMPTS: usize = 10;
+
+const COMMON_WORDS: [&str; 24] = [
+    "password",
+    "passwort",
+    "qwerty",
+    "qwertz",
+    "asdf",
+    "admin",
+    "welcome",
+    "letmein",
+    "hallo",
+    "sommer",
+    "winter",
+    "geheim",
+    "bambus",
+    "bambushain",
+    "panda",
+    "pandas",
+    "hain",
+    "login",
+    "iloveyou",
+    "dragon",
+    "monkey",
+    "shadow",
+    "master",
+    "sunshine",
+];
+const YEAR_RANGE_START: u32 = 1950;
+const KEYBOARD_ROWS: [&str; 4] = ["1234567890", "qwertzuiop", "qwertyuiop", "asdfghjkl"];
+
+pub struct PasswordPolicy {
+    pub min_length: usize,
+    pub min_strength: u8,
+    pub check_breached: bool,
+    pub breached_passwords_api_url: Option<String>,
+}
+
+impl PasswordPolicy {
+    /// Reads the policy from `PASSWORD_MIN_LENGTH`, `PASSWORD_MIN_STRENGTH` (0 to 4) and `PASSWORD_CHECK_BREACHED`.
+    /// The breached passwords are only looked up online if `BREACHED_PASSWORDS_API_URL` points to a range api like
+    /// `https://api.pwnedpasswords.com/range/`, otherwise only the local list is used.
+    pub fn from_env() -> Self {
+        Self {
+            min_length: std::env::var("PASSWORD_MIN_LENGTH")
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(10),
+            min_strength: std::env::var("PASSWORD_MIN_STRENGTH")
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(3)
+                .min(4),
+            check_breached: std::env::var("PASSWORD_CHECK_BREACHED")
+                .map(|value| value.to_lowercase() != "false")
+                .unwrap_or(true),
+            breached_passwords_api_url: std::env::var("BREACHED_PASSWORDS_API_URL")
+                .ok()
+                .filter(|url| !url.trim().is_empty()),
+        }
+    }
+}
+
+/// The local list is always checked first, the bundled hashes cover the most common passwords with their usual
+/// variations. The hashes are grouped by their first five characters, the same k-anonymity layout used by
+/// Have I Been Pwned. Larger lists in the `HASH:COUNT` format can be configured with `BREACHED_PASSWORDS_FILE`.
+fn get_breached_passwords() -> &'static HashMap<String, HashSet<String>> {
+    static BREACHED_PASSWORDS: OnceLock<HashMap<String, HashSet<String>>> = OnceLock::new();
+
+    BREACHED_PASSWORDS.get_or_init(|| {
+        let mut breached_passwords = HashMap::<String, HashSet<String>>::new();
+        let additional_passwords = std::env::var("BREACHED_PASSWORDS_FILE")
+            .ok()
+            .and_then(|path| {
+                std::fs::read_to_string(path.clone())
+                    .map_err(|err| log::error!("Failed to read breached passwords {path}: {err}"))
+                    .ok()
+            })
+            .unwrap_or_default();
+
+        for line in BUNDLED_BREACHED_PASSWORDS
+            .lines()
+            .chain(additional_passwords.lines())
+        {
+            let hash = line.split(':').next().unwrap_or_default().trim();
+            if hash.len() > HASH_PREFIX_LENGTH {
+                let (prefix, suffix) = hash.split_at(HASH_PREFIX_LENGTH);
+                breached_passwords
+                    .entry(prefix.to_uppercase())
+                    .or_default()
+                    .insert(suffix.to_uppercase());
+            }
+        }
+
+        breached_passwords
+    })
+}
+
+fn is_locally_breached(prefix: &str, suffix: &str) -> bool {
+    get_breached_passwords()
+        .get(prefix)
+        .map(|suffixes| suffixes.contains(suffix))
+        .unwrap_or(false)
+}
+
+/// Only the first five characters of the hash leave the server, the api answers with all suffixes of that prefix.
+async fn is_breached_online(api_url: &str, prefix: &str, suffix: &str) -> Option<bool> {
+    let response = reqwest::Client::new()
+        .get(format!("{}/{prefix}", api_url.trim_end_matches('/')))
+        .header("Add-Padding", "true")
+        .timeout(BREACHED_PASSWORDS_API_TIMEOUT)
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(|err| log::warn!("Failed to look up breached passwords {err}"))
+        .ok()?;
+    let body = response
+        .text()
+        .await
+        .map_err(|err| log::warn!("Failed to read breached passwords {err}"))
+        .ok()?;
+
+    Some(body.lines().any(|line| {
+        line.split_once(':')
+            .map(|(breached_suffix, count)| {
+                breached_suffix.trim().eq_ignore_ascii_case(suffix)
+                    && count.trim().parse::<u64>().unwrap_or(0) > 0
+            })
+            .unwrap_or(false)
+    }))
+}
+
+async fn is_breached(password: &str, policy: &PasswordPolicy) -> bool {
+    let hash = format!("{:X}", Sha1::digest(password.as_bytes()));
+    let (prefix, suffix) = hash.split_at(HASH_PREFIX_LENGTH);
+
+    if is_locally_breached(prefix, suffix) {
+        return true;
+    }
+
+    if let Some(api_url) = &policy.breached_passwords_api_url {
+        is_breached_online(api_url, prefix, suffix)
+            .await
+            .unwrap_or(false)
+    } else {
+        false
+    }
+}
+
+fn charset_size(password: &str) -> f64 {
+    let mut size = 0;
+    if password.chars().any(|char| char.is_ascii_lowercase()) {
+        size += 26;
+    }
+    if password.chars().any(|char| char.is_ascii_uppercase()) {
+        size += 26;
+    }
+    if password.chars().any(|char| char.is_ascii_digit()) {
+        size += 10;
+    }
+    if password
+        .chars()
+        .any(|char| char.is_ascii_punctuation() || char == ' ')
+    {
+        size += 33;
+    }
+    if !password.is_ascii() {
+        size += 100;
+    }
+
+    size.max(1) as f64
+}
+
+fn is_sequential(previous: char, current: char) -> bool {
+    let distance = current as i64 - previous as i64;
+    distance.abs() <= 1
+        || KEYBOARD_ROWS.iter().any(|row| {
+            row.find(previous)
+                .zip(row.find(current))
+                .map(|(previous, current)| previous.abs_diff(current) == 1)
+                .unwrap_or(false)
+        })
+}
+
+/// Estimates the strength on the zxcvbn scale from 0 (too guessable) to 4 (very unguessable).
+/// Dictionary words, personal data and years only count as a single guess from a small dictionary,
+/// repeated and sequential characters only count a quarter.
+fn estimate_strength(password: &str, user_inputs: &[String]) -> u8 {
+    let mut words = COMMON_WORDS
+        .iter()
+        .map(|word| word.to_string())
+        .chain(user_inputs.iter().map(|input| input.to_lowercase()))
+        .filter(|word| word.chars().count() >= 3)
+        .collect::<Vec<String>>();
+    // Longer words first, otherwise a common word inside the name would break up the name
+    words.sort_by_key(|word| std::cmp::Reverse(word.len()));
+
+    let mut remaining = password.to_lowercase();
+    let mut dictionary_bits = 0_f64;
+    for word in words {
+        while let Some(position) = remaining.find(word.as_str()) {
+            remaining.replace_range(position..position + word.len(), "");
+            dictionary_bits += 10.0;
+        }
+    }
+
+    // Years are about as guessable as a birthday, so they only count as one of a hundred guesses
+    for year in (YEAR_RANGE_START..YEAR_RANGE_START + 100).map(|year| year.to_string()) {
+        while let Some(position) = remaining.find(year.as_str()) {
+            remaining.replace_range(position..position + year.len(), "");
+            dictionary_bits += 100_f64.log2();
+        }
+    }
+
+    let mut effective_length = 0_f64;
+    let mut previous = None as Option<char>;
+    for current in remaining.chars() {
+        effective_length += match previous {
+            Some(previous) if is_sequential(previous, current) => 0.25,
+            _ => 1.0,
+        };
+        previous = Some(current);
+    }
+
+    let bits = effective_length * charset_size(password).log2() + dictionary_bits;
+    let guesses_log10 = bits * std::f64::consts::LOG10_2;
+
+    match guesses_log10 {
+        guesses if guesses < 3.0 => 0,
+        guesses if guesses < 6.0 => 1,
+        guesses if guesses < 8.0 => 2,
+        guesses if guesses < 10.0 => 3,
+        _ => 4,
+    }
+}
+
+/// Only the local part of an email is personal, the domain is shared with lots of other pandas.
+fn get_user_inputs(user_inputs: &[String]) -> Vec<String> {
+    user_inputs
+        .iter()
+        .map(|input| {
+            input
+                .split_once('@')
+                .map(|(local_part, _)| local_part.to_string())
+                .unwrap_or(input.clone())
+        })
+        .flat_map(|input| {
+            input
+                .split(|char: char| !char.is_alphanumeric())
+                .map(|part| part.to_string())
+                .chain(std::iter::once(input.clone()))
+                .collect::<Vec<String>>()
+        })
+        .filter(|input| input.chars().count() >= 3)
+        .collect()
+}
+
+/// Checks the password against the configured policy. The user inputs, like the email and the name,
+/// must not be a significant part of the password.
+pub async fn check_password_policy(password: &str, user_inputs: &[String]) -> BambooErrorResult {
+    check_password_with_policy(password, user_inputs, &PasswordPolicy::from_env()).await
+}
+
+async fn check_password_with_policy(
+    password: &str,
+    user_inputs: &[String],
+    policy: &PasswordPolicy,
+) -> BambooErrorResult {
+    let user_inputs = get_user_inputs(user_inputs);
+    let mut details = vec![];
+
+    if password.chars().count() < policy.min_length {
+        details.push(BambooErrorDetail::new("password", "tooShort"));
+    }
+    if user_inputs.iter().any(|input| {
+        password
+            .to_lowercase()
+            .contains(input.to_lowercase().as_str())
+    }) {
+        details.push(BambooErrorDetail::new("password", "containsPersonalData"));
+    }
+    if estimate_strength(password, &user_inputs) < policy.min_strength {
+        details.push(BambooErrorDetail::new("password", "tooWeak"));
+    }
+    if policy.check_breached && is_breached(password, policy).await {
+        details.push(BambooErrorDetail::new("password", "breached"));
+    }
+
+    if details.is_empty() {
+        Ok(())
+    } else {
+        Err(
+            BambooError::validation("user", "The password does not match the password policy")
+                .with_details(details),
+        )
+    }
+}
+
+/// Generates a password for a new panda or a reset by a mod, it is at least as long as the policy requires and strong
+/// enough. A random password isn't in any breach, so the breached passwords are not checked.
+pub async fn generate_password(user_inputs: &[String]) -> BambooResult<String> {
+    let policy = PasswordPolicy {
+        check_breached: false,
+        ..PasswordPolicy::from_env()
+    };
+    let length = policy.min_length.max(GENERATED_PASSWORD_MIN_LENGTH);
+
+    for _ in 0..GENERATED_PASSWORD_MAX_ATTEMPTS {
+        let password = rand::thread_rng()
+            .sample_iter(&Alphanumeric)
+            .take(length)
+            .map(char::from)
+            .collect::<String>();
+        if check_password_with_policy(password.as_str(), user_inputs, &policy)
+            .await
+            .is_ok()
+        {
+            return Ok(password);
+        }
+    }
+
+    Err(BambooError::unknown(
+        "user",
+        "Failed to generate a password that matches the password policy",
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn offline_policy() -> PasswordPolicy {
+        PasswordPolicy {
+            min_length: 10,
+            min_strength: 3,
+            check_breached: true,
+            breached_passwords_api_url: None,
+        }
+    }
+
+    fn detail_codes(result: BambooErrorResult) -> Vec<String> {
+        result
+            .err()
+            .map(|err| err.details.into_iter().map(|detail| detail.code).collect())
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn common_passwords_are_too_guessable() {
+        assert!(estimate_strength("password", &[]) < 2);
+        assert!(estimate_strength("passwort123", &[]) < 3);
+        assert!(estimate_strength("Sommer2024!", &[]) < 3);
+    }
+
+    #[test]
+    fn sequences_and_repetitions_are_weak() {
+        assert!(estimate_strength("1234567890", &[]) < 2);
+        assert!(estimate_strength("qwertzuiop", &[]) < 2);
+        assert!(estimate_strength("aaaaaaaaaaaa", &[]) < 2);
+    }
+
+    #[test]
+    fn random_passwords_are_strong() {
+        assert_eq!(estimate_strength("vT7#qL2m!Xe9Rz", &[]), 4);
+        assert_eq!(estimate_strength("k3Hw9QpZ7sLm2VbX", &[]), 4);
+    }
+
+    #[test]
+    fn long_passphrases_are_strong() {
+        assert!(estimate_strength("grüner tee mit sieben zitronen", &[]) >= 3);
+    }
+
+    #[test]
+    fn personal_data_only_counts_as_one_word() {
+        let user_inputs = get_user_inputs(&["lieblingspanda@bambushain.app".to_string()]);
+
+        assert!(
+            estimate_strength("lieblingspanda1", &user_inputs)
+                < estimate_strength("lieblingspanda1", &[])
+        );
+    }
+
+    #[test]
+    fn user_inputs_are_split_into_parts() {
+        let user_inputs = get_user_inputs(&[
+            "Bamboo Panda".to_string(),
+            "red.panda@gmail.com".to_string(),
+            "ab@cd.de".to_string(),
+        ]);
+
+        assert!(user_inputs.contains(&"Bamboo".to_string()));
+        assert!(user_inputs.contains(&"Panda".to_string()));
+        assert!(user_inputs.contains(&"Bamboo Panda".to_string()));
+        assert!(user_inputs.contains(&"red.panda".to_string()));
+        assert!(user_inputs.contains(&"red".to_string()));
+        assert!(!user_inputs.contains(&"ab".to_string()));
+    }
+
+    #[test]
+    fn the_domain_of_the_email_is_not_personal() {
+        let user_inputs = get_user_inputs(&["red.panda@gmail.com".to_string()]);
+
+        assert!(!user_inputs.iter().any(|input| input.contains("gmail")));
+        assert!(!user_inputs.iter().any(|input| input.contains("com")));
+    }
+
+    #[test]
+    fn bundled_breached_passwords_are_found() {
+        let hash = format!("{:X}", Sha1::digest("password".as_bytes()));
+        let (prefix, suffix) = hash.split_at(HASH_PREFIX_LENGTH);
+
+        assert!(is_locally_breached(prefix, suffix));
+    }
+
+    #[actix_web::test]
+    async fn weak_passwords_violate_the_policy() {
+        let codes = detail_codes(
+            check_password_with_policy("password", &["Panda".to_string()], &offline_policy()).await,
+        );
+
+        assert!(codes.contains(&"tooShort".to_string()));
+        assert!(codes.contains(&"tooWeak".to_string()));
+        assert!(codes.contains(&"breached".to_string()));
+        assert!(!codes.contains(&"containsPersonalData".to_string()));
+    }
+
+    #[actix_web::test]
+    async fn passwords_must_not_contain_personal_data() {
+        let codes = detail_codes(
+            check_password_with_policy(
+                "xQ7!mightypanda#R2",
+                &["mightypanda@bambushain.app".to_string()],
+                &offline_policy(),
+            )
+            .await,
+        );
+
+        assert_eq!(codes, vec!["containsPersonalData".to_string()]);
+    }
+
+    #[actix_web::test]
+    async fn generated_passwords_match_the_policy() {
+        let password = generate_password(&["Panda".to_string()]).await.unwrap();
+
+        assert!(check_password_with_policy(
+            password.as_str(),
+            &["Panda".to_string()],
+            &offline_policy()
+        )
+        .await
+        .is_ok());
+    }
+
+    #[actix_web::test]
+    async fn strong_passwords_match_the_policy() {
+        assert!(check_password_with_policy(
+            "vT7#qL2m!Xe9Rz",
+            &["Panda".to_string()],
+            &offline_policy()
+        )
+        .await
+        .is_ok());
+    }
+}
@@ -0,0 +1,224 @@
+use base64::Engine;
+use chrono::{Duration, Utc};
+use rand::RngCore;
+use sea_orm::prelude::*;
+use sea_orm::sea_query::Expr;
+use sea_orm::{NotSet, Set};
+use sha2::{Digest, Sha256};
+
+use bamboo_common_core::entities::*;
+use bamboo_common_core::error::*;
+
+use crate as dbal;
+
+const PASSWORD_RESET_TOKEN_VALID_MINUTES: i64 = 60;
+pub const IMPORTED_USER_PASSWORD_TOKEN_VALID_DAYS: i64 = 14;
+
+fn hash_reset_token(token: &str) -> String {
+    format!("{:x}", Sha256::digest(token.as_bytes()))
+}
+
+pub async fn create_password_reset_token(
+    user_id: i32,
+    db: &DatabaseConnection,
+) -> BambooResult<String> {
+    insert_password_reset_token(
+        user_id,
+        Duration::minutes(PASSWORD_RESET_TOKEN_VALID_MINUTES),
+        db,
+    )
+    .await
+}
+
+/// Imported pandas set their first password with a reset link, they get more time than for a forgotten password.
+pub async fn create_imported_user_password_token(
+    user_id: i32,
+    db: &DatabaseConnection,
+) -> BambooResult<String> {
+    insert_password_reset_token(
+        user_id,
+        Duration::days(IMPORTED_USER_PASSWORD_TOKEN_VALID_DAYS),
+        db,
+    )
+    .await
+}
+
+async fn insert_password_reset_token(
+    user_id: i32,
+    valid_for: Duration,
+    db: &DatabaseConnection,
+) -> BambooResult<String> {
+    let mut token = [0_u8; 32];
+    rand::thread_rng().fill_bytes(&mut token);
+    let token = base64::prelude::BASE64_URL_SAFE_NO_PAD.encode(token);
+
+    password_reset_token::Entity::delete_many()
+        .filter(password_reset_token::Column::UserId.eq(user_id))
+        .exec(db)
+        .await
+        .map_err(|err| {
+            log::error!("{err}");
+            BambooError::database("user", "Failed to create password reset token")
+        })?;
+
+    password_reset_token::ActiveModel {
+        id: NotSet,
+        user_id: Set(user_id),
+        token_hash: Set(hash_reset_token(token.as_str())),
+        expires_at: Set(Utc::now().naive_utc() + valid_for),
+    }
+    .insert(db)
+    .await
+    .map_err(|err| {
+        log::error!("{err}");
+        BambooError::database("user", "Failed to create password reset token")
+    })
+    .map(|_| token)
+}
+
+/// A reset link only proves access to the mailbox, so users with an authenticator app have to confirm the reset with
+/// a code from the app or a recovery code. Returns whether the totp secret can still be read after the reset, secrets
+/// encrypted with the old password can't and have to be set up again.
+async fn validate_reset_second_factor(
+    user: &User,
+    two_factor_code: Option<String>,
+    db: &DatabaseConnection,
+) -> BambooResult<bool> {
+    if !user.totp_validated.unwrap_or(false) {
+        return Ok(true);
+    }
+
+    let code = two_factor_code
+        .filter(|code| !code.trim().is_empty())
+        .ok_or(BambooError::unauthorized(
+            "user",
+            "The two factor code is required",
+        ))?;
+    dbal::check_login_lockout(Some(user.id), None, db).await?;
+
+    let secret_is_readable = !user.totp_secret_encrypted || user.totp_secret_key_id.is_some();
+
+    let totp_secret = if !secret_is_readable {
+        None
+    } else if user.totp_secret_encrypted {
+        user.totp_secret.clone().and_then(|secret| {
+            dbal::decrypt_secret(secret, user.totp_secret_key_id.clone(), String::new()).ok()
+        })
+    } else {
+        user.totp_secret.clone()
+    };
+
+    let totp_is_valid = totp_secret.is_some_and(|secret| {
+        dbal::check_totp_code(secret, code.clone(), user.display_name.clone()).is_ok()
+    });
+    if !totp_is_valid {
+        if let Err(err) = dbal::use_totp_recovery_code(user.id, code, db).await {
+            let _ = dbal::register_failed_login(Some(user.id), None, db).await;
+            return Err(err);
+        }
+    }
+
+    Ok(secret_is_readable)
+}
+
+pub async fn reset_password_with_token(
+    token: String,
+    new_password: String,
+    two_factor_code: Option<String>,
+    db: &DatabaseConnection,
+) -> BambooErrorResult {
+    let reset_token = password_reset_token::Entity::find()
+        .filter(password_reset_token::Column::TokenHash.eq(hash_reset_token(token.as_str())))
+        .one(db)
+        .await
+        .map_err(|err| {
+            log::error!("{err}");
+            BambooError::database("user", "Failed to load password reset token")
+        })?
+        .ok_or(BambooError::validation(
+            "user",
+            "The password reset link is invalid or expired",
+        ))?;
+
+    if reset_token.expires_at < Utc::now().naive_utc() {
+        delete_password_reset_token(reset_token.id, db).await?;
+
+        return Err(BambooError::validation(
+            "user",
+            "The password reset link is invalid or expired",
+        ));
+    }
+
+    let user = dbal::get_user_by_id_only(reset_token.user_id, db).await?;
+    dbal::check_password_policy(
+        new_password.as_str(),
+        &[user.email.clone(), user.display_name.clone()],
+    )
+    .await?;
+
+    let keeps_totp = validate_reset_second_factor(&user, two_factor_code, db).await?;
+
+    delete_password_reset_token(reset_token.id, db).await?;
+    set_reset_password(&user, new_password, keeps_totp, db).await?;
+    dbal::reset_failed_logins(user.id, db).await
+}
+
+/// The second factor stays enabled, unless its secret was encrypted with the old password.
+pub(crate) async fn set_reset_password(
+    user: &User,
+    new_password: String,
+    keeps_totp: bool,
+    db: &DatabaseConnection,
+) -> BambooErrorResult {
+    let hashed_password = user::hash_password(new_password.as_str()).map_err(|err| {
+        log::error!("{err}");
+        BambooError::unknown("user", "Failed to hash the password")
+    })?;
+
+    token::Entity::delete_many()
+        .filter(token::Column::UserId.eq(user.id))
+        .exec(db)
+        .await
+        .map_err(|err| {
+            log::error!("{err}");
+            BambooError::database("user", "Failed to update user")
+        })?;
+
+    let mut update = user::Entity::update_many()
+        .col_expr(user::Column::Password, Expr::value(hashed_password))
+        .filter(user::Column::Id.eq(user.id));
+    if !keeps_totp {
+        dbal::delete_totp_recovery_codes(user.id, db).await?;
+        update = update
+            .col_expr(
+                user::Column::TotpSecret,
+                Expr::value::<Option<Vec<u8>>>(None),
+            )
+            .col_expr(user::Column::TotpSecretEncrypted, Expr::value(false))
+            .col_expr(
+                user::Column::TotpSecretKeyId,
+                Expr::value::<Option<String>>(None),
+            )
+            .col_expr(user::Column::TotpValidated, Expr::value(false));
+    }
+
+    update
+        .exec(db)
+        .await
+        .map_err(|err| {
+            log::error!("{err}");
+            BambooError::database("user", "Failed to update user")
+        })
+        .map(|_| ())
+}
+
+async fn delete_password_reset_token(id: i32, db: &DatabaseConnection) -> BambooErrorResult {
+    password_reset_token::Entity::delete_by_id(id)
+        .exec(db)
+        .await
+        .map(|_| ())
+        .map_err(|err| {
+            log::error!("{err}");
+            BambooError::database("user", "Failed to invalidate password reset token")
+        })
+}
@@ -0,0 +1,170 @@
+use base64::Engine;
+use chrono::Utc;
+use rand::RngCore;
+use sea_orm::prelude::*;
+use sea_orm::{IntoActiveModel, NotSet, QueryOrder, Set};
+use sha2::{Digest, Sha256};
+
+use bamboo_common_core::entities::*;
+use bamboo_common_core::error::*;
+
+use crate as dbal;
+
+const PERSONAL_ACCESS_TOKEN_PREFIX: &str = "bamboo_pat_";
+
+fn hash_personal_access_token(token: &str) -> String {
+    format!("{:x}", Sha256::digest(token.as_bytes()))
+}
+
+pub async fn get_personal_access_tokens(
+    user_id: i32,
+    db: &DatabaseConnection,
+) -> BambooResult<Vec<PersonalAccessToken>> {
+    personal_access_token::Entity::find()
+        .filter(personal_access_token::Column::UserId.eq(user_id))
+        .order_by_asc(personal_access_token::Column::CreatedAt)
+        .all(db)
+        .await
+        .map_err(|err| {
+            log::error!("{err}");
+            BambooError::database(
+                "personal_access_token",
+                "Failed to load personal access tokens",
+            )
+        })
+}
+
+pub async fn create_personal_access_token(
+    user_id: i32,
+    data: CreatePersonalAccessToken,
+    db: &DatabaseConnection,
+) -> BambooResult<CreatedPersonalAccessToken> {
+    let now = Utc::now().naive_utc();
+    if data.name.trim().is_empty() {
+        return Err(BambooError::validation(
+            "personal_access_token",
+            "The name must not be empty",
+        ));
+    }
+    if data.scopes.is_empty() {
+        return Err(BambooError::validation(
+            "personal_access_token",
+            "At least one scope is required",
+        ));
+    }
+    if data.expires_at.is_some_and(|expires_at| expires_at <= now) {
+        return Err(BambooError::validation(
+            "personal_access_token",
+            "The expiry date must be in the future",
+        ));
+    }
+
+    let mut scopes = data.scopes;
+    scopes.sort();
+    scopes.dedup();
+
+    let mut token = [0_u8; 32];
+    rand::thread_rng().fill_bytes(&mut token);
+    let token = format!(
+        "{PERSONAL_ACCESS_TOKEN_PREFIX}{}",
+        base64::prelude::BASE64_URL_SAFE_NO_PAD.encode(token)
+    );
+
+    personal_access_token::ActiveModel {
+        id: NotSet,
+        user_id: Set(user_id),
+        name: Set(data.name.trim().to_string()),
+        token_hash: Set(hash_personal_access_token(token.as_str())),
+        scopes: Set(TokenScopes(scopes)),
+        created_at: Set(now),
+        expires_at: Set(data.expires_at),
+        last_used_at: Set(None),
+    }
+    .insert(db)
+    .await
+    .map_err(|err| {
+        log::error!("{err}");
+        BambooError::database(
+            "personal_access_token",
+            "Failed to create personal access token",
+        )
+    })
+    .map(|personal_access_token| CreatedPersonalAccessToken {
+        personal_access_token,
+        token,
+    })
+}
+
+pub async fn delete_personal_access_token(
+    user_id: i32,
+    token_id: i32,
+    db: &DatabaseConnection,
+) -> BambooErrorResult {
+    let result = personal_access_token::Entity::delete_many()
+        .filter(personal_access_token::Column::Id.eq(token_id))
+        .filter(personal_access_token::Column::UserId.eq(user_id))
+        .exec(db)
+        .await
+        .map_err(|err| {
+            log::error!("{err}");
+            BambooError::database(
+                "personal_access_token",
+                "Failed to delete personal access token",
+            )
+        })?;
+
+    if result.rows_affected == 0 {
+        Err(BambooError::not_found(
+            "personal_access_token",
+            "The personal access token was not found",
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+pub async fn get_user_by_personal_access_token(
+    token: String,
+    db: &DatabaseConnection,
+) -> BambooResult<(User, PersonalAccessToken)> {
+    let unauthorized =
+        BambooError::unauthorized("authentication", "Personal access token not found");
+    let personal_access_token = personal_access_token::Entity::find()
+        .filter(
+            personal_access_token::Column::TokenHash.eq(hash_personal_access_token(token.as_str())),
+        )
+        .one(db)
+        .await
+        .map_err(|err| {
+            log::error!("{err}");
+            unauthorized.clone()
+        })?
+        .ok_or(unauthorized.clone())?;
+
+    let now = Utc::now().naive_utc();
+    if personal_access_token
+        .expires_at
+        .is_some_and(|expires_at| expires_at <= now)
+    {
+        return Err(BambooError::unauthorized(
+            "authentication",
+            "Personal access token expired",
+        ));
+    }
+
+    let user = dbal::get_user_by_id_only(personal_access_token.user_id, db)
+        .await
+        .map_err(|_| unauthorized)?;
+
+    let mut active_model = personal_access_token.into_active_model();
+    active_model.last_used_at = Set(Some(now));
+    let personal_access_token = active_model.update(db).await.map_err(|err| {
+        log::error!("{err}");
+        BambooError::database(
+            "personal_access_token",
+            "Failed to update personal access token",
+        )
+    })?;
+
+    Ok((user, personal_access_token))
+}
@@ -0,0 +1,111 @@
+use rand::distributions::{Alphanumeric, DistString};
+use sea_orm::prelude::*;
+use sea_orm::{NotSet, PaginatorTrait, Set};
+use sha2::{Digest, Sha256};
+
+use bamboo_common_core::entities::*;
+use bamboo_common_core::error::*;
+
+const TOTP_RECOVERY_CODE_COUNT: usize = 10;
+const TOTP_RECOVERY_CODE_PART_LENGTH: usize = 5;
+
+fn normalize_recovery_code(code: &str) -> String {
+    code.trim().replace('-', "").to_lowercase()
+}
+
+fn hash_recovery_code(code: &str) -> String {
+    format!(
+        "{:x}",
+        Sha256::digest(normalize_recovery_code(code).as_bytes())
+    )
+}
+
+fn generate_recovery_code() -> String {
+    let code = Alphanumeric
+        .sample_string(&mut rand::thread_rng(), TOTP_RECOVERY_CODE_PART_LENGTH * 2)
+        .to_lowercase();
+
+    format!(
+        "{}-{}",
+        &code[..TOTP_RECOVERY_CODE_PART_LENGTH],
+        &code[TOTP_RECOVERY_CODE_PART_LENGTH..]
+    )
+}
+
+pub async fn create_totp_recovery_codes(
+    user_id: i32,
+    db: &DatabaseConnection,
+) -> BambooResult<TotpRecoveryCodes> {
+    delete_totp_recovery_codes(user_id, db).await?;
+
+    let codes = (0..TOTP_RECOVERY_CODE_COUNT)
+        .map(|_| generate_recovery_code())
+        .collect::<Vec<String>>();
+
+    totp_recovery_code::Entity::insert_many(codes.iter().map(|code| {
+        totp_recovery_code::ActiveModel {
+            id: NotSet,
+            user_id: Set(user_id),
+            code_hash: Set(hash_recovery_code(code.as_str())),
+        }
+    }))
+    .exec(db)
+    .await
+    .map_err(|err| {
+        log::error!("{err}");
+        BambooError::database("user", "Failed to create recovery codes")
+    })
+    .map(|_| TotpRecoveryCodes { codes })
+}
+
+pub async fn count_totp_recovery_codes(
+    user_id: i32,
+    db: &DatabaseConnection,
+) -> BambooResult<TotpRecoveryCodeCount> {
+    totp_recovery_code::Entity::find()
+        .filter(totp_recovery_code::Column::UserId.eq(user_id))
+        .count(db)
+        .await
+        .map_err(|err| {
+            log::error!("{err}");
+            BambooError::database("user", "Failed to count recovery codes")
+        })
+        .map(|remaining| TotpRecoveryCodeCount { remaining })
+}
+
+pub async fn use_totp_recovery_code(
+    user_id: i32,
+    code: String,
+    db: &DatabaseConnection,
+) -> BambooErrorResult {
+    let result = totp_recovery_code::Entity::delete_many()
+        .filter(totp_recovery_code::Column::UserId.eq(user_id))
+        .filter(totp_recovery_code::Column::CodeHash.eq(hash_recovery_code(code.as_str())))
+        .exec(db)
+        .await
+        .map_err(|err| {
+            log::error!("{err}");
+            BambooError::database("user", "Failed to validate recovery code")
+        })?;
+
+    if result.rows_affected == 0 {
+        Err(BambooError::unauthorized("user", "Failed to validate"))
+    } else {
+        Ok(())
+    }
+}
+
+pub async fn delete_totp_recovery_codes(
+    user_id: i32,
+    db: &DatabaseConnection,
+) -> BambooErrorResult {
+    totp_recovery_code::Entity::delete_many()
+        .filter(totp_recovery_code::Column::UserId.eq(user_id))
+        .exec(db)
+        .await
+        .map_err(|err| {
+            log::error!("{err}");
+            BambooError::database("user", "Failed to delete recovery codes")
+        })
+        .map(|_| ())
+}
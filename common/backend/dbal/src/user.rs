@@ -1,8 +1,8 @@
 use sea_orm::prelude::*;
 use sea_orm::sea_query::Expr;
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, Condition, DatabaseConnection, EntityTrait, IntoActiveModel,
-    JoinType, NotSet, QueryFilter, QueryOrder, QuerySelect, RelationTrait, Set,
+    ActiveModelTrait, ColumnTrait, Condition, ConnectionTrait, DatabaseConnection, EntityTrait,
+    IntoActiveModel, JoinType, NotSet, QueryFilter, QueryOrder, QuerySelect, RelationTrait, Set,
 };
 
 use bamboo_common_core::entities::*;
@@ -27,9 +27,17 @@ pub async fn get_user_by_id_only(id: i32, db: &DatabaseConnection) -> BambooResu
         })?
 }
 
+fn apply_membership(data: (User, Option<GroveMembership>)) -> User {
+    match data {
+        (user, Some(membership)) => user.with_membership(&membership),
+        (user, None) => user,
+    }
+}
+
 pub async fn get_user(grove_id: i32, id: i32, db: &DatabaseConnection) -> BambooResult<User> {
     user::Entity::find_by_id(id)
-        .filter(user::Column::GroveId.eq(grove_id))
+        .find_also_related(grove_membership::Entity)
+        .filter(grove_membership::Column::GroveId.eq(grove_id))
         .one(db)
         .await
         .map_err(|err| {
@@ -38,7 +46,7 @@ pub async fn get_user(grove_id: i32, id: i32, db: &DatabaseConnection) -> Bamboo
         })
         .map(|data| {
             if let Some(data) = data {
-                Ok(data)
+                Ok(apply_membership(data))
             } else {
                 Err(BambooError::not_found("user", "The user was not found"))
             }
@@ -79,22 +87,10 @@ pub async fn get_user_by_email_or_username(
                         .add(user::Column::Email.eq(username.clone()))
                         .add(user::Column::DisplayName.eq(username)),
                 )
-                .add(
-                    Condition::any()
-                        .add(
-                            Condition::all()
-                                .add(grove::Column::IsSuspended.eq(false))
-                                .add(grove::Column::IsEnabled.eq(true)),
-                        )
-                        .add(
-                            Condition::all()
-                                .add(grove::Column::IsSuspended.eq(false))
-                                .add(grove::Column::IsEnabled.eq(false))
-                                .add(user::Column::IsMod.eq(true)),
-                        ),
-                ),
+                .add(dbal::usable_grove_condition()),
         )
-        .inner_join(grove::Entity)
+        .join(JoinType::InnerJoin, user::Relation::GroveMembership.def())
+        .join(JoinType::InnerJoin, grove_membership::Relation::Grove.def())
         .one(db)
         .await
         .map_err(|err| {
@@ -112,10 +108,12 @@ pub async fn get_user_by_email_or_username(
 
 pub async fn get_users(grove_id: i32, db: &DatabaseConnection) -> BambooResult<Vec<User>> {
     user::Entity::find()
-        .filter(user::Column::GroveId.eq(grove_id))
+        .find_also_related(grove_membership::Entity)
+        .filter(grove_membership::Column::GroveId.eq(grove_id))
         .order_by_asc(user::Column::DisplayName)
         .all(db)
         .await
+        .map(|data| data.into_iter().map(apply_membership).collect())
         .map_err(|err| {
             log::error!("{err}");
             BambooError::database("user", "Failed to load users")
@@ -129,30 +127,42 @@ pub async fn get_users_with_mod_rights(
     let grove = dbal::get_grove_by_user_id(user_id, db).await?;
 
     user::Entity::find()
-        .filter(user::Column::IsMod.eq(true))
-        .filter(user::Column::GroveId.eq(grove.id))
+        .find_also_related(grove_membership::Entity)
+        .filter(grove_membership::Column::IsMod.eq(true))
+        .filter(grove_membership::Column::GroveId.eq(grove.id))
         .order_by_asc(user::Column::DisplayName)
         .all(db)
         .await
+        .map(|data| data.into_iter().map(apply_membership).collect())
         .map_err(|err| {
             log::error!("{err}");
             BambooError::database("user", "Failed to load users")
         })
 }
 
-pub(crate) async fn user_exists_by_id(
+pub(crate) async fn display_name_exists_by_id(
     id: i32,
-    email: String,
     name: String,
     db: &DatabaseConnection,
 ) -> BambooResult<bool> {
     user::Entity::find()
         .filter(user::Column::Id.ne(id))
-        .filter(
-            Condition::any()
-                .add(user::Column::Email.eq(email))
-                .add(user::Column::DisplayName.eq(name)),
-        )
+        .filter(user::Column::DisplayName.eq(name))
+        .count(db)
+        .await
+        .map(|count| count > 0)
+        .map_err(|err| {
+            log::error!("Failed to load users {err}");
+            BambooError::database("user", "Failed to load users")
+        })
+}
+
+pub(crate) async fn display_name_exists(
+    name: String,
+    db: &impl ConnectionTrait,
+) -> BambooResult<bool> {
+    user::Entity::find()
+        .filter(user::Column::DisplayName.eq(name))
         .count(db)
         .await
         .map(|count| count > 0)
@@ -195,6 +205,11 @@ pub async fn create_user(
         ));
     }
 
+    if let Some(role_id) = user.role_id {
+        dbal::get_grove_role(grove_id, role_id, db).await?;
+    }
+
+    let (is_mod, role_id) = (user.is_mod, user.role_id);
     let mut model = user.into_active_model();
     model.id = NotSet;
     model.grove_id = Set(grove_id);
@@ -203,22 +218,55 @@ pub async fn create_user(
         BambooError::database("user", "Failed to hash password user")
     })?;
 
-    model.insert(db).await.map_err(|err| {
+    let user = model.insert(db).await.map_err(|err| {
         log::error!("{err}");
         BambooError::database("user", "Failed to create user")
-    })
+    })?;
+    let membership = dbal::create_grove_membership(grove_id, user.id, is_mod, role_id, db).await?;
+
+    Ok(user.with_membership(&membership))
 }
 
-pub async fn delete_user(grove_id: i32, id: i32, db: &DatabaseConnection) -> BambooErrorResult {
-    user::Entity::delete_by_id(id)
-        .filter(user::Column::GroveId.eq(grove_id))
-        .exec(db)
+/// Adds an existing account to the grove after it accepted an invitation, returns `None` if there is no account with the email.
+pub async fn add_user_to_grove(
+    grove_id: i32,
+    email: String,
+    is_mod: bool,
+    role_id: Option<i32>,
+    db: &DatabaseConnection,
+) -> BambooResult<Option<User>> {
+    let Some(user) = user::Entity::find()
+        .filter(user::Column::Email.eq(email))
+        .one(db)
         .await
         .map_err(|err| {
             log::error!("{err}");
-            BambooError::database("user", "Failed to delete user")
-        })
-        .map(|_| ())
+            BambooError::database("user", "Failed to load user")
+        })?
+    else {
+        return Ok(None);
+    };
+
+    if dbal::get_grove_membership(grove_id, user.id, db)
+        .await
+        .is_ok()
+    {
+        return Err(BambooError::exists_already(
+            "user",
+            "The user is already a member of the grove",
+        ));
+    }
+    if let Some(role_id) = role_id {
+        dbal::get_grove_role(grove_id, role_id, db).await?;
+    }
+
+    let membership = dbal::create_grove_membership(grove_id, user.id, is_mod, role_id, db).await?;
+
+    Ok(Some(user.with_membership(&membership)))
+}
+
+pub async fn delete_user(grove_id: i32, id: i32, db: &DatabaseConnection) -> BambooErrorResult {
+    dbal::leave_grove(grove_id, id, db).await
 }
 
 pub async fn change_mod_status(
@@ -227,68 +275,48 @@ pub async fn change_mod_status(
     is_mod: bool,
     db: &DatabaseConnection,
 ) -> BambooErrorResult {
-    user::Entity::update_many()
-        .filter(user::Column::GroveId.eq(grove_id))
-        .filter(user::Column::Id.eq(id))
-        .col_expr(user::Column::IsMod, Expr::value(is_mod))
-        .exec(db)
+    let txn = dbal::begin_transaction(db).await?;
+    if !is_mod {
+        dbal::ensure_grove_keeps_mod(grove_id, id, &txn).await?;
+    }
+
+    grove_membership::Entity::update_many()
+        .filter(grove_membership::Column::GroveId.eq(grove_id))
+        .filter(grove_membership::Column::UserId.eq(id))
+        .col_expr(grove_membership::Column::IsMod, Expr::value(is_mod))
+        .exec(&txn)
         .await
         .map_err(|err| {
             log::error!("{err}");
             BambooError::database("user", "Failed to update user")
-        })
-        .map(|_| ())
+        })?;
+
+    dbal::commit_transaction(txn).await
 }
 
+/// Legacy totp secrets encrypted with the old password can't be read after the reset and are removed, secrets
+/// encrypted with a server side key stay enabled.
 pub async fn change_password(
     grove_id: i32,
     id: i32,
     password: String,
     db: &DatabaseConnection,
 ) -> BambooErrorResult {
-    let hashed_password = bcrypt::hash(password, 12).map_err(|err| {
-        log::error!("{err}");
-        BambooError::unknown("user", "Failed to hash the password")
-    })?;
+    let user = get_user(grove_id, id, db).await?;
+    let keeps_totp = !user.totp_secret_encrypted || user.totp_secret_key_id.is_some();
 
-    token::Entity::delete_many()
-        .filter(token::Column::UserId.eq(id))
-        .exec(db)
-        .await
-        .map_err(|err| {
-            log::error!("{err}");
-            BambooError::database("user", "Failed to update user")
-        })
-        .map(|_| ())?;
-
-    user::Entity::update_many()
-        .col_expr(user::Column::Password, Expr::value(hashed_password))
-        .col_expr(
-            user::Column::TotpSecret,
-            Expr::value::<Option<Vec<u8>>>(None),
-        )
-        .col_expr(user::Column::TotpSecretEncrypted, Expr::value(false))
-        .col_expr(user::Column::TotpValidated, Expr::value(false))
-        .filter(user::Column::GroveId.eq(grove_id))
-        .filter(user::Column::Id.eq(id))
-        .exec(db)
-        .await
-        .map_err(|err| {
-            log::error!("{err}");
-            BambooError::database("user", "Failed to update user")
-        })
-        .map(|_| ())
+    dbal::set_reset_password(&user, password, keeps_totp, db).await
 }
 
 pub async fn update_profile(
     grove_id: i32,
     id: i32,
-    email: String,
     display_name: String,
     discord_name: String,
     db: &DatabaseConnection,
 ) -> BambooErrorResult {
-    if user_exists_by_id(id, email.clone(), display_name.clone(), db).await? {
+    get_user(grove_id, id, db).await?;
+    if display_name_exists_by_id(id, display_name.clone(), db).await? {
         return Err(BambooError::exists_already(
             "user",
             "A user with that email or name exists already",
@@ -296,10 +324,8 @@ pub async fn update_profile(
     }
 
     user::Entity::update_many()
-        .col_expr(user::Column::Email, Expr::value(email))
         .col_expr(user::Column::DisplayName, Expr::value(display_name))
         .col_expr(user::Column::DiscordName, Expr::value(discord_name))
-        .filter(user::Column::GroveId.eq(grove_id))
         .filter(user::Column::Id.eq(id))
         .exec(db)
         .await
@@ -311,6 +337,9 @@ pub async fn update_profile(
 }
 
 pub async fn disable_totp(grove_id: i32, id: i32, db: &DatabaseConnection) -> BambooErrorResult {
+    get_user(grove_id, id, db).await?;
+    dbal::delete_totp_recovery_codes(id, db).await?;
+
     user::Entity::update_many()
         .col_expr(
             user::Column::TotpSecret,
@@ -318,8 +347,11 @@ pub async fn disable_totp(grove_id: i32, id: i32, db: &DatabaseConnection) -> Ba
         )
         .col_expr(user::Column::TotpValidated, Expr::value(false))
         .col_expr(user::Column::TotpSecretEncrypted, Expr::value(false))
+        .col_expr(
+            user::Column::TotpSecretKeyId,
+            Expr::value::<Option<String>>(None),
+        )
         .filter(user::Column::Id.eq(id))
-        .filter(user::Column::GroveId.eq(grove_id))
         .exec(db)
         .await
         .map_err(|_| BambooError::database("user", "Failed to disable totp"))
@@ -331,10 +363,12 @@ pub async fn get_users_filtered_for_management(
     db: &DatabaseConnection,
 ) -> BambooResult<Vec<User>> {
     user::Entity::find()
-        .filter(user::Column::GroveId.eq(grove_id))
+        .find_also_related(grove_membership::Entity)
+        .filter(grove_membership::Column::GroveId.eq(grove_id))
         .order_by_asc(user::Column::Id)
         .all(db)
         .await
+        .map(|data| data.into_iter().map(apply_membership).collect())
         .map_err(|err| {
             log::error!("{err}");
             BambooError::database("user", "Failed to load users")
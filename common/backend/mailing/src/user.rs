@@ -107,3 +107,295 @@ Alles Gute vom 🐼"#
         BambooError::mailing("Failed to send change password email")
     })
 }
+
+pub async fn send_email_change_confirmation(
+    display_name: String,
+    to: String,
+    confirm_link: String,
+    env_service: EnvService,
+) -> BambooErrorResult {
+    let env_service = env_service.clone();
+    let html_body = format!(
+        r#"
+<html lang="de" style="font-family: system-ui,-apple-system,'Segoe UI','Roboto','Ubuntu','Cantarell','Noto Sans',sans-serif,'Apple Color Emoji','Segoe UI Emoji','Segoe UI Symbol','Noto Color Emoji';">
+<head>
+
+</head>
+<body>
+    <article style="margin: 4rem 0; padding: 4rem 2rem; border-radius: 0.25rem; background: #fff; box-shadow: 0.0145rem 0.029rem 0.174rem rgba(27, 40, 50, 0.01698),0.0335rem 0.067rem 0.402rem rgba(27, 40, 50, 0.024),0.0625rem 0.125rem 0.75rem rgba(27, 40, 50, 0.03),0.1125rem 0.225rem 1.35rem rgba(27, 40, 50, 0.036),0.2085rem 0.417rem 2.502rem rgba(27, 40, 50, 0.04302),0.5rem 1rem 6rem rgba(27, 40, 50, 0.06),0 0 0 0.0625rem rgba(27, 40, 50, 0.015);">
+        Hallo {display_name},<br><br>
+        für deinen Account im Bambushain soll diese Email-Adresse hinterlegt werden. Bestätige die Änderung unter <a style="color: #598c79;text-decoration: none" href="{confirm_link}">{confirm_link}</a>. Der Link ist 24 Stunden gültig und funktioniert nur einmal.<br><br>
+        Bis du die Änderung bestätigst, bleibt deine alte Email-Adresse aktiv. Wenn du die Änderung nicht angefordert hast, kannst du diese Email einfach ignorieren.<br><br>
+        Alles Gute vom 🐼
+    </article>
+</body>
+</html>"#
+    );
+    let plain_body = format!(
+        r#"
+Hallo {display_name},
+
+für deinen Account im Bambushain soll diese Email-Adresse hinterlegt werden. Bestätige die Änderung unter {confirm_link}. Der Link ist 24 Stunden gültig und funktioniert nur einmal.
+
+Bis du die Änderung bestätigst, bleibt deine alte Email-Adresse aktiv. Wenn du die Änderung nicht angefordert hast, kannst du diese Email einfach ignorieren.
+
+Alles Gute vom 🐼"#
+    );
+
+    send_mail(
+        env_service,
+        "Bestätige deine neue Email-Adresse für den Bambushain",
+        to,
+        plain_body,
+        html_body,
+    )
+    .await
+    .map_err(|err| {
+        log::error!("Failed to send email {err}");
+        log::error!("{err:#?}");
+
+        BambooError::mailing("Failed to send email change confirmation")
+    })
+}
+
+pub async fn send_email_change_notice(
+    display_name: String,
+    to: String,
+    new_email: String,
+    env_service: EnvService,
+) {
+    let env_service = env_service.clone();
+    let html_body = format!(
+        r#"
+<html lang="de" style="font-family: system-ui,-apple-system,'Segoe UI','Roboto','Ubuntu','Cantarell','Noto Sans',sans-serif,'Apple Color Emoji','Segoe UI Emoji','Segoe UI Symbol','Noto Color Emoji';">
+<head>
+
+</head>
+<body>
+    <article style="margin: 4rem 0; padding: 4rem 2rem; border-radius: 0.25rem; background: #fff; box-shadow: 0.0145rem 0.029rem 0.174rem rgba(27, 40, 50, 0.01698),0.0335rem 0.067rem 0.402rem rgba(27, 40, 50, 0.024),0.0625rem 0.125rem 0.75rem rgba(27, 40, 50, 0.03),0.1125rem 0.225rem 1.35rem rgba(27, 40, 50, 0.036),0.2085rem 0.417rem 2.502rem rgba(27, 40, 50, 0.04302),0.5rem 1rem 6rem rgba(27, 40, 50, 0.06),0 0 0 0.0625rem rgba(27, 40, 50, 0.015);">
+        Hallo {display_name},<br><br>
+        für deinen Account im Bambushain wurde die Email-Adresse {new_email} angefordert. Sie wird erst aktiv, wenn der Link in der Email an die neue Adresse geöffnet wird. Wenn du das nicht warst, ändere bitte dein Passwort und wende dich an deine Mods.<br><br>
+        Alles Gute vom 🐼
+    </article>
+</body>
+</html>"#
+    );
+    let plain_body = format!(
+        r#"
+Hallo {display_name},
+
+für deinen Account im Bambushain wurde die Email-Adresse {new_email} angefordert. Sie wird erst aktiv, wenn der Link in der Email an die neue Adresse geöffnet wird. Wenn du das nicht warst, ändere bitte dein Passwort und wende dich an deine Mods.
+
+Alles Gute vom 🐼"#
+    );
+
+    let _ = send_mail(
+        env_service,
+        "Deine Email-Adresse im Bambushain soll geändert werden",
+        to,
+        plain_body,
+        html_body,
+    )
+    .await
+    .map(|_| ())
+    .map_err(|err| {
+        log::error!("Failed to send email {err}");
+        log::error!("{err:#?}");
+    });
+}
+
+pub async fn send_grove_invitation(
+    grove_name: String,
+    invited_by: String,
+    to: String,
+    invitation_link: String,
+    env_service: EnvService,
+) -> BambooErrorResult {
+    let env_service = env_service.clone();
+    let html_body = format!(
+        r#"
+<html lang="de" style="font-family: system-ui,-apple-system,'Segoe UI','Roboto','Ubuntu','Cantarell','Noto Sans',sans-serif,'Apple Color Emoji','Segoe UI Emoji','Segoe UI Symbol','Noto Color Emoji';">
+<head>
+
+</head>
+<body>
+    <article style="margin: 4rem 0; padding: 4rem 2rem; border-radius: 0.25rem; background: #fff; box-shadow: 0.0145rem 0.029rem 0.174rem rgba(27, 40, 50, 0.01698),0.0335rem 0.067rem 0.402rem rgba(27, 40, 50, 0.024),0.0625rem 0.125rem 0.75rem rgba(27, 40, 50, 0.03),0.1125rem 0.225rem 1.35rem rgba(27, 40, 50, 0.036),0.2085rem 0.417rem 2.502rem rgba(27, 40, 50, 0.04302),0.5rem 1rem 6rem rgba(27, 40, 50, 0.06),0 0 0 0.0625rem rgba(27, 40, 50, 0.015);">
+        Hallo,<br><br>
+        {invited_by} hat dich in den Hain {grove_name} im Bambushain eingeladen. Unter <a style="color: #598c79;text-decoration: none" href="{invitation_link}">{invitation_link}</a> kannst du dir deinen Account mit deinem eigenen Namen und Passwort anlegen.<br><br>
+        Alles Gute vom 🐼
+    </article>
+</body>
+</html>"#
+    );
+    let plain_body = format!(
+        r#"
+Hallo,
+
+{invited_by} hat dich in den Hain {grove_name} im Bambushain eingeladen. Unter {invitation_link} kannst du dir deinen Account mit deinem eigenen Namen und Passwort anlegen.
+
+Alles Gute vom 🐼"#
+    );
+
+    send_mail(
+        env_service,
+        "Du wurdest in den Bambushain eingeladen",
+        to,
+        plain_body,
+        html_body,
+    )
+    .await
+    .map_err(|err| {
+        log::error!("Failed to send email {err}");
+        log::error!("{err:#?}");
+
+        BambooError::mailing("Failed to send invitation email")
+    })
+}
+
+pub async fn send_grove_join_invitation(
+    display_name: String,
+    invited_by: String,
+    grove_name: String,
+    to: String,
+    invitation_link: String,
+    env_service: EnvService,
+) -> BambooErrorResult {
+    let env_service = env_service.clone();
+    let html_body = format!(
+        r#"
+<html lang="de" style="font-family: system-ui,-apple-system,'Segoe UI','Roboto','Ubuntu','Cantarell','Noto Sans',sans-serif,'Apple Color Emoji','Segoe UI Emoji','Segoe UI Symbol','Noto Color Emoji';">
+<head>
+
+</head>
+<body>
+    <article style="margin: 4rem 0; padding: 4rem 2rem; border-radius: 0.25rem; background: #fff; box-shadow: 0.0145rem 0.029rem 0.174rem rgba(27, 40, 50, 0.01698),0.0335rem 0.067rem 0.402rem rgba(27, 40, 50, 0.024),0.0625rem 0.125rem 0.75rem rgba(27, 40, 50, 0.03),0.1125rem 0.225rem 1.35rem rgba(27, 40, 50, 0.036),0.2085rem 0.417rem 2.502rem rgba(27, 40, 50, 0.04302),0.5rem 1rem 6rem rgba(27, 40, 50, 0.06),0 0 0 0.0625rem rgba(27, 40, 50, 0.015);">
+        Hallo {display_name},<br><br>
+        {invited_by} hat dich in den Hain {grove_name} im Bambushain eingeladen. Wenn du beitreten möchtest, öffne <a style="color: #598c79;text-decoration: none" href="{invitation_link}">{invitation_link}</a> und bestätige die Einladung mit deinem Namen und deinem bisherigen Passwort. Ohne deine Bestätigung wirst du nicht hinzugefügt, die Einladung läuft nach einer Woche ab.<br><br>
+        Alles Gute vom 🐼
+    </article>
+</body>
+</html>"#
+    );
+    let plain_body = format!(
+        r#"
+Hallo {display_name},
+
+{invited_by} hat dich in den Hain {grove_name} im Bambushain eingeladen. Wenn du beitreten möchtest, öffne {invitation_link} und bestätige die Einladung mit deinem Namen und deinem bisherigen Passwort. Ohne deine Bestätigung wirst du nicht hinzugefügt, die Einladung läuft nach einer Woche ab.
+
+Alles Gute vom 🐼"#
+    );
+
+    send_mail(
+        env_service,
+        "Du wurdest in einen Hain eingeladen",
+        to,
+        plain_body,
+        html_body,
+    )
+    .await
+    .map_err(|err| {
+        log::error!("Failed to send email {err}");
+        log::error!("{err:#?}");
+
+        BambooError::mailing("Failed to send invitation email")
+    })
+}
+
+pub async fn send_added_to_grove(
+    display_name: String,
+    added_by: String,
+    grove_name: String,
+    to: String,
+    env_service: EnvService,
+) -> BambooErrorResult {
+    let env_service = env_service.clone();
+    let html_body = format!(
+        r#"
+<html lang="de" style="font-family: system-ui,-apple-system,'Segoe UI','Roboto','Ubuntu','Cantarell','Noto Sans',sans-serif,'Apple Color Emoji','Segoe UI Emoji','Segoe UI Symbol','Noto Color Emoji';">
+<head>
+
+</head>
+<body>
+    <article style="margin: 4rem 0; padding: 4rem 2rem; border-radius: 0.25rem; background: #fff; box-shadow: 0.0145rem 0.029rem 0.174rem rgba(27, 40, 50, 0.01698),0.0335rem 0.067rem 0.402rem rgba(27, 40, 50, 0.024),0.0625rem 0.125rem 0.75rem rgba(27, 40, 50, 0.03),0.1125rem 0.225rem 1.35rem rgba(27, 40, 50, 0.036),0.2085rem 0.417rem 2.502rem rgba(27, 40, 50, 0.04302),0.5rem 1rem 6rem rgba(27, 40, 50, 0.06),0 0 0 0.0625rem rgba(27, 40, 50, 0.015);">
+        Hallo {display_name},<br><br>
+        {added_by} hat dich zum Hain {grove_name} im Bambushain hinzugefügt. Du kannst dich wie gewohnt mit deinem Account anmelden und oben über <strong>Hain wechseln</strong> zwischen deinen Hainen wechseln.<br><br>
+        Alles Gute vom 🐼
+    </article>
+</body>
+</html>"#
+    );
+    let plain_body = format!(
+        r#"
+Hallo {display_name},
+
+{added_by} hat dich zum Hain {grove_name} im Bambushain hinzugefügt. Du kannst dich wie gewohnt mit deinem Account anmelden und oben über Hain wechseln zwischen deinen Hainen wechseln.
+
+Alles Gute vom 🐼"#
+    );
+
+    send_mail(
+        env_service,
+        "Du wurdest zu einem Hain hinzugefügt",
+        to,
+        plain_body,
+        html_body,
+    )
+    .await
+    .map_err(|err| {
+        log::error!("Failed to send email {err}");
+        log::error!("{err:#?}");
+
+        BambooError::mailing("Failed to send grove email")
+    })
+}
+
+pub async fn send_grove_imported(
+    display_name: String,
+    grove_name: String,
+    to: String,
+    password_link: String,
+    env_service: EnvService,
+) -> BambooErrorResult {
+    let env_service = env_service.clone();
+    let html_body = format!(
+        r#"
+<html lang="de" style="font-family: system-ui,-apple-system,'Segoe UI','Roboto','Ubuntu','Cantarell','Noto Sans',sans-serif,'Apple Color Emoji','Segoe UI Emoji','Segoe UI Symbol','Noto Color Emoji';">
+<head>
+
+</head>
+<body>
+    <article style="margin: 4rem 0; padding: 4rem 2rem; border-radius: 0.25rem; background: #fff; box-shadow: 0.0145rem 0.029rem 0.174rem rgba(27, 40, 50, 0.01698),0.0335rem 0.067rem 0.402rem rgba(27, 40, 50, 0.024),0.0625rem 0.125rem 0.75rem rgba(27, 40, 50, 0.03),0.1125rem 0.225rem 1.35rem rgba(27, 40, 50, 0.036),0.2085rem 0.417rem 2.502rem rgba(27, 40, 50, 0.04302),0.5rem 1rem 6rem rgba(27, 40, 50, 0.06),0 0 0 0.0625rem rgba(27, 40, 50, 0.015);">
+        Hallo {display_name},<br><br>
+        der Hain {grove_name} ist in den Bambushain umgezogen und du bist mit umgezogen, willkommen 🙂 Deine Charaktere und Events haben wir für dich mitgenommen.<br><br>
+        Unter <a style="color: #598c79;text-decoration: none" href="{password_link}">{password_link}</a> kannst du dir ein Passwort setzen und dich danach mit der Emailadresse {to} anmelden. Der Link ist 14 Tage gültig und funktioniert nur einmal, danach kannst du dir jederzeit über <strong>Passwort vergessen</strong> einen neuen Link schicken lassen.<br><br>
+        Alles Gute vom 🐼
+    </article>
+</body>
+</html>"#
+    );
+    let plain_body = format!(
+        r#"
+Hallo {display_name},
+
+der Hain {grove_name} ist in den Bambushain umgezogen und du bist mit umgezogen, willkommen 🙂 Deine Charaktere und Events haben wir für dich mitgenommen.
+
+Unter {password_link} kannst du dir ein Passwort setzen und dich danach mit der Emailadresse {to} anmelden. Der Link ist 14 Tage gültig und funktioniert nur einmal, danach kannst du dir jederzeit über Passwort vergessen einen neuen Link schicken lassen.
+
+Alles Gute vom 🐼"#
+    );
+
+    send_mail(
+        env_service,
+        "Dein Hain ist in den Bambushain umgezogen",
+        to,
+        plain_body,
+        html_body,
+    )
+    .await
+    .map_err(|err| {
+        log::error!("Failed to send email {err}");
+        log::error!("{err:#?}");
+
+        BambooError::mailing("Failed to send import email")
+    })
+}
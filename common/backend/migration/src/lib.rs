@@ -26,6 +26,26 @@ mod m20231230_231220_update_table_character_change_unique;
 mod m20240117_125532_fix_foreign_key_custom_character_field;
 mod m20240628_235106_dawntrail_jobs;
 mod m20240629_094035_character_world_name_unique_fix;
+mod m20240715_201512_create_table_login_lockout;
+mod m20240718_193044_create_table_password_reset_token;
+mod m20240722_211730_create_table_passkey;
+mod m20240725_180412_create_table_totp_recovery_code;
+mod m20240728_154907_create_table_external_identity;
+mod m20240731_192215_create_table_personal_access_token;
+mod m20240803_101834_add_two_factor_code_expiry;
+mod m20240806_172345_create_table_email_change_token;
+mod m20240809_193512_create_table_grove_invitation;
+mod m20240812_084512_add_encryption_key_ids_to_user;
+mod m20240814_183021_create_table_grove_role;
+mod m20240814_183455_add_event_created_by;
+mod m20240816_201347_create_table_grove_membership;
+mod m20240818_164212_create_table_audit_log_entry;
+mod m20240820_191438_create_table_grove_settings;
+mod m20240822_203114_add_grove_deletion_schedule;
+mod m20240824_174526_create_table_grove_export;
+mod m20240826_090512_add_grove_membership_last_active_at;
+mod m20240827_103215_remove_playstore_demo_user;
+mod m20240828_091204_add_mod_rights_transferred_audit_action;
 
 pub struct Migrator;
 
@@ -59,6 +79,26 @@ impl MigratorTrait for Migrator {
             Box::new(m20240117_125532_fix_foreign_key_custom_character_field::Migration),
             Box::new(m20240628_235106_dawntrail_jobs::Migration),
             Box::new(m20240629_094035_character_world_name_unique_fix::Migration),
+            Box::new(m20240715_201512_create_table_login_lockout::Migration),
+            Box::new(m20240718_193044_create_table_password_reset_token::Migration),
+            Box::new(m20240722_211730_create_table_passkey::Migration),
+            Box::new(m20240725_180412_create_table_totp_recovery_code::Migration),
+            Box::new(m20240728_154907_create_table_external_identity::Migration),
+            Box::new(m20240731_192215_create_table_personal_access_token::Migration),
+            Box::new(m20240803_101834_add_two_factor_code_expiry::Migration),
+            Box::new(m20240806_172345_create_table_email_change_token::Migration),
+            Box::new(m20240809_193512_create_table_grove_invitation::Migration),
+            Box::new(m20240812_084512_add_encryption_key_ids_to_user::Migration),
+            Box::new(m20240814_183021_create_table_grove_role::Migration),
+            Box::new(m20240814_183455_add_event_created_by::Migration),
+            Box::new(m20240816_201347_create_table_grove_membership::Migration),
+            Box::new(m20240818_164212_create_table_audit_log_entry::Migration),
+            Box::new(m20240820_191438_create_table_grove_settings::Migration),
+            Box::new(m20240822_203114_add_grove_deletion_schedule::Migration),
+            Box::new(m20240824_174526_create_table_grove_export::Migration),
+            Box::new(m20240826_090512_add_grove_membership_last_active_at::Migration),
+            Box::new(m20240827_103215_remove_playstore_demo_user::Migration),
+            Box::new(m20240828_091204_add_mod_rights_transferred_audit_action::Migration),
         ]
     }
 }
@@ -0,0 +1,127 @@
+use sea_orm_migration::prelude::*;
+
+use crate::m20220101_000001_create_schemas::Schemas;
+use crate::m20230724_121011_create_table_user::User;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table((Schemas::Authentication, Passkey::Table))
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Passkey::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Passkey::UserId).integer().not_null())
+                    .col(ColumnDef::new(Passkey::Name).string().not_null())
+                    .col(
+                        ColumnDef::new(Passkey::CredentialId)
+                            .string()
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(ColumnDef::new(Passkey::Credential).text().not_null())
+                    .col(ColumnDef::new(Passkey::CreatedAt).timestamp().not_null())
+                    .col(ColumnDef::new(Passkey::LastUsedAt).timestamp().null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from((Schemas::Authentication, Passkey::Table), Passkey::UserId)
+                            .to((Schemas::Authentication, User::Table), User::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table((Schemas::Authentication, WebauthnChallenge::Table))
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(WebauthnChallenge::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(WebauthnChallenge::ChallengeId)
+                            .string()
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(
+                        ColumnDef::new(WebauthnChallenge::UserId)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(WebauthnChallenge::State).text().not_null())
+                    .col(
+                        ColumnDef::new(WebauthnChallenge::ExpiresAt)
+                            .timestamp()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(
+                                (Schemas::Authentication, WebauthnChallenge::Table),
+                                WebauthnChallenge::UserId,
+                            )
+                            .to((Schemas::Authentication, User::Table), User::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .table((Schemas::Authentication, WebauthnChallenge::Table))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_table(
+                Table::drop()
+                    .table((Schemas::Authentication, Passkey::Table))
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Passkey {
+    Table,
+    Id,
+    UserId,
+    Name,
+    CredentialId,
+    Credential,
+    CreatedAt,
+    LastUsedAt,
+}
+
+#[derive(DeriveIden)]
+enum WebauthnChallenge {
+    Table,
+    Id,
+    ChallengeId,
+    UserId,
+    State,
+    ExpiresAt,
+}
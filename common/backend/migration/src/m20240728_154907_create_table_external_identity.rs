@@ -0,0 +1,124 @@
+use sea_orm_migration::prelude::*;
+
+use crate::m20220101_000001_create_schemas::Schemas;
+use crate::m20230724_121011_create_table_user::User;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table((Schemas::Authentication, ExternalIdentity::Table))
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ExternalIdentity::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(ExternalIdentity::UserId)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(ExternalIdentity::Issuer).string().not_null())
+                    .col(
+                        ColumnDef::new(ExternalIdentity::Subject)
+                            .string()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(
+                                (Schemas::Authentication, ExternalIdentity::Table),
+                                ExternalIdentity::UserId,
+                            )
+                            .to((Schemas::Authentication, User::Table), User::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .index(
+                        Index::create()
+                            .col(ExternalIdentity::Issuer)
+                            .col(ExternalIdentity::Subject)
+                            .unique(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table((Schemas::Authentication, OidcLoginState::Table))
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(OidcLoginState::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(OidcLoginState::State)
+                            .string()
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(ColumnDef::new(OidcLoginState::Nonce).string().not_null())
+                    .col(
+                        ColumnDef::new(OidcLoginState::PkceVerifier)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(OidcLoginState::ExpiresAt)
+                            .timestamp()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .table((Schemas::Authentication, OidcLoginState::Table))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_table(
+                Table::drop()
+                    .table((Schemas::Authentication, ExternalIdentity::Table))
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum ExternalIdentity {
+    Table,
+    Id,
+    UserId,
+    Issuer,
+    Subject,
+}
+
+#[derive(DeriveIden)]
+enum OidcLoginState {
+    Table,
+    Id,
+    State,
+    Nonce,
+    PkceVerifier,
+    ExpiresAt,
+}
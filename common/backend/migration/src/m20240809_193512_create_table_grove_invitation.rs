@@ -0,0 +1,89 @@
+use sea_orm_migration::prelude::*;
+
+use crate::m20220101_000001_create_schemas::Schemas;
+use crate::m20231229_235511_create_table_grove::Grove;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table((Schemas::Grove, GroveInvitation::Table))
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(GroveInvitation::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(GroveInvitation::GroveId)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(GroveInvitation::TokenHash)
+                            .string()
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(ColumnDef::new(GroveInvitation::Email).string().null())
+                    .col(ColumnDef::new(GroveInvitation::MaxUses).integer().null())
+                    .col(
+                        ColumnDef::new(GroveInvitation::UseCount)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(GroveInvitation::CreatedAt)
+                            .timestamp()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(GroveInvitation::ExpiresAt)
+                            .timestamp()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(
+                                (Schemas::Grove, GroveInvitation::Table),
+                                GroveInvitation::GroveId,
+                            )
+                            .to((Schemas::Grove, Grove::Table), Grove::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .table((Schemas::Grove, GroveInvitation::Table))
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum GroveInvitation {
+    Table,
+    Id,
+    GroveId,
+    TokenHash,
+    Email,
+    MaxUses,
+    UseCount,
+    CreatedAt,
+    ExpiresAt,
+}
@@ -0,0 +1,97 @@
+use sea_orm_migration::prelude::*;
+
+use crate::m20220101_000001_create_schemas::Schemas;
+use crate::m20230724_121011_create_table_user::User;
+use crate::m20231229_235511_create_table_grove::Grove;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table((Schemas::Grove, GroveRole::Table))
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(GroveRole::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(GroveRole::GroveId).integer().not_null())
+                    .col(ColumnDef::new(GroveRole::Name).string().not_null())
+                    .col(
+                        ColumnDef::new(GroveRole::Permissions)
+                            .json_binary()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from((Schemas::Grove, GroveRole::Table), GroveRole::GroveId)
+                            .to((Schemas::Grove, Grove::Table), Grove::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .index(
+                        Index::create()
+                            .col(GroveRole::GroveId)
+                            .col(GroveRole::Name)
+                            .unique(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table((Schemas::Authentication, User::Table))
+                    .add_column(ColumnDef::new(UserRole::RoleId).integer().null())
+                    .add_foreign_key(
+                        ForeignKey::create()
+                            .from((Schemas::Authentication, User::Table), UserRole::RoleId)
+                            .to((Schemas::Grove, GroveRole::Table), GroveRole::Id)
+                            .on_delete(ForeignKeyAction::SetNull)
+                            .get_foreign_key(),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table((Schemas::Authentication, User::Table))
+                    .drop_column(UserRole::RoleId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_table(
+                Table::drop()
+                    .table((Schemas::Grove, GroveRole::Table))
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum GroveRole {
+    Table,
+    Id,
+    GroveId,
+    Name,
+    Permissions,
+}
+
+#[derive(DeriveIden)]
+enum UserRole {
+    RoleId,
+}
@@ -0,0 +1,45 @@
+use sea_orm_migration::prelude::*;
+
+use crate::m20220101_000001_create_schemas::Schemas;
+use crate::m20230724_121011_create_table_user::User;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table((Schemas::Bamboo, Event::Table))
+                    .add_column(ColumnDef::new(Event::CreatedById).integer().null())
+                    .add_foreign_key(
+                        ForeignKey::create()
+                            .from((Schemas::Bamboo, Event::Table), Event::CreatedById)
+                            .to((Schemas::Authentication, User::Table), User::Id)
+                            .on_delete(ForeignKeyAction::SetNull)
+                            .get_foreign_key(),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table((Schemas::Bamboo, Event::Table))
+                    .drop_column(Event::CreatedById)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Event {
+    Table,
+    CreatedById,
+}
@@ -0,0 +1,159 @@
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::Statement;
+
+use crate::m20220101_000001_create_schemas::Schemas;
+use crate::m20230724_121011_create_table_user::User;
+use crate::m20231229_235511_create_table_grove::Grove;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table((Schemas::Grove, GroveMembership::Table))
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(GroveMembership::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(GroveMembership::UserId).integer().not_null())
+                    .col(
+                        ColumnDef::new(GroveMembership::GroveId)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(GroveMembership::IsMod)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .col(ColumnDef::new(GroveMembership::RoleId).integer().null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(
+                                (Schemas::Grove, GroveMembership::Table),
+                                GroveMembership::UserId,
+                            )
+                            .to((Schemas::Authentication, User::Table), User::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(
+                                (Schemas::Grove, GroveMembership::Table),
+                                GroveMembership::GroveId,
+                            )
+                            .to((Schemas::Grove, Grove::Table), Grove::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(
+                                (Schemas::Grove, GroveMembership::Table),
+                                GroveMembership::RoleId,
+                            )
+                            .to((Schemas::Grove, GroveRole::Table), GroveRole::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .index(
+                        Index::create()
+                            .col(GroveMembership::UserId)
+                            .col(GroveMembership::GroveId)
+                            .unique(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        let db = manager.get_connection();
+        let copy_memberships_stmt = Statement::from_string(
+            manager.get_database_backend(),
+            r#"INSERT INTO grove.grove_membership (user_id, grove_id, is_mod, role_id)
+SELECT id, grove_id, is_mod, role_id FROM authentication."user" WHERE grove_id IS NOT NULL"#,
+        );
+        db.execute(copy_memberships_stmt).await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table((Schemas::Authentication, User::Table))
+                    .drop_column(UserMembership::IsMod)
+                    .drop_column(UserMembership::RoleId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table((Schemas::Authentication, User::Table))
+                    .add_column(
+                        ColumnDef::new(UserMembership::IsMod)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .add_column(ColumnDef::new(UserMembership::RoleId).integer().null())
+                    .add_foreign_key(
+                        ForeignKey::create()
+                            .from(
+                                (Schemas::Authentication, User::Table),
+                                UserMembership::RoleId,
+                            )
+                            .to((Schemas::Grove, GroveRole::Table), GroveRole::Id)
+                            .on_delete(ForeignKeyAction::SetNull)
+                            .get_foreign_key(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        let db = manager.get_connection();
+        let copy_memberships_stmt = Statement::from_string(
+            manager.get_database_backend(),
+            r#"UPDATE authentication."user" u SET is_mod = m.is_mod, role_id = m.role_id
+FROM grove.grove_membership m WHERE m.user_id = u.id AND m.grove_id = u.grove_id"#,
+        );
+        db.execute(copy_memberships_stmt).await?;
+
+        manager
+            .drop_table(
+                Table::drop()
+                    .table((Schemas::Grove, GroveMembership::Table))
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum GroveMembership {
+    Table,
+    Id,
+    UserId,
+    GroveId,
+    IsMod,
+    RoleId,
+}
+
+#[derive(DeriveIden)]
+enum GroveRole {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum UserMembership {
+    IsMod,
+    RoleId,
+}
@@ -0,0 +1,117 @@
+use sea_orm_migration::prelude::extension::postgres::Type;
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::{EnumIter, Iterable};
+
+use crate::m20220101_000001_create_schemas::Schemas;
+use crate::m20231229_235511_create_table_grove::Grove;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum((Schemas::Grove, Alias::new("audit_action")))
+                    .values(AuditAction::iter().collect::<Vec<AuditAction>>())
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_table(
+                Table::create()
+                    .table((Schemas::Grove, AuditLogEntry::Table))
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(AuditLogEntry::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(AuditLogEntry::GroveId).integer().not_null())
+                    .col(ColumnDef::new(AuditLogEntry::ActorId).integer().not_null())
+                    .col(ColumnDef::new(AuditLogEntry::ActorName).string().not_null())
+                    .col(ColumnDef::new(AuditLogEntry::TargetId).integer().null())
+                    .col(ColumnDef::new(AuditLogEntry::TargetName).string().null())
+                    .col(
+                        ColumnDef::new(AuditLogEntry::Action)
+                            .custom(Alias::new("grove.audit_action"))
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(AuditLogEntry::CreatedAt)
+                            .timestamp()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(
+                                (Schemas::Grove, AuditLogEntry::Table),
+                                AuditLogEntry::GroveId,
+                            )
+                            .to((Schemas::Grove, Grove::Table), Grove::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .table((Schemas::Grove, AuditLogEntry::Table))
+                    .col(AuditLogEntry::GroveId)
+                    .col(AuditLogEntry::CreatedAt)
+                    .name("idx_audit_log_entry_grove_created_at")
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .table((Schemas::Grove, AuditLogEntry::Table))
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .drop_type(
+                Type::drop()
+                    .name((Schemas::Grove, Alias::new("audit_action")))
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum AuditLogEntry {
+    Table,
+    Id,
+    GroveId,
+    ActorId,
+    ActorName,
+    TargetId,
+    TargetName,
+    Action,
+    CreatedAt,
+}
+
+#[derive(Iden, EnumIter)]
+enum AuditAction {
+    UserCreated,
+    UserAdded,
+    UserDeleted,
+    ModStatusGranted,
+    ModStatusRevoked,
+    RoleChanged,
+    PasswordReset,
+    TotpDisabled,
+    GroveEnabled,
+    GroveDisabled,
+}
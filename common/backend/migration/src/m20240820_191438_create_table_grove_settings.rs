@@ -0,0 +1,124 @@
+use sea_orm_migration::prelude::extension::postgres::Type;
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::{EnumIter, Iterable};
+
+use crate::m20220101_000001_create_schemas::Schemas;
+use crate::m20231229_235511_create_table_grove::Grove;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum((Schemas::Grove, Alias::new("grove_language")))
+                    .values(GroveLanguage::iter().collect::<Vec<GroveLanguage>>())
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_table(
+                Table::create()
+                    .table((Schemas::Grove, GroveSettings::Table))
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(GroveSettings::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(GroveSettings::GroveId)
+                            .integer()
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(
+                        ColumnDef::new(GroveSettings::DisplayName)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(GroveSettings::Description)
+                            .text()
+                            .not_null()
+                            .default(""),
+                    )
+                    .col(
+                        ColumnDef::new(GroveSettings::Language)
+                            .custom(Alias::new("grove.grove_language"))
+                            .not_null()
+                            .default(Expr::cust("'german'")),
+                    )
+                    .col(
+                        ColumnDef::new(GroveSettings::Timezone)
+                            .string()
+                            .not_null()
+                            .default("Europe/Berlin"),
+                    )
+                    .col(
+                        ColumnDef::new(GroveSettings::AccentColor)
+                            .string()
+                            .not_null()
+                            .default("#598c79"),
+                    )
+                    .col(
+                        ColumnDef::new(GroveSettings::HasLogo)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(
+                                (Schemas::Grove, GroveSettings::Table),
+                                GroveSettings::GroveId,
+                            )
+                            .to((Schemas::Grove, Grove::Table), Grove::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .table((Schemas::Grove, GroveSettings::Table))
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .drop_type(
+                Type::drop()
+                    .name((Schemas::Grove, Alias::new("grove_language")))
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum GroveSettings {
+    Table,
+    Id,
+    GroveId,
+    DisplayName,
+    Description,
+    Language,
+    Timezone,
+    AccentColor,
+    HasLogo,
+}
+
+#[derive(Iden, EnumIter)]
+enum GroveLanguage {
+    German,
+    English,
+}
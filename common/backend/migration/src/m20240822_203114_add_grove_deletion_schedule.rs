@@ -0,0 +1,64 @@
+use sea_orm_migration::prelude::extension::postgres::Type;
+use sea_orm_migration::prelude::*;
+
+use crate::m20220101_000001_create_schemas::Schemas;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table((Schemas::Grove, Grove::Table))
+                    .add_column(ColumnDef::new(Grove::DeletedAt).timestamp().null())
+                    .add_column(ColumnDef::new(Grove::PurgeAt).timestamp().null())
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_type(
+                Type::alter()
+                    .name((Schemas::Grove, Alias::new("audit_action")))
+                    .add_value(AuditAction::GroveDeleted)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_type(
+                Type::alter()
+                    .name((Schemas::Grove, Alias::new("audit_action")))
+                    .add_value(AuditAction::GroveRestored)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    /// Postgres can't drop enum values, so the new audit actions stay in place.
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table((Schemas::Grove, Grove::Table))
+                    .drop_column(Grove::DeletedAt)
+                    .drop_column(Grove::PurgeAt)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Grove {
+    Table,
+    DeletedAt,
+    PurgeAt,
+}
+
+#[derive(Iden)]
+enum AuditAction {
+    GroveDeleted,
+    GroveRestored,
+}
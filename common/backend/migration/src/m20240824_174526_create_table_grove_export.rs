@@ -0,0 +1,110 @@
+use sea_orm_migration::prelude::extension::postgres::Type;
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::{EnumIter, Iterable};
+
+use crate::m20220101_000001_create_schemas::Schemas;
+use crate::m20231229_235511_create_table_grove::Grove;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum((Schemas::Grove, Alias::new("grove_export_status")))
+                    .values(GroveExportStatus::iter().collect::<Vec<GroveExportStatus>>())
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_table(
+                Table::create()
+                    .table((Schemas::Grove, GroveExport::Table))
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(GroveExport::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(GroveExport::GroveId).integer().not_null())
+                    .col(
+                        ColumnDef::new(GroveExport::RequestedByName)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(GroveExport::Status)
+                            .custom(Alias::new("grove.grove_export_status"))
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(GroveExport::CreatedAt)
+                            .timestamp()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(GroveExport::FinishedAt).timestamp().null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from((Schemas::Grove, GroveExport::Table), GroveExport::GroveId)
+                            .to((Schemas::Grove, Grove::Table), Grove::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_type(
+                Type::alter()
+                    .name((Schemas::Grove, Alias::new("audit_action")))
+                    .add_value(AuditAction::GroveExported)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    /// Postgres can't drop enum values, so the new audit action stays in place.
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .table((Schemas::Grove, GroveExport::Table))
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .drop_type(
+                Type::drop()
+                    .name((Schemas::Grove, Alias::new("grove_export_status")))
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum GroveExport {
+    Table,
+    Id,
+    GroveId,
+    RequestedByName,
+    Status,
+    CreatedAt,
+    FinishedAt,
+}
+
+#[derive(Iden, EnumIter)]
+enum GroveExportStatus {
+    Running,
+    Finished,
+    Failed,
+}
+
+#[derive(Iden)]
+enum AuditAction {
+    GroveExported,
+}
@@ -0,0 +1,34 @@
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::Statement;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    /// The Play Store review account was replaced by the configurable demo account, its grove is only removed
+    /// when nobody else joined it.
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        let user_stmt = Statement::from_string(
+            manager.get_database_backend(),
+            r#"DELETE FROM authentication."user" WHERE email = 'playstore@google.bambushain'"#,
+        );
+        let grove_stmt = Statement::from_string(
+            manager.get_database_backend(),
+            r#"DELETE FROM grove.grove
+               WHERE name = 'Google'
+               AND NOT EXISTS (SELECT 1 FROM grove.grove_membership WHERE grove_membership.grove_id = grove.id)"#,
+        );
+
+        db.execute(user_stmt).await?;
+        db.execute(grove_stmt).await?;
+
+        Ok(())
+    }
+
+    /// The removed account can't be restored.
+    async fn down(&self, _manager: &SchemaManager) -> Result<(), DbErr> {
+        Ok(())
+    }
+}
@@ -0,0 +1,31 @@
+use sea_orm_migration::prelude::extension::postgres::Type;
+use sea_orm_migration::prelude::*;
+
+use crate::m20220101_000001_create_schemas::Schemas;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_type(
+                Type::alter()
+                    .name((Schemas::Grove, Alias::new("audit_action")))
+                    .add_value(AuditAction::ModRightsTransferred)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    /// Postgres can't drop enum values, so the new audit action stays in place.
+    async fn down(&self, _manager: &SchemaManager) -> Result<(), DbErr> {
+        Ok(())
+    }
+}
+
+#[derive(Iden)]
+enum AuditAction {
+    ModRightsTransferred,
+}
@@ -1,3 +1,5 @@
+use std::net::IpAddr;
+
 #[derive(Default, Clone)]
 pub struct EnvironmentService {}
 
@@ -13,4 +15,19 @@ impl EnvironmentService {
     pub fn get_env_opt(&self, key: impl Into<String>) -> Option<String> {
         std::env::var(key.into()).ok()
     }
+
+    /// The days a deleted grove can be restored before it gets purged.
+    pub fn get_grove_deletion_grace_period_days(&self) -> u64 {
+        self.get_env("GROVE_DELETION_GRACE_PERIOD_DAYS", "14")
+            .parse::<u64>()
+            .unwrap_or(14)
+    }
+
+    /// The reverse proxies whose forwarding headers are trusted to carry the client address.
+    pub fn get_trusted_proxies(&self) -> Vec<IpAddr> {
+        self.get_env("TRUSTED_PROXIES", "")
+            .split(',')
+            .filter_map(|proxy| proxy.trim().parse::<IpAddr>().ok())
+            .collect()
+    }
 }
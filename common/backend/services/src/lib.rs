@@ -5,7 +5,9 @@ pub use crate::environment_service::EnvironmentService;
 
 mod environment_service;
 pub mod minio_service;
+pub mod webauthn_service;
 
 pub type EnvService = web::Data<crate::environment_service::EnvironmentService>;
 pub type DbConnection = web::Data<DatabaseConnection>;
 pub type MinioService = web::Data<crate::minio_service::MinioClient>;
+pub type WebauthnService = web::Data<webauthn_rs::Webauthn>;
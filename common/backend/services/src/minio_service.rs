@@ -6,6 +6,21 @@ use std::str::FromStr;
 
 use bamboo_common_core::error::{BambooError, BambooErrorResult, BambooResult};
 
+/// Only raster images are accepted, their type is detected from the data instead of trusting the upload.
+pub fn get_image_content_type(data: &[u8]) -> Option<&'static str> {
+    if data.starts_with(&[0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A]) {
+        Some("image/png")
+    } else if data.starts_with(&[0xFF, 0xD8, 0xFF]) {
+        Some("image/jpeg")
+    } else if data.starts_with(b"GIF87a") || data.starts_with(b"GIF89a") {
+        Some("image/gif")
+    } else if data.len() >= 12 && data.starts_with(b"RIFF") && &data[8..12] == b"WEBP" {
+        Some("image/webp")
+    } else {
+        None
+    }
+}
+
 #[derive(Clone)]
 pub struct MinioClient {
     bucket: Bucket,
@@ -77,4 +92,162 @@ impl MinioClient {
             Ok(data)
         }
     }
+
+    fn get_grove_logo_path(&self, grove_id: i32) -> String {
+        format!("/grove/logo/{grove_id}")
+    }
+
+    pub async fn upload_grove_logo(&self, grove_id: i32, data: &[u8]) -> BambooErrorResult {
+        let content_type = get_image_content_type(data).ok_or_else(|| {
+            BambooError::validation("grove", "The logo must be a png, jpeg, gif or webp image")
+        })?;
+        let response = self
+            .bucket
+            .put_object_with_content_type(self.get_grove_logo_path(grove_id), data, content_type)
+            .await
+            .map_err(|err| {
+                log::error!("Failed to save grove logo {err}");
+                BambooError::io("grove", "Failed to save grove logo")
+            })?;
+        if response.status_code() != 200 {
+            Err(BambooError::io("grove", "Failed to save grove logo"))
+        } else {
+            Ok(())
+        }
+    }
+
+    pub async fn get_grove_logo(&self, grove_id: i32) -> BambooResult<Bytes> {
+        let response = self
+            .bucket
+            .get_object(self.get_grove_logo_path(grove_id))
+            .await
+            .map_err(|err| {
+                log::error!("Failed to get grove logo {err}");
+                BambooError::io("grove", "Failed to get grove logo")
+            })?;
+        if response.status_code() != 200 {
+            Err(BambooError::io("grove", "Failed to get grove logo"))
+        } else {
+            let data = response.bytes().clone();
+            Ok(data)
+        }
+    }
+
+    pub async fn delete_grove_logo(&self, grove_id: i32) -> BambooErrorResult {
+        let response = self
+            .bucket
+            .delete_object(self.get_grove_logo_path(grove_id))
+            .await
+            .map_err(|err| {
+                log::error!("Failed to delete grove logo {err}");
+                BambooError::io("grove", "Failed to delete grove logo")
+            })?;
+        if response.status_code() >= 300 {
+            Err(BambooError::io("grove", "Failed to delete grove logo"))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Missing objects count as empty, the users without a profile picture are the common case.
+    async fn get_object_size(&self, path: String) -> BambooResult<u64> {
+        match self.bucket.head_object(path).await {
+            Ok((result, 200)) => Ok(result.content_length.unwrap_or_default().max(0) as u64),
+            Ok((_, 404)) | Err(S3Error::HttpFailWithBody(404, _)) => Ok(0),
+            Ok((_, status)) => {
+                log::error!("Failed to load object size, status {status}");
+                Err(BambooError::io("storage", "Failed to load object size"))
+            }
+            Err(err) => {
+                log::error!("Failed to load object size {err}");
+                Err(BambooError::io("storage", "Failed to load object size"))
+            }
+        }
+    }
+
+    /// Sums up the profile pictures of the given users and the logo of the grove.
+    /// Only the objects of the grove are requested, listing the whole bucket would grow with every grove on the instance.
+    pub async fn get_picture_storage_size(
+        &self,
+        grove_id: i32,
+        user_ids: &[i32],
+    ) -> BambooResult<u64> {
+        let mut size = self
+            .get_object_size(self.get_grove_logo_path(grove_id))
+            .await?;
+        for user_id in user_ids {
+            size += self
+                .get_object_size(self.get_profile_picture_path(*user_id))
+                .await?;
+        }
+
+        Ok(size)
+    }
+
+    fn get_grove_export_path(&self, grove_id: i32, export_id: i32) -> String {
+        format!("/grove/export/{grove_id}/{export_id}")
+    }
+
+    pub async fn upload_grove_export(
+        &self,
+        grove_id: i32,
+        export_id: i32,
+        data: &[u8],
+    ) -> BambooErrorResult {
+        let response = self
+            .bucket
+            .put_object(self.get_grove_export_path(grove_id, export_id), data)
+            .await
+            .map_err(|err| {
+                log::error!("Failed to save grove export {err}");
+                BambooError::io("grove_export", "Failed to save grove export")
+            })?;
+        if response.status_code() != 200 {
+            Err(BambooError::io(
+                "grove_export",
+                "Failed to save grove export",
+            ))
+        } else {
+            Ok(())
+        }
+    }
+
+    pub async fn get_grove_export(&self, grove_id: i32, export_id: i32) -> BambooResult<Bytes> {
+        let response = self
+            .bucket
+            .get_object(self.get_grove_export_path(grove_id, export_id))
+            .await
+            .map_err(|err| {
+                log::error!("Failed to get grove export {err}");
+                BambooError::io("grove_export", "Failed to get grove export")
+            })?;
+        if response.status_code() != 200 {
+            Err(BambooError::io(
+                "grove_export",
+                "Failed to get grove export",
+            ))
+        } else {
+            let data = response.bytes().clone();
+            Ok(data)
+        }
+    }
+
+    pub async fn delete_grove_export(&self, grove_id: i32, export_id: i32) -> BambooErrorResult {
+        let response = self
+            .bucket
+            .delete_object(self.get_grove_export_path(grove_id, export_id))
+            .await
+            .map_err(|err| {
+                log::error!("Failed to delete grove export {err}");
+                BambooError::io("grove_export", "Failed to delete grove export")
+            })?;
+        if response.status_code() >= 300 {
+            Err(BambooError::io(
+                "grove_export",
+                "Failed to delete grove export",
+            ))
+        } else {
+            Ok(())
+        }
+    }
 }
@@ -0,0 +1,17 @@
+use webauthn_rs::prelude::{Url, WebauthnError};
+use webauthn_rs::{Webauthn, WebauthnBuilder};
+
+pub fn create_webauthn(
+    rp_id: String,
+    rp_origin: String,
+    rp_name: String,
+) -> Result<Webauthn, WebauthnError> {
+    let rp_origin = Url::parse(rp_origin.as_str()).map_err(|err| {
+        log::error!("Failed to parse the webauthn origin {err}");
+        WebauthnError::Configuration
+    })?;
+
+    WebauthnBuilder::new(rp_id.as_str(), &rp_origin)?
+        .rp_name(rp_name.as_str())
+        .build()
+}
@@ -0,0 +1,261 @@
+use std::collections::BTreeMap;
+
+use base64::Engine;
+use openssl::bn::{BigNum, BigNumContext};
+use openssl::ec::{EcGroup, EcKey};
+use openssl::hash::MessageDigest;
+use openssl::nid::Nid;
+use openssl::pkey::{PKey, Private};
+use openssl::sha::sha256;
+use openssl::sign::Signer;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_cbor_2::Value;
+use serde_json::json;
+use webauthn_rs::prelude::{
+    CreationChallengeResponse, PasskeyAuthentication, PasskeyRegistration, PublicKeyCredential,
+    RegisterPublicKeyCredential, RequestChallengeResponse, Uuid,
+};
+
+use bamboo_common_backend_services::webauthn_service::create_webauthn;
+
+const RP_ID: &str = "pandas.bambushain.app";
+const RP_ORIGIN: &str = "https://pandas.bambushain.app";
+
+const FLAG_USER_PRESENT: u8 = 0x01;
+const FLAG_USER_VERIFIED: u8 = 0x04;
+const FLAG_ATTESTED_CREDENTIAL_DATA: u8 = 0x40;
+
+fn encode(data: &[u8]) -> String {
+    base64::prelude::BASE64_URL_SAFE_NO_PAD.encode(data)
+}
+
+/// A software passkey with a single ES256 credential that answers with none attestation like most platform authenticators.
+struct SoftwareAuthenticator {
+    credential_id: Vec<u8>,
+    key: PKey<Private>,
+    sign_count: u32,
+}
+
+impl SoftwareAuthenticator {
+    fn new() -> Self {
+        let group = EcGroup::from_curve_name(Nid::X9_62_PRIME256V1).unwrap();
+
+        Self {
+            credential_id: Uuid::new_v4().as_bytes().to_vec(),
+            key: PKey::from_ec_key(EcKey::generate(&group).unwrap()).unwrap(),
+            sign_count: 0,
+        }
+    }
+
+    fn cose_public_key(&self) -> Vec<u8> {
+        let ec_key = self.key.ec_key().unwrap();
+        let mut x = BigNum::new().unwrap();
+        let mut y = BigNum::new().unwrap();
+        ec_key
+            .public_key()
+            .affine_coordinates(
+                ec_key.group(),
+                &mut x,
+                &mut y,
+                &mut BigNumContext::new().unwrap(),
+            )
+            .unwrap();
+
+        serde_cbor_2::to_vec(&Value::Map(BTreeMap::from([
+            (Value::Integer(1), Value::Integer(2)),
+            (Value::Integer(3), Value::Integer(-7)),
+            (Value::Integer(-1), Value::Integer(1)),
+            (
+                Value::Integer(-2),
+                Value::Bytes(x.to_vec_padded(32).unwrap()),
+            ),
+            (
+                Value::Integer(-3),
+                Value::Bytes(y.to_vec_padded(32).unwrap()),
+            ),
+        ])))
+        .unwrap()
+    }
+
+    fn authenticator_data(&mut self, attested_credential_data: bool) -> Vec<u8> {
+        self.sign_count += 1;
+
+        let mut flags = FLAG_USER_PRESENT | FLAG_USER_VERIFIED;
+        if attested_credential_data {
+            flags |= FLAG_ATTESTED_CREDENTIAL_DATA;
+        }
+
+        let mut data = sha256(RP_ID.as_bytes()).to_vec();
+        data.push(flags);
+        data.extend(self.sign_count.to_be_bytes());
+        if attested_credential_data {
+            data.extend([0_u8; 16]);
+            data.extend((self.credential_id.len() as u16).to_be_bytes());
+            data.extend(self.credential_id.clone());
+            data.extend(self.cose_public_key());
+        }
+
+        data
+    }
+
+    fn client_data(challenge: &str, ceremony: &str) -> Vec<u8> {
+        serde_json::to_vec(&json!({
+            "type": ceremony,
+            "challenge": challenge,
+            "origin": RP_ORIGIN,
+            "crossOrigin": false,
+        }))
+        .unwrap()
+    }
+
+    fn register(&mut self, options: &CreationChallengeResponse) -> RegisterPublicKeyCredential {
+        let options = serde_json::to_value(options).unwrap();
+        let challenge = options["publicKey"]["challenge"].as_str().unwrap();
+
+        let attestation_object = serde_cbor_2::to_vec(&Value::Map(BTreeMap::from([
+            (Value::Text("fmt".into()), Value::Text("none".into())),
+            (Value::Text("attStmt".into()), Value::Map(BTreeMap::new())),
+            (
+                Value::Text("authData".into()),
+                Value::Bytes(self.authenticator_data(true)),
+            ),
+        ])))
+        .unwrap();
+
+        serde_json::from_value(json!({
+            "id": encode(self.credential_id.as_slice()),
+            "rawId": encode(self.credential_id.as_slice()),
+            "response": {
+                "attestationObject": encode(attestation_object.as_slice()),
+                "clientDataJSON": encode(Self::client_data(challenge, "webauthn.create").as_slice()),
+            },
+            "type": "public-key",
+        }))
+        .unwrap()
+    }
+
+    fn authenticate(&mut self, options: &RequestChallengeResponse) -> PublicKeyCredential {
+        let options = serde_json::to_value(options).unwrap();
+        let challenge = options["publicKey"]["challenge"].as_str().unwrap();
+
+        let authenticator_data = self.authenticator_data(false);
+        let client_data = Self::client_data(challenge, "webauthn.get");
+
+        let mut signer = Signer::new(MessageDigest::sha256(), &self.key).unwrap();
+        signer.update(authenticator_data.as_slice()).unwrap();
+        signer.update(&sha256(client_data.as_slice())).unwrap();
+        let signature = signer.sign_to_vec().unwrap();
+
+        serde_json::from_value(json!({
+            "id": encode(self.credential_id.as_slice()),
+            "rawId": encode(self.credential_id.as_slice()),
+            "response": {
+                "authenticatorData": encode(authenticator_data.as_slice()),
+                "clientDataJSON": encode(client_data.as_slice()),
+                "signature": encode(signature.as_slice()),
+                "userHandle": null,
+            },
+            "type": "public-key",
+        }))
+        .unwrap()
+    }
+}
+
+/// The challenge states are stored as json between the two requests of a ceremony.
+fn store_state<T: Serialize + DeserializeOwned>(state: T) -> T {
+    serde_json::from_str(serde_json::to_string(&state).unwrap().as_str()).unwrap()
+}
+
+#[test]
+fn passkey_registration_and_login() {
+    let webauthn = create_webauthn(
+        RP_ID.to_string(),
+        RP_ORIGIN.to_string(),
+        "Bambushain".to_string(),
+    )
+    .unwrap();
+    let mut authenticator = SoftwareAuthenticator::new();
+
+    let (options, state) = webauthn
+        .start_passkey_registration(
+            Uuid::from_u64_pair(0, 1),
+            "panda@bambushain.app",
+            "Panda",
+            None,
+        )
+        .unwrap();
+    let state: PasskeyRegistration = store_state(state);
+    let passkey = webauthn
+        .finish_passkey_registration(&authenticator.register(&options), &state)
+        .unwrap();
+    assert_eq!(
+        passkey.cred_id().as_slice(),
+        authenticator.credential_id.as_slice()
+    );
+
+    let (options, state) = webauthn
+        .start_passkey_authentication(std::slice::from_ref(&passkey))
+        .unwrap();
+    let state: PasskeyAuthentication = store_state(state);
+    let result = webauthn
+        .finish_passkey_authentication(&authenticator.authenticate(&options), &state)
+        .unwrap();
+    assert_eq!(result.cred_id(), passkey.cred_id());
+    assert!(result.user_verified());
+    assert_eq!(result.counter(), 2);
+}
+
+#[test]
+fn passkey_login_fails_with_the_wrong_authenticator() {
+    let webauthn = create_webauthn(
+        RP_ID.to_string(),
+        RP_ORIGIN.to_string(),
+        "Bambushain".to_string(),
+    )
+    .unwrap();
+    let mut authenticator = SoftwareAuthenticator::new();
+
+    let (options, state) = webauthn
+        .start_passkey_registration(
+            Uuid::from_u64_pair(0, 1),
+            "panda@bambushain.app",
+            "Panda",
+            None,
+        )
+        .unwrap();
+    let passkey = webauthn
+        .finish_passkey_registration(&authenticator.register(&options), &state)
+        .unwrap();
+
+    let mut other_authenticator = SoftwareAuthenticator::new();
+    other_authenticator.credential_id = authenticator.credential_id.clone();
+
+    let (options, state) = webauthn.start_passkey_authentication(&[passkey]).unwrap();
+    assert!(webauthn
+        .finish_passkey_authentication(&other_authenticator.authenticate(&options), &state)
+        .is_err());
+}
+
+#[test]
+fn passkey_registration_fails_for_another_origin() {
+    let webauthn = create_webauthn(
+        RP_ID.to_string(),
+        "https://pandas.bambushain.app:8443".to_string(),
+        "Bambushain".to_string(),
+    )
+    .unwrap();
+    let mut authenticator = SoftwareAuthenticator::new();
+
+    let (options, state) = webauthn
+        .start_passkey_registration(
+            Uuid::from_u64_pair(0, 1),
+            "panda@bambushain.app",
+            "Panda",
+            None,
+        )
+        .unwrap();
+    assert!(webauthn
+        .finish_passkey_registration(&authenticator.register(&options), &state)
+        .is_err());
+}
@@ -0,0 +1,150 @@
+use std::fmt::{Display, Formatter};
+
+use chrono::NaiveDateTime;
+#[cfg(feature = "backend")]
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "backend")]
+use bamboo_common_backend_macros::*;
+#[cfg(feature = "frontend")]
+use strum_macros::EnumIter;
+
+#[derive(
+    Serialize, Deserialize, EnumIter, Debug, Eq, PartialEq, Ord, PartialOrd, Clone, Copy, Default,
+)]
+#[cfg_attr(
+    feature = "backend",
+    derive(DeriveActiveEnum),
+    sea_orm(rs_type = "String", db_type = "Enum", enum_name = "grove.audit_action")
+)]
+#[serde(rename_all = "camelCase")]
+pub enum AuditAction {
+    #[default]
+    #[cfg_attr(feature = "backend", sea_orm(string_value = "user_created"))]
+    UserCreated,
+    #[cfg_attr(feature = "backend", sea_orm(string_value = "user_added"))]
+    UserAdded,
+    #[cfg_attr(feature = "backend", sea_orm(string_value = "user_deleted"))]
+    UserDeleted,
+    #[cfg_attr(feature = "backend", sea_orm(string_value = "mod_status_granted"))]
+    ModStatusGranted,
+    #[cfg_attr(feature = "backend", sea_orm(string_value = "mod_status_revoked"))]
+    ModStatusRevoked,
+    #[cfg_attr(feature = "backend", sea_orm(string_value = "role_changed"))]
+    RoleChanged,
+    #[cfg_attr(feature = "backend", sea_orm(string_value = "password_reset"))]
+    PasswordReset,
+    #[cfg_attr(feature = "backend", sea_orm(string_value = "totp_disabled"))]
+    TotpDisabled,
+    #[cfg_attr(feature = "backend", sea_orm(string_value = "grove_enabled"))]
+    GroveEnabled,
+    #[cfg_attr(feature = "backend", sea_orm(string_value = "grove_disabled"))]
+    GroveDisabled,
+    #[cfg_attr(feature = "backend", sea_orm(string_value = "grove_deleted"))]
+    GroveDeleted,
+    #[cfg_attr(feature = "backend", sea_orm(string_value = "grove_restored"))]
+    GroveRestored,
+    #[cfg_attr(feature = "backend", sea_orm(string_value = "grove_exported"))]
+    GroveExported,
+    #[cfg_attr(feature = "backend", sea_orm(string_value = "mod_rights_transferred"))]
+    ModRightsTransferred,
+}
+
+impl Display for AuditAction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            AuditAction::UserCreated => "Panda erstellt",
+            AuditAction::UserAdded => "Panda hinzugefügt",
+            AuditAction::UserDeleted => "Panda entfernt",
+            AuditAction::ModStatusGranted => "Zum Mod gemacht",
+            AuditAction::ModStatusRevoked => "Modrechte entzogen",
+            AuditAction::RoleChanged => "Rolle geändert",
+            AuditAction::PasswordReset => "Passwort zurückgesetzt",
+            AuditAction::TotpDisabled => "Zwei-Faktor-Authentifizierung deaktiviert",
+            AuditAction::GroveEnabled => "Hain aktiviert",
+            AuditAction::GroveDisabled => "Hain deaktiviert",
+            AuditAction::GroveDeleted => "Hain gelöscht",
+            AuditAction::GroveRestored => "Hain wiederhergestellt",
+            AuditAction::GroveExported => "Daten exportiert",
+            AuditAction::ModRightsTransferred => "Modrechte übertragen und Hain verlassen",
+        })
+    }
+}
+
+impl AuditAction {
+    pub fn get_name(self) -> String {
+        match self {
+            AuditAction::UserCreated => "userCreated",
+            AuditAction::UserAdded => "userAdded",
+            AuditAction::UserDeleted => "userDeleted",
+            AuditAction::ModStatusGranted => "modStatusGranted",
+            AuditAction::ModStatusRevoked => "modStatusRevoked",
+            AuditAction::RoleChanged => "roleChanged",
+            AuditAction::PasswordReset => "passwordReset",
+            AuditAction::TotpDisabled => "totpDisabled",
+            AuditAction::GroveEnabled => "groveEnabled",
+            AuditAction::GroveDisabled => "groveDisabled",
+            AuditAction::GroveDeleted => "groveDeleted",
+            AuditAction::GroveRestored => "groveRestored",
+            AuditAction::GroveExported => "groveExported",
+            AuditAction::ModRightsTransferred => "modRightsTransferred",
+        }
+        .to_string()
+    }
+}
+
+/// The names of actor and target are stored with the entry, so the log stays readable after pandas left the grove.
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Ord, PartialOrd, Clone, Default)]
+#[cfg_attr(
+    feature = "backend",
+    derive(DeriveEntityModel, Responder),
+    sea_orm(table_name = "audit_log_entry", schema_name = "grove")
+)]
+#[serde(rename_all = "camelCase")]
+pub struct Model {
+    #[cfg_attr(feature = "backend", sea_orm(primary_key))]
+    #[serde(default)]
+    pub id: i32,
+    #[serde(skip)]
+    pub grove_id: i32,
+    pub actor_id: i32,
+    pub actor_name: String,
+    pub target_id: Option<i32>,
+    pub target_name: Option<String>,
+    pub action: AuditAction,
+    pub created_at: NaiveDateTime,
+}
+
+#[cfg(feature = "backend")]
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::grove::Entity",
+        from = "Column::GroveId",
+        to = "super::grove::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    Grove,
+}
+
+#[cfg(feature = "backend")]
+impl Related<super::grove::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Grove.def()
+    }
+}
+
+#[cfg(feature = "backend")]
+impl ActiveModelBehavior for ActiveModel {}
+
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "backend", derive(Responder))]
+pub struct AuditLogPage {
+    pub entries: Vec<Model>,
+    pub page: u64,
+    pub page_count: u64,
+    pub total_count: u64,
+}
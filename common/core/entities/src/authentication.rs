@@ -58,3 +58,26 @@ pub struct ChangeMyPassword {
 pub struct ForgotPassword {
     pub email: String,
 }
+
+#[derive(Serialize, Deserialize, Eq, PartialEq, Ord, PartialOrd, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ResetPassword {
+    pub token: String,
+    pub new_password: String,
+    #[serde(default)]
+    pub two_factor_code: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Eq, PartialEq, Ord, PartialOrd, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfirmEmailChange {
+    pub token: String,
+}
+
+#[derive(Serialize, Deserialize, Eq, PartialEq, Ord, PartialOrd, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "backend", derive(Responder))]
+pub struct OidcLoginConfig {
+    pub enabled: bool,
+    pub provider_name: String,
+}
@@ -0,0 +1,46 @@
+use chrono::NaiveDateTime;
+#[cfg(feature = "backend")]
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Ord, PartialOrd, Clone, Default)]
+#[cfg_attr(
+    feature = "backend",
+    derive(DeriveEntityModel),
+    sea_orm(table_name = "email_change_token", schema_name = "authentication")
+)]
+#[serde(rename_all = "camelCase")]
+pub struct Model {
+    #[cfg_attr(feature = "backend", sea_orm(primary_key))]
+    #[serde(skip)]
+    pub id: i32,
+    pub user_id: i32,
+    pub new_email: String,
+    #[cfg_attr(feature = "backend", sea_orm(unique))]
+    #[serde(skip)]
+    pub token_hash: String,
+    pub expires_at: NaiveDateTime,
+}
+
+#[cfg(feature = "backend")]
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::user::Entity",
+        from = "Column::UserId",
+        to = "super::user::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    User,
+}
+
+#[cfg(feature = "backend")]
+impl Related<super::user::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::User.def()
+    }
+}
+
+#[cfg(feature = "backend")]
+impl ActiveModelBehavior for ActiveModel {}
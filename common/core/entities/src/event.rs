@@ -33,6 +33,8 @@ pub struct Model {
     pub is_private: bool,
     #[serde(skip)]
     pub user_id: Option<i32>,
+    #[serde(skip)]
+    pub created_by_id: Option<i32>,
     #[cfg(feature = "backend")]
     #[serde(skip)]
     pub grove_id: i32,
@@ -49,6 +51,14 @@ pub enum Relation {
         on_delete = "Cascade"
     )]
     User,
+    #[sea_orm(
+        belongs_to = "super::user::Entity",
+        from = "Column::CreatedById",
+        to = "super::user::Column::Id",
+        on_update = "Cascade",
+        on_delete = "SetNull"
+    )]
+    CreatedBy,
     #[sea_orm(
         belongs_to = "super::grove::Entity",
         from = "Column::GroveId",
@@ -95,6 +105,7 @@ impl Model {
             color: color.hex(),
             is_private,
             user_id: None,
+            created_by_id: None,
         }
     }
 
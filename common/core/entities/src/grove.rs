@@ -1,3 +1,4 @@
+use chrono::NaiveDateTime;
 #[cfg(feature = "backend")]
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
@@ -29,6 +30,10 @@ pub struct Model {
     pub is_suspended: bool,
     #[serde(default = "set_true")]
     pub is_enabled: bool,
+    #[serde(default)]
+    pub deleted_at: Option<NaiveDateTime>,
+    #[serde(default)]
+    pub purge_at: Option<NaiveDateTime>,
 }
 
 #[cfg(feature = "backend")]
@@ -38,6 +43,16 @@ pub enum Relation {
     User,
     #[sea_orm(has_many = "super::event::Entity")]
     Event,
+    #[sea_orm(has_many = "super::grove_invitation::Entity")]
+    GroveInvitation,
+    #[sea_orm(has_many = "super::grove_membership::Entity")]
+    GroveMembership,
+    #[sea_orm(has_many = "super::audit_log_entry::Entity")]
+    AuditLogEntry,
+    #[sea_orm(has_one = "super::grove_settings::Entity")]
+    GroveSettings,
+    #[sea_orm(has_many = "super::grove_export::Entity")]
+    GroveExport,
 }
 
 #[cfg(feature = "backend")]
@@ -54,6 +69,41 @@ impl Related<super::event::Entity> for Entity {
     }
 }
 
+#[cfg(feature = "backend")]
+impl Related<super::grove_invitation::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::GroveInvitation.def()
+    }
+}
+
+#[cfg(feature = "backend")]
+impl Related<super::grove_membership::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::GroveMembership.def()
+    }
+}
+
+#[cfg(feature = "backend")]
+impl Related<super::audit_log_entry::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::AuditLogEntry.def()
+    }
+}
+
+#[cfg(feature = "backend")]
+impl Related<super::grove_settings::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::GroveSettings.def()
+    }
+}
+
+#[cfg(feature = "backend")]
+impl Related<super::grove_export::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::GroveExport.def()
+    }
+}
+
 #[cfg(feature = "backend")]
 impl ActiveModelBehavior for ActiveModel {}
 
@@ -64,8 +114,15 @@ impl Model {
             name,
             is_suspended,
             is_enabled,
+            deleted_at: None,
+            purge_at: None,
         }
     }
+
+    /// Deleted groves stay locked until they get restored or purged.
+    pub fn is_deleted(&self) -> bool {
+        self.deleted_at.is_some()
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug, Eq, Ord, PartialOrd, PartialEq, Clone, Default)]
@@ -85,3 +142,50 @@ impl CreateGroveRequest {
         }
     }
 }
+
+/// The roles of the Bambushain team in the groves admin backend, every role includes the rights of the roles before it.
+#[derive(
+    Serialize, Deserialize, Debug, Eq, PartialEq, Ord, PartialOrd, Clone, Copy, Hash, Default,
+)]
+#[serde(rename_all = "camelCase")]
+pub enum GrovesAdminRole {
+    #[default]
+    Viewer,
+    Support,
+    Admin,
+}
+
+impl GrovesAdminRole {
+    pub fn from_role_name(role_name: &str) -> Option<Self> {
+        match role_name.to_lowercase().as_str() {
+            "viewer" => Some(Self::Viewer),
+            "support" => Some(Self::Support),
+            "admin" => Some(Self::Admin),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "backend", derive(Responder))]
+pub struct GrovesAdmin {
+    pub name: String,
+    pub role: GrovesAdminRole,
+}
+
+/// Active users are members that used the grove in the given number of days, the storage counts profile pictures and the logo.
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "backend", derive(Responder))]
+pub struct GroveStatistics {
+    pub member_count: u64,
+    pub mod_count: u64,
+    pub active_users_last_7_days: u64,
+    pub active_users_last_30_days: u64,
+    pub event_count: u64,
+    pub character_count: u64,
+    pub picture_storage_bytes: u64,
+    #[serde(default)]
+    pub last_activity_at: Option<NaiveDateTime>,
+}
@@ -0,0 +1,177 @@
+use std::fmt::{Display, Formatter};
+
+use chrono::NaiveDateTime;
+#[cfg(feature = "backend")]
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "backend")]
+use bamboo_common_backend_macros::*;
+#[cfg(feature = "frontend")]
+use strum_macros::EnumIter;
+
+use crate::{
+    CharacterHousing, CharacterRace, Crafter, CustomCharacterField, Event, Fighter, FreeCompany,
+    Grove, GroveRole, GroveSettings, WebUser,
+};
+
+/// The version of the archive layout, it needs to be increased whenever the layout changes in an incompatible way.
+pub const GROVE_EXPORT_VERSION: u32 = 1;
+
+#[derive(
+    Serialize, Deserialize, EnumIter, Debug, Eq, PartialEq, Ord, PartialOrd, Clone, Copy, Default,
+)]
+#[cfg_attr(
+    feature = "backend",
+    derive(DeriveActiveEnum),
+    sea_orm(
+        rs_type = "String",
+        db_type = "Enum",
+        enum_name = "grove.grove_export_status"
+    )
+)]
+#[serde(rename_all = "camelCase")]
+pub enum GroveExportStatus {
+    #[default]
+    #[cfg_attr(feature = "backend", sea_orm(string_value = "running"))]
+    Running,
+    #[cfg_attr(feature = "backend", sea_orm(string_value = "finished"))]
+    Finished,
+    #[cfg_attr(feature = "backend", sea_orm(string_value = "failed"))]
+    Failed,
+}
+
+impl Display for GroveExportStatus {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            GroveExportStatus::Running => "Läuft",
+            GroveExportStatus::Finished => "Fertig",
+            GroveExportStatus::Failed => "Fehlgeschlagen",
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Ord, PartialOrd, Clone, Default)]
+#[cfg_attr(
+    feature = "backend",
+    derive(DeriveEntityModel, Responder),
+    sea_orm(table_name = "grove_export", schema_name = "grove")
+)]
+#[serde(rename_all = "camelCase")]
+pub struct Model {
+    #[cfg_attr(feature = "backend", sea_orm(primary_key))]
+    #[serde(default)]
+    pub id: i32,
+    #[serde(skip)]
+    pub grove_id: i32,
+    pub requested_by_name: String,
+    pub status: GroveExportStatus,
+    pub created_at: NaiveDateTime,
+    #[serde(default)]
+    pub finished_at: Option<NaiveDateTime>,
+}
+
+#[cfg(feature = "backend")]
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::grove::Entity",
+        from = "Column::GroveId",
+        to = "super::grove::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    Grove,
+}
+
+#[cfg(feature = "backend")]
+impl Related<super::grove::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Grove.def()
+    }
+}
+
+#[cfg(feature = "backend")]
+impl ActiveModelBehavior for ActiveModel {}
+
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportedCharacter {
+    pub id: i32,
+    pub race: CharacterRace,
+    pub name: String,
+    pub world: String,
+    #[serde(default)]
+    pub free_company_id: Option<i32>,
+    #[serde(default)]
+    pub fighters: Vec<Fighter>,
+    #[serde(default)]
+    pub crafters: Vec<Crafter>,
+    #[serde(default)]
+    pub housings: Vec<CharacterHousing>,
+    #[serde(default)]
+    pub custom_field_option_ids: Vec<i32>,
+}
+
+/// Passwords, two factor secrets and passkeys never leave the grove, pandas need to reset them after an import.
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportedUser {
+    #[serde(flatten)]
+    pub user: WebUser,
+    #[serde(default)]
+    pub profile_picture: Option<String>,
+    #[serde(default)]
+    pub characters: Vec<ExportedCharacter>,
+    #[serde(default)]
+    pub free_companies: Vec<FreeCompany>,
+    #[serde(default)]
+    pub custom_fields: Vec<CustomCharacterField>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportedEvent {
+    #[serde(flatten)]
+    pub event: Event,
+    #[serde(default)]
+    pub user_id: Option<i32>,
+    #[serde(default)]
+    pub created_by_id: Option<i32>,
+}
+
+/// The ids in the archive are the ids of the exporting grove, they only link the entries inside the archive.
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct GroveExportArchive {
+    pub version: u32,
+    pub exported_at: NaiveDateTime,
+    pub grove_name: String,
+    pub settings: GroveSettings,
+    #[serde(default)]
+    pub roles: Vec<GroveRole>,
+    #[serde(default)]
+    pub users: Vec<ExportedUser>,
+    #[serde(default)]
+    pub events: Vec<ExportedEvent>,
+}
+
+/// Pandas that already have an account on this instance keep it, new pandas may get a different name if theirs is taken.
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportedUser {
+    pub id: i32,
+    pub exported_id: i32,
+    pub display_name: String,
+    pub email: String,
+    pub has_existing_account: bool,
+    pub was_renamed: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone, Default)]
+#[cfg_attr(feature = "backend", derive(Responder))]
+#[serde(rename_all = "camelCase")]
+pub struct GroveImport {
+    pub grove: Grove,
+    pub users: Vec<ImportedUser>,
+}
@@ -0,0 +1,96 @@
+use chrono::NaiveDateTime;
+#[cfg(feature = "backend")]
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "backend")]
+use bamboo_common_backend_macros::*;
+
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Ord, PartialOrd, Clone, Default)]
+#[cfg_attr(
+    feature = "backend",
+    derive(DeriveEntityModel, Responder),
+    sea_orm(table_name = "grove_invitation", schema_name = "grove")
+)]
+#[serde(rename_all = "camelCase")]
+pub struct Model {
+    #[cfg_attr(feature = "backend", sea_orm(primary_key))]
+    #[serde(default)]
+    pub id: i32,
+    #[serde(skip)]
+    pub grove_id: i32,
+    #[cfg_attr(feature = "backend", sea_orm(unique))]
+    #[serde(skip)]
+    pub token_hash: String,
+    pub email: Option<String>,
+    pub max_uses: Option<i32>,
+    pub use_count: i32,
+    pub created_at: NaiveDateTime,
+    pub expires_at: NaiveDateTime,
+}
+
+#[cfg(feature = "backend")]
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::grove::Entity",
+        from = "Column::GroveId",
+        to = "super::grove::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    Grove,
+}
+
+#[cfg(feature = "backend")]
+impl Related<super::grove::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Grove.def()
+    }
+}
+
+#[cfg(feature = "backend")]
+impl ActiveModelBehavior for ActiveModel {}
+
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateGroveInvitation {
+    #[serde(default)]
+    pub email: Option<String>,
+    #[serde(default)]
+    pub max_uses: Option<i32>,
+    pub expires_at: NaiveDateTime,
+}
+
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "backend", derive(Responder))]
+pub struct CreatedGroveInvitation {
+    pub grove_invitation: Model,
+    pub link: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct GroveInvitationToken {
+    pub token: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "backend", derive(Responder))]
+pub struct GroveInvitationDetails {
+    pub grove_name: String,
+    pub email: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct AcceptGroveInvitation {
+    pub token: String,
+    pub email: String,
+    pub display_name: String,
+    #[serde(default)]
+    pub discord_name: String,
+    pub password: String,
+}
@@ -0,0 +1,92 @@
+use chrono::NaiveDateTime;
+#[cfg(feature = "backend")]
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "backend")]
+use bamboo_common_backend_macros::*;
+
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Ord, PartialOrd, Clone, Default)]
+#[cfg_attr(
+    feature = "backend",
+    derive(DeriveEntityModel, Responder),
+    sea_orm(table_name = "grove_membership", schema_name = "grove")
+)]
+#[serde(rename_all = "camelCase")]
+pub struct Model {
+    #[cfg_attr(feature = "backend", sea_orm(primary_key))]
+    #[serde(default)]
+    pub id: i32,
+    pub user_id: i32,
+    pub grove_id: i32,
+    pub is_mod: bool,
+    #[serde(default)]
+    pub role_id: Option<i32>,
+    #[serde(default)]
+    pub last_active_at: Option<NaiveDateTime>,
+}
+
+#[cfg(feature = "backend")]
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::user::Entity",
+        from = "Column::UserId",
+        to = "super::user::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    User,
+    #[sea_orm(
+        belongs_to = "super::grove::Entity",
+        from = "Column::GroveId",
+        to = "super::grove::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    Grove,
+    #[sea_orm(
+        belongs_to = "super::grove_role::Entity",
+        from = "Column::RoleId",
+        to = "super::grove_role::Column::Id",
+        on_update = "Cascade",
+        on_delete = "SetNull"
+    )]
+    GroveRole,
+}
+
+#[cfg(feature = "backend")]
+impl Related<super::user::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::User.def()
+    }
+}
+
+#[cfg(feature = "backend")]
+impl Related<super::grove::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Grove.def()
+    }
+}
+
+#[cfg(feature = "backend")]
+impl Related<super::grove_role::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::GroveRole.def()
+    }
+}
+
+#[cfg(feature = "backend")]
+impl ActiveModelBehavior for ActiveModel {}
+
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangeActiveGrove {
+    pub grove_id: i32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct TransferModRights {
+    pub successor_id: i32,
+}
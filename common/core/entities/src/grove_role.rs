@@ -0,0 +1,122 @@
+use std::fmt::{Display, Formatter};
+
+#[cfg(feature = "backend")]
+use sea_orm::entity::prelude::*;
+#[cfg(feature = "backend")]
+use sea_orm::FromJsonQueryResult;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "backend")]
+use bamboo_common_backend_macros::*;
+#[cfg(feature = "frontend")]
+use strum_macros::EnumIter;
+
+#[derive(
+    Serialize, Deserialize, EnumIter, Debug, Eq, PartialEq, Ord, PartialOrd, Clone, Copy, Hash,
+)]
+#[serde(rename_all = "camelCase")]
+pub enum GrovePermission {
+    ManageUsers,
+    ManageEvents,
+    ManageInvitations,
+    ManageGrove,
+    ManageRoles,
+    ViewAuditLog,
+}
+
+impl Display for GrovePermission {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            GrovePermission::ManageUsers => "Pandas verwalten",
+            GrovePermission::ManageEvents => "Events anderer Pandas verwalten",
+            GrovePermission::ManageInvitations => "Einladungen verwalten",
+            GrovePermission::ManageGrove => "Hain verwalten",
+            GrovePermission::ManageRoles => "Rollen und Modrechte verwalten",
+            GrovePermission::ViewAuditLog => "Moderationsprotokoll einsehen",
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Ord, PartialOrd, Clone, Default)]
+#[cfg_attr(feature = "backend", derive(FromJsonQueryResult))]
+#[serde(transparent)]
+pub struct GrovePermissions(pub Vec<GrovePermission>);
+
+impl GrovePermissions {
+    pub fn contains(&self, permission: GrovePermission) -> bool {
+        self.0.contains(&permission)
+    }
+
+    /// Mods have every permission, roles are only needed to give single permissions to other pandas.
+    pub fn all() -> Self {
+        Self(vec![
+            GrovePermission::ManageUsers,
+            GrovePermission::ManageEvents,
+            GrovePermission::ManageInvitations,
+            GrovePermission::ManageGrove,
+            GrovePermission::ManageRoles,
+            GrovePermission::ViewAuditLog,
+        ])
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Ord, PartialOrd, Clone, Default)]
+#[cfg_attr(
+    feature = "backend",
+    derive(DeriveEntityModel, Responder),
+    sea_orm(table_name = "grove_role", schema_name = "grove")
+)]
+#[serde(rename_all = "camelCase")]
+pub struct Model {
+    #[cfg_attr(feature = "backend", sea_orm(primary_key))]
+    #[serde(default)]
+    pub id: i32,
+    #[serde(skip)]
+    pub grove_id: i32,
+    pub name: String,
+    #[cfg_attr(feature = "backend", sea_orm(column_type = "JsonBinary"))]
+    pub permissions: GrovePermissions,
+}
+
+#[cfg(feature = "backend")]
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::grove::Entity",
+        from = "Column::GroveId",
+        to = "super::grove::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    Grove,
+    #[sea_orm(has_many = "super::grove_membership::Entity")]
+    GroveMembership,
+}
+
+#[cfg(feature = "backend")]
+impl Related<super::grove::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Grove.def()
+    }
+}
+
+#[cfg(feature = "backend")]
+impl Related<super::grove_membership::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::GroveMembership.def()
+    }
+}
+
+#[cfg(feature = "backend")]
+impl ActiveModelBehavior for ActiveModel {}
+
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangeUserRole {
+    #[serde(default)]
+    pub role_id: Option<i32>,
+}
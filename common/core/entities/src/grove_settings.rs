@@ -0,0 +1,162 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use color_art::{color, Color};
+#[cfg(feature = "backend")]
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "backend")]
+use bamboo_common_backend_macros::*;
+#[cfg(feature = "frontend")]
+use strum_macros::EnumIter;
+
+pub const DEFAULT_ACCENT_COLOR: &str = "#598c79";
+pub const DEFAULT_TIMEZONE: &str = "Europe/Berlin";
+
+/// The timezones a grove can choose from, the list covers the regions our pandas play in.
+pub const TIMEZONES: [&str; 12] = [
+    "Europe/Berlin",
+    "Europe/Vienna",
+    "Europe/Zurich",
+    "Europe/Amsterdam",
+    "Europe/Brussels",
+    "Europe/Luxembourg",
+    "Europe/London",
+    "Europe/Paris",
+    "UTC",
+    "America/New_York",
+    "America/Los_Angeles",
+    "Asia/Tokyo",
+];
+
+#[derive(
+    Serialize, Deserialize, EnumIter, Debug, Eq, PartialEq, Ord, PartialOrd, Clone, Copy, Default,
+)]
+#[cfg_attr(
+    feature = "backend",
+    derive(DeriveActiveEnum),
+    sea_orm(
+        rs_type = "String",
+        db_type = "Enum",
+        enum_name = "grove.grove_language"
+    )
+)]
+#[serde(rename_all = "camelCase")]
+pub enum GroveLanguage {
+    #[default]
+    #[cfg_attr(feature = "backend", sea_orm(string_value = "german"))]
+    German,
+    #[cfg_attr(feature = "backend", sea_orm(string_value = "english"))]
+    English,
+}
+
+impl GroveLanguage {
+    pub fn get_name(self) -> String {
+        match self {
+            GroveLanguage::German => "german",
+            GroveLanguage::English => "english",
+        }
+        .to_string()
+    }
+
+    /// The language tag used for the lang attribute of the page.
+    pub fn get_code(self) -> String {
+        match self {
+            GroveLanguage::German => "de",
+            GroveLanguage::English => "en",
+        }
+        .to_string()
+    }
+}
+
+impl Display for GroveLanguage {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            GroveLanguage::German => "Deutsch",
+            GroveLanguage::English => "Englisch",
+        })
+    }
+}
+
+impl From<String> for GroveLanguage {
+    fn from(value: String) -> Self {
+        match value.as_str() {
+            "english" => GroveLanguage::English,
+            _ => GroveLanguage::German,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Ord, PartialOrd, Clone)]
+#[cfg_attr(
+    feature = "backend",
+    derive(DeriveEntityModel, Responder),
+    sea_orm(table_name = "grove_settings", schema_name = "grove")
+)]
+#[serde(rename_all = "camelCase")]
+pub struct Model {
+    #[cfg_attr(feature = "backend", sea_orm(primary_key))]
+    #[serde(default)]
+    pub id: i32,
+    #[cfg_attr(feature = "backend", sea_orm(unique))]
+    #[serde(skip)]
+    pub grove_id: i32,
+    pub display_name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub language: GroveLanguage,
+    pub timezone: String,
+    pub accent_color: String,
+    #[serde(default)]
+    pub has_logo: bool,
+}
+
+impl Default for Model {
+    fn default() -> Self {
+        Self {
+            id: i32::default(),
+            grove_id: i32::default(),
+            display_name: String::default(),
+            description: String::default(),
+            language: GroveLanguage::default(),
+            timezone: DEFAULT_TIMEZONE.to_string(),
+            accent_color: DEFAULT_ACCENT_COLOR.to_string(),
+            has_logo: false,
+        }
+    }
+}
+
+impl Model {
+    pub fn set_accent_color(&mut self, color: Color) {
+        self.accent_color = color.hex();
+    }
+
+    pub fn accent_color(&self) -> Color {
+        Color::from_str(self.accent_color.as_str()).unwrap_or(color!(#598c79))
+    }
+}
+
+#[cfg(feature = "backend")]
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::grove::Entity",
+        from = "Column::GroveId",
+        to = "super::grove::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    Grove,
+}
+
+#[cfg(feature = "backend")]
+impl Related<super::grove::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Grove.def()
+    }
+}
+
+#[cfg(feature = "backend")]
+impl ActiveModelBehavior for ActiveModel {}
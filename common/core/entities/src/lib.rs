@@ -1,3 +1,5 @@
+pub use crate::audit_log_entry::Model as AuditLogEntry;
+pub use crate::audit_log_entry::{AuditAction, AuditLogPage};
 pub use crate::authentication::*;
 pub use crate::character::CharacterRace;
 pub use crate::character::Model as Character;
@@ -11,20 +13,56 @@ pub use crate::custom_character_field::Model as CustomCharacterField;
 pub use crate::custom_character_field_option::Model as CustomCharacterFieldOption;
 pub use crate::custom_character_field_value::Model as CustomCharacterFieldValue;
 pub use crate::dependency::*;
+pub use crate::email_change_token::Model as EmailChangeToken;
 pub use crate::event::Model as Event;
+pub use crate::external_identity::Model as ExternalIdentity;
 pub use crate::fighter::FighterJob;
 pub use crate::fighter::Model as Fighter;
 pub use crate::free_company::Model as FreeCompany;
 pub use crate::grove::Model as Grove;
+pub use crate::grove::{GroveStatistics, GrovesAdmin, GrovesAdminRole};
+pub use crate::grove_export::Model as GroveExport;
+pub use crate::grove_export::{
+    ExportedCharacter, ExportedEvent, ExportedUser, GroveExportArchive, GroveExportStatus,
+    GroveImport, ImportedUser, GROVE_EXPORT_VERSION,
+};
+pub use crate::grove_invitation::Model as GroveInvitation;
+pub use crate::grove_invitation::{
+    AcceptGroveInvitation, CreateGroveInvitation, CreatedGroveInvitation, GroveInvitationDetails,
+    GroveInvitationToken,
+};
+pub use crate::grove_membership::Model as GroveMembership;
+pub use crate::grove_membership::{ChangeActiveGrove, TransferModRights};
+pub use crate::grove_role::Model as GroveRole;
+pub use crate::grove_role::{ChangeUserRole, GrovePermission, GrovePermissions};
+pub use crate::grove_settings::Model as GroveSettings;
+pub use crate::grove_settings::{GroveLanguage, TIMEZONES};
+pub use crate::login_lockout::Model as LoginLockout;
+pub use crate::oidc_login_state::Model as OidcLoginState;
+pub use crate::passkey::Model as Passkey;
+pub use crate::passkey::{
+    FinishPasskeyLogin, FinishPasskeyRegistration, PasskeyLoginChallenge,
+    PasskeyRegistrationChallenge, StartPasskeyLogin,
+};
+pub use crate::password_reset_token::Model as PasswordResetToken;
+pub use crate::personal_access_token::Model as PersonalAccessToken;
+pub use crate::personal_access_token::{
+    CreatePersonalAccessToken, CreatedPersonalAccessToken, TokenScope, TokenScopes,
+};
 pub use crate::support::*;
 pub use crate::token::Model as Token;
+pub use crate::totp_recovery_code::Model as TotpRecoveryCode;
 pub use crate::user::GroveUser;
 pub use crate::user::Model as User;
 pub use crate::user::TotpQrCode;
+pub use crate::user::TotpRecoveryCodeCount;
+pub use crate::user::TotpRecoveryCodes;
 pub use crate::user::UpdateProfile;
 pub use crate::user::ValidateTotp;
 pub use crate::user::WebUser;
+pub use crate::webauthn_challenge::Model as WebauthnChallenge;
 
+pub mod audit_log_entry;
 pub mod authentication;
 pub mod character;
 pub mod character_housing;
@@ -33,10 +71,24 @@ pub mod custom_character_field;
 pub mod custom_character_field_option;
 pub mod custom_character_field_value;
 pub mod dependency;
+pub mod email_change_token;
 pub mod event;
+pub mod external_identity;
 pub mod fighter;
 pub mod free_company;
 pub mod grove;
+pub mod grove_export;
+pub mod grove_invitation;
+pub mod grove_membership;
+pub mod grove_role;
+pub mod grove_settings;
+pub mod login_lockout;
+pub mod oidc_login_state;
+pub mod passkey;
+pub mod password_reset_token;
+pub mod personal_access_token;
 pub mod support;
 pub mod token;
+pub mod totp_recovery_code;
 pub mod user;
+pub mod webauthn_challenge;
@@ -0,0 +1,122 @@
+use std::fmt::{Display, Formatter};
+
+use chrono::NaiveDateTime;
+#[cfg(feature = "backend")]
+use sea_orm::entity::prelude::*;
+#[cfg(feature = "backend")]
+use sea_orm::FromJsonQueryResult;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "backend")]
+use bamboo_common_backend_macros::*;
+#[cfg(feature = "frontend")]
+use strum_macros::EnumIter;
+
+#[derive(
+    Serialize, Deserialize, EnumIter, Debug, Eq, PartialEq, Ord, PartialOrd, Clone, Copy, Hash,
+)]
+#[serde(rename_all = "camelCase")]
+pub enum TokenScope {
+    ReadEvents,
+    WriteEvents,
+    ReadCharacters,
+    WriteCharacters,
+    ReadUsers,
+    WriteUsers,
+    ReadGrove,
+    WriteGrove,
+    ReadProfile,
+    WriteProfile,
+}
+
+impl Display for TokenScope {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            TokenScope::ReadEvents => "Events lesen",
+            TokenScope::WriteEvents => "Events schreiben",
+            TokenScope::ReadCharacters => "Charaktere lesen",
+            TokenScope::WriteCharacters => "Charaktere schreiben",
+            TokenScope::ReadUsers => "Pandas lesen",
+            TokenScope::WriteUsers => "Pandas verwalten",
+            TokenScope::ReadGrove => "Hain lesen",
+            TokenScope::WriteGrove => "Hain verwalten",
+            TokenScope::ReadProfile => "Profil lesen",
+            TokenScope::WriteProfile => "Profil bearbeiten",
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Ord, PartialOrd, Clone, Default)]
+#[cfg_attr(feature = "backend", derive(FromJsonQueryResult))]
+#[serde(transparent)]
+pub struct TokenScopes(pub Vec<TokenScope>);
+
+impl TokenScopes {
+    pub fn contains(&self, scope: TokenScope) -> bool {
+        self.0.contains(&scope)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Ord, PartialOrd, Clone, Default)]
+#[cfg_attr(
+    feature = "backend",
+    derive(DeriveEntityModel, Responder),
+    sea_orm(table_name = "personal_access_token", schema_name = "authentication")
+)]
+#[serde(rename_all = "camelCase")]
+pub struct Model {
+    #[cfg_attr(feature = "backend", sea_orm(primary_key))]
+    #[serde(default)]
+    pub id: i32,
+    #[serde(skip)]
+    pub user_id: i32,
+    pub name: String,
+    #[cfg_attr(feature = "backend", sea_orm(unique))]
+    #[serde(skip)]
+    pub token_hash: String,
+    #[cfg_attr(feature = "backend", sea_orm(column_type = "JsonBinary"))]
+    pub scopes: TokenScopes,
+    pub created_at: NaiveDateTime,
+    pub expires_at: Option<NaiveDateTime>,
+    pub last_used_at: Option<NaiveDateTime>,
+}
+
+#[cfg(feature = "backend")]
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::user::Entity",
+        from = "Column::UserId",
+        to = "super::user::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    User,
+}
+
+#[cfg(feature = "backend")]
+impl Related<super::user::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::User.def()
+    }
+}
+
+#[cfg(feature = "backend")]
+impl ActiveModelBehavior for ActiveModel {}
+
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct CreatePersonalAccessToken {
+    pub name: String,
+    pub scopes: Vec<TokenScope>,
+    #[serde(default)]
+    pub expires_at: Option<NaiveDateTime>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "backend", derive(Responder))]
+pub struct CreatedPersonalAccessToken {
+    pub personal_access_token: Model,
+    pub token: String,
+}
@@ -1,5 +1,11 @@
 use std::fmt::{Display, Formatter};
 
+#[cfg(feature = "backend")]
+use argon2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+#[cfg(feature = "backend")]
+use argon2::{Algorithm, Argon2, Params, Version};
+#[cfg(feature = "backend")]
+use chrono::NaiveDateTime;
 #[cfg(feature = "backend")]
 use sea_orm::entity::prelude::*;
 #[cfg(feature = "backend")]
@@ -26,19 +32,35 @@ pub struct Model {
     #[serde(skip)]
     pub password: String,
     pub display_name: String,
+    #[cfg_attr(feature = "backend", sea_orm(ignore))]
     pub is_mod: bool,
     pub discord_name: String,
     #[cfg(feature = "backend")]
     pub two_factor_code: Option<String>,
     #[cfg(feature = "backend")]
+    #[serde(skip)]
+    pub two_factor_code_issued_at: Option<NaiveDateTime>,
+    #[cfg(feature = "backend")]
+    #[serde(skip)]
+    pub two_factor_code_attempts: i32,
+    #[cfg(feature = "backend")]
+    #[serde(skip)]
+    pub two_factor_code_key_id: Option<String>,
+    #[cfg(feature = "backend")]
     pub totp_secret: Option<Vec<u8>>,
     #[cfg(feature = "backend")]
     #[serde(default)]
     pub totp_secret_encrypted: bool,
+    #[cfg(feature = "backend")]
+    #[serde(skip)]
+    pub totp_secret_key_id: Option<String>,
     pub totp_validated: Option<bool>,
     #[cfg(feature = "backend")]
     #[serde(skip)]
     pub grove_id: i32,
+    #[cfg_attr(feature = "backend", sea_orm(ignore))]
+    #[serde(default)]
+    pub role_id: Option<i32>,
 }
 
 #[cfg(feature = "backend")]
@@ -50,6 +72,20 @@ pub enum Relation {
     Token,
     #[sea_orm(has_many = "super::event::Entity")]
     Event,
+    #[sea_orm(has_many = "super::login_lockout::Entity")]
+    LoginLockout,
+    #[sea_orm(has_many = "super::password_reset_token::Entity")]
+    PasswordResetToken,
+    #[sea_orm(has_many = "super::passkey::Entity")]
+    Passkey,
+    #[sea_orm(has_many = "super::totp_recovery_code::Entity")]
+    TotpRecoveryCode,
+    #[sea_orm(has_many = "super::external_identity::Entity")]
+    ExternalIdentity,
+    #[sea_orm(has_many = "super::personal_access_token::Entity")]
+    PersonalAccessToken,
+    #[sea_orm(has_many = "super::email_change_token::Entity")]
+    EmailChangeToken,
     #[sea_orm(
         belongs_to = "super::grove::Entity",
         from = "Column::GroveId",
@@ -58,6 +94,8 @@ pub enum Relation {
         on_delete = "Cascade"
     )]
     Grove,
+    #[sea_orm(has_many = "super::grove_membership::Entity")]
+    GroveMembership,
 }
 
 #[cfg(feature = "backend")]
@@ -81,6 +119,55 @@ impl Related<super::event::Entity> for Entity {
     }
 }
 
+#[cfg(feature = "backend")]
+impl Related<super::login_lockout::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::LoginLockout.def()
+    }
+}
+
+#[cfg(feature = "backend")]
+impl Related<super::password_reset_token::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::PasswordResetToken.def()
+    }
+}
+
+#[cfg(feature = "backend")]
+impl Related<super::passkey::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Passkey.def()
+    }
+}
+
+#[cfg(feature = "backend")]
+impl Related<super::totp_recovery_code::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::TotpRecoveryCode.def()
+    }
+}
+
+#[cfg(feature = "backend")]
+impl Related<super::external_identity::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ExternalIdentity.def()
+    }
+}
+
+#[cfg(feature = "backend")]
+impl Related<super::personal_access_token::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::PersonalAccessToken.def()
+    }
+}
+
+#[cfg(feature = "backend")]
+impl Related<super::email_change_token::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::EmailChangeToken.def()
+    }
+}
+
 #[cfg(feature = "backend")]
 impl Related<super::grove::Entity> for Entity {
     fn to() -> RelationDef {
@@ -88,20 +175,55 @@ impl Related<super::grove::Entity> for Entity {
     }
 }
 
+#[cfg(feature = "backend")]
+impl Related<super::grove_membership::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::GroveMembership.def()
+    }
+}
+
 #[cfg(feature = "backend")]
 impl ActiveModelBehavior for ActiveModel {}
 
+#[cfg(feature = "backend")]
+fn get_password_hash_params() -> Result<Params, argon2::Error> {
+    let get_param = |key: &str, default: u32| {
+        std::env::var(key)
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(default)
+    };
+
+    Params::new(
+        get_param("PASSWORD_ARGON2_MEMORY_KIB", Params::DEFAULT_M_COST),
+        get_param("PASSWORD_ARGON2_ITERATIONS", Params::DEFAULT_T_COST),
+        get_param("PASSWORD_ARGON2_PARALLELISM", Params::DEFAULT_P_COST),
+        None,
+    )
+}
+
+/// Hashes the password with Argon2id, the parameters are read from `PASSWORD_ARGON2_MEMORY_KIB`,
+/// `PASSWORD_ARGON2_ITERATIONS` and `PASSWORD_ARGON2_PARALLELISM`.
+#[cfg(feature = "backend")]
+pub fn hash_password(plain_password: &str) -> Result<String, argon2::password_hash::Error> {
+    let salt = SaltString::generate(&mut rand::rngs::OsRng);
+    Argon2::new(
+        Algorithm::Argon2id,
+        Version::V0x13,
+        get_password_hash_params()?,
+    )
+    .hash_password(plain_password.as_bytes(), &salt)
+    .map(|hash| hash.to_string())
+}
+
 #[cfg(feature = "backend")]
 impl ActiveModel {
-    pub fn set_password(&mut self, plain_password: &String) -> Result<(), bcrypt::BcryptError> {
-        let hashed = bcrypt::hash(plain_password.as_bytes(), 12);
-        match hashed {
-            Ok(hashed_password) => {
-                self.password = Set(hashed_password);
-                Ok(())
-            }
-            Err(err) => Err(err),
-        }
+    pub fn set_password(
+        &mut self,
+        plain_password: &str,
+    ) -> Result<(), argon2::password_hash::Error> {
+        self.password = Set(hash_password(plain_password)?);
+        Ok(())
     }
 }
 
@@ -118,18 +240,50 @@ impl Model {
             #[cfg(feature = "backend")]
             two_factor_code: None,
             #[cfg(feature = "backend")]
+            two_factor_code_issued_at: None,
+            #[cfg(feature = "backend")]
+            two_factor_code_attempts: 0,
+            #[cfg(feature = "backend")]
+            two_factor_code_key_id: None,
+            #[cfg(feature = "backend")]
             totp_secret: None,
             #[cfg(feature = "backend")]
             totp_secret_encrypted: false,
+            #[cfg(feature = "backend")]
+            totp_secret_key_id: None,
             totp_validated: None,
             #[cfg(feature = "backend")]
             grove_id: -1,
+            role_id: None,
         }
     }
 
+    /// The mod status and the role depend on the grove, applying the membership switches the user to its grove.
+    #[cfg(feature = "backend")]
+    pub fn with_membership(self, membership: &crate::GroveMembership) -> Self {
+        Self {
+            grove_id: membership.grove_id,
+            is_mod: membership.is_mod,
+            role_id: membership.role_id,
+            ..self
+        }
+    }
+
+    /// Accepts Argon2id hashes and the bcrypt hashes of users that didn't login since the switch to Argon2id.
     #[cfg(feature = "backend")]
     pub fn validate_password(&self, password: String) -> bool {
-        let result = bcrypt::verify(password, self.password.as_str());
+        let result = if self.password.starts_with("$argon2") {
+            PasswordHash::new(self.password.as_str())
+                .and_then(|hash| Argon2::default().verify_password(password.as_bytes(), &hash))
+                .map(|_| true)
+                .or_else(|err| match err {
+                    argon2::password_hash::Error::Password => Ok(false),
+                    err => Err(err.to_string()),
+                })
+        } else {
+            bcrypt::verify(password, self.password.as_str()).map_err(|err| err.to_string())
+        };
+
         match result {
             Ok(res) => res,
             Err(err) => {
@@ -138,6 +292,25 @@ impl Model {
             }
         }
     }
+
+    /// Bcrypt hashes and Argon2id hashes with outdated parameters need to be replaced after the next successful login.
+    #[cfg(feature = "backend")]
+    pub fn password_needs_rehash(&self) -> bool {
+        let Ok(params) = get_password_hash_params() else {
+            return false;
+        };
+
+        PasswordHash::new(self.password.as_str())
+            .ok()
+            .filter(|hash| hash.algorithm == argon2::ARGON2ID_IDENT)
+            .and_then(|hash| Params::try_from(&hash).ok())
+            .map(|hash_params| {
+                hash_params.m_cost() != params.m_cost()
+                    || hash_params.t_cost() != params.t_cost()
+                    || hash_params.p_cost() != params.p_cost()
+            })
+            .unwrap_or(true)
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Ord, PartialOrd, Clone, Default)]
@@ -152,6 +325,8 @@ pub struct WebUser {
     pub discord_name: String,
     #[serde(default)]
     pub app_totp_enabled: bool,
+    #[serde(default)]
+    pub role_id: Option<i32>,
 }
 
 impl From<Model> for WebUser {
@@ -163,6 +338,7 @@ impl From<Model> for WebUser {
             email: value.email.to_string(),
             discord_name: value.discord_name.clone(),
             app_totp_enabled: value.totp_validated.unwrap_or(false),
+            role_id: value.role_id,
         }
     }
 }
@@ -210,6 +386,20 @@ pub struct TotpQrCode {
     pub secret: String,
 }
 
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Ord, PartialOrd, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "backend", derive(Responder))]
+pub struct TotpRecoveryCodes {
+    pub codes: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Ord, PartialOrd, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "backend", derive(Responder))]
+pub struct TotpRecoveryCodeCount {
+    pub remaining: u64,
+}
+
 #[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Ord, PartialOrd, Clone, Default)]
 #[serde(rename_all = "camelCase")]
 #[cfg_attr(feature = "backend", derive(Responder))]
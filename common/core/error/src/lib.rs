@@ -17,6 +17,7 @@ pub enum BambooErrorCode {
     Mailing,
     NotFound,
     Serialization,
+    TooManyRequests,
     Unauthorized,
     Unknown,
     Validation,
@@ -42,12 +43,30 @@ impl Display for BambooErrorCode {
     }
 }
 
+#[derive(Ord, PartialOrd, Eq, PartialEq, Clone, Debug, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct BambooErrorDetail {
+    pub field: String,
+    pub code: String,
+}
+
+impl BambooErrorDetail {
+    pub fn new(field: impl Into<String>, code: impl Into<String>) -> Self {
+        Self {
+            field: field.into(),
+            code: code.into(),
+        }
+    }
+}
+
 #[derive(Ord, PartialOrd, Eq, PartialEq, Clone, Debug, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct BambooError {
     pub entity_type: String,
     pub error_type: BambooErrorCode,
     pub message: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub details: Vec<BambooErrorDetail>,
 }
 
 #[cfg(target_arch = "wasm32")]
@@ -74,6 +93,7 @@ impl ResponseError for BambooError {
             BambooErrorCode::ExistsAlready => http::StatusCode::CONFLICT,
             BambooErrorCode::Unauthorized => http::StatusCode::UNAUTHORIZED,
             BambooErrorCode::InsufficientRights => http::StatusCode::FORBIDDEN,
+            BambooErrorCode::TooManyRequests => http::StatusCode::TOO_MANY_REQUESTS,
             BambooErrorCode::InvalidData
             | BambooErrorCode::Serialization
             | BambooErrorCode::Validation => http::StatusCode::BAD_REQUEST,
@@ -96,6 +116,7 @@ impl Responder for BambooError {
             BambooErrorCode::ExistsAlready => HttpResponse::Conflict(),
             BambooErrorCode::Unauthorized => HttpResponse::Unauthorized(),
             BambooErrorCode::InsufficientRights => HttpResponse::Forbidden(),
+            BambooErrorCode::TooManyRequests => HttpResponse::TooManyRequests(),
             BambooErrorCode::InvalidData
             | BambooErrorCode::Serialization
             | BambooErrorCode::Validation => HttpResponse::BadRequest(),
@@ -119,9 +140,15 @@ impl BambooError {
             entity_type: entity_type.into(),
             message: message.into(),
             error_type,
+            details: vec![],
         }
     }
 
+    pub fn with_details(mut self, details: Vec<BambooErrorDetail>) -> Self {
+        self.details = details;
+        self
+    }
+
     pub fn crypto(entity_type: impl Into<String>, message: impl Into<String>) -> Self {
         Self::new(entity_type, message, BambooErrorCode::Crypto)
     }
@@ -158,6 +185,10 @@ impl BambooError {
         Self::new(entity_type, message, BambooErrorCode::Serialization)
     }
 
+    pub fn too_many_requests(entity_type: impl Into<String>, message: impl Into<String>) -> Self {
+        Self::new(entity_type, message, BambooErrorCode::TooManyRequests)
+    }
+
     pub fn validation(entity_type: impl Into<String>, message: impl Into<String>) -> Self {
         Self::new(entity_type, message, BambooErrorCode::Validation)
     }
@@ -174,6 +205,7 @@ impl BambooError {
 pub enum PasswordError {
     WrongPassword,
     UserNotFound,
+    PolicyViolation(BambooError),
     Unknown,
 }
 
@@ -184,6 +216,7 @@ impl From<PasswordError> for BambooError {
                 BambooError::insufficient_rights("user", "The current password is wrong")
             }
             PasswordError::UserNotFound => BambooError::not_found("user", "The user was not found"),
+            PasswordError::PolicyViolation(err) => err,
             PasswordError::Unknown => BambooError::unknown("user", "An unknown error occurred"),
         }
     }
@@ -7,6 +7,10 @@ use serde::{Deserialize, Serialize};
 
 use bamboo_common_core::error::*;
 
+pub use crate::webauthn::*;
+
+mod webauthn;
+
 macro_rules! error_code {
     ($name:tt,$code:literal) => {
         pub const $name: ErrorCode = ErrorCode($code);
@@ -85,6 +89,13 @@ impl ApiError {
             bamboo_error: BambooError::default(),
         }
     }
+
+    pub fn passkey_error() -> Self {
+        Self {
+            code: PASSKEY_ERROR,
+            bamboo_error: BambooError::default(),
+        }
+    }
 }
 
 impl std::error::Error for ApiError {}
@@ -100,10 +111,13 @@ pub type BambooApiResult<T> = Result<T, ApiError>;
 error_code!(SEND_ERROR, -1);
 error_code!(JSON_SERIALIZE_ERROR, -2);
 error_code!(JSON_DESERIALIZE_ERROR, -3);
+error_code!(PASSKEY_ERROR, -4);
 error_code!(NO_CONTENT, 204);
+error_code!(BAD_REQUEST, 400);
 error_code!(FORBIDDEN, 403);
 error_code!(NOT_FOUND, 404);
 error_code!(CONFLICT, 409);
+error_code!(TOO_MANY_REQUESTS, 429);
 error_code!(INTERNAL_SERVER_ERROR, 500);
 
 pub async fn handle_response<OUT: DeserializeOwned>(response: Response) -> BambooApiResult<OUT> {
@@ -233,6 +247,45 @@ pub async fn post<IN: Serialize, OUT: DeserializeOwned>(
     handle_response(request).await
 }
 
+pub async fn post_optional<IN: Serialize, OUT: DeserializeOwned>(
+    uri: impl Into<String>,
+    body: &IN,
+) -> BambooApiResult<Option<OUT>> {
+    let uri = uri.into();
+    log::debug!("Execute post request against {uri}");
+    let request = Request::post(uri.as_str())
+        .headers(authorization_header!())
+        .json(body)
+        .map_err(|_| ApiError::json_serialize_error())?
+        .send()
+        .await
+        .map_err(|_| ApiError::send_error())?;
+
+    if request.status() == 204 {
+        log::debug!("Request returned no content");
+        Ok(None)
+    } else {
+        handle_response(request).await.map(Some)
+    }
+}
+
+pub async fn put<IN: Serialize, OUT: DeserializeOwned>(
+    uri: impl Into<String>,
+    body: &IN,
+) -> BambooApiResult<OUT> {
+    let uri = uri.into();
+    log::debug!("Execute put request against {uri}");
+    let request = Request::put(uri.as_str())
+        .headers(authorization_header!())
+        .json(body)
+        .map_err(|_| ApiError::json_serialize_error())?
+        .send()
+        .await
+        .map_err(|_| ApiError::send_error())?;
+
+    handle_response(request).await
+}
+
 pub async fn delete(uri: impl Into<String>) -> BambooApiResult<()> {
     let uri = uri.into();
     log::debug!("Execute delete request against {uri}");
@@ -283,3 +336,20 @@ pub async fn upload_file(uri: impl Into<String>, file: web_sys::File) -> BambooA
 
     handle_response_code(request).await
 }
+
+pub async fn post_file<OUT: DeserializeOwned>(
+    uri: impl Into<String>,
+    file: web_sys::File,
+) -> BambooApiResult<OUT> {
+    let uri = uri.into();
+    log::debug!("Execute post request against {uri}");
+    let request = Request::post(uri.as_str())
+        .headers(authorization_header!())
+        .body(file)
+        .unwrap()
+        .send()
+        .await
+        .map_err(|_| ApiError::send_error())?;
+
+    handle_response(request).await
+}
@@ -0,0 +1,49 @@
+use wasm_bindgen_futures::JsFuture;
+use webauthn_rs_proto::{
+    CreationChallengeResponse, PublicKeyCredential, RegisterPublicKeyCredential,
+    RequestChallengeResponse,
+};
+
+use crate::{ApiError, BambooApiResult};
+
+fn credentials_container() -> BambooApiResult<web_sys::CredentialsContainer> {
+    web_sys::window()
+        .map(|window| window.navigator().credentials())
+        .ok_or(ApiError::passkey_error())
+}
+
+pub async fn create_passkey_credential(
+    options: CreationChallengeResponse,
+) -> BambooApiResult<RegisterPublicKeyCredential> {
+    log::debug!("Create passkey credential");
+    let promise = credentials_container()?
+        .create_with_options(&options.into())
+        .map_err(|err| {
+            log::error!("Failed to create passkey credential {err:?}");
+            ApiError::passkey_error()
+        })?;
+    let credential = JsFuture::from(promise).await.map_err(|err| {
+        log::error!("Failed to create passkey credential {err:?}");
+        ApiError::passkey_error()
+    })?;
+
+    Ok(web_sys::PublicKeyCredential::from(credential).into())
+}
+
+pub async fn get_passkey_credential(
+    options: RequestChallengeResponse,
+) -> BambooApiResult<PublicKeyCredential> {
+    log::debug!("Get passkey credential");
+    let promise = credentials_container()?
+        .get_with_options(&options.into())
+        .map_err(|err| {
+            log::error!("Failed to get passkey credential {err:?}");
+            ApiError::passkey_error()
+        })?;
+    let credential = JsFuture::from(promise).await.map_err(|err| {
+        log::error!("Failed to get passkey credential {err:?}");
+        ApiError::passkey_error()
+    })?;
+
+    Ok(web_sys::PublicKeyCredential::from(credential).into())
+}
@@ -1,7 +1,9 @@
 use actix_web::{middleware, App, HttpServer};
 
-use bamboo_common::backend::services::DbConnection;
+use bamboo_common::backend::services::minio_service::MinioClient;
+use bamboo_common::backend::services::{DbConnection, EnvironmentService, MinioService};
 
+use crate::authentication::{OidcCache, OidcConfig, OidcService};
 use crate::routes;
 
 pub async fn start_server() -> std::io::Result<()> {
@@ -11,11 +13,27 @@ pub async fn start_server() -> std::io::Result<()> {
     let db = bamboo_common::backend::database::get_database()
         .await
         .map_err(std::io::Error::other)?;
+    let minio_client = MinioClient::new(
+        std::env::var("S3_BUCKET").map_err(std::io::Error::other)?,
+        std::env::var("S3_ACCESS_KEY").map_err(std::io::Error::other)?,
+        std::env::var("S3_SECRET_KEY").map_err(std::io::Error::other)?,
+        std::env::var("S3_REGION").map_err(std::io::Error::other)?,
+        std::env::var("S3_ENDPOINT").ok(),
+        std::env::var("S3_USE_PATH_STYLE")
+            .ok()
+            .is_some_and(|val| val.eq_ignore_ascii_case("true")),
+    )
+    .map_err(std::io::Error::other)?;
+    let oidc_service = OidcService::new(OidcCache::new(OidcConfig::from_env(
+        &EnvironmentService::new(),
+    )));
 
     HttpServer::new(move || {
         App::new()
             .wrap(middleware::Compress::default())
             .app_data(DbConnection::new(db.clone()))
+            .app_data(MinioService::new(minio_client.clone()))
+            .app_data(oidc_service.clone())
             .configure(routes::configure_routes)
     })
     .bind(("0.0.0.0", 8070))?
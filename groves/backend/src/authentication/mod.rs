@@ -1,63 +1,287 @@
-use openidconnect::core::{CoreClient, CoreProviderMetadata, CoreUserInfoClaims};
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+use actix_web::web;
+use openidconnect::core::{
+    CoreClient, CoreGenderClaim, CoreIdTokenVerifier, CoreJsonWebKeyType,
+    CoreJweContentEncryptionAlgorithm, CoreJwsSigningAlgorithm, CoreProviderMetadata,
+};
 use openidconnect::reqwest::async_http_client;
 use openidconnect::{
-    AccessToken, AdditionalClaims, ClientId, ClientSecret, IssuerUrl, RevocationUrl,
+    AccessToken, AdditionalClaims, ClaimsVerificationError, ClientId, ClientSecret, IdToken,
+    IssuerUrl, Nonce, RevocationUrl, UserInfoClaims,
 };
+use parking_lot::Mutex;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 
-use bamboo_common::backend::services::EnvService;
+use bamboo_common::backend::services::EnvironmentService;
+use bamboo_common::core::entities::{GrovesAdmin, GrovesAdminRole};
 use bamboo_common::core::error::{BambooError, BambooResult};
 
+/// Zitadel only sends the project roles if the scope `urn:zitadel:iam:org:project:roles` is requested,
+/// the plain `roles` claim is supported for issuers that map the roles with an action.
 #[derive(Debug, Deserialize, Serialize)]
 pub struct ZitadelClaims {
+    #[serde(default)]
     pub roles: Vec<String>,
+    #[serde(rename = "urn:zitadel:iam:org:project:roles", default)]
+    pub project_roles: HashMap<String, serde_json::Value>,
 }
 
 impl AdditionalClaims for ZitadelClaims {}
 
-pub async fn get_client(env_service: EnvService) -> BambooResult<CoreClient> {
-    let provider_metadata = CoreProviderMetadata::discover_async(
-        IssuerUrl::new(env_service.get_env("ISSUER_URL", ""))
+impl ZitadelClaims {
+    pub fn role(&self) -> Option<GrovesAdminRole> {
+        self.roles
+            .iter()
+            .chain(self.project_roles.keys())
+            .filter_map(|role_name| GrovesAdminRole::from_role_name(role_name))
+            .max()
+    }
+}
+
+/// JWT access tokens carry the same registered claims as an id token, so they are verified like one.
+type JwtAccessToken = IdToken<
+    ZitadelClaims,
+    CoreGenderClaim,
+    CoreJweContentEncryptionAlgorithm,
+    CoreJwsSigningAlgorithm,
+    CoreJsonWebKeyType,
+>;
+
+/// The keys of the issuer are not refreshed more often than this, even if tokens with unknown keys arrive.
+const MIN_DISCOVERY_REFRESH_INTERVAL: Duration = Duration::from_secs(60);
+
+pub struct OidcConfig {
+    pub issuer_url: String,
+    pub client_id: String,
+    pub client_secret: String,
+    pub revocation_url: Option<String>,
+    pub trusted_audiences: Vec<String>,
+    pub discovery_cache_duration: Duration,
+    pub user_info_cache_duration: Duration,
+}
+
+impl OidcConfig {
+    pub fn from_env(env_service: &EnvironmentService) -> Self {
+        let get_seconds = |key: &str, default: u64| {
+            Duration::from_secs(
+                env_service
+                    .get_env_opt(key)
+                    .and_then(|value| value.parse().ok())
+                    .unwrap_or(default),
+            )
+        };
+
+        Self {
+            issuer_url: env_service.get_env("ISSUER_URL", ""),
+            client_id: env_service.get_env("CLIENT_ID", ""),
+            client_secret: env_service.get_env("CLIENT_SECRET", ""),
+            revocation_url: env_service.get_env_opt("REVOCATION_URL"),
+            trusted_audiences: [
+                env_service.get_env_opt("FRONTEND_CLIENT_ID"),
+                env_service.get_env_opt("OIDC_AUD"),
+            ]
+            .into_iter()
+            .flatten()
+            .filter(|audience| !audience.is_empty())
+            .collect(),
+            discovery_cache_duration: get_seconds("OIDC_DISCOVERY_CACHE_SECONDS", 3600),
+            user_info_cache_duration: get_seconds("OIDC_USERINFO_CACHE_SECONDS", 60),
+        }
+    }
+}
+
+struct CachedClient {
+    client: CoreClient,
+    discovered_at: Instant,
+}
+
+/// Caches the provider metadata including the signing keys and the userinfo results, so that authenticating
+/// a request normally doesn't need a round trip to the issuer. The issuer is only taken from the config,
+/// the tests point it to a local mock issuer.
+pub struct OidcCache {
+    config: OidcConfig,
+    client: Mutex<Option<CachedClient>>,
+    user_infos: Mutex<HashMap<String, (GrovesAdmin, Instant)>>,
+}
+
+pub type OidcService = web::Data<OidcCache>;
+
+impl OidcCache {
+    pub fn new(config: OidcConfig) -> Self {
+        Self {
+            config,
+            client: Mutex::new(None),
+            user_infos: Mutex::new(HashMap::new()),
+        }
+    }
+
+    async fn discover_client(&self) -> BambooResult<CoreClient> {
+        let issuer_url = IssuerUrl::new(self.config.issuer_url.clone()).map_err(|err| {
+            log::error!("Failed to create issuer url {err}");
+            BambooError::unauthorized("login", "Invalid configuration")
+        })?;
+        let provider_metadata = CoreProviderMetadata::discover_async(issuer_url, async_http_client)
+            .await
             .map_err(|err| {
-                log::error!("Failed to create issuer url {err}");
+                log::error!("Failed to discover the issuer {err}");
                 BambooError::unauthorized("login", "Invalid configuration")
-            })
-            .map_err(|_| BambooError::unauthorized("login", "Invalid configuration"))?,
-        async_http_client,
-    )
-    .await
-    .map_err(|_| BambooError::unauthorized("login", "Invalid configuration"))?;
-
-    let mut client = CoreClient::from_provider_metadata(
-        provider_metadata,
-        ClientId::new(env_service.get_env("CLIENT_ID", "")),
-        Some(ClientSecret::new(env_service.get_env("CLIENT_SECRET", ""))),
-    );
-
-    if let Some(revocation_url) = env_service.get_env_opt("REVOCATION_URL") {
-        if let Ok(revocation_url) = RevocationUrl::new(revocation_url) {
-            client = client.set_revocation_uri(revocation_url);
+            })?;
+
+        let mut client = CoreClient::from_provider_metadata(
+            provider_metadata,
+            ClientId::new(self.config.client_id.clone()),
+            Some(ClientSecret::new(self.config.client_secret.clone())),
+        );
+
+        if let Some(revocation_url) = self.config.revocation_url.clone() {
+            if let Ok(revocation_url) = RevocationUrl::new(revocation_url) {
+                client = client.set_revocation_uri(revocation_url);
+            }
         }
+
+        *self.client.lock() = Some(CachedClient {
+            client: client.clone(),
+            discovered_at: Instant::now(),
+        });
+
+        Ok(client)
     }
 
-    Ok(client)
-}
+    async fn get_client(&self) -> BambooResult<CoreClient> {
+        let cached_client = self
+            .client
+            .lock()
+            .as_ref()
+            .filter(|cached| cached.discovered_at.elapsed() < self.config.discovery_cache_duration)
+            .map(|cached| cached.client.clone());
 
-pub async fn validate_user(access_token: AccessToken, client: CoreClient) -> BambooResult<String> {
-    let user_info: CoreUserInfoClaims = client
-        .user_info(access_token.clone(), None)
-        .map_err(|_| BambooError::unauthorized("login", "Invalid user"))?
-        .request_async(async_http_client)
-        .await
-        .map_err(|_| BambooError::unauthorized("login", "Invalid user"))?;
-
-    user_info
-        .name()
-        .ok_or(BambooError::unauthorized("user", "The name is required"))
-        .map(|name| {
-            name.iter()
-                .next()
-                .map(|(_, name)| name.to_string())
-                .ok_or(BambooError::unauthorized("user", "The name is required"))
-        })?
+        if let Some(client) = cached_client {
+            Ok(client)
+        } else {
+            self.discover_client().await
+        }
+    }
+
+    /// Discovers the issuer again to pick up rotated signing keys, returns `None` if the last discovery is too recent.
+    async fn refresh_client(&self) -> BambooResult<Option<CoreClient>> {
+        let is_recent =
+            self.client.lock().as_ref().is_some_and(|cached| {
+                cached.discovered_at.elapsed() < MIN_DISCOVERY_REFRESH_INTERVAL
+            });
+
+        if is_recent {
+            Ok(None)
+        } else {
+            self.discover_client().await.map(Some)
+        }
+    }
+
+    fn get_cached_user_info(&self, cache_key: &str) -> Option<GrovesAdmin> {
+        self.user_infos
+            .lock()
+            .get(cache_key)
+            .filter(|(_, cached_at)| cached_at.elapsed() < self.config.user_info_cache_duration)
+            .map(|(admin, _)| admin.clone())
+    }
+
+    fn cache_user_info(&self, cache_key: String, admin: GrovesAdmin) {
+        let mut user_infos = self.user_infos.lock();
+        user_infos
+            .retain(|_, (_, cached_at)| cached_at.elapsed() < self.config.user_info_cache_duration);
+        user_infos.insert(cache_key, (admin, Instant::now()));
+    }
+
+    fn verify_access_token(
+        &self,
+        access_token: &JwtAccessToken,
+        client: &CoreClient,
+    ) -> Result<Option<GrovesAdmin>, ClaimsVerificationError> {
+        let trusted_audiences = self.config.trusted_audiences.clone();
+        let verifier: CoreIdTokenVerifier = client
+            .id_token_verifier()
+            .set_other_audience_verifier_fn(move |audience| trusted_audiences.contains(audience));
+        let claims = access_token.claims(&verifier, |_: Option<&Nonce>| Ok(()))?;
+
+        let name = claims
+            .name()
+            .and_then(|name| name.iter().next().map(|(_, name)| name.to_string()))
+            .or_else(|| {
+                claims
+                    .preferred_username()
+                    .map(|username| username.to_string())
+            });
+        let role = claims.additional_claims().role();
+
+        Ok(name
+            .zip(role)
+            .map(|(name, role)| GrovesAdmin { name, role }))
+    }
+
+    async fn request_user_info(
+        &self,
+        access_token: AccessToken,
+        client: CoreClient,
+    ) -> BambooResult<GrovesAdmin> {
+        let user_info: UserInfoClaims<ZitadelClaims, CoreGenderClaim> = client
+            .user_info(access_token, None)
+            .map_err(|_| BambooError::unauthorized("login", "Invalid user"))?
+            .request_async(async_http_client)
+            .await
+            .map_err(|_| BambooError::unauthorized("login", "Invalid user"))?;
+
+        let name = user_info
+            .name()
+            .and_then(|name| name.iter().next().map(|(_, name)| name.to_string()))
+            .ok_or(BambooError::unauthorized("user", "The name is required"))?;
+        let role = user_info
+            .additional_claims()
+            .role()
+            .ok_or(BambooError::insufficient_rights(
+                "user",
+                "You have no role in the groves admin",
+            ))?;
+
+        Ok(GrovesAdmin { name, role })
+    }
+
+    /// Validates JWT access tokens locally against the cached signing keys. Opaque tokens and tokens without
+    /// the name or the role are validated with the userinfo endpoint, the result is cached briefly.
+    pub async fn validate_user(&self, access_token: AccessToken) -> BambooResult<GrovesAdmin> {
+        let cache_key = format!("{:x}", Sha256::digest(access_token.secret().as_bytes()));
+        if let Some(admin) = self.get_cached_user_info(cache_key.as_str()) {
+            return Ok(admin);
+        }
+
+        let mut client = self.get_client().await?;
+        if let Ok(jwt) = JwtAccessToken::from_str(access_token.secret()) {
+            let mut result = self.verify_access_token(&jwt, &client);
+            if let Err(ClaimsVerificationError::SignatureVerification(_)) = result {
+                if let Some(refreshed_client) = self.refresh_client().await? {
+                    result = self.verify_access_token(&jwt, &refreshed_client);
+                    client = refreshed_client;
+                }
+            }
+
+            match result {
+                Ok(Some(admin)) => return Ok(admin),
+                Ok(None) => {}
+                Err(err) => {
+                    log::debug!("The access token is invalid {err}");
+                    return Err(BambooError::unauthorized("login", "Invalid token"));
+                }
+            }
+        }
+
+        let admin = self.request_user_info(access_token, client).await?;
+        self.cache_user_info(cache_key, admin.clone());
+
+        Ok(admin)
+    }
 }
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,246 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use actix_web::{web, App, HttpRequest, HttpResponse, HttpServer};
+use base64::Engine;
+use openidconnect::AccessToken;
+use openssl::hash::MessageDigest;
+use openssl::pkey::{PKey, Private};
+use openssl::rsa::Rsa;
+use openssl::sign::Signer;
+use serde_json::{json, Value};
+
+use bamboo_common::core::entities::{GrovesAdmin, GrovesAdminRole};
+use bamboo_common::core::error::BambooErrorCode;
+
+use super::{OidcCache, OidcConfig};
+
+const CLIENT_ID: &str = "groves-backend";
+const KEY_ID: &str = "mock-key";
+const OPAQUE_TOKEN: &str = "opaque-access-token";
+
+fn encode(data: &[u8]) -> String {
+    base64::prelude::BASE64_URL_SAFE_NO_PAD.encode(data)
+}
+
+struct MockIssuerState {
+    issuer_url: String,
+    key: PKey<Private>,
+    user_info_requests: AtomicUsize,
+}
+
+/// A local issuer with discovery, signing keys and a userinfo endpoint that knows a single opaque token.
+struct MockIssuer {
+    state: Arc<MockIssuerState>,
+}
+
+async fn discovery(state: web::Data<MockIssuerState>) -> HttpResponse {
+    HttpResponse::Ok().json(json!({
+        "issuer": state.issuer_url,
+        "authorization_endpoint": format!("{}/authorize", state.issuer_url),
+        "token_endpoint": format!("{}/token", state.issuer_url),
+        "userinfo_endpoint": format!("{}/userinfo", state.issuer_url),
+        "jwks_uri": format!("{}/keys", state.issuer_url),
+        "response_types_supported": ["code"],
+        "subject_types_supported": ["public"],
+        "id_token_signing_alg_values_supported": ["RS256"],
+    }))
+}
+
+async fn keys(state: web::Data<MockIssuerState>) -> HttpResponse {
+    let rsa = state.key.rsa().unwrap();
+
+    HttpResponse::Ok().json(json!({
+        "keys": [{
+            "kty": "RSA",
+            "use": "sig",
+            "alg": "RS256",
+            "kid": KEY_ID,
+            "n": encode(rsa.n().to_vec().as_slice()),
+            "e": encode(rsa.e().to_vec().as_slice()),
+        }],
+    }))
+}
+
+async fn user_info(req: HttpRequest, state: web::Data<MockIssuerState>) -> HttpResponse {
+    state.user_info_requests.fetch_add(1, Ordering::SeqCst);
+
+    let authorization = req
+        .headers()
+        .get("Authorization")
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default();
+    if authorization == format!("Bearer {OPAQUE_TOKEN}") {
+        HttpResponse::Ok().json(json!({
+            "sub": "opaque-panda",
+            "name": "Opaque Panda",
+            "roles": ["support"],
+        }))
+    } else {
+        HttpResponse::Unauthorized().finish()
+    }
+}
+
+impl MockIssuer {
+    async fn start() -> Self {
+        let key = PKey::from_rsa(Rsa::generate(2048).unwrap()).unwrap();
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let state = Arc::new(MockIssuerState {
+            issuer_url: format!("http://{}", listener.local_addr().unwrap()),
+            key,
+            user_info_requests: AtomicUsize::new(0),
+        });
+
+        let data = web::Data::from(state.clone());
+        let server = HttpServer::new(move || {
+            App::new()
+                .app_data(data.clone())
+                .route(
+                    "/.well-known/openid-configuration",
+                    web::get().to(discovery),
+                )
+                .route("/keys", web::get().to(keys))
+                .route("/userinfo", web::get().to(user_info))
+        })
+        .workers(1)
+        .listen(listener)
+        .unwrap()
+        .run();
+        actix_web::rt::spawn(server);
+
+        Self { state }
+    }
+
+    fn cache(&self) -> OidcCache {
+        OidcCache::new(OidcConfig {
+            issuer_url: self.state.issuer_url.clone(),
+            client_id: CLIENT_ID.to_string(),
+            client_secret: "secret".to_string(),
+            revocation_url: None,
+            trusted_audiences: vec![],
+            discovery_cache_duration: Duration::from_secs(3600),
+            user_info_cache_duration: Duration::from_secs(60),
+        })
+    }
+
+    fn sign_with(&self, key: &PKey<Private>, claims: Value) -> AccessToken {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        let mut claims = claims;
+        claims["iss"] = json!(self.state.issuer_url);
+        claims["aud"] = json!([CLIENT_ID]);
+        claims["iat"] = json!(now);
+        claims["exp"] = json!(now + 300);
+
+        let header = encode(
+            serde_json::to_vec(&json!({"alg": "RS256", "typ": "JWT", "kid": KEY_ID}))
+                .unwrap()
+                .as_slice(),
+        );
+        let payload = encode(serde_json::to_vec(&claims).unwrap().as_slice());
+        let signing_input = format!("{header}.{payload}");
+
+        let mut signer = Signer::new(MessageDigest::sha256(), key).unwrap();
+        signer.update(signing_input.as_bytes()).unwrap();
+        let signature = encode(signer.sign_to_vec().unwrap().as_slice());
+
+        AccessToken::new(format!("{signing_input}.{signature}"))
+    }
+
+    fn sign(&self, claims: Value) -> AccessToken {
+        self.sign_with(&self.state.key, claims)
+    }
+
+    fn user_info_requests(&self) -> usize {
+        self.state.user_info_requests.load(Ordering::SeqCst)
+    }
+}
+
+#[actix_web::test]
+async fn jwt_access_tokens_are_validated_locally() {
+    let issuer = MockIssuer::start().await;
+    let cache = issuer.cache();
+
+    let admin = cache
+        .validate_user(issuer.sign(json!({
+            "sub": "admin-panda",
+            "name": "Admin Panda",
+            "roles": ["admin"],
+        })))
+        .await
+        .unwrap();
+
+    assert_eq!(
+        admin,
+        GrovesAdmin {
+            name: "Admin Panda".to_string(),
+            role: GrovesAdminRole::Admin,
+        }
+    );
+    assert_eq!(issuer.user_info_requests(), 0);
+}
+
+#[actix_web::test]
+async fn jwt_access_tokens_with_a_foreign_signature_are_rejected() {
+    let issuer = MockIssuer::start().await;
+    let cache = issuer.cache();
+    let foreign_key = PKey::from_rsa(Rsa::generate(2048).unwrap()).unwrap();
+
+    let err = cache
+        .validate_user(issuer.sign_with(
+            &foreign_key,
+            json!({
+                "sub": "admin-panda",
+                "name": "Admin Panda",
+                "roles": ["admin"],
+            }),
+        ))
+        .await
+        .unwrap_err();
+
+    assert_eq!(err.error_type, BambooErrorCode::Unauthorized);
+    assert_eq!(issuer.user_info_requests(), 0);
+}
+
+#[actix_web::test]
+async fn opaque_tokens_fall_back_to_the_cached_userinfo() {
+    let issuer = MockIssuer::start().await;
+    let cache = issuer.cache();
+    let expected = GrovesAdmin {
+        name: "Opaque Panda".to_string(),
+        role: GrovesAdminRole::Support,
+    };
+
+    let first = cache
+        .validate_user(AccessToken::new(OPAQUE_TOKEN.to_string()))
+        .await
+        .unwrap();
+    let second = cache
+        .validate_user(AccessToken::new(OPAQUE_TOKEN.to_string()))
+        .await
+        .unwrap();
+
+    assert_eq!(first, expected);
+    assert_eq!(second, expected);
+    assert_eq!(issuer.user_info_requests(), 1);
+}
+
+#[actix_web::test]
+async fn jwt_access_tokens_without_a_role_fall_back_to_the_userinfo() {
+    let issuer = MockIssuer::start().await;
+    let cache = issuer.cache();
+
+    let err = cache
+        .validate_user(issuer.sign(json!({
+            "sub": "unknown-panda",
+            "name": "Unknown Panda",
+        })))
+        .await
+        .unwrap_err();
+
+    assert_eq!(err.error_type, BambooErrorCode::Unauthorized);
+    assert_eq!(issuer.user_info_requests(), 1);
+}
@@ -1,21 +1,23 @@
 use actix_web::{body, dev, Error, HttpMessage};
 use actix_web_lab::middleware::Next;
-use bamboo_common::backend::services::EnvService;
+use bamboo_common::core::entities::{GrovesAdmin, GrovesAdminRole};
 use bamboo_common::core::error::BambooError;
 use openidconnect::AccessToken;
 
-use crate::authentication::{get_client, validate_user};
+use crate::authentication::OidcService;
 
 pub type Username = String;
 
+/// Authenticates the user against the issuer and checks that the role from the token is at least the required role of the route.
 pub async fn authenticate_user(
     req: dev::ServiceRequest,
     next: Next<impl body::MessageBody>,
+    required_role: GrovesAdminRole,
 ) -> Result<dev::ServiceResponse<impl body::MessageBody>, Error> {
-    let env_service = req
-        .app_data::<EnvService>()
-        .ok_or(BambooError::unauthorized("login", "Invalid data"))?;
-    let client = get_client(env_service.clone()).await?;
+    let oidc_service = req
+        .app_data::<OidcService>()
+        .ok_or(BambooError::unauthorized("login", "Invalid data"))?
+        .clone();
     let authorization_header = req.headers().get("Authorization");
     if let Some(authorization_header) = authorization_header {
         let access_token = authorization_header
@@ -23,9 +25,19 @@ pub async fn authenticate_user(
             .map_err(|_| BambooError::unauthorized("login", "Invalid header"))?
             .to_string();
         if let Some(access_token) = access_token.strip_prefix("Bearer ") {
-            let name = validate_user(AccessToken::new(access_token.to_string()), client).await?;
+            let admin = oidc_service
+                .validate_user(AccessToken::new(access_token.to_string()))
+                .await?;
+            if admin.role < required_role {
+                return Err(BambooError::insufficient_rights(
+                    "user",
+                    "Your role is not allowed to perform this action",
+                )
+                .into());
+            }
 
-            req.extensions_mut().insert(name as Username);
+            req.extensions_mut().insert(admin.name.clone() as Username);
+            req.extensions_mut().insert(admin as GrovesAdmin);
 
             next.call(req).await
         } else {
@@ -37,8 +49,12 @@ pub async fn authenticate_user(
 }
 
 macro_rules! authenticate {
-    () => {
-        actix_web_lab::middleware::from_fn(crate::middleware::authenticate_user::authenticate_user)
+    ($role:expr) => {
+        actix_web_lab::middleware::from_fn(
+            |req: actix_web::dev::ServiceRequest, next: actix_web_lab::middleware::Next<_>| {
+                crate::middleware::authenticate_user::authenticate_user(req, next, $role)
+            },
+        )
     };
 }
 
@@ -1,24 +1,29 @@
 use actix_web::{delete, get, post, put, web};
+use base64::Engine;
 
 use bamboo_common::backend::response::{
     check_invalid_path, check_missing_fields, created, list, no_content, ok,
 };
-use bamboo_common::backend::services::{DbConnection, EnvService};
-use bamboo_common::backend::utils::get_random_password;
+use bamboo_common::backend::services::{DbConnection, EnvService, MinioService};
 use bamboo_common::backend::{dbal, mailing};
 use bamboo_common::core::entities::grove::CreateGroveRequest;
-use bamboo_common::core::entities::{Grove, User};
-use bamboo_common::core::error::{BambooApiResponseResult, BambooApiResult};
+use bamboo_common::core::entities::{
+    Grove, GroveExportArchive, GroveImport, GroveStatistics, GrovesAdminRole, User,
+};
+use bamboo_common::core::error::{BambooApiResponseResult, BambooApiResult, BambooError};
 
 use crate::middleware::authenticate_user::{authenticate, Username};
 use crate::path::GrovePath;
 
-#[get("/api/grove", wrap = "authenticate!()")]
+#[get("/api/grove", wrap = "authenticate!(GrovesAdminRole::Viewer)")]
 pub async fn get_groves(db: DbConnection) -> BambooApiResponseResult {
     dbal::get_groves(&db).await.map(|data| list!(data))
 }
 
-#[get("/api/grove/{grove_id}", wrap = "authenticate!()")]
+#[get(
+    "/api/grove/{grove_id}",
+    wrap = "authenticate!(GrovesAdminRole::Viewer)"
+)]
 pub async fn get_grove(
     path: Option<web::Path<GrovePath>>,
     db: DbConnection,
@@ -29,7 +34,34 @@ pub async fn get_grove(
         .map(|grove| ok!(grove))
 }
 
-#[post("/api/grove", wrap = "authenticate!()")]
+#[get(
+    "/api/grove/{grove_id}/statistics",
+    wrap = "authenticate!(GrovesAdminRole::Viewer)"
+)]
+pub async fn get_grove_statistics(
+    path: Option<web::Path<GrovePath>>,
+    db: DbConnection,
+    minio: MinioService,
+) -> BambooApiResult<GroveStatistics> {
+    let path = check_invalid_path!(path, "grove")?;
+
+    let statistics = dbal::get_grove_statistics(path.grove_id, &db).await?;
+    let user_ids = dbal::get_users(path.grove_id, &db)
+        .await?
+        .iter()
+        .map(|user| user.id)
+        .collect::<Vec<i32>>();
+    let picture_storage_bytes = minio
+        .get_picture_storage_size(path.grove_id, &user_ids)
+        .await?;
+
+    Ok(ok!(GroveStatistics {
+        picture_storage_bytes,
+        ..statistics
+    }))
+}
+
+#[post("/api/grove", wrap = "authenticate!(GrovesAdminRole::Admin)")]
 pub async fn create_grove(
     create_grove: Option<web::Json<CreateGroveRequest>>,
     db: DbConnection,
@@ -37,8 +69,12 @@ pub async fn create_grove(
     username: web::ReqData<Username>,
 ) -> BambooApiResult<Grove> {
     let create_grove = check_missing_fields!(create_grove, "grove")?;
+    let password = dbal::generate_password(&[
+        create_grove.mod_email.clone(),
+        create_grove.mod_name.clone(),
+    ])
+    .await?;
     let grove = dbal::create_grove(create_grove.grove_name.clone(), &db).await?;
-    let password = get_random_password();
     let created_mod = dbal::create_user(
         grove.id,
         User::new(
@@ -63,7 +99,80 @@ pub async fn create_grove(
     Ok(created!(grove))
 }
 
-#[delete("/api/grove/{grove_id}/suspension", wrap = "authenticate!()")]
+/// The archive comes as raw body, since it is usually way bigger than the json limit of the other routes.
+/// The route is registered as resource in [`super::configure_routes`], only there the bigger body limit can be attached.
+pub async fn import_grove(
+    body: web::Bytes,
+    db: DbConnection,
+    minio: MinioService,
+    env_service: EnvService,
+    username: web::ReqData<Username>,
+) -> BambooApiResult<GroveImport> {
+    let archive = serde_json::from_slice::<GroveExportArchive>(&body).map_err(|err| {
+        log::error!("Failed to read grove archive {err}");
+        BambooError::validation("grove_import", "The archive could not be read")
+    })?;
+    let profile_pictures = archive
+        .users
+        .iter()
+        .filter_map(|user| {
+            user.profile_picture
+                .as_ref()
+                .map(|profile_picture| (user.user.id, profile_picture.clone()))
+        })
+        .collect::<Vec<(i32, String)>>();
+
+    let grove_import = dbal::import_grove(archive, &db).await?;
+    let grove_name = grove_import.grove.name.clone();
+    let pandas_base_url = env_service.get_env("PANDAS_BASE_URL", "https://pandas.bambushain.app");
+    let imported_by = format!("{} (Bambushainteam)", username.into_inner());
+    for imported_user in grove_import.users.iter() {
+        if imported_user.has_existing_account {
+            let _ = mailing::user::send_added_to_grove(
+                imported_user.display_name.clone(),
+                imported_by.clone(),
+                grove_name.clone(),
+                imported_user.email.clone(),
+                env_service.clone(),
+            )
+            .await;
+            continue;
+        }
+
+        if let Some((_, profile_picture)) = profile_pictures
+            .iter()
+            .find(|(exported_id, _)| *exported_id == imported_user.exported_id)
+        {
+            if let Ok(data) = base64::prelude::BASE64_STANDARD.decode(profile_picture) {
+                let _ = minio.upload_profile_picture(imported_user.id, &data).await;
+            }
+        }
+
+        match dbal::create_imported_user_password_token(imported_user.id, &db).await {
+            Ok(token) => {
+                let _ = mailing::user::send_grove_imported(
+                    imported_user.display_name.clone(),
+                    grove_name.clone(),
+                    imported_user.email.clone(),
+                    format!(
+                        "{}/reset-password?token={token}",
+                        pandas_base_url.trim_end_matches('/')
+                    ),
+                    env_service.clone(),
+                )
+                .await;
+            }
+            Err(err) => log::error!("Failed to invite imported user {err}"),
+        }
+    }
+
+    Ok(created!(grove_import))
+}
+
+#[delete(
+    "/api/grove/{grove_id}/suspension",
+    wrap = "authenticate!(GrovesAdminRole::Support)"
+)]
 pub async fn suspend_grove(
     path: Option<web::Path<GrovePath>>,
     db: DbConnection,
@@ -75,19 +184,45 @@ pub async fn suspend_grove(
         .map(|_| no_content!())
 }
 
-#[delete("/api/grove/{grove_id}", wrap = "authenticate!()")]
+#[delete(
+    "/api/grove/{grove_id}",
+    wrap = "authenticate!(GrovesAdminRole::Admin)"
+)]
 pub async fn delete_grove(
     path: Option<web::Path<GrovePath>>,
     db: DbConnection,
+    env_service: EnvService,
+) -> BambooApiResponseResult {
+    let path = check_invalid_path!(path, "grove")?;
+
+    dbal::schedule_grove_deletion(
+        path.grove_id,
+        env_service.get_grove_deletion_grace_period_days(),
+        &db,
+    )
+    .await
+    .map(|_| no_content!())
+}
+
+#[delete(
+    "/api/grove/{grove_id}/deletion",
+    wrap = "authenticate!(GrovesAdminRole::Support)"
+)]
+pub async fn restore_grove(
+    path: Option<web::Path<GrovePath>>,
+    db: DbConnection,
 ) -> BambooApiResponseResult {
     let path = check_invalid_path!(path, "grove")?;
 
-    dbal::delete_grove(path.grove_id, &db)
+    dbal::restore_grove(path.grove_id, &db)
         .await
         .map(|_| no_content!())
 }
 
-#[put("/api/grove/{grove_id}/suspension", wrap = "authenticate!()")]
+#[put(
+    "/api/grove/{grove_id}/suspension",
+    wrap = "authenticate!(GrovesAdminRole::Support)"
+)]
 pub async fn resume_grove(
     path: Option<web::Path<GrovePath>>,
     db: DbConnection,
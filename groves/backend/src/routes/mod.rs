@@ -1,11 +1,18 @@
 use actix_web::web;
 use bamboo_common::backend::services::{EnvService, EnvironmentService};
+use bamboo_common::core::entities::GrovesAdminRole;
 use std::fs::File;
 use uuid::Uuid;
 
+use crate::middleware::authenticate_user::authenticate;
+
 mod groves;
+mod my;
 mod user;
 
+/// Grove archives contain the profile pictures of all pandas, so the body limit is a lot bigger than the default.
+const MAX_IMPORT_SIZE: usize = 256 * 1024 * 1024;
+
 fn prepare_index_file(
     frontend_base_path: impl Into<String> + std::fmt::Display,
 ) -> impl Into<String> + std::fmt::Display + Clone {
@@ -43,12 +50,21 @@ pub fn configure_routes(cfg: &mut web::ServiceConfig) {
     log::info!("The index file is stored in {}", index_file.clone());
 
     cfg.app_data(environment_service)
+        .service(my::get_me)
         .service(groves::get_groves)
         .service(groves::get_grove)
+        .service(groves::get_grove_statistics)
         .service(groves::create_grove)
+        .service(
+            web::resource("/api/grove/import")
+                .app_data(web::PayloadConfig::new(MAX_IMPORT_SIZE))
+                .wrap(authenticate!(GrovesAdminRole::Admin))
+                .route(web::post().to(groves::import_grove)),
+        )
         .service(groves::suspend_grove)
         .service(groves::resume_grove)
         .service(groves::delete_grove)
+        .service(groves::restore_grove)
         .service(user::get_users)
         .service(user::reset_user_password)
         .service(user::make_user_mod)
@@ -0,0 +1,12 @@
+use actix_web::{get, web};
+
+use bamboo_common::backend::response::ok;
+use bamboo_common::core::entities::{GrovesAdmin, GrovesAdminRole};
+use bamboo_common::core::error::BambooApiResult;
+
+use crate::middleware::authenticate_user::authenticate;
+
+#[get("/api/my", wrap = "authenticate!(GrovesAdminRole::Viewer)")]
+pub async fn get_me(admin: web::ReqData<GrovesAdmin>) -> BambooApiResult<GrovesAdmin> {
+    Ok(ok!(admin.into_inner()))
+}
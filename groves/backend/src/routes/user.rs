@@ -2,15 +2,17 @@ use actix_web::{delete, get, put, web};
 
 use bamboo_common::backend::response::{check_invalid_path, list, no_content};
 use bamboo_common::backend::services::{DbConnection, EnvService};
-use bamboo_common::backend::utils::get_random_password;
 use bamboo_common::backend::{dbal, mailing};
-use bamboo_common::core::entities::GroveUser;
+use bamboo_common::core::entities::{GroveUser, GrovesAdminRole};
 use bamboo_common::core::error::{BambooApiResponseResult, BambooError};
 
 use crate::middleware::authenticate_user::authenticate;
 use crate::path::{GrovePath, GroveUserPath};
 
-#[get("/api/grove/{grove_id}/user", wrap = "authenticate!()")]
+#[get(
+    "/api/grove/{grove_id}/user",
+    wrap = "authenticate!(GrovesAdminRole::Viewer)"
+)]
 pub async fn get_users(
     path: Option<web::Path<GrovePath>>,
     db: DbConnection,
@@ -28,7 +30,7 @@ pub async fn get_users(
 
 #[put(
     "/api/grove/{grove_id}/user/{user_id}/password",
-    wrap = "authenticate!()"
+    wrap = "authenticate!(GrovesAdminRole::Support)"
 )]
 pub async fn reset_user_password(
     path: Option<web::Path<GroveUserPath>>,
@@ -39,7 +41,8 @@ pub async fn reset_user_password(
     let user = dbal::get_user(path.grove_id, path.user_id, &db).await?;
 
     if user.is_mod {
-        let password = get_random_password();
+        let password =
+            dbal::generate_password(&[user.email.clone(), user.display_name.clone()]).await?;
         dbal::change_password(path.grove_id, path.user_id, password.clone(), &db).await?;
         mailing::user::send_password_changed(
             user.display_name.clone(),
@@ -58,7 +61,10 @@ pub async fn reset_user_password(
     }
 }
 
-#[put("/api/grove/{grove_id}/user/{user_id}/mod", wrap = "authenticate!()")]
+#[put(
+    "/api/grove/{grove_id}/user/{user_id}/mod",
+    wrap = "authenticate!(GrovesAdminRole::Support)"
+)]
 pub async fn make_user_mod(
     path: Option<web::Path<GroveUserPath>>,
     db: DbConnection,
@@ -69,7 +75,10 @@ pub async fn make_user_mod(
         .map(|_| no_content!())
 }
 
-#[delete("/api/grove/{grove_id}/user/{user_id}/mod", wrap = "authenticate!()")]
+#[delete(
+    "/api/grove/{grove_id}/user/{user_id}/mod",
+    wrap = "authenticate!(GrovesAdminRole::Support)"
+)]
 pub async fn remove_user_mod(
     path: Option<web::Path<GroveUserPath>>,
     db: DbConnection,
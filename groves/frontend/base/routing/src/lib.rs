@@ -9,4 +9,6 @@ pub enum AppRoute {
     Groves,
     #[at("/app/mods/:grove_id")]
     Users { grove_id: i32 },
+    #[at("/app/grove/:grove_id")]
+    Grove { grove_id: i32 },
 }
@@ -1,12 +1,24 @@
 use bamboo_common::core::entities::grove::CreateGroveRequest;
-use bamboo_common::core::entities::Grove;
-use bamboo_common::frontend::api::{delete, get, post, put_no_body_no_content, BambooApiResult};
+use bamboo_common::core::entities::{Grove, GroveImport, GroveStatistics};
+use bamboo_common::frontend::api::{
+    delete, get, post, post_file, put_no_body_no_content, BambooApiResult,
+};
 
 pub async fn get_groves() -> BambooApiResult<Vec<Grove>> {
     log::debug!("Get all groves");
     get("/api/grove").await
 }
 
+pub async fn get_grove(id: i32) -> BambooApiResult<Grove> {
+    log::debug!("Get grove {id}");
+    get(format!("/api/grove/{id}")).await
+}
+
+pub async fn get_grove_statistics(id: i32) -> BambooApiResult<GroveStatistics> {
+    log::debug!("Get statistics of grove {id}");
+    get(format!("/api/grove/{id}/statistics")).await
+}
+
 pub async fn create_grove(
     grove_name: String,
     mod_name: String,
@@ -20,11 +32,21 @@ pub async fn create_grove(
     .await
 }
 
+pub async fn import_grove(archive: web_sys::File) -> BambooApiResult<GroveImport> {
+    log::debug!("Import grove from {}", archive.name());
+    post_file("/api/grove/import", archive).await
+}
+
 pub async fn delete_grove(id: i32) -> BambooApiResult<()> {
     log::debug!("Delete grove {id}");
     delete(format!("/api/grove/{id}")).await
 }
 
+pub async fn restore_grove(id: i32) -> BambooApiResult<()> {
+    log::debug!("Restore grove {id}");
+    delete(format!("/api/grove/{id}/deletion")).await
+}
+
 pub async fn suspend_grove(id: i32) -> BambooApiResult<()> {
     log::debug!("Suspend grove {id}");
     delete(format!("/api/grove/{id}/suspension")).await
@@ -1,3 +1,4 @@
+pub use pages::grove::*;
 pub use pages::groves::*;
 
 mod api;
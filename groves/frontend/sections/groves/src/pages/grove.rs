@@ -0,0 +1,89 @@
+use chrono::{Local, TimeZone};
+use yew::prelude::*;
+use yew_autoprops::autoprops;
+use yew_cosmo::prelude::*;
+use yew_hooks::{use_async, use_mount};
+
+use bamboo_common::core::entities::GroveStatistics;
+
+use crate::api;
+
+fn format_storage(bytes: u64) -> String {
+    let units = ["Bytes", "KB", "MB", "GB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < units.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{bytes} {}", units[unit])
+    } else {
+        format!("{size:.1} {}", units[unit]).replace('.', ",")
+    }
+}
+
+#[autoprops]
+#[function_component(GroveStatisticsList)]
+fn grove_statistics_list(statistics: &GroveStatistics) -> Html {
+    let last_activity = statistics
+        .last_activity_at
+        .map(|last_activity_at| {
+            Local
+                .from_utc_datetime(&last_activity_at)
+                .format("%d.%m.%Y %H:%M")
+                .to_string()
+        })
+        .unwrap_or("Noch keine Aktivität".to_string());
+
+    html!(
+        <CosmoKeyValueList>
+            <CosmoKeyValueListItem title="Mitglieder">{statistics.member_count}</CosmoKeyValueListItem>
+            <CosmoKeyValueListItem title="Mods">{statistics.mod_count}</CosmoKeyValueListItem>
+            <CosmoKeyValueListItem title="Aktiv in den letzten 7 Tagen">{statistics.active_users_last_7_days}</CosmoKeyValueListItem>
+            <CosmoKeyValueListItem title="Aktiv in den letzten 30 Tagen">{statistics.active_users_last_30_days}</CosmoKeyValueListItem>
+            <CosmoKeyValueListItem title="Events">{statistics.event_count}</CosmoKeyValueListItem>
+            <CosmoKeyValueListItem title="Charaktere">{statistics.character_count}</CosmoKeyValueListItem>
+            <CosmoKeyValueListItem title="Speicher für Bilder">{format_storage(statistics.picture_storage_bytes)}</CosmoKeyValueListItem>
+            <CosmoKeyValueListItem title="Letzte Aktivität">{last_activity}</CosmoKeyValueListItem>
+        </CosmoKeyValueList>
+    )
+}
+
+#[autoprops]
+#[function_component(GroveDetailsPage)]
+pub fn grove_details_page(grove_id: i32) -> Html {
+    log::debug!("Render grove details");
+    let grove_state = use_async(async move { api::get_grove(grove_id).await });
+    let statistics_state = use_async(async move { api::get_grove_statistics(grove_id).await });
+
+    {
+        let grove_state = grove_state.clone();
+        let statistics_state = statistics_state.clone();
+
+        use_mount(move || {
+            grove_state.run();
+            statistics_state.run();
+        });
+    }
+
+    html!(
+        <>
+            if let Some(grove) = grove_state.data.clone() {
+                <CosmoTitle title={grove.name} />
+            } else {
+                <CosmoTitle title="Hain" />
+            }
+            <CosmoHeader level={CosmoHeaderLevel::H3} header="Nutzung" />
+            if statistics_state.loading {
+                <CosmoProgressRing />
+            } else if statistics_state.error.is_some() {
+                <CosmoMessage message_type={CosmoMessageType::Negative} header="Fehler beim Laden" message="Leider konnte die Nutzung des Hains nicht geladen werden" />
+            } else if let Some(statistics) = statistics_state.data.clone() {
+                <GroveStatisticsList statistics={statistics} />
+                <CosmoParagraph>{"Als aktiv zählen Pandas, die den Hain im Zeitraum benutzt haben. Der Speicher umfasst die Profilbilder der Mitglieder und das Logo des Hains."}</CosmoParagraph>
+            }
+        </>
+    )
+}
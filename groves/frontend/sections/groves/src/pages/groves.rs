@@ -6,8 +6,8 @@ use yew_hooks::{use_async, use_bool_toggle, use_mount};
 use yew_icons::{Icon, IconId};
 use yew_router::prelude::use_navigator;
 
-use bamboo_common::core::entities::Grove;
-use bamboo_common::frontend::api::CONFLICT;
+use bamboo_common::core::entities::{Grove, GroveImport, GrovesAdminRole};
+use bamboo_common::frontend::api::{ApiError, BAD_REQUEST, CONFLICT};
 use bamboo_groves_frontend_base_routing::AppRoute;
 
 use crate::api;
@@ -75,15 +75,74 @@ fn create_grove_dialog(on_saved: &Callback<Grove>, on_close: &Callback<()>) -> H
     )
 }
 
+#[autoprops]
+#[function_component(ImportGroveDialog)]
+fn import_grove_dialog(on_close: &Callback<()>) -> Html {
+    let archive_state = use_state_eq(|| None as Option<web_sys::File>);
+
+    let import_state = {
+        let archive_state = archive_state.clone();
+
+        use_async(async move {
+            if let Some(archive) = (*archive_state).clone() {
+                api::import_grove(archive).await.map(Some)
+            } else {
+                Ok::<_, ApiError>(None as Option<GroveImport>)
+            }
+        })
+    };
+
+    let select_archive = use_callback(archive_state.clone(), |file, state| state.set(Some(file)));
+    let on_import = use_callback(import_state.clone(), |_, import_state| import_state.run());
+
+    html!(
+        <CosmoModal title="Hain importieren" is_form={true} on_form_submit={on_import} buttons={
+            html!(
+                <>
+                    <CosmoButton on_click={on_close.clone()} label={if import_state.data.clone().flatten().is_some() { "Schließen" } else { "Abbrechen" }} />
+                    <CosmoButton is_submit={true} enabled={!import_state.loading && archive_state.is_some()} label="Hain importieren" />
+                </>
+            )}>
+            <>
+                if import_state.loading {
+                    <CosmoProgressRing />
+                } else if let Some(err) = &import_state.error {
+                    if err.code == CONFLICT {
+                        <CosmoMessage message="Ein Hain mit diesem Namen existiert bereits" message_type={CosmoMessageType::Negative} />
+                    } else if err.code == BAD_REQUEST {
+                        <CosmoMessage message="Das Archiv konnte nicht gelesen werden, es muss ein Export aus dem Bambushain in einer unterstützten Version sein" message_type={CosmoMessageType::Negative} />
+                    } else {
+                        <CosmoMessage message="Der Hain konnte leider nicht importiert werden" message_type={CosmoMessageType::Negative} />
+                    }
+                } else if let Some(grove_import) = import_state.data.clone().flatten() {
+                    <CosmoMessage message_type={CosmoMessageType::Positive} header="Hain importiert" message={format!(
+                        "Der Hain {} wurde mit {} Pandas importiert, davon hatten {} bereits einen Account und {} mussten umbenannt werden. Alle Pandas haben eine Mail bekommen.",
+                        grove_import.grove.name,
+                        grove_import.users.len(),
+                        grove_import.users.iter().filter(|user| user.has_existing_account).count(),
+                        grove_import.users.iter().filter(|user| user.was_renamed).count(),
+                    )} />
+                } else {
+                    <CosmoMessage message_type={CosmoMessageType::Information} header="Hain importieren" message="Wähle einen Export aus dem Bambushain aus. Neue Pandas bekommen eine Mail mit einem Link um ihr Passwort zu setzen, Pandas mit Account werden zum Hain hinzugefügt." />
+                }
+                <CosmoFilePicker label="Archiv" on_select={select_archive} />
+            </>
+        </CosmoModal>
+    )
+}
+
+#[autoprops]
 #[function_component(GrovesPage)]
-pub fn groves_page() -> Html {
+pub fn groves_page(role: &GrovesAdminRole) -> Html {
     log::debug!("Render groves overview");
     let navigator = use_navigator().expect("Router needs to be available");
 
     let create_grove_open_toggle = use_bool_toggle(false);
+    let import_grove_open_toggle = use_bool_toggle(false);
     let grove_to_suspend_state = use_state_eq(|| None as Option<Grove>);
     let grove_to_resume_state = use_state_eq(|| None as Option<Grove>);
     let grove_to_delete_state = use_state_eq(|| None as Option<Grove>);
+    let grove_to_restore_state = use_state_eq(|| None as Option<Grove>);
 
     let groves_state = use_async(async move { api::get_groves().await });
     let suspend_grove_state = {
@@ -143,6 +202,25 @@ pub fn groves_page() -> Html {
             }
         })
     };
+    let restore_grove_state = {
+        let grove_to_restore_state = grove_to_restore_state.clone();
+
+        let groves_state = groves_state.clone();
+
+        use_async(async move {
+            if let Some(grove) = (*grove_to_restore_state).clone() {
+                grove_to_restore_state.set(None);
+                let result = api::restore_grove(grove.id).await;
+                if result.is_ok() {
+                    groves_state.run();
+                }
+
+                result
+            } else {
+                Ok(())
+            }
+        })
+    };
 
     let close_create_dialog = use_callback(create_grove_open_toggle.clone(), |_, toggle| {
         toggle.set(false)
@@ -158,6 +236,17 @@ pub fn groves_page() -> Html {
         },
     );
 
+    let close_import_dialog = use_callback(
+        (import_grove_open_toggle.clone(), groves_state.clone()),
+        |_, (toggle, state)| {
+            toggle.set(false);
+            state.run();
+        },
+    );
+    let open_import_dialog = use_callback(import_grove_open_toggle.clone(), |_, toggle| {
+        toggle.set(true)
+    });
+
     let close_suspend_dialog = use_callback(grove_to_suspend_state.clone(), |_, state| {
         state.set(None);
     });
@@ -188,9 +277,25 @@ pub fn groves_page() -> Html {
         state.run();
     });
 
+    let close_restore_dialog = use_callback(grove_to_restore_state.clone(), |_, state| {
+        state.set(None);
+    });
+    let open_restore_dialog = use_callback(grove_to_restore_state.clone(), |grove, state| {
+        state.set(Some(grove));
+    });
+    let confirm_restore_dialog = use_callback(restore_grove_state.clone(), |_, state| {
+        state.run();
+    });
+
     let open_users_page = use_callback(navigator.clone(), |grove: Grove, navigator| {
         navigator.push(&AppRoute::Users { grove_id: grove.id });
     });
+    let open_details_page = use_callback(navigator.clone(), |grove: Grove, navigator| {
+        navigator.push(&AppRoute::Grove { grove_id: grove.id });
+    });
+
+    let can_create_or_delete = *role >= GrovesAdminRole::Admin;
+    let can_suspend = *role >= GrovesAdminRole::Support;
 
     {
         let groves_state = groves_state.clone();
@@ -217,22 +322,32 @@ pub fn groves_page() -> Html {
                 if delete_grove_state.error.is_some() {
                     <CosmoMessage message_type={CosmoMessageType::Negative} header="Fehler beim Löschen" message="Leider konnte der Hain nicht gelöscht werden" />
                 }
-                <CosmoToolbar>
-                    <CosmoToolbarGroup>
-                        <CosmoButton label="Neuer Hain" on_click={open_create_dialog} />
-                    </CosmoToolbarGroup>
-                </CosmoToolbar>
-                <CosmoTable headers={vec![AttrValue::from("#"), AttrValue::from("Name"), AttrValue::from("Pausiert"), AttrValue::from("Aktiviert"), AttrValue::from("Aktionen")]}>
+                if restore_grove_state.error.is_some() {
+                    <CosmoMessage message_type={CosmoMessageType::Negative} header="Fehler beim Wiederherstellen" message="Leider konnte der Hain nicht wiederhergestellt werden" />
+                }
+                if can_create_or_delete {
+                    <CosmoToolbar>
+                        <CosmoToolbarGroup>
+                            <CosmoButton label="Neuer Hain" on_click={open_create_dialog} />
+                            <CosmoButton label="Hain importieren" on_click={open_import_dialog} />
+                        </CosmoToolbarGroup>
+                    </CosmoToolbar>
+                }
+                <CosmoTable headers={vec![AttrValue::from("#"), AttrValue::from("Name"), AttrValue::from("Pausiert"), AttrValue::from("Aktiviert"), AttrValue::from("Endgültig gelöscht am"), AttrValue::from("Aktionen")]}>
                     {for data.iter().map(|grove| {
                         let open_suspend_dialog = open_suspend_dialog.clone();
                         let open_resume_dialog = open_resume_dialog.clone();
                         let open_delete_dialog = open_delete_dialog.clone();
+                        let open_restore_dialog = open_restore_dialog.clone();
                         let open_users_page = open_users_page.clone();
+                        let open_details_page = open_details_page.clone();
 
                         let suspend_grove = grove.clone();
                         let resume_grove = grove.clone();
                         let delete_grove = grove.clone();
+                        let restore_grove = grove.clone();
                         let users_grove = grove.clone();
+                        let details_grove = grove.clone();
 
                         CosmoTableRow::from_table_cells(vec![
                             CosmoTableCell::from_html(html!({grove.id}), None),
@@ -251,13 +366,26 @@ pub fn groves_page() -> Html {
                                     <Icon icon_id={IconId::LucideX} />
                                 }
                             ), None),
+                            CosmoTableCell::from_html(html!(
+                                if let Some(purge_at) = grove.purge_at {
+                                    {purge_at.format("%d.%m.%Y").to_string()}
+                                }
+                            ), None),
                             CosmoTableCell::from_html(html!(
                                 <>
                                     <CosmoToolbarGroup>
+                                        <CosmoButton label="Details anzeigen" on_click={move |_| open_details_page.emit(details_grove.clone())} />
                                         <CosmoButton label="Mods anzeigen" on_click={move |_| open_users_page.emit(users_grove.clone())} />
-                                        <CosmoButton label="Starten" enabled={grove.is_suspended} on_click={move |_| open_resume_dialog.emit(resume_grove.clone())} />
-                                        <CosmoButton label="Pausieren" enabled={!grove.is_suspended} on_click={move |_| open_suspend_dialog.emit(suspend_grove.clone())} />
-                                        <CosmoButton label="Löschen" on_click={move |_| open_delete_dialog.emit(delete_grove.clone())} />
+                                        if can_suspend {
+                                            <CosmoButton label="Starten" enabled={grove.is_suspended} on_click={move |_| open_resume_dialog.emit(resume_grove.clone())} />
+                                            <CosmoButton label="Pausieren" enabled={!grove.is_suspended} on_click={move |_| open_suspend_dialog.emit(suspend_grove.clone())} />
+                                        }
+                                        if can_suspend && grove.is_deleted() {
+                                            <CosmoButton label="Wiederherstellen" on_click={move |_| open_restore_dialog.emit(restore_grove.clone())} />
+                                        }
+                                        if can_create_or_delete {
+                                            <CosmoButton label="Löschen" enabled={!grove.is_deleted()} on_click={move |_| open_delete_dialog.emit(delete_grove.clone())} />
+                                        }
                                     </CosmoToolbarGroup>
                                 </>
                             ), None),
@@ -267,14 +395,20 @@ pub fn groves_page() -> Html {
                 if *create_grove_open_toggle {
                     <CreateGroveDialog on_close={close_create_dialog} on_saved={saved_create_dialog} />
                 }
+                if *import_grove_open_toggle {
+                    <ImportGroveDialog on_close={close_import_dialog} />
+                }
                 if let Some(grove) = (*grove_to_suspend_state).clone() {
                     <CosmoConfirm title="Hain pausieren" message={format!("Soll der Hain {} pausiert werden? Wenn der Hain pausiert wird kann sich niemand mehr anmelden.", grove.name.clone())} decline_label="Nicht pausieren" confirm_label="Hain pausieren" confirm_type={CosmoModalType::Warning} on_confirm={confirm_suspend_dialog.clone()} on_decline={close_suspend_dialog.clone()} />
                 }
                 if let Some(grove) = (*grove_to_resume_state).clone() {
                     <CosmoConfirm title="Hain starten" message={format!("Soll der Hain {} gestartet werden? Wenn der Hain gestartet wird können sich die Pandas wieder anmelden.", grove.name.clone())} decline_label="Nicht starten" confirm_label="Hain starten" confirm_type={CosmoModalType::Warning} on_confirm={confirm_resume_dialog.clone()} on_decline={close_resume_dialog.clone()} />
                 }
+                if let Some(grove) = (*grove_to_restore_state).clone() {
+                    <CosmoConfirm title="Hain wiederherstellen" message={format!("Soll der Hain {} wiederhergestellt werden? Die Löschung wird abgebrochen und die Pandas können sich wieder anmelden.", grove.name.clone())} decline_label="Nicht wiederherstellen" confirm_label="Hain wiederherstellen" confirm_type={CosmoModalType::Warning} on_confirm={confirm_restore_dialog.clone()} on_decline={close_restore_dialog.clone()} />
+                }
                 if let Some(grove) = (*grove_to_delete_state).clone() {
-                    <CosmoConfirm title="Hain löschen" message={format!("Soll der Hain {} gelöscht werden? Wenn der Hain gelöscht wird, werden alle Pandas, Events und Charaktere.\nEine Alternative ist es den Hain zu pausieren und den Mods Bescheid zu geben warum du den Hain pausiert hast", grove.name.clone())} decline_label="Nicht löschen" confirm_label="Hain löschen" confirm_type={CosmoModalType::Negative} on_confirm={confirm_delete_dialog.clone()} on_decline={close_delete_dialog.clone()} />
+                    <CosmoConfirm title="Hain löschen" message={format!("Soll der Hain {} gelöscht werden? Der Hain wird sofort gesperrt und nach Ablauf der Frist mit allen Pandas, Events und Charakteren endgültig gelöscht. Bis dahin kann er wiederhergestellt werden.\nEine Alternative ist es den Hain zu pausieren und den Mods Bescheid zu geben warum du den Hain pausiert hast", grove.name.clone())} decline_label="Nicht löschen" confirm_label="Hain löschen" confirm_type={CosmoModalType::Negative} on_confirm={confirm_delete_dialog.clone()} on_decline={close_delete_dialog.clone()} />
                 }
             }
         </>
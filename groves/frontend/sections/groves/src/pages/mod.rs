@@ -1 +1,2 @@
+pub mod grove;
 pub mod groves;
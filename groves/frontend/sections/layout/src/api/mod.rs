@@ -0,0 +1,7 @@
+use bamboo_common::core::entities::GrovesAdmin;
+use bamboo_common::frontend::api::{get, BambooApiResult};
+
+pub async fn get_me() -> BambooApiResult<GrovesAdmin> {
+    log::debug!("Get current admin");
+    get("/api/my").await
+}
@@ -1,3 +1,4 @@
 pub use pages::layout::*;
 
+mod api;
 pub mod pages;
@@ -7,18 +7,23 @@ use web_sys::wasm_bindgen::JsCast;
 use web_sys::Element;
 use yew::prelude::*;
 use yew_cosmo::prelude::*;
+use yew_hooks::{use_async, use_mount};
 use yew_oauth2::agent::{LoginOptions, OAuth2Operations};
 use yew_oauth2::hook::use_latest_access_token;
 use yew_oauth2::openid::{use_auth_agent, Config, OAuth2};
 use yew_oauth2::prelude::{Authenticated, NotAuthenticated};
 use yew_router::prelude::*;
 
+use bamboo_common::core::entities::GrovesAdminRole;
+use bamboo_common::frontend::api::FORBIDDEN;
 use bamboo_groves_frontend_base::routing::AppRoute;
-use bamboo_groves_frontend_section_groves::GrovesPage;
+use bamboo_groves_frontend_section_groves::{GroveDetailsPage, GrovesPage};
 use bamboo_groves_frontend_section_login::LoginPage;
 use bamboo_groves_frontend_section_users::UsersPage;
 
-fn switch_app(route: AppRoute) -> Html {
+use crate::api;
+
+fn switch_app(route: AppRoute, role: GrovesAdminRole) -> Html {
     match route {
         AppRoute::Home => html!(
             <Redirect<AppRoute> to={AppRoute::Groves} />
@@ -28,7 +33,7 @@ fn switch_app(route: AppRoute) -> Html {
                 <Helmet>
                     <title>{"Haine"}</title>
                 </Helmet>
-                <GrovesPage />
+                <GrovesPage role={role} />
             </>
         ),
         AppRoute::Users { grove_id } => html!(
@@ -36,7 +41,15 @@ fn switch_app(route: AppRoute) -> Html {
                 <Helmet>
                     <title>{"Benutzer"}</title>
                 </Helmet>
-                <UsersPage grove_id={grove_id} />
+                <UsersPage grove_id={grove_id} role={role} />
+            </>
+        ),
+        AppRoute::Grove { grove_id } => html!(
+            <>
+                <Helmet>
+                    <title>{"Hain"}</title>
+                </Helmet>
+                <GroveDetailsPage grove_id={grove_id} />
             </>
         ),
     }
@@ -58,6 +71,14 @@ fn render_users_route_sub_menu_entry(route: AppRoute) -> Html {
     )
 }
 
+fn render_grove_route_sub_menu_entry(route: AppRoute) -> Html {
+    let is_active = matches!(route, AppRoute::Grove { .. });
+
+    html!(
+        <CosmoSubMenuItem label="Details" is_active={is_active} />
+    )
+}
+
 #[function_component(StoreToken)]
 fn store_token() -> Html {
     let access_token = use_latest_access_token();
@@ -71,6 +92,33 @@ fn store_token() -> Html {
     html!()
 }
 
+#[function_component(AuthenticatedContent)]
+fn authenticated_content() -> Html {
+    let admin_state = use_async(async move { api::get_me().await });
+
+    {
+        let admin_state = admin_state.clone();
+
+        use_mount(move || {
+            admin_state.run();
+        });
+    }
+
+    html!(
+        if admin_state.loading {
+            <CosmoProgressRing />
+        } else if let Some(err) = &admin_state.error {
+            if err.code == FORBIDDEN {
+                <CosmoMessage message_type={CosmoMessageType::Negative} header="Keine Berechtigung" message="Dir wurde keine Rolle für die Hainverwaltung zugewiesen" />
+            } else {
+                <CosmoMessage message_type={CosmoMessageType::Negative} header="Fehler beim Laden" message="Leider konnten deine Berechtigungen nicht geladen werden" />
+            }
+        } else if let Some(admin) = admin_state.data.clone() {
+            <Switch<AppRoute> render={move |route| switch_app(route, admin.role)} />
+        }
+    )
+}
+
 #[function_component(LayoutInner)]
 fn layout_inner() -> Html {
     log::debug!("Render app layout");
@@ -101,10 +149,11 @@ fn layout_inner() -> Html {
                     <CosmoSubMenuBar>
                         <Switch<AppRoute> render={render_groves_route_sub_menu_entry} />
                         <Switch<AppRoute> render={render_users_route_sub_menu_entry} />
+                        <Switch<AppRoute> render={render_grove_route_sub_menu_entry} />
                     </CosmoSubMenuBar>
                 </CosmoMenuBar>
                 <CosmoPageBody>
-                    <Switch<AppRoute> render={switch_app} />
+                    <AuthenticatedContent />
                </CosmoPageBody>
            </Authenticated>
         </>
@@ -155,7 +204,7 @@ pub fn layout() -> Html {
     .with_after_logout_url(redirect_url);
 
     html!(
-        <OAuth2 {config} scopes={vec!["openid".to_string(), "profile".to_string()]} login_options={login_options}>
+        <OAuth2 {config} scopes={vec!["openid".to_string(), "profile".to_string(), "urn:zitadel:iam:org:project:roles".to_string()]} login_options={login_options}>
             <LayoutInner />
         </OAuth2>
     )
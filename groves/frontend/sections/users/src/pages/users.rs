@@ -5,13 +5,14 @@ use yew_cosmo::prelude::*;
 use yew_hooks::{use_async, use_mount};
 use yew_icons::{Icon, IconId};
 
-use bamboo_common::core::entities::GroveUser;
+use bamboo_common::core::entities::{GroveUser, GrovesAdminRole};
+use bamboo_common::frontend::api::BAD_REQUEST;
 
 use crate::api;
 
 #[autoprops]
 #[function_component(UsersPage)]
-pub fn users_page(grove_id: i32) -> Html {
+pub fn users_page(grove_id: i32, role: &GrovesAdminRole) -> Html {
     log::debug!("Render users overview");
     let user_to_reset_password_state = use_state_eq(|| None as Option<GroveUser>);
     let user_to_make_mod_state = use_state_eq(|| None as Option<GroveUser>);
@@ -111,6 +112,8 @@ pub fn users_page(grove_id: i32) -> Html {
         state.run();
     });
 
+    let can_manage_users = *role >= GrovesAdminRole::Support;
+
     {
         let users_state = users_state.clone();
         let grove_state = grove_state.clone();
@@ -139,8 +142,12 @@ pub fn users_page(grove_id: i32) -> Html {
                 if make_user_mod_state.error.is_some() {
                     <CosmoMessage message_type={CosmoMessageType::Negative} header="Fehler beim Ernennen" message="Leider konnte der Benutzer nicht zum Mod ernannt werden" />
                 }
-                if remove_user_mod_state.error.is_some() {
-                    <CosmoMessage message_type={CosmoMessageType::Negative} header="Fehler beim Entziehen" message="Leider konnten dem Benutzer die Modrecht nicht entzogen werden" />
+                if let Some(err) = &remove_user_mod_state.error {
+                    if err.code == BAD_REQUEST {
+                        <CosmoMessage message_type={CosmoMessageType::Negative} header="Fehler beim Entziehen" message="Der Benutzer ist der letzte Mod im Hain, jeder Hain braucht mindestens einen Mod" />
+                    } else {
+                        <CosmoMessage message_type={CosmoMessageType::Negative} header="Fehler beim Entziehen" message="Leider konnten dem Benutzer die Modrecht nicht entzogen werden" />
+                    }
                 }
                 <CosmoTable headers={vec![AttrValue::from("#"), AttrValue::from("Name"), AttrValue::from("Email"), AttrValue::from("Ist Mod"), AttrValue::from("Aktionen")]}>
                     {for data.iter().map(|user| {
@@ -166,9 +173,11 @@ pub fn users_page(grove_id: i32) -> Html {
                             CosmoTableCell::from_html(html!(
                                 <>
                                     <CosmoToolbarGroup>
-                                        <CosmoButton label="Passwort zurücksetzen" enabled={user.is_mod} on_click={move |_| open_reset_password_dialog.emit(reset_password_user.clone())} />
-                                        <CosmoButton label="Modrechte entziehen" enabled={user.is_mod} on_click={move |_| open_remove_user_mod_dialog.emit(user_to_remove_mod.clone())} />
-                                        <CosmoButton label="Zum Mod machen" enabled={!user.is_mod} on_click={move |_| open_make_user_mod_dialog.emit(user_to_make_mod.clone())} />
+                                        if can_manage_users {
+                                            <CosmoButton label="Passwort zurücksetzen" enabled={user.is_mod} on_click={move |_| open_reset_password_dialog.emit(reset_password_user.clone())} />
+                                            <CosmoButton label="Modrechte entziehen" enabled={user.is_mod} on_click={move |_| open_remove_user_mod_dialog.emit(user_to_remove_mod.clone())} />
+                                            <CosmoButton label="Zum Mod machen" enabled={!user.is_mod} on_click={move |_| open_make_user_mod_dialog.emit(user_to_make_mod.clone())} />
+                                        }
                                     </CosmoToolbarGroup>
                                 </>
                             ), None),
@@ -1,71 +1,69 @@
 use actix_web::{middleware, App, HttpServer};
+use sea_orm::DatabaseConnection;
 
 use bamboo_common::backend::dbal;
 use bamboo_common::backend::migration::{Migrator, MigratorTrait};
 use bamboo_common::backend::services::minio_service::MinioClient;
-use bamboo_common::backend::services::DbConnection;
+use bamboo_common::backend::services::{
+    webauthn_service, DbConnection, EnvironmentService, WebauthnService,
+};
 
+use crate::demo;
 use crate::notifier;
+use crate::purge;
 use crate::routes;
 
-async fn setup_google_playstore_grove(
-    db: &sea_orm::DatabaseConnection,
-) -> std::io::Result<bamboo_common::core::entities::Grove> {
-    if let Ok(grove) = dbal::get_grove_by_name("Google".to_string(), db).await {
-        Ok(grove)
-    } else {
-        dbal::create_grove("Google".to_string(), db)
-            .await
-            .map_err(std::io::Error::other)
-    }
-}
+async fn connect_and_migrate() -> std::io::Result<DatabaseConnection> {
+    let db = bamboo_common::backend::database::get_database()
+        .await
+        .map_err(std::io::Error::other)?;
 
-async fn setup_google_playstore_user(db: &sea_orm::DatabaseConnection) -> std::io::Result<()> {
-    let email = "playstore@google.bambushain".to_string();
-    let password = "NkWHoLDmzg4aVEx".to_string();
+    let migrations = Migrator::get_pending_migrations(&db)
+        .await
+        .map_err(std::io::Error::other)?;
+    log::info!("Running {} migrations", migrations.len());
 
-    if let Ok(user) = dbal::get_user_by_email_or_username(email.clone(), db).await {
-        dbal::change_password(user.grove_id, user.id, password, db)
-            .await
-            .map_err(std::io::Error::other)
-            .map(|_| ())
-    } else {
-        let grove = setup_google_playstore_grove(db).await?;
-        dbal::create_user(
-            grove.id,
-            bamboo_common::core::entities::User::new(
-                email,
-                "Google Playstore".to_string(),
-                "google".to_string(),
-                true,
-            ),
-            password,
-            db,
-        )
+    Migrator::up(&db, None)
         .await
-        .map_err(std::io::Error::other)
-        .map(|_| ())
-    }
+        .map_err(std::io::Error::other)?;
+    log::info!("Successfully migrated database");
+
+    Ok(db)
 }
 
-pub fn start_server() -> std::io::Result<()> {
+/// Encrypts all secrets with the key from `ENCRYPTION_CURRENT_KEY_ID`, the old key can be removed afterwards.
+/// Legacy secrets encrypted with the password stay on that scheme until their users log in again.
+pub fn rotate_encryption_key() -> std::io::Result<()> {
     env_logger::init();
 
     actix_web::rt::System::new().block_on(async {
-        log::info!("Open the bamboo grove");
-        let db = bamboo_common::backend::database::get_database()
+        let db = connect_and_migrate().await?;
+        let rotation = dbal::rotate_encryption_key(&db)
             .await
             .map_err(std::io::Error::other)?;
 
-        let migrations = Migrator::get_pending_migrations(&db)
-            .await
-            .map_err(std::io::Error::other)?;
-        log::info!("Running {} migrations", migrations.len());
+        log::info!(
+            "Encrypted {} secrets with the current key",
+            rotation.rotated_secrets
+        );
+        if rotation.legacy_secrets > 0 {
+            log::warn!(
+                "{} users still have secrets encrypted with their password, they are migrated on their next login",
+                rotation.legacy_secrets
+            );
+        }
 
-        Migrator::up(&db, None)
-            .await
-            .map_err(std::io::Error::other)?;
-        log::info!("Successfully migrated database");
+        Ok(())
+    })
+}
+
+pub fn start_server() -> std::io::Result<()> {
+    env_logger::init();
+
+    actix_web::rt::System::new().block_on(async {
+        log::info!("Open the bamboo grove");
+        dbal::check_encryption_key().map_err(std::io::Error::other)?;
+        let db = connect_and_migrate().await?;
         let groves = dbal::get_groves(&db).await.map_err(std::io::Error::other)?;
         let minio_client = MinioClient::new(
             std::env::var("S3_BUCKET").map_err(std::io::Error::other)?,
@@ -79,10 +77,14 @@ pub fn start_server() -> std::io::Result<()> {
         )
         .map_err(std::io::Error::other)?;
 
+        let demo_account = demo::get_demo_account(&EnvironmentService::new());
+        let demo_grove_name = demo_account
+            .as_ref()
+            .map(|demo_account| demo_account.grove_name.clone());
         if groves.is_empty()
             || groves
                 .iter()
-                .filter(|grove| grove.name == *"Google")
+                .filter(|grove| Some(grove.name.clone()) == demo_grove_name)
                 .count()
                 == groves.len()
         {
@@ -100,7 +102,21 @@ pub fn start_server() -> std::io::Result<()> {
                 .map_err(std::io::Error::other)?;
         }
 
-        setup_google_playstore_user(&db).await?;
+        if let Some(demo_account) = demo_account {
+            log::info!("Demo account is enabled, reset it periodically");
+            demo::start_demo_reset_schedule(demo_account, db.clone());
+        }
+
+        log::info!("Purge deleted groves once their grace period is over");
+        purge::start_grove_purge_schedule(minio_client.clone(), db.clone());
+
+        let webauthn = webauthn_service::create_webauthn(
+            std::env::var("WEBAUTHN_RP_ID").unwrap_or("pandas.bambushain.app".to_string()),
+            std::env::var("WEBAUTHN_RP_ORIGIN")
+                .unwrap_or("https://pandas.bambushain.app".to_string()),
+            std::env::var("WEBAUTHN_RP_NAME").unwrap_or("Bambushain".to_string()),
+        )
+        .map_err(std::io::Error::other)?;
 
         let notifier = notifier::NotifierState::new();
 
@@ -112,6 +128,7 @@ pub fn start_server() -> std::io::Result<()> {
                 ))
                 .app_data(notifier::Notifier::new(notifier.clone()))
                 .app_data(DbConnection::new(db.clone()))
+                .app_data(WebauthnService::new(webauthn.clone()))
                 .configure(routes::configure_routes)
         })
         .bind(("0.0.0.0", 8070))?
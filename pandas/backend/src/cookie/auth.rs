@@ -1,7 +1,9 @@
 use std::future::{ready, Ready};
 
+use actix_web::cookie::{Cookie, SameSite};
 use actix_web::{dev, FromRequest, HttpRequest};
 
+use bamboo_common::backend::services::EnvService;
 use bamboo_common::core::error::*;
 
 pub const BAMBOO_AUTH_COOKIE: &str = "BambooAuth";
@@ -25,3 +27,45 @@ impl FromRequest for BambooAuthCookie {
         )
     }
 }
+
+/// Builds the auth cookie with the attributes from `AUTH_COOKIE_SECURE`, `AUTH_COOKIE_SAME_SITE` and `AUTH_COOKIE_DOMAIN`.
+/// Browsers only accept `SameSite=None` for secure cookies, so it always sets `Secure`.
+pub fn build_auth_cookie(token: String, env_service: &EnvService) -> Cookie<'static> {
+    build_cookie(BAMBOO_AUTH_COOKIE, token, env_service)
+}
+
+pub(super) fn build_cookie(
+    name: &'static str,
+    value: String,
+    env_service: &EnvService,
+) -> Cookie<'static> {
+    let same_site = match env_service
+        .get_env("AUTH_COOKIE_SAME_SITE", "lax")
+        .to_lowercase()
+        .as_str()
+    {
+        "strict" => SameSite::Strict,
+        "none" => SameSite::None,
+        _ => SameSite::Lax,
+    };
+    let secure = same_site == SameSite::None
+        || env_service
+            .get_env("AUTH_COOKIE_SECURE", "true")
+            .to_lowercase()
+            != "false";
+
+    let mut cookie = Cookie::build(name, value)
+        .path("/")
+        .http_only(true)
+        .secure(secure)
+        .same_site(same_site)
+        .finish();
+    if let Some(domain) = env_service
+        .get_env_opt("AUTH_COOKIE_DOMAIN")
+        .filter(|domain| !domain.is_empty())
+    {
+        cookie.set_domain(domain);
+    }
+
+    cookie
+}
@@ -0,0 +1,36 @@
+use std::future::{ready, Ready};
+
+use actix_web::cookie::Cookie;
+use actix_web::{dev, FromRequest, HttpRequest};
+
+use bamboo_common::backend::services::EnvService;
+use bamboo_common::core::error::*;
+
+use crate::cookie::auth::build_cookie;
+
+pub const BAMBOO_GROVE_COOKIE: &str = "BambooGrove";
+
+/// Remembers the grove a panda switched to in the browser.
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub struct BambooGroveCookie {
+    pub grove_id: i32,
+}
+
+impl FromRequest for BambooGroveCookie {
+    type Error = BambooError;
+    type Future = Ready<Result<Self, BambooError>>;
+
+    fn from_request(req: &HttpRequest, _payload: &mut dev::Payload) -> Self::Future {
+        ready(
+            req.cookie(BAMBOO_GROVE_COOKIE)
+                .and_then(|cookie| cookie.value().parse::<i32>().ok())
+                .ok_or(BambooError::not_found("grove", "Grove cookie is not set"))
+                .map(|grove_id| Self { grove_id }),
+        )
+    }
+}
+
+/// Uses the same attributes as the auth cookie.
+pub fn build_grove_cookie(grove_id: i32, env_service: &EnvService) -> Cookie<'static> {
+    build_cookie(BAMBOO_GROVE_COOKIE, grove_id.to_string(), env_service)
+}
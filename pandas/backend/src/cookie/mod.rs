@@ -1,4 +1,11 @@
+pub(crate) use auth::build_auth_cookie;
 pub(crate) use auth::BambooAuthCookie;
-pub(crate) use auth::BAMBOO_AUTH_COOKIE;
+pub(crate) use grove::build_grove_cookie;
+pub(crate) use grove::BambooGroveCookie;
+pub(crate) use oidc::build_oidc_state_cookie;
+pub(crate) use oidc::build_oidc_state_removal_cookie;
+pub(crate) use oidc::BambooOidcStateCookie;
 
 pub mod auth;
+pub mod grove;
+pub mod oidc;
@@ -0,0 +1,58 @@
+use std::future::{ready, Ready};
+
+use actix_web::cookie::time::Duration;
+use actix_web::cookie::{Cookie, SameSite};
+use actix_web::{dev, FromRequest, HttpRequest};
+
+use bamboo_common::backend::services::EnvService;
+use bamboo_common::core::error::*;
+
+use crate::cookie::auth::build_cookie;
+
+pub const BAMBOO_OIDC_STATE_COOKIE: &str = "BambooOidcState";
+
+const OIDC_STATE_COOKIE_PATH: &str = "/api/login/oidc";
+const OIDC_STATE_COOKIE_VALID_MINUTES: i64 = 10;
+
+/// Binds an oidc login to the browser that started it.
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub struct BambooOidcStateCookie {
+    pub state: String,
+}
+
+impl FromRequest for BambooOidcStateCookie {
+    type Error = BambooError;
+    type Future = Ready<Result<Self, BambooError>>;
+
+    fn from_request(req: &HttpRequest, _payload: &mut dev::Payload) -> Self::Future {
+        ready(
+            req.cookie(BAMBOO_OIDC_STATE_COOKIE)
+                .ok_or(BambooError::unauthorized(
+                    "oidc",
+                    "Oidc state cookie is not set",
+                ))
+                .map(|cookie| Self {
+                    state: cookie.value().to_string(),
+                }),
+        )
+    }
+}
+
+/// The callback is a cross site navigation from the issuer, so the cookie is never `SameSite=Strict`.
+pub fn build_oidc_state_cookie(state: String, env_service: &EnvService) -> Cookie<'static> {
+    let mut cookie = build_cookie(BAMBOO_OIDC_STATE_COOKIE, state, env_service);
+    cookie.set_path(OIDC_STATE_COOKIE_PATH);
+    cookie.set_max_age(Duration::minutes(OIDC_STATE_COOKIE_VALID_MINUTES));
+    if cookie.same_site() == Some(SameSite::Strict) {
+        cookie.set_same_site(SameSite::Lax);
+    }
+
+    cookie
+}
+
+pub fn build_oidc_state_removal_cookie(env_service: &EnvService) -> Cookie<'static> {
+    let mut cookie = build_oidc_state_cookie(String::new(), env_service);
+    cookie.make_removal();
+
+    cookie
+}
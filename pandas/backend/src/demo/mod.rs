@@ -0,0 +1,173 @@
+use std::time::Duration;
+
+use chrono::{Days, Utc};
+use sea_orm::DatabaseConnection;
+
+use bamboo_common::backend::dbal;
+use bamboo_common::backend::services::EnvironmentService;
+use bamboo_common::core::entities::{Character, CharacterRace, Event, Grove, User};
+use bamboo_common::core::error::BambooResult;
+
+#[derive(Clone)]
+pub struct DemoAccount {
+    pub email: String,
+    pub password: String,
+    pub display_name: String,
+    pub grove_name: String,
+    pub read_only: bool,
+    pub skip_two_factor: bool,
+    pub reset_interval: Duration,
+}
+
+impl DemoAccount {
+    pub fn is_demo_login(&self, email: &str) -> bool {
+        self.email.eq_ignore_ascii_case(email.trim())
+    }
+
+    pub fn is_demo_user(&self, user: &User) -> bool {
+        self.email.eq_ignore_ascii_case(user.email.as_str())
+    }
+}
+
+fn get_flag(env_service: &EnvironmentService, key: &str) -> bool {
+    env_service.get_env(key, "false").to_lowercase() == "true"
+}
+
+/// The demo account is only enabled if both the email and the password are configured.
+pub fn get_demo_account(env_service: &EnvironmentService) -> Option<DemoAccount> {
+    let email = env_service.get_env_opt("DEMO_ACCOUNT_EMAIL")?;
+    let password = env_service.get_env_opt("DEMO_ACCOUNT_PASSWORD")?;
+    let reset_interval = env_service
+        .get_env("DEMO_ACCOUNT_RESET_INTERVAL_MINUTES", "1440")
+        .parse::<u64>()
+        .unwrap_or(1440)
+        .max(1);
+
+    Some(DemoAccount {
+        email,
+        password,
+        display_name: env_service.get_env("DEMO_ACCOUNT_NAME", "Demo Panda"),
+        grove_name: env_service.get_env("DEMO_GROVE_NAME", "Demo"),
+        read_only: get_flag(env_service, "DEMO_ACCOUNT_READ_ONLY"),
+        skip_two_factor: get_flag(env_service, "DEMO_ACCOUNT_SKIP_TWO_FACTOR"),
+        reset_interval: Duration::from_secs(reset_interval * 60),
+    })
+}
+
+async fn setup_demo_grove(
+    demo_account: &DemoAccount,
+    db: &DatabaseConnection,
+) -> BambooResult<Grove> {
+    if let Ok(grove) = dbal::get_grove_by_name(demo_account.grove_name.clone(), db).await {
+        Ok(grove)
+    } else {
+        dbal::create_grove(demo_account.grove_name.clone(), db).await
+    }
+}
+
+async fn setup_demo_user(
+    demo_account: &DemoAccount,
+    db: &DatabaseConnection,
+) -> BambooResult<User> {
+    if let Ok(user) = dbal::get_user_by_email_or_username(demo_account.email.clone(), db).await {
+        dbal::change_password(user.grove_id, user.id, demo_account.password.clone(), db).await?;
+
+        Ok(user)
+    } else {
+        let grove = setup_demo_grove(demo_account, db).await?;
+        dbal::create_user(
+            grove.id,
+            User::new(
+                demo_account.email.clone(),
+                demo_account.display_name.clone(),
+                "".to_string(),
+                false,
+            ),
+            demo_account.password.clone(),
+            db,
+        )
+        .await
+    }
+}
+
+async fn seed_demo_data(user: &User, db: &DatabaseConnection) -> BambooResult<()> {
+    let today = Utc::now().date_naive();
+    let events = vec![
+        Event {
+            title: "Raid Abend".to_string(),
+            description: "Wir gehen gemeinsam in den aktuellen Raid".to_string(),
+            start_date: today + Days::new(2),
+            end_date: today + Days::new(2),
+            color: "#9f2637".to_string(),
+            ..Default::default()
+        },
+        Event {
+            title: "Housing Tour".to_string(),
+            description: "Wir schauen uns die Häuser der Freie Gesellschaft an".to_string(),
+            start_date: today + Days::new(5),
+            end_date: today + Days::new(6),
+            color: "#2a6e3f".to_string(),
+            ..Default::default()
+        },
+    ];
+    for event in events {
+        dbal::create_event(event, user.grove_id, user.id, db).await?;
+    }
+
+    dbal::create_character(
+        user.id,
+        Character::new(
+            CharacterRace::Lalafell,
+            "Demo Panda".to_string(),
+            "Shiva".to_string(),
+            vec![],
+            None,
+        ),
+        db,
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Resets the password of the demo account and replaces its own events and characters with the sample data.
+pub async fn reset_demo_account(
+    demo_account: &DemoAccount,
+    db: &DatabaseConnection,
+) -> BambooResult<()> {
+    let user = setup_demo_user(demo_account, db).await?;
+    let grove = dbal::get_grove_by_user_id(user.id, db).await?;
+    if grove.name != demo_account.grove_name {
+        log::warn!(
+            "The demo account is not part of the demo grove {}, skip resetting its data",
+            demo_account.grove_name
+        );
+        return Ok(());
+    }
+
+    // The demo panda is a regular member, so visitors don't get into the mod area
+    let membership = dbal::get_grove_membership(grove.id, user.id, db).await?;
+    if membership.is_mod {
+        dbal::revoke_mod_status_of_demo_account(grove.id, user.id, db).await?;
+    }
+
+    dbal::delete_events_of_user(user.grove_id, user.id, db).await?;
+    for character in dbal::get_characters(user.id, db).await? {
+        dbal::delete_character(character.id, user.id, db).await?;
+    }
+
+    seed_demo_data(&user, db).await
+}
+
+pub fn start_demo_reset_schedule(demo_account: DemoAccount, db: DatabaseConnection) {
+    actix_web::rt::spawn(async move {
+        let mut interval = actix_web::rt::time::interval(demo_account.reset_interval);
+        loop {
+            interval.tick().await;
+            log::info!("Reset the demo account");
+            if let Err(err) = reset_demo_account(&demo_account, &db).await {
+                log::error!("Failed to reset the demo account {err}");
+            }
+        }
+    });
+}
@@ -0,0 +1,59 @@
+use bamboo_common::backend::services::{DbConnection, EnvService};
+use bamboo_common::backend::{dbal, mailing};
+use bamboo_common::core::entities::User;
+use bamboo_common::core::error::BambooErrorResult;
+
+fn is_email_changed(user: &User, new_email: &str) -> bool {
+    !new_email.trim().eq_ignore_ascii_case(user.email.as_str())
+}
+
+/// Fails if the new email can't be requested, so that a profile update can be rejected before anything is saved.
+pub async fn check_email_change(
+    user: &User,
+    new_email: &str,
+    db: &DbConnection,
+) -> BambooErrorResult {
+    if is_email_changed(user, new_email) {
+        dbal::check_email_available(user.id, new_email.trim().to_string(), db).await
+    } else {
+        Ok(())
+    }
+}
+
+/// Email changes stay pending until the link sent to the new address is opened, the old address gets a notice.
+pub async fn request_email_change(
+    user: &User,
+    new_email: String,
+    db: &DbConnection,
+    env_service: EnvService,
+) -> BambooErrorResult {
+    if !is_email_changed(user, new_email.as_str()) {
+        return Ok(());
+    }
+
+    let new_email = new_email.trim().to_string();
+    let token = dbal::create_email_change_token(user.id, new_email.clone(), db).await?;
+    let confirm_link = format!(
+        "{}/confirm-email?token={token}",
+        env_service
+            .get_env("PANDAS_BASE_URL", "https://pandas.bambushain.app")
+            .trim_end_matches('/')
+    );
+
+    mailing::user::send_email_change_confirmation(
+        user.display_name.clone(),
+        new_email.clone(),
+        confirm_link,
+        env_service.clone(),
+    )
+    .await?;
+    mailing::user::send_email_change_notice(
+        user.display_name.clone(),
+        user.email.clone(),
+        new_email,
+        env_service,
+    )
+    .await;
+
+    Ok(())
+}
@@ -0,0 +1,56 @@
+use base64::Engine;
+use sea_orm::DatabaseConnection;
+
+use bamboo_common::backend::dbal;
+use bamboo_common::backend::services::minio_service::MinioClient;
+use bamboo_common::core::entities::{GroveExport, GroveExportStatus};
+use bamboo_common::core::error::{BambooError, BambooErrorCode, BambooErrorResult};
+
+async fn write_grove_export(
+    export: &GroveExport,
+    minio_client: &MinioClient,
+    db: &DatabaseConnection,
+) -> BambooErrorResult {
+    let mut archive = dbal::get_grove_export_archive(export.grove_id, db).await?;
+    for exported_user in archive.users.iter_mut() {
+        if let Ok(profile_picture) = minio_client
+            .get_profile_picture(exported_user.user.id)
+            .await
+        {
+            exported_user.profile_picture =
+                Some(base64::prelude::BASE64_STANDARD.encode(profile_picture));
+        }
+    }
+
+    let data = serde_json::to_vec(&archive).map_err(|err| {
+        log::error!("Failed to serialize grove export {err}");
+        BambooError::unknown("grove_export", "Failed to serialize grove export")
+    })?;
+
+    minio_client
+        .upload_grove_export(export.grove_id, export.id, &data)
+        .await
+}
+
+/// Runs the export in the background, the mods check the state of the export and download it once it is finished.
+pub fn start_grove_export(export: GroveExport, minio_client: MinioClient, db: DatabaseConnection) {
+    actix_web::rt::spawn(async move {
+        let status = if let Err(err) = write_grove_export(&export, &minio_client, &db).await {
+            log::error!("Failed to export grove {} {err}", export.grove_id);
+            GroveExportStatus::Failed
+        } else {
+            GroveExportStatus::Finished
+        };
+
+        match dbal::finish_grove_export(export.id, status, &db).await {
+            Err(err) if err.error_type == BambooErrorCode::NotFound => {
+                log::warn!(
+                    "Export {} is no longer running, keeping its state",
+                    export.id
+                )
+            }
+            Err(err) => log::error!("Failed to finish export {} {err}", export.id),
+            Ok(_) => {}
+        }
+    });
+}
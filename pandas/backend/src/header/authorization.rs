@@ -2,13 +2,20 @@ use actix_web::{error, http::header, HttpMessage};
 
 pub(crate) struct AuthorizationHeader {
     pub authorization: Option<String>,
+    pub personal_access_token: Option<String>,
 }
 
 impl header::TryIntoHeaderValue for AuthorizationHeader {
     type Error = header::InvalidHeaderValue;
 
     fn try_into_value(self) -> Result<header::HeaderValue, Self::Error> {
-        header::HeaderValue::from_str(self.authorization.unwrap_or_default().as_str())
+        let value = if let Some(personal_access_token) = self.personal_access_token {
+            format!("Token {personal_access_token}")
+        } else {
+            format!("Panda {}", self.authorization.unwrap_or_default())
+        };
+
+        header::HeaderValue::from_str(value.as_str())
     }
 }
 
@@ -18,15 +25,20 @@ impl header::Header for AuthorizationHeader {
     }
 
     fn parse<M: HttpMessage>(msg: &M) -> Result<Self, error::ParseError> {
-        let authorization = if let Some(header) = msg.headers().get(header::AUTHORIZATION) {
+        let header = if let Some(header) = msg.headers().get(header::AUTHORIZATION) {
             Ok(header)
         } else {
             Err(error::ParseError::Header)
         }?
         .to_str()
-        .map_err(|_| error::ParseError::Header)
-        .map(|header| header.strip_prefix("Panda ").map(|res| res.to_string()))?;
+        .map_err(|_| error::ParseError::Header)?;
+
+        let authorization = header.strip_prefix("Panda ").map(|res| res.to_string());
+        let personal_access_token = header.strip_prefix("Token ").map(|res| res.to_string());
 
-        Ok(AuthorizationHeader { authorization })
+        Ok(AuthorizationHeader {
+            authorization,
+            personal_access_token,
+        })
     }
 }
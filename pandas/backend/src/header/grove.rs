@@ -0,0 +1,34 @@
+use actix_web::{error, http::header, HttpMessage};
+
+/// Selects the grove a request works in for pandas that are members of several groves.
+pub(crate) struct GroveHeader {
+    pub grove_id: i32,
+}
+
+impl header::TryIntoHeaderValue for GroveHeader {
+    type Error = header::InvalidHeaderValue;
+
+    fn try_into_value(self) -> Result<header::HeaderValue, Self::Error> {
+        Ok(header::HeaderValue::from(self.grove_id))
+    }
+}
+
+impl header::Header for GroveHeader {
+    fn name() -> header::HeaderName {
+        header::HeaderName::from_static("x-bamboo-grove")
+    }
+
+    fn parse<M: HttpMessage>(msg: &M) -> Result<Self, error::ParseError> {
+        let grove_id = msg
+            .headers()
+            .get(Self::name())
+            .ok_or(error::ParseError::Header)?
+            .to_str()
+            .map_err(|_| error::ParseError::Header)?
+            .trim()
+            .parse::<i32>()
+            .map_err(|_| error::ParseError::Header)?;
+
+        Ok(GroveHeader { grove_id })
+    }
+}
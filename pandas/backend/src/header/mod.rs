@@ -1,3 +1,5 @@
 pub(crate) use self::authorization::AuthorizationHeader;
+pub(crate) use self::grove::GroveHeader;
 
 mod authorization;
+mod grove;
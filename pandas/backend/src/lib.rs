@@ -1,10 +1,18 @@
-pub use app::start_server;
+pub use app::{rotate_encryption_key, start_server};
 
 mod app;
 pub(crate) mod cookie;
+pub(crate) mod demo;
+pub(crate) mod email_change;
+pub(crate) mod export;
 pub(crate) mod header;
 pub(crate) mod middleware;
 pub(crate) mod notifier;
+pub(crate) mod oidc;
 pub(crate) mod path;
+pub(crate) mod purge;
 pub(crate) mod routes;
 pub(crate) mod sse;
+
+#[cfg(test)]
+mod tests;
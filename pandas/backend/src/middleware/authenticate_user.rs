@@ -1,12 +1,14 @@
-use actix_web::{body, dev, web, Error, HttpMessage};
+use actix_web::http::Method;
+use actix_web::{body, dev, http, web, Error, HttpMessage};
 use actix_web_lab::middleware::Next;
 
 use bamboo_common::backend::dbal;
-use bamboo_common::backend::services::DbConnection;
+use bamboo_common::backend::services::{DbConnection, EnvService};
 use bamboo_common::core::entities::*;
 use bamboo_common::core::error::BambooError;
 
 use crate::cookie;
+use crate::demo;
 use crate::header;
 use crate::middleware::helpers;
 
@@ -18,23 +20,214 @@ pub(crate) struct AuthenticationState {
 
 pub(crate) type Authentication = web::ReqData<AuthenticationState>;
 
+/// The routes a personal access token can call and the scope it needs for them, `{}` matches a single path segment.
+/// Everything else, especially the mod area, the account security and the deletion of the grove, needs a regular login.
+const TOKEN_ROUTES: &[(TokenScope, Method, &[&str])] = &[
+    (
+        TokenScope::ReadEvents,
+        Method::GET,
+        &["/api/bamboo-grove/event", "/sse/event"],
+    ),
+    (
+        TokenScope::WriteEvents,
+        Method::POST,
+        &["/api/bamboo-grove/event"],
+    ),
+    (
+        TokenScope::WriteEvents,
+        Method::PUT,
+        &["/api/bamboo-grove/event/{}"],
+    ),
+    (
+        TokenScope::WriteEvents,
+        Method::DELETE,
+        &["/api/bamboo-grove/event/{}"],
+    ),
+    (
+        TokenScope::ReadCharacters,
+        Method::GET,
+        &[
+            "/api/final-fantasy/character",
+            "/api/final-fantasy/character/{}",
+            "/api/final-fantasy/character/{}/housing",
+            "/api/final-fantasy/character/{}/housing/{}",
+            "/api/final-fantasy/character/{}/crafter",
+            "/api/final-fantasy/character/{}/crafter/{}",
+            "/api/final-fantasy/character/{}/fighter",
+            "/api/final-fantasy/character/{}/fighter/{}",
+            "/api/final-fantasy/character/custom-field",
+            "/api/final-fantasy/character/custom-field/{}",
+            "/api/final-fantasy/character/custom-field/{}/option",
+            "/api/final-fantasy/free-company",
+            "/api/final-fantasy/free-company/{}",
+        ],
+    ),
+    (
+        TokenScope::WriteCharacters,
+        Method::POST,
+        &[
+            "/api/final-fantasy/character",
+            "/api/final-fantasy/character/{}/housing",
+            "/api/final-fantasy/character/{}/crafter",
+            "/api/final-fantasy/character/{}/fighter",
+            "/api/final-fantasy/character/custom-field",
+            "/api/final-fantasy/character/custom-field/{}/option",
+            "/api/final-fantasy/free-company",
+        ],
+    ),
+    (
+        TokenScope::WriteCharacters,
+        Method::PUT,
+        &[
+            "/api/final-fantasy/character/{}",
+            "/api/final-fantasy/character/{}/housing/{}",
+            "/api/final-fantasy/character/{}/crafter/{}",
+            "/api/final-fantasy/character/{}/fighter/{}",
+            "/api/final-fantasy/character/custom-field/{}",
+            "/api/final-fantasy/character/custom-field/{}/option/{}",
+            "/api/final-fantasy/character/custom-field/{}/{}",
+            "/api/final-fantasy/free-company/{}",
+        ],
+    ),
+    (
+        TokenScope::WriteCharacters,
+        Method::DELETE,
+        &[
+            "/api/final-fantasy/character/{}",
+            "/api/final-fantasy/character/{}/housing/{}",
+            "/api/final-fantasy/character/{}/crafter/{}",
+            "/api/final-fantasy/character/{}/fighter/{}",
+            "/api/final-fantasy/character/custom-field/{}",
+            "/api/final-fantasy/character/custom-field/{}/option/{}",
+            "/api/final-fantasy/free-company/{}",
+        ],
+    ),
+    (
+        TokenScope::ReadUsers,
+        Method::GET,
+        &[
+            "/api/user",
+            "/api/user/{}",
+            "/api/user/{}/picture",
+            "/api/lockout",
+        ],
+    ),
+    (TokenScope::WriteUsers, Method::POST, &["/api/user"]),
+    (
+        TokenScope::WriteUsers,
+        Method::DELETE,
+        &["/api/user/{}/lockout"],
+    ),
+    (
+        TokenScope::ReadGrove,
+        Method::GET,
+        &["/api/grove", "/api/grove/settings", "/api/grove/logo"],
+    ),
+    (
+        TokenScope::WriteGrove,
+        Method::PUT,
+        &["/api/grove/settings", "/api/grove/logo"],
+    ),
+    (TokenScope::WriteGrove, Method::DELETE, &["/api/grove/logo"]),
+    (TokenScope::ReadProfile, Method::GET, &["/api/my/profile"]),
+    (
+        TokenScope::WriteProfile,
+        Method::PUT,
+        &["/api/my/profile", "/api/my/picture"],
+    ),
+];
+
+fn matches_route(pattern: &str, path: &str) -> bool {
+    let pattern = pattern.split('/').collect::<Vec<&str>>();
+    let path = path.trim_end_matches('/').split('/').collect::<Vec<&str>>();
+
+    pattern.len() == path.len()
+        && pattern.iter().zip(path.iter()).all(|(pattern, segment)| {
+            *pattern == *segment || (*pattern == "{}" && !segment.is_empty())
+        })
+}
+
+/// Maps a route to the scope a personal access token needs to call it, routes that are not listed are not available to tokens.
+fn required_scope(method: &http::Method, path: &str) -> Option<TokenScope> {
+    let method = if method == http::Method::HEAD {
+        &http::Method::GET
+    } else {
+        method
+    };
+
+    TOKEN_ROUTES
+        .iter()
+        .find(|(_, route_method, patterns)| {
+            route_method == method && patterns.iter().any(|pattern| matches_route(pattern, path))
+        })
+        .map(|(scope, _, _)| *scope)
+}
+
+/// A deleted grove is read only, only restoring, exporting and leaving it for another grove stay possible.
+fn is_allowed_in_deleted_grove(method: &http::Method, path: &str) -> bool {
+    method == http::Method::GET
+        || method == http::Method::HEAD
+        || (method == http::Method::DELETE && path == "/api/grove/deletion")
+        || (method == http::Method::POST && path == "/api/grove/export")
+        || (method == http::Method::DELETE && path == "/api/login")
+        || (method == http::Method::PUT && path == "/api/my/grove")
+}
+
 pub(crate) async fn authenticate_user(
     db: DbConnection,
+    env_service: EnvService,
     authorization: Option<web::Header<header::AuthorizationHeader>>,
     auth_cookie: Option<cookie::BambooAuthCookie>,
-    req: dev::ServiceRequest,
+    mut req: dev::ServiceRequest,
     next: Next<impl body::MessageBody>,
 ) -> Result<dev::ServiceResponse<impl body::MessageBody>, Error> {
-    let (token, user) = if authorization.is_some() {
+    let (token, user, scopes) = if authorization.is_some() {
         helpers::get_user_and_token_by_header(&db, authorization).await?
     } else {
         helpers::get_user_and_token_by_cookie(&db, auth_cookie).await?
     };
 
-    let grove = dbal::get_grove_by_user_id(user.id, &db).await?;
-    if (!grove.is_enabled && !user.is_mod) || grove.is_suspended {
+    let grove_header = req
+        .extract::<Option<web::Header<header::GroveHeader>>>()
+        .await?;
+    let grove_cookie = req.extract::<Option<cookie::BambooGroveCookie>>().await?;
+    let (grove, membership) =
+        helpers::get_active_grove(&db, &user, grove_header, grove_cookie).await?;
+    if ((!grove.is_enabled || grove.is_deleted()) && !membership.is_mod) || grove.is_suspended {
         return Err(BambooError::unauthorized("user", "Authorization failed").into());
     }
+    if grove.is_deleted() && !is_allowed_in_deleted_grove(req.method(), req.path()) {
+        return Err(BambooError::insufficient_rights(
+            "grove",
+            "The grove is scheduled for deletion and read only",
+        )
+        .into());
+    }
+    let _ = dbal::mark_grove_membership_active(&membership, &db).await;
+    let user = user.with_membership(&membership);
+
+    if let Some(demo_account) = demo::get_demo_account(&env_service) {
+        let is_read = req.method() == http::Method::GET || req.method() == http::Method::HEAD;
+        let is_logout = req.method() == http::Method::DELETE && req.path() == "/api/login";
+        if demo_account.read_only && demo_account.is_demo_user(&user) && !is_read && !is_logout {
+            return Err(
+                BambooError::insufficient_rights("user", "The demo account is read only").into(),
+            );
+        }
+    }
+
+    if let Some(scopes) = scopes {
+        let has_scope = required_scope(req.method(), req.path())
+            .map(|scope| scopes.contains(scope))
+            .unwrap_or(false);
+        if !has_scope {
+            return Err(BambooError::insufficient_rights(
+                "personal_access_token",
+                "The personal access token is missing the scope for this action",
+            )
+            .into());
+        }
+    }
 
     req.extensions_mut()
         .insert(AuthenticationState { token, user });
@@ -0,0 +1,93 @@
+use actix_web::{body, dev, http, web, Error};
+use actix_web_lab::middleware::Next;
+
+use bamboo_common::backend::services::EnvService;
+use bamboo_common::core::error::BambooError;
+
+use crate::{cookie, header};
+
+fn get_origin(url: &str) -> Option<String> {
+    let (scheme, rest) = url.split_once("://")?;
+    let host = rest.split(['/', '?', '#']).next()?;
+
+    Some(format!(
+        "{}://{}",
+        scheme.to_lowercase(),
+        host.to_lowercase()
+    ))
+}
+
+fn get_trusted_origins(env_service: &EnvService) -> Vec<String> {
+    let base_url = env_service.get_env("PANDAS_BASE_URL", "https://pandas.bambushain.app");
+    let trusted_origins = env_service.get_env("CSRF_TRUSTED_ORIGINS", "");
+
+    std::iter::once(base_url.as_str())
+        .chain(trusted_origins.split(','))
+        .filter_map(|url| get_origin(url.trim()))
+        .collect()
+}
+
+fn is_same_origin(origin: &str, req: &dev::ServiceRequest, env_service: &EnvService) -> bool {
+    let connection_info = req.connection_info();
+    let request_origin = format!(
+        "{}://{}",
+        connection_info.scheme().to_lowercase(),
+        connection_info.host().to_lowercase()
+    );
+
+    get_origin(origin).is_some_and(|origin| {
+        origin == request_origin || get_trusted_origins(env_service).contains(&origin)
+    })
+}
+
+/// Rejects cross site requests that change data and are only authenticated by the auth cookie.
+/// Browsers send `Sec-Fetch-Site` or at least the `Origin` with those requests, requests with a token in the
+/// authorization header can't be forged by another site and are not checked.
+pub(crate) async fn check_csrf(
+    env_service: EnvService,
+    authorization: Option<web::Header<header::AuthorizationHeader>>,
+    auth_cookie: Option<cookie::BambooAuthCookie>,
+    req: dev::ServiceRequest,
+    next: Next<impl body::MessageBody>,
+) -> Result<dev::ServiceResponse<impl body::MessageBody>, Error> {
+    let is_safe_method = matches!(
+        *req.method(),
+        http::Method::GET | http::Method::HEAD | http::Method::OPTIONS
+    );
+    if is_safe_method || authorization.is_some() || auth_cookie.is_none() {
+        return next.call(req).await;
+    }
+
+    let get_header = |name: http::header::HeaderName| {
+        req.headers()
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .map(|value| value.to_string())
+    };
+
+    let is_allowed = if let Some(fetch_site) =
+        get_header(http::header::HeaderName::from_static("sec-fetch-site"))
+    {
+        fetch_site == "same-origin" || fetch_site == "none"
+    } else if let Some(origin) = get_header(http::header::ORIGIN) {
+        is_same_origin(origin.as_str(), &req, &env_service)
+    } else if let Some(referer) = get_header(http::header::REFERER) {
+        is_same_origin(referer.as_str(), &req, &env_service)
+    } else {
+        true
+    };
+
+    if is_allowed {
+        next.call(req).await
+    } else {
+        Err(BambooError::insufficient_rights("user", "Cross site requests are not allowed").into())
+    }
+}
+
+macro_rules! csrf {
+    () => {
+        actix_web_lab::middleware::from_fn(crate::middleware::check_csrf::check_csrf)
+    };
+}
+
+pub(crate) use csrf;
@@ -7,30 +7,34 @@ use bamboo_common::core::error::BambooError;
 use crate::middleware::helpers;
 use crate::{cookie, header};
 
+/// Checks that the user is a mod of the grove, for actions a role must not be able to grant.
 pub(crate) async fn check_mod(
     db: DbConnection,
     authorization: Option<web::Header<header::AuthorizationHeader>>,
     auth_cookie: Option<cookie::BambooAuthCookie>,
+    grove_header: Option<web::Header<header::GroveHeader>>,
+    grove_cookie: Option<cookie::BambooGroveCookie>,
     req: dev::ServiceRequest,
     next: Next<impl body::MessageBody>,
 ) -> Result<dev::ServiceResponse<impl body::MessageBody>, Error> {
-    let (_, user) = if authorization.is_some() {
+    let (_, user, _) = if authorization.is_some() {
         helpers::get_user_and_token_by_header(&db, authorization).await?
     } else {
         helpers::get_user_and_token_by_cookie(&db, auth_cookie).await?
     };
 
-    if user.is_mod {
+    let (_, membership) = helpers::get_active_grove(&db, &user, grove_header, grove_cookie).await?;
+    if membership.is_mod {
         next.call(req).await
     } else {
-        Err(BambooError::insufficient_rights("user", "You need to be a mod").into())
+        Err(BambooError::insufficient_rights("user", "Only mods can perform this action").into())
     }
 }
 
-macro_rules! is_mod {
+macro_rules! moderator {
     () => {
         actix_web_lab::middleware::from_fn(crate::middleware::check_mod::check_mod)
     };
 }
 
-pub(crate) use is_mod;
+pub(crate) use moderator;
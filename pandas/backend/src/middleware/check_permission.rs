@@ -0,0 +1,56 @@
+use actix_web::{body, dev, web, Error};
+use actix_web_lab::middleware::Next;
+
+use bamboo_common::backend::dbal;
+use bamboo_common::backend::services::DbConnection;
+use bamboo_common::core::entities::GrovePermission;
+use bamboo_common::core::error::BambooError;
+
+use crate::middleware::helpers;
+use crate::{cookie, header};
+
+/// Checks that the user has the permission, either as mod or through the role assigned in the grove.
+pub(crate) async fn check_permission(
+    mut req: dev::ServiceRequest,
+    next: Next<impl body::MessageBody>,
+    permission: GrovePermission,
+) -> Result<dev::ServiceResponse<impl body::MessageBody>, Error> {
+    let db = req.extract::<DbConnection>().await?;
+    let authorization = req
+        .extract::<Option<web::Header<header::AuthorizationHeader>>>()
+        .await?;
+    let auth_cookie = req.extract::<Option<cookie::BambooAuthCookie>>().await?;
+    let grove_header = req
+        .extract::<Option<web::Header<header::GroveHeader>>>()
+        .await?;
+    let grove_cookie = req.extract::<Option<cookie::BambooGroveCookie>>().await?;
+
+    let (_, user, _) = if authorization.is_some() {
+        helpers::get_user_and_token_by_header(&db, authorization).await?
+    } else {
+        helpers::get_user_and_token_by_cookie(&db, auth_cookie).await?
+    };
+
+    let (_, membership) = helpers::get_active_grove(&db, &user, grove_header, grove_cookie).await?;
+    if dbal::has_permission(&user.with_membership(&membership), permission, &db).await? {
+        next.call(req).await
+    } else {
+        Err(BambooError::insufficient_rights(
+            "user",
+            "You don't have the permission for this action",
+        )
+        .into())
+    }
+}
+
+macro_rules! permission {
+    ($permission:expr) => {
+        actix_web_lab::middleware::from_fn(
+            |req: actix_web::dev::ServiceRequest, next: actix_web_lab::middleware::Next<_>| {
+                crate::middleware::check_permission::check_permission(req, next, $permission)
+            },
+        )
+    };
+}
+
+pub(crate) use permission;
@@ -18,7 +18,7 @@ pub(crate) async fn extract_character(
     req: dev::ServiceRequest,
     next: Next<impl body::MessageBody>,
 ) -> Result<dev::ServiceResponse<impl body::MessageBody>, Error> {
-    let (_, user) = if authorization.is_some() {
+    let (_, user, _) = if authorization.is_some() {
         helpers::get_user_and_token_by_header(&db, authorization).await?
     } else {
         helpers::get_user_and_token_by_cookie(&db, auth_cookie).await?
@@ -11,29 +11,35 @@ use crate::header;
 pub async fn get_user_and_token_by_header(
     db: &DatabaseConnection,
     authorization: Option<web::Header<header::AuthorizationHeader>>,
-) -> BambooResult<(String, User)> {
+) -> BambooResult<(String, User, Option<TokenScopes>)> {
     let unauthorized = BambooError::unauthorized("user", "Authorization failed");
-    let token = if let Some(header) = authorization {
-        if let Some(authorization) = header.authorization.clone() {
-            Ok(authorization)
-        } else {
-            Err(unauthorized.clone())
-        }
-    } else {
-        Err(unauthorized.clone())
-    }?;
+    let header = authorization.ok_or(unauthorized.clone())?;
+
+    if let Some(personal_access_token) = header.personal_access_token.clone() {
+        let (user, personal_access_token) =
+            dbal::get_user_by_personal_access_token(personal_access_token.clone(), db)
+                .await
+                .map_err(|_| unauthorized.clone())?;
+
+        return Ok((
+            personal_access_token.token_hash,
+            user,
+            Some(personal_access_token.scopes),
+        ));
+    }
 
+    let token = header.authorization.clone().ok_or(unauthorized.clone())?;
     let user = dbal::get_user_by_token(token.clone(), db)
         .await
         .map_err(|_| unauthorized.clone())?;
 
-    Ok((token, user))
+    Ok((token, user, None))
 }
 
 pub async fn get_user_and_token_by_cookie(
     db: &DatabaseConnection,
     auth_cookie: Option<cookie::BambooAuthCookie>,
-) -> BambooResult<(String, User)> {
+) -> BambooResult<(String, User, Option<TokenScopes>)> {
     let unauthorized = BambooError::unauthorized("user", "Authorization failed");
     let token = if let Some(cookie) = auth_cookie {
         Ok(cookie.token.clone())
@@ -45,5 +51,29 @@ pub async fn get_user_and_token_by_cookie(
         .await
         .map_err(|_| unauthorized.clone())?;
 
-    Ok((token, user))
+    Ok((token, user, None))
+}
+
+/// A grove selected with the header has to be one of the groves of the user. The cookie only remembers the last
+/// switch in the browser, if the user left that grove in the meantime the default grove is used instead.
+pub async fn get_active_grove(
+    db: &DatabaseConnection,
+    user: &User,
+    grove_header: Option<web::Header<header::GroveHeader>>,
+    grove_cookie: Option<cookie::BambooGroveCookie>,
+) -> BambooResult<(Grove, GroveMembership)> {
+    if let Some(grove_header) = grove_header {
+        dbal::get_active_grove(user, Some(grove_header.grove_id), db)
+            .await
+            .map_err(|_| {
+                BambooError::insufficient_rights("grove", "You are not a member of this grove")
+            })
+    } else if let Some(grove_cookie) = grove_cookie {
+        match dbal::get_active_grove(user, Some(grove_cookie.grove_id), db).await {
+            Ok(active_grove) => Ok(active_grove),
+            Err(_) => dbal::get_active_grove(user, None, db).await,
+        }
+    } else {
+        dbal::get_active_grove(user, None, db).await
+    }
 }
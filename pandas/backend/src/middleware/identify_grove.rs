@@ -1,7 +1,6 @@
 use actix_web::{body, dev, web, Error, HttpMessage};
 use actix_web_lab::middleware::Next;
 
-use bamboo_common::backend::dbal;
 use bamboo_common::backend::services::DbConnection;
 use bamboo_common::core::entities::Grove as DbGrove;
 
@@ -20,16 +19,18 @@ pub(crate) async fn identify_grove(
     db: DbConnection,
     authorization: Option<web::Header<header::AuthorizationHeader>>,
     auth_cookie: Option<cookie::BambooAuthCookie>,
+    grove_header: Option<web::Header<header::GroveHeader>>,
+    grove_cookie: Option<cookie::BambooGroveCookie>,
     req: dev::ServiceRequest,
     next: Next<impl body::MessageBody>,
 ) -> Result<dev::ServiceResponse<impl body::MessageBody>, Error> {
-    let (_, user) = if authorization.is_some() {
+    let (_, user, _) = if authorization.is_some() {
         helpers::get_user_and_token_by_header(&db, authorization).await?
     } else {
         helpers::get_user_and_token_by_cookie(&db, auth_cookie).await?
     };
 
-    let grove = dbal::get_grove_by_user_id(user.id, &db).await?;
+    let (grove, _) = helpers::get_active_grove(&db, &user, grove_header, grove_cookie).await?;
 
     req.extensions_mut().insert(GroveState { grove });
 
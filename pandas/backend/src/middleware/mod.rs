@@ -1,5 +1,7 @@
 pub(crate) mod authenticate_user;
+pub(crate) mod check_csrf;
 pub(crate) mod check_mod;
+pub(crate) mod check_permission;
 pub(crate) mod extract_character;
 mod helpers;
 pub(crate) mod identify_grove;
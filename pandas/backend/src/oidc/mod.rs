@@ -0,0 +1,168 @@
+use std::time::{Duration, Instant};
+
+use openidconnect::core::{CoreAuthenticationFlow, CoreClient, CoreProviderMetadata};
+use openidconnect::reqwest::async_http_client;
+use openidconnect::{
+    AuthorizationCode, ClientId, ClientSecret, CsrfToken, IssuerUrl, Nonce, PkceCodeChallenge,
+    PkceCodeVerifier, RedirectUrl, Scope, TokenResponse,
+};
+use parking_lot::Mutex;
+
+use bamboo_common::backend::services::EnvService;
+use bamboo_common::core::error::{BambooError, BambooResult};
+
+const PROVIDER_METADATA_CACHE_DURATION: Duration = Duration::from_secs(60 * 60);
+
+struct CachedProviderMetadata {
+    issuer_url: IssuerUrl,
+    provider_metadata: CoreProviderMetadata,
+    discovered_at: Instant,
+}
+
+/// The discovery contains the signing keys of the issuer, it is dropped after an hour or when an id token
+/// couldn't be verified so that rotated keys are picked up.
+static PROVIDER_METADATA: Mutex<Option<CachedProviderMetadata>> = Mutex::new(None);
+
+pub struct OidcAuthorization {
+    pub url: String,
+    pub state: String,
+    pub nonce: String,
+    pub pkce_verifier: String,
+}
+
+pub struct OidcIdentity {
+    pub issuer: String,
+    pub subject: String,
+    pub email: Option<String>,
+    pub email_verified: bool,
+}
+
+pub fn is_enabled(env_service: &EnvService) -> bool {
+    env_service.get_env_opt("OIDC_ISSUER_URL").is_some()
+}
+
+pub fn get_allowed_groves(env_service: &EnvService) -> Option<Vec<i32>> {
+    env_service
+        .get_env_opt("OIDC_ALLOWED_GROVES")
+        .map(|groves| {
+            groves
+                .split(',')
+                .filter_map(|grove| grove.trim().parse::<i32>().ok())
+                .collect()
+        })
+}
+
+pub fn get_frontend_url(env_service: &EnvService) -> String {
+    env_service
+        .get_env("PANDAS_BASE_URL", "https://pandas.bambushain.app")
+        .trim_end_matches('/')
+        .to_string()
+}
+
+async fn discover_provider(issuer_url: IssuerUrl) -> BambooResult<CoreProviderMetadata> {
+    if let Some(cached) = PROVIDER_METADATA.lock().as_ref() {
+        if cached.issuer_url == issuer_url
+            && cached.discovered_at.elapsed() < PROVIDER_METADATA_CACHE_DURATION
+        {
+            return Ok(cached.provider_metadata.clone());
+        }
+    }
+
+    let provider_metadata =
+        CoreProviderMetadata::discover_async(issuer_url.clone(), async_http_client)
+            .await
+            .map_err(|err| {
+                log::error!("Failed to discover the oidc provider {err}");
+                BambooError::unauthorized("oidc", "Invalid configuration")
+            })?;
+    *PROVIDER_METADATA.lock() = Some(CachedProviderMetadata {
+        issuer_url,
+        provider_metadata: provider_metadata.clone(),
+        discovered_at: Instant::now(),
+    });
+
+    Ok(provider_metadata)
+}
+
+async fn get_client(env_service: &EnvService) -> BambooResult<CoreClient> {
+    let issuer_url = IssuerUrl::new(env_service.get_env("OIDC_ISSUER_URL", "")).map_err(|err| {
+        log::error!("Failed to create issuer url {err}");
+        BambooError::unauthorized("oidc", "Invalid configuration")
+    })?;
+    let provider_metadata = discover_provider(issuer_url).await?;
+    let redirect_url = RedirectUrl::new(format!(
+        "{}/api/login/oidc/callback",
+        get_frontend_url(env_service)
+    ))
+    .map_err(|err| {
+        log::error!("Failed to create redirect url {err}");
+        BambooError::unauthorized("oidc", "Invalid configuration")
+    })?;
+
+    Ok(CoreClient::from_provider_metadata(
+        provider_metadata,
+        ClientId::new(env_service.get_env("OIDC_CLIENT_ID", "")),
+        env_service
+            .get_env_opt("OIDC_CLIENT_SECRET")
+            .map(ClientSecret::new),
+    )
+    .set_redirect_uri(redirect_url))
+}
+
+pub async fn create_authorization(env_service: &EnvService) -> BambooResult<OidcAuthorization> {
+    let client = get_client(env_service).await?;
+    let (pkce_challenge, pkce_verifier) = PkceCodeChallenge::new_random_sha256();
+    let (url, state, nonce) = client
+        .authorize_url(
+            CoreAuthenticationFlow::AuthorizationCode,
+            CsrfToken::new_random,
+            Nonce::new_random,
+        )
+        .add_scope(Scope::new("email".to_string()))
+        .add_scope(Scope::new("profile".to_string()))
+        .set_pkce_challenge(pkce_challenge)
+        .url();
+
+    Ok(OidcAuthorization {
+        url: url.to_string(),
+        state: state.secret().clone(),
+        nonce: nonce.secret().clone(),
+        pkce_verifier: pkce_verifier.secret().clone(),
+    })
+}
+
+pub async fn exchange_code(
+    code: String,
+    nonce: String,
+    pkce_verifier: String,
+    env_service: &EnvService,
+) -> BambooResult<OidcIdentity> {
+    let client = get_client(env_service).await?;
+    let token_response = client
+        .exchange_code(AuthorizationCode::new(code))
+        .set_pkce_verifier(PkceCodeVerifier::new(pkce_verifier))
+        .request_async(async_http_client)
+        .await
+        .map_err(|err| {
+            log::error!("Failed to exchange the authorization code {err}");
+            BambooError::unauthorized("oidc", "Invalid authorization code")
+        })?;
+
+    let id_token = token_response
+        .id_token()
+        .ok_or(BambooError::unauthorized("oidc", "The id token is missing"))?;
+    let claims = id_token
+        .claims(&client.id_token_verifier(), &Nonce::new(nonce))
+        .map_err(|err| {
+            log::error!("Failed to verify the id token {err}");
+            PROVIDER_METADATA.lock().take();
+            BambooError::unauthorized("oidc", "Invalid id token")
+        })?;
+
+    Ok(OidcIdentity {
+        issuer: claims.issuer().to_string(),
+        subject: claims.subject().to_string(),
+        email: claims.email().map(|email| email.to_string()),
+        email_verified: claims.email_verified().unwrap_or(false),
+    })
+}
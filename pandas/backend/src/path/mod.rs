@@ -48,6 +48,31 @@ pub struct FreeCompanyPathInfo {
     pub free_company_id: i32,
 }
 
+#[derive(Deserialize)]
+pub struct GroveExportPathInfo {
+    pub export_id: i32,
+}
+
+#[derive(Deserialize)]
+pub struct GroveInvitationPathInfo {
+    pub invitation_id: i32,
+}
+
+#[derive(Deserialize)]
+pub struct GroveRolePathInfo {
+    pub role_id: i32,
+}
+
+#[derive(Deserialize)]
+pub struct PasskeyPathInfo {
+    pub passkey_id: i32,
+}
+
+#[derive(Deserialize)]
+pub struct PersonalAccessTokenPathInfo {
+    pub token_id: i32,
+}
+
 #[derive(Deserialize)]
 pub struct UserPathInfo {
     pub user_id: i32,
@@ -62,4 +87,9 @@ pub type CustomFieldPositionPath = web::Path<CustomFieldPositionPathInfo>;
 pub type EventPath = web::Path<EventPathInfo>;
 pub type FighterPath = web::Path<FighterPathInfo>;
 pub type FreeCompanyPath = web::Path<FreeCompanyPathInfo>;
+pub type GroveExportPath = web::Path<GroveExportPathInfo>;
+pub type GroveInvitationPath = web::Path<GroveInvitationPathInfo>;
+pub type GroveRolePath = web::Path<GroveRolePathInfo>;
+pub type PasskeyPath = web::Path<PasskeyPathInfo>;
+pub type PersonalAccessTokenPath = web::Path<PersonalAccessTokenPathInfo>;
 pub type UserPath = web::Path<UserPathInfo>;
@@ -0,0 +1,40 @@
+use std::time::Duration;
+
+use sea_orm::DatabaseConnection;
+
+use bamboo_common::backend::dbal;
+use bamboo_common::backend::services::minio_service::MinioClient;
+use bamboo_common::core::error::BambooResult;
+
+const PURGE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Removes all groves whose grace period ran out, including their logo and exports.
+pub async fn purge_deleted_groves(
+    minio_client: &MinioClient,
+    db: &DatabaseConnection,
+) -> BambooResult<()> {
+    for grove in dbal::get_groves_due_for_purge(db).await? {
+        log::info!("Purge grove {} ({})", grove.name, grove.id);
+        for grove_export in dbal::get_grove_exports(grove.id, db).await? {
+            let _ = minio_client
+                .delete_grove_export(grove.id, grove_export.id)
+                .await;
+        }
+        dbal::purge_grove(grove.id, db).await?;
+        let _ = minio_client.delete_grove_logo(grove.id).await;
+    }
+
+    Ok(())
+}
+
+pub fn start_grove_purge_schedule(minio_client: MinioClient, db: DatabaseConnection) {
+    actix_web::rt::spawn(async move {
+        let mut interval = actix_web::rt::time::interval(PURGE_INTERVAL);
+        loop {
+            interval.tick().await;
+            if let Err(err) = purge_deleted_groves(&minio_client, &db).await {
+                log::error!("Failed to purge deleted groves {err}");
+            }
+        }
+    });
+}
@@ -0,0 +1,47 @@
+use actix_web::{get, web};
+use serde::Deserialize;
+
+use bamboo_common::backend::dbal;
+use bamboo_common::backend::response::*;
+use bamboo_common::backend::services::DbConnection;
+use bamboo_common::core::entities::{AuditAction, AuditLogPage, GrovePermission};
+use bamboo_common::core::error::*;
+
+use crate::middleware::authenticate_user::authenticate;
+use crate::middleware::check_permission::permission;
+use crate::middleware::identify_grove::{grove, CurrentGrove};
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetAuditLogQuery {
+    #[serde(default)]
+    pub page: u64,
+    pub action: Option<AuditAction>,
+    pub actor_id: Option<i32>,
+    pub target_id: Option<i32>,
+}
+
+#[get(
+    "/api/grove/audit-log",
+    wrap = "authenticate!()",
+    wrap = "grove!()",
+    wrap = "permission!(GrovePermission::ViewAuditLog)"
+)]
+pub async fn get_audit_log(
+    query: Option<web::Query<GetAuditLogQuery>>,
+    current_grove: CurrentGrove,
+    db: DbConnection,
+) -> BambooApiResult<AuditLogPage> {
+    let query = check_invalid_query!(query, "audit_log_entry")?;
+
+    dbal::get_audit_log_entries(
+        current_grove.grove.id,
+        query.action,
+        query.actor_id,
+        query.target_id,
+        query.page,
+        &db,
+    )
+    .await
+    .map(|data| ok!(data))
+}
@@ -1,73 +1,153 @@
-use actix_web::cookie::Cookie;
-use actix_web::{delete, post, web, HttpResponse};
+use std::net::IpAddr;
+
+use actix_web::http::header;
+use actix_web::{delete, get, post, web, HttpRequest, HttpResponse};
+use serde::Deserialize;
 
 use bamboo_common::backend::response::*;
-use bamboo_common::backend::services::{DbConnection, EnvService};
+use bamboo_common::backend::services::{DbConnection, EnvService, WebauthnService};
 use bamboo_common::backend::{dbal, mailing};
 use bamboo_common::core::entities::*;
 use bamboo_common::core::error::*;
 
+use crate::cookie;
+use crate::demo;
 use crate::middleware::authenticate_user::{authenticate, Authentication};
+use crate::middleware::check_csrf::csrf;
+use crate::oidc;
+
+/// The address of the client, the forwarding headers are only honored when the peer is a trusted proxy
+fn get_client_ip(req: &HttpRequest, env_service: &EnvService) -> Option<String> {
+    let peer_ip = req.peer_addr()?.ip();
+    let trusted_proxies = env_service.get_trusted_proxies();
+    if !trusted_proxies.contains(&peer_ip) {
+        return Some(peer_ip.to_string());
+    }
+
+    let forwarded_ips = req
+        .headers()
+        .get_all("X-Forwarded-For")
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .filter_map(|ip| ip.trim().parse::<IpAddr>().ok())
+        .collect::<Vec<_>>();
+
+    Some(
+        forwarded_ips
+            .into_iter()
+            .rev()
+            .find(|ip| !trusted_proxies.contains(ip))
+            .unwrap_or(peer_ip)
+            .to_string(),
+    )
+}
+
+async fn register_failed_login(
+    user: Option<User>,
+    ip_address: Option<String>,
+    db: &DbConnection,
+    env_service: EnvService,
+) {
+    match dbal::register_failed_login(user.as_ref().map(|user| user.id), ip_address, db).await {
+        Ok(Some(lockout)) => {
+            if let (Some(user), Some(locked_until)) = (user, lockout.locked_until) {
+                mailing::authentication::send_account_locked_mail(
+                    user.display_name,
+                    user.email,
+                    locked_until.format("%d.%m.%Y %H:%M").to_string(),
+                    env_service,
+                )
+                .await
+            }
+        }
+        Ok(None) => (),
+        Err(err) => log::error!("Failed to register failed login {err}"),
+    }
+}
 
 #[post("/api/login")]
 pub async fn login(
     body: Option<web::Json<Login>>,
+    req: HttpRequest,
     db: DbConnection,
     env_service: EnvService,
 ) -> BambooApiResponseResult {
     let body = check_missing_fields!(body, "authentication")?;
 
-    if body.email.clone() == "playstore@google.bambushain" {
-        dbal::create_google_auth_token(body.password.clone(), &db)
-            .await
-            .map_err(|err| {
-                log::error!("Failed to login {err}");
-                BambooError::unauthorized("user", "Login data is invalid")
-            })
-            .map(|data| {
+    let ip_address = get_client_ip(&req, &env_service);
+    let user = dbal::get_user_by_email_or_username(body.email.clone(), &db)
+        .await
+        .ok();
+    dbal::check_login_lockout(user.as_ref().map(|user| user.id), ip_address.clone(), &db).await?;
+
+    let demo_account = demo::get_demo_account(&env_service)
+        .filter(|demo_account| demo_account.skip_two_factor)
+        .filter(|demo_account| demo_account.is_demo_login(body.email.as_str()));
+    if let Some(demo_account) = demo_account {
+        let result = dbal::validate_demo_auth_and_create_token(
+            demo_account.email,
+            body.email.clone(),
+            body.password.clone(),
+            &db,
+        )
+        .await;
+        match result {
+            Ok(data) => {
+                let _ = dbal::reset_failed_logins(data.user.id, &db).await;
                 let mut response = list!(data.clone());
-                let _ = response.add_cookie(
-                    &Cookie::build(crate::cookie::BAMBOO_AUTH_COOKIE, data.token.clone())
-                        .path("/")
-                        .http_only(true)
-                        .finish(),
-                );
-                response
-            })
+                let _ = response
+                    .add_cookie(&cookie::build_auth_cookie(data.token.clone(), &env_service));
+
+                Ok(response)
+            }
+            Err(err) => {
+                log::error!("Failed to login {err}");
+                register_failed_login(user, ip_address, &db, env_service).await;
+
+                Err(BambooError::unauthorized("user", "Login data is invalid"))
+            }
+        }
     } else if let Some(two_factor_code) = body.two_factor_code.clone() {
-        dbal::validate_auth_and_create_token(
+        let result = dbal::validate_auth_and_create_token(
             body.email.clone(),
             body.password.clone(),
             two_factor_code,
             &db,
         )
-        .await
-        .map_err(|err| {
-            log::error!("Failed to login {err}");
-            BambooError::unauthorized("user", "Login data is invalid")
-        })
-        .map(|data| {
-            let mut response = list!(data.clone());
-            let _ = response.add_cookie(
-                &Cookie::build(crate::cookie::BAMBOO_AUTH_COOKIE, data.token.clone())
-                    .path("/")
-                    .http_only(true)
-                    .finish(),
-            );
+        .await;
+        match result {
+            Ok(data) => {
+                let _ = dbal::reset_failed_logins(data.user.id, &db).await;
+                let mut response = list!(data.clone());
+                let _ = response
+                    .add_cookie(&cookie::build_auth_cookie(data.token.clone(), &env_service));
 
-            response
-        })
+                Ok(response)
+            }
+            Err(err) => {
+                log::error!("Failed to login {err}");
+                register_failed_login(user, ip_address, &db, env_service).await;
+
+                Err(BambooError::unauthorized("user", "Login data is invalid"))
+            }
+        }
     } else {
-        let data = dbal::validate_auth_and_set_two_factor_code(
+        let result = dbal::validate_auth_and_set_two_factor_code(
             body.email.clone(),
             body.password.clone(),
             &db,
         )
-        .await
-        .map_err(|err| {
-            log::error!("Failed to login {err}");
-            BambooError::unauthorized("user", "Login data is invalid")
-        })?;
+        .await;
+        let data = match result {
+            Ok(data) => data,
+            Err(err) if err.error_type == BambooErrorCode::TooManyRequests => return Err(err),
+            Err(err) => {
+                log::error!("Failed to login {err}");
+                register_failed_login(user, ip_address, &db, env_service).await;
+
+                return Err(BambooError::unauthorized("user", "Login data is invalid"));
+            }
+        };
         if let Some(two_factor_code) = data.two_factor_code {
             mailing::authentication::send_two_factor_mail(
                 data.user.display_name,
@@ -83,6 +163,227 @@ pub async fn login(
     }
 }
 
+#[post("/api/login/passkey/challenge")]
+pub async fn start_passkey_login(
+    body: Option<web::Json<StartPasskeyLogin>>,
+    req: HttpRequest,
+    webauthn: WebauthnService,
+    db: DbConnection,
+    env_service: EnvService,
+) -> BambooApiResult<PasskeyLoginChallenge> {
+    let body = check_missing_fields!(body, "authentication")?;
+
+    let ip_address = get_client_ip(&req, &env_service);
+    let user = dbal::get_user_by_email_or_username(body.email.clone(), &db)
+        .await
+        .ok();
+    dbal::check_login_lockout(user.as_ref().map(|user| user.id), ip_address, &db).await?;
+
+    let user = user.ok_or(BambooError::unauthorized("user", "Login data is invalid"))?;
+    dbal::start_passkey_authentication(user.id, &webauthn, &db)
+        .await
+        .map(|data| ok!(data))
+        .map_err(|err| {
+            log::error!("Failed to start passkey login {err}");
+            BambooError::unauthorized("user", "Login data is invalid")
+        })
+}
+
+#[post("/api/login/passkey")]
+pub async fn passkey_login(
+    body: Option<web::Json<FinishPasskeyLogin>>,
+    req: HttpRequest,
+    webauthn: WebauthnService,
+    db: DbConnection,
+    env_service: EnvService,
+) -> BambooApiResponseResult {
+    let body = check_missing_fields!(body, "authentication")?;
+
+    let ip_address = get_client_ip(&req, &env_service);
+    let user = dbal::get_user_by_email_or_username(body.email.clone(), &db)
+        .await
+        .ok();
+    dbal::check_login_lockout(user.as_ref().map(|user| user.id), ip_address.clone(), &db).await?;
+
+    let result = dbal::validate_passkey_and_create_token(
+        body.email.clone(),
+        body.challenge_id.clone(),
+        body.credential.clone(),
+        &webauthn,
+        &db,
+    )
+    .await;
+    match result {
+        Ok(data) => {
+            let _ = dbal::reset_failed_logins(data.user.id, &db).await;
+            let mut response = list!(data.clone());
+            let _ =
+                response.add_cookie(&cookie::build_auth_cookie(data.token.clone(), &env_service));
+
+            Ok(response)
+        }
+        Err(err) => {
+            log::error!("Failed to login {err}");
+            register_failed_login(user, ip_address, &db, env_service).await;
+
+            Err(BambooError::unauthorized("user", "Login data is invalid"))
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub struct OidcCallbackQuery {
+    code: Option<String>,
+    state: Option<String>,
+}
+
+fn redirect_to_frontend(fragment: String, env_service: &EnvService) -> HttpResponse {
+    HttpResponse::Found()
+        .insert_header((
+            header::LOCATION,
+            format!(
+                "{}/login/oidc#{fragment}",
+                oidc::get_frontend_url(env_service)
+            ),
+        ))
+        .finish()
+}
+
+#[get("/api/login/oidc/config")]
+pub async fn get_oidc_login_config(env_service: EnvService) -> BambooApiResult<OidcLoginConfig> {
+    Ok(ok!(OidcLoginConfig {
+        enabled: oidc::is_enabled(&env_service),
+        provider_name: env_service.get_env("OIDC_PROVIDER_NAME", "Single Sign-On"),
+    }))
+}
+
+#[get("/api/login/oidc")]
+pub async fn start_oidc_login(
+    db: DbConnection,
+    env_service: EnvService,
+) -> BambooApiResponseResult {
+    if !oidc::is_enabled(&env_service) {
+        return Err(BambooError::not_found("oidc", "The oidc login is disabled"));
+    }
+
+    let authorization = oidc::create_authorization(&env_service).await?;
+    dbal::create_oidc_login_state(
+        authorization.state.clone(),
+        authorization.nonce,
+        authorization.pkce_verifier,
+        &db,
+    )
+    .await?;
+
+    Ok(HttpResponse::Found()
+        .insert_header((header::LOCATION, authorization.url))
+        .cookie(cookie::build_oidc_state_cookie(
+            authorization.state,
+            &env_service,
+        ))
+        .finish())
+}
+
+#[get("/api/login/oidc/callback")]
+pub async fn oidc_login_callback(
+    query: web::Query<OidcCallbackQuery>,
+    state_cookie: Option<cookie::BambooOidcStateCookie>,
+    db: DbConnection,
+    env_service: EnvService,
+) -> HttpResponse {
+    let (Some(code), Some(state)) = (query.code.clone(), query.state.clone()) else {
+        return redirect_to_frontend("error".to_string(), &env_service);
+    };
+
+    let result = async {
+        // Only the browser that started the login may finish it
+        if state_cookie.map(|cookie| cookie.state) != Some(state.clone()) {
+            return Err(BambooError::unauthorized(
+                "oidc",
+                "The oidc state doesn't belong to this browser",
+            ));
+        }
+
+        let login_state = dbal::take_oidc_login_state(state, &db).await?;
+        let identity = oidc::exchange_code(
+            code,
+            login_state.nonce,
+            login_state.pkce_verifier,
+            &env_service,
+        )
+        .await?;
+
+        dbal::validate_external_identity_and_create_token(
+            identity.issuer,
+            identity.subject,
+            identity.email,
+            identity.email_verified,
+            oidc::get_allowed_groves(&env_service),
+            &db,
+        )
+        .await
+    }
+    .await;
+
+    let mut response = match result {
+        Ok(data) => {
+            let _ = dbal::reset_failed_logins(data.user.id, &db).await;
+            let mut response = redirect_to_frontend(format!("token={}", data.token), &env_service);
+            let _ =
+                response.add_cookie(&cookie::build_auth_cookie(data.token.clone(), &env_service));
+
+            response
+        }
+        Err(err) => {
+            log::error!("Failed to login with oidc {err}");
+            redirect_to_frontend("error".to_string(), &env_service)
+        }
+    };
+    let _ = response.add_cookie(&cookie::build_oidc_state_removal_cookie(&env_service));
+
+    response
+}
+
+async fn notify_mods_about_forgotten_password(
+    user: &User,
+    db: &DbConnection,
+    env_service: EnvService,
+) {
+    if let Ok(mods) = dbal::get_users_with_mod_rights(user.id, db).await {
+        for bamboo_mod in mods {
+            mailing::authentication::send_forgot_password_mail(
+                user.display_name.clone(),
+                bamboo_mod.display_name.clone(),
+                bamboo_mod.email.clone(),
+                env_service.clone(),
+            )
+            .await
+        }
+    }
+}
+
+async fn send_password_reset_link(
+    user: &User,
+    db: &DbConnection,
+    env_service: EnvService,
+) -> BambooErrorResult {
+    let token = dbal::create_password_reset_token(user.id, db).await?;
+    let reset_link = format!(
+        "{}/reset-password?token={token}",
+        env_service
+            .get_env("PANDAS_BASE_URL", "https://pandas.bambushain.app")
+            .trim_end_matches('/')
+    );
+
+    mailing::authentication::send_password_reset_mail(
+        user.display_name.clone(),
+        user.email.clone(),
+        reset_link,
+        env_service,
+    )
+    .await
+}
+
 #[post("/api/forgot-password")]
 pub async fn forgot_password(
     body: Option<web::Json<ForgotPassword>>,
@@ -91,16 +392,13 @@ pub async fn forgot_password(
 ) -> HttpResponse {
     if let Ok(body) = check_missing_fields!(body, "user") {
         if let Ok(user) = dbal::get_user_by_email_or_username(body.email.clone(), &db).await {
-            if let Ok(mods) = dbal::get_users_with_mod_rights(user.id, &db).await {
-                for bamboo_mod in mods {
-                    mailing::authentication::send_forgot_password_mail(
-                        user.display_name.clone(),
-                        bamboo_mod.display_name.clone(),
-                        bamboo_mod.email.clone(),
-                        env_service.clone(),
-                    )
-                    .await
-                }
+            let reset_mode = env_service.get_env("PASSWORD_RESET_MODE", "link");
+            if reset_mode == "mods" {
+                notify_mods_about_forgotten_password(&user, &db, env_service.clone()).await;
+            } else if let Err(err) = send_password_reset_link(&user, &db, env_service.clone()).await
+            {
+                log::warn!("Failed to send password reset link, falling back to the mods {err}");
+                notify_mods_about_forgotten_password(&user, &db, env_service.clone()).await;
             }
         }
     }
@@ -108,7 +406,36 @@ pub async fn forgot_password(
     no_content!()
 }
 
-#[delete("/api/login", wrap = "authenticate!()")]
+#[post("/api/reset-password")]
+pub async fn reset_password(
+    body: Option<web::Json<ResetPassword>>,
+    db: DbConnection,
+) -> BambooApiResponseResult {
+    let body = check_missing_fields!(body, "user")?;
+
+    dbal::reset_password_with_token(
+        body.token.clone(),
+        body.new_password.clone(),
+        body.two_factor_code.clone(),
+        &db,
+    )
+    .await
+    .map(|_| no_content!())
+}
+
+#[post("/api/confirm-email")]
+pub async fn confirm_email_change(
+    body: Option<web::Json<ConfirmEmailChange>>,
+    db: DbConnection,
+) -> BambooApiResponseResult {
+    let body = check_missing_fields!(body, "user")?;
+
+    dbal::confirm_email_change(body.token.clone(), &db)
+        .await
+        .map(|_| no_content!())
+}
+
+#[delete("/api/login", wrap = "authenticate!()", wrap = "csrf!()")]
 pub async fn logout(auth: Authentication, db: DbConnection) -> HttpResponse {
     let _ = dbal::delete_token(auth.token.clone(), &db).await;
 
@@ -7,6 +7,7 @@ use bamboo_common::core::entities::*;
 use bamboo_common::core::error::*;
 
 use crate::middleware::authenticate_user::{authenticate, Authentication};
+use crate::middleware::check_csrf::csrf;
 use crate::path;
 
 #[get("/api/final-fantasy/character", wrap = "authenticate!()")]
@@ -35,7 +36,11 @@ pub async fn get_character(
         .map(|data| ok!(data))
 }
 
-#[post("/api/final-fantasy/character", wrap = "authenticate!()")]
+#[post(
+    "/api/final-fantasy/character",
+    wrap = "authenticate!()",
+    wrap = "csrf!()"
+)]
 pub async fn create_character(
     body: Option<web::Json<Character>>,
     authentication: Authentication,
@@ -50,7 +55,8 @@ pub async fn create_character(
 
 #[put(
     "/api/final-fantasy/character/{character_id}",
-    wrap = "authenticate!()"
+    wrap = "authenticate!()",
+    wrap = "csrf!()"
 )]
 pub async fn update_character(
     body: Option<web::Json<Character>>,
@@ -73,7 +79,8 @@ pub async fn update_character(
 
 #[delete(
     "/api/final-fantasy/character/{character_id}",
-    wrap = "authenticate!()"
+    wrap = "authenticate!()",
+    wrap = "csrf!()"
 )]
 pub async fn delete_character(
     path: Option<path::CharacterPath>,
@@ -7,6 +7,7 @@ use bamboo_common::core::entities::*;
 use bamboo_common::core::error::*;
 
 use crate::middleware::authenticate_user::{authenticate, Authentication};
+use crate::middleware::check_csrf::csrf;
 use crate::middleware::extract_character::{character, CharacterData};
 use crate::path;
 
@@ -51,6 +52,7 @@ pub async fn get_character_housing(
 #[post(
     "/api/final-fantasy/character/{character_id}/housing",
     wrap = "authenticate!()",
+    wrap = "csrf!()",
     wrap = "character!()"
 )]
 pub async fn create_character_housing(
@@ -69,6 +71,7 @@ pub async fn create_character_housing(
 #[put(
     "/api/final-fantasy/character/{character_id}/housing/{character_housing_id}",
     wrap = "authenticate!()",
+    wrap = "csrf!()",
     wrap = "character!()"
 )]
 pub async fn update_character_housing(
@@ -95,6 +98,7 @@ pub async fn update_character_housing(
 #[delete(
     "/api/final-fantasy/character/{character_id}/housing/{character_housing_id}",
     wrap = "authenticate!()",
+    wrap = "csrf!()",
     wrap = "character!()"
 )]
 pub async fn delete_character_housing(
@@ -7,6 +7,7 @@ use bamboo_common::core::entities::*;
 use bamboo_common::core::error::*;
 
 use crate::middleware::authenticate_user::{authenticate, Authentication};
+use crate::middleware::check_csrf::csrf;
 use crate::middleware::extract_character::{character, CharacterData};
 use crate::path;
 
@@ -46,6 +47,7 @@ pub async fn get_crafter(
 #[post(
     "/api/final-fantasy/character/{character_id}/crafter",
     wrap = "authenticate!()",
+    wrap = "csrf!()",
     wrap = "character!()"
 )]
 pub async fn create_crafter(
@@ -64,6 +66,7 @@ pub async fn create_crafter(
 #[put(
     "/api/final-fantasy/character/{character_id}/crafter/{crafter_id}",
     wrap = "authenticate!()",
+    wrap = "csrf!()",
     wrap = "character!()"
 )]
 pub async fn update_crafter(
@@ -90,6 +93,7 @@ pub async fn update_crafter(
 #[delete(
     "/api/final-fantasy/character/{character_id}/crafter/{crafter_id}",
     wrap = "authenticate!()",
+    wrap = "csrf!()",
     wrap = "character!()"
 )]
 pub async fn delete_crafter(
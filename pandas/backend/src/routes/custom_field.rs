@@ -7,6 +7,7 @@ use bamboo_common::core::entities::*;
 use bamboo_common::core::error::*;
 
 use crate::middleware::authenticate_user::{authenticate, Authentication};
+use crate::middleware::check_csrf::csrf;
 use crate::path;
 
 #[get("/api/final-fantasy/character/custom-field", wrap = "authenticate!()")]
@@ -35,7 +36,11 @@ pub async fn get_custom_field(
         .map(|data| ok!(data))
 }
 
-#[post("/api/final-fantasy/character/custom-field", wrap = "authenticate!()")]
+#[post(
+    "/api/final-fantasy/character/custom-field",
+    wrap = "authenticate!()",
+    wrap = "csrf!()"
+)]
 pub async fn create_custom_field(
     body: Option<web::Json<CustomField>>,
     authentication: Authentication,
@@ -50,7 +55,8 @@ pub async fn create_custom_field(
 
 #[put(
     "/api/final-fantasy/character/custom-field/{field_id}",
-    wrap = "authenticate!()"
+    wrap = "authenticate!()",
+    wrap = "csrf!()"
 )]
 pub async fn update_custom_field(
     path: Option<path::CustomFieldPath>,
@@ -73,7 +79,8 @@ pub async fn update_custom_field(
 
 #[delete(
     "/api/final-fantasy/character/custom-field/{field_id}",
-    wrap = "authenticate!()"
+    wrap = "authenticate!()",
+    wrap = "csrf!()"
 )]
 pub async fn delete_custom_field(
     path: Option<path::CustomFieldPath>,
@@ -89,7 +96,8 @@ pub async fn delete_custom_field(
 
 #[post(
     "/api/final-fantasy/character/custom-field/{field_id}/option",
-    wrap = "authenticate!()"
+    wrap = "authenticate!()",
+    wrap = "csrf!()"
 )]
 pub async fn create_custom_field_option(
     path: Option<path::CustomFieldPath>,
@@ -128,7 +136,8 @@ pub async fn get_custom_field_options(
 
 #[put(
     "/api/final-fantasy/character/custom-field/{field_id}/option/{option_id}",
-    wrap = "authenticate!()"
+    wrap = "authenticate!()",
+    wrap = "csrf!()"
 )]
 pub async fn update_custom_field_option(
     path: Option<path::CustomFieldOptionPath>,
@@ -152,7 +161,8 @@ pub async fn update_custom_field_option(
 
 #[delete(
     "/api/final-fantasy/character/custom-field/{field_id}/option/{option_id}",
-    wrap = "authenticate!()"
+    wrap = "authenticate!()",
+    wrap = "csrf!()"
 )]
 pub async fn delete_custom_field_option(
     path: Option<path::CustomFieldOptionPath>,
@@ -167,7 +177,8 @@ pub async fn delete_custom_field_option(
 
 #[put(
     "/api/final-fantasy/character/custom-field/{field_id}/{position}",
-    wrap = "authenticate!()"
+    wrap = "authenticate!()",
+    wrap = "csrf!()"
 )]
 pub async fn move_custom_field(
     path: Option<path::CustomFieldPositionPath>,
@@ -6,10 +6,11 @@ use serde::Deserialize;
 use bamboo_common::backend::dbal;
 use bamboo_common::backend::response::*;
 use bamboo_common::backend::services::DbConnection;
-use bamboo_common::core::entities::Event;
+use bamboo_common::core::entities::{Event, GrovePermission, User};
 use bamboo_common::core::error::*;
 
 use crate::middleware::authenticate_user::{authenticate, Authentication};
+use crate::middleware::check_csrf::csrf;
 use crate::middleware::identify_grove::{grove, CurrentGrove};
 use crate::notifier;
 use crate::path;
@@ -20,6 +21,26 @@ pub struct GetEventsQuery {
     pub end: NaiveDate,
 }
 
+/// Events created by other pandas can only be changed with the permission to manage events,
+/// events created before the creator was stored can be changed by everyone.
+async fn check_event_permission(
+    event: &Event,
+    user: &User,
+    db: &DbConnection,
+) -> BambooErrorResult {
+    let is_foreign_event = event
+        .created_by_id
+        .is_some_and(|created_by_id| created_by_id != user.id);
+    if is_foreign_event && !dbal::has_permission(user, GrovePermission::ManageEvents, db).await? {
+        Err(BambooError::insufficient_rights(
+            "event",
+            "You can only change your own events",
+        ))
+    } else {
+        Ok(())
+    }
+}
+
 #[get("/api/bamboo-grove/event", wrap = "authenticate!()", wrap = "grove!()")]
 pub async fn get_events(
     query: Option<web::Query<GetEventsQuery>>,
@@ -38,7 +59,12 @@ pub async fn get_events(
         .map(|data| list!(data))
 }
 
-#[post("/api/bamboo-grove/event", wrap = "authenticate!()", wrap = "grove!()")]
+#[post(
+    "/api/bamboo-grove/event",
+    wrap = "authenticate!()",
+    wrap = "csrf!()",
+    wrap = "grove!()"
+)]
 pub async fn create_event(
     body: Option<web::Json<Event>>,
     notifier: notifier::Notifier,
@@ -63,6 +89,7 @@ pub async fn create_event(
 #[put(
     "/api/bamboo-grove/event/{event_id}",
     wrap = "authenticate!()",
+    wrap = "csrf!()",
     wrap = "grove!()"
 )]
 pub async fn update_event(
@@ -76,6 +103,15 @@ pub async fn update_event(
     let path = check_invalid_path!(path, "event")?;
     let body = check_missing_fields!(body, "event")?;
 
+    let event = dbal::get_event(
+        path.event_id,
+        current_grove.grove.id,
+        authentication.user.id,
+        &db,
+    )
+    .await?;
+    check_event_permission(&event, &authentication.user, &db).await?;
+
     dbal::update_event(
         current_grove.grove.id,
         path.event_id,
@@ -99,6 +135,7 @@ pub async fn update_event(
 #[delete(
     "/api/bamboo-grove/event/{event_id}",
     wrap = "authenticate!()",
+    wrap = "csrf!()",
     wrap = "grove!()"
 )]
 pub async fn delete_event(
@@ -117,6 +154,7 @@ pub async fn delete_event(
         &db,
     )
     .await?;
+    check_event_permission(&event, &authentication.user, &db).await?;
     dbal::delete_event(current_grove.grove.id, path.event_id, &db).await?;
     notifier.notify_event_delete(event);
 
@@ -0,0 +1,102 @@
+use actix_web::http::header::{ContentDisposition, DispositionParam, DispositionType};
+use actix_web::{get, post, HttpResponse};
+
+use bamboo_common::backend::dbal;
+use bamboo_common::backend::response::*;
+use bamboo_common::backend::services::{DbConnection, MinioService};
+use bamboo_common::core::entities::{AuditAction, GroveExport, GroveExportStatus};
+use bamboo_common::core::error::*;
+
+use crate::export;
+use crate::middleware::authenticate_user::{authenticate, Authentication};
+use crate::middleware::check_csrf::csrf;
+use crate::middleware::check_mod::moderator;
+use crate::middleware::identify_grove::{grove, CurrentGrove};
+use crate::path;
+
+/// The export contains the emails of all pandas, so only mods can request it, a role is not enough.
+#[get(
+    "/api/grove/export",
+    wrap = "authenticate!()",
+    wrap = "grove!()",
+    wrap = "moderator!()"
+)]
+pub async fn get_grove_exports(
+    current_grove: CurrentGrove,
+    db: DbConnection,
+) -> BambooApiResponseResult {
+    dbal::get_grove_exports(current_grove.grove.id, &db)
+        .await
+        .map(|data| list!(data))
+}
+
+#[post(
+    "/api/grove/export",
+    wrap = "authenticate!()",
+    wrap = "csrf!()",
+    wrap = "grove!()",
+    wrap = "moderator!()"
+)]
+pub async fn start_grove_export(
+    authentication: Authentication,
+    current_grove: CurrentGrove,
+    minio: MinioService,
+    db: DbConnection,
+) -> BambooApiResult<GroveExport> {
+    let grove_export =
+        dbal::create_grove_export(current_grove.grove.id, &authentication.user, &db).await?;
+    let _ = dbal::create_audit_log_entry(
+        current_grove.grove.id,
+        &authentication.user,
+        AuditAction::GroveExported,
+        None,
+        &db,
+    )
+    .await;
+    export::start_grove_export(
+        grove_export.clone(),
+        minio.get_ref().clone(),
+        db.get_ref().clone(),
+    );
+
+    Ok(created!(grove_export))
+}
+
+#[get(
+    "/api/grove/export/{export_id}/download",
+    wrap = "authenticate!()",
+    wrap = "grove!()",
+    wrap = "moderator!()"
+)]
+pub async fn download_grove_export(
+    path: Option<path::GroveExportPath>,
+    current_grove: CurrentGrove,
+    minio: MinioService,
+    db: DbConnection,
+) -> BambooResult<HttpResponse> {
+    let path = check_invalid_path!(path, "grove_export")?;
+
+    let grove_export = dbal::get_grove_export(current_grove.grove.id, path.export_id, &db).await?;
+    if grove_export.status != GroveExportStatus::Finished {
+        return Err(BambooError::not_found(
+            "grove_export",
+            "The export is not finished",
+        ));
+    }
+
+    let data = minio
+        .get_grove_export(current_grove.grove.id, grove_export.id)
+        .await?;
+
+    Ok(HttpResponse::Ok()
+        .content_type("application/json")
+        .insert_header(ContentDisposition {
+            disposition: DispositionType::Attachment,
+            parameters: vec![DispositionParam::Filename(format!(
+                "hain-export-{}-{}.json",
+                grove_export.id,
+                grove_export.created_at.format("%Y-%m-%d")
+            ))],
+        })
+        .body(data))
+}
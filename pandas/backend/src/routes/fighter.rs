@@ -7,6 +7,7 @@ use bamboo_common::core::entities::*;
 use bamboo_common::core::error::*;
 
 use crate::middleware::authenticate_user::{authenticate, Authentication};
+use crate::middleware::check_csrf::csrf;
 use crate::middleware::extract_character::{character, CharacterData};
 use crate::path;
 
@@ -46,6 +47,7 @@ pub async fn get_fighter(
 #[post(
     "/api/final-fantasy/character/{character_id}/fighter",
     wrap = "authenticate!()",
+    wrap = "csrf!()",
     wrap = "character!()"
 )]
 pub async fn create_fighter(
@@ -64,6 +66,7 @@ pub async fn create_fighter(
 #[put(
     "/api/final-fantasy/character/{character_id}/fighter/{fighter_id}",
     wrap = "authenticate!()",
+    wrap = "csrf!()",
     wrap = "character!()"
 )]
 pub async fn update_fighter(
@@ -90,6 +93,7 @@ pub async fn update_fighter(
 #[delete(
     "/api/final-fantasy/character/{character_id}/fighter/{fighter_id}",
     wrap = "authenticate!()",
+    wrap = "csrf!()",
     wrap = "character!()"
 )]
 pub async fn delete_fighter(
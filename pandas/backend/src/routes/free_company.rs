@@ -7,6 +7,7 @@ use bamboo_common::core::entities::*;
 use bamboo_common::core::error::*;
 
 use crate::middleware::authenticate_user::{authenticate, Authentication};
+use crate::middleware::check_csrf::csrf;
 use crate::path;
 
 #[get("/api/final-fantasy/free-company", wrap = "authenticate!()")]
@@ -35,7 +36,11 @@ pub async fn get_free_company(
         .map(|data| ok!(data.unwrap()))
 }
 
-#[post("/api/final-fantasy/free-company", wrap = "authenticate!()")]
+#[post(
+    "/api/final-fantasy/free-company",
+    wrap = "authenticate!()",
+    wrap = "csrf!()"
+)]
 pub async fn create_free_company(
     body: Option<web::Json<FreeCompany>>,
     authentication: Authentication,
@@ -50,7 +55,8 @@ pub async fn create_free_company(
 
 #[put(
     "/api/final-fantasy/free-company/{free_company_id}",
-    wrap = "authenticate!()"
+    wrap = "authenticate!()",
+    wrap = "csrf!()"
 )]
 pub async fn update_free_company(
     body: Option<web::Json<FreeCompany>>,
@@ -73,7 +79,8 @@ pub async fn update_free_company(
 
 #[delete(
     "/api/final-fantasy/free-company/{free_company_id}",
-    wrap = "authenticate!()"
+    wrap = "authenticate!()",
+    wrap = "csrf!()"
 )]
 pub async fn delete_free_company(
     path: Option<path::FreeCompanyPath>,
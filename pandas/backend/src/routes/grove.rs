@@ -1,13 +1,16 @@
-use actix_web::{delete, get, put};
+use actix_web::web::Bytes;
+use actix_web::{delete, get, put, web, Responder};
 
 use bamboo_common::backend::dbal;
 use bamboo_common::backend::response::*;
-use bamboo_common::backend::services::DbConnection;
-use bamboo_common::core::entities::Grove;
+use bamboo_common::backend::services::{minio_service, DbConnection, EnvService, MinioService};
+use bamboo_common::core::entities::{AuditAction, Grove, GrovePermission, GroveSettings};
 use bamboo_common::core::error::*;
 
-use crate::middleware::authenticate_user::authenticate;
-use crate::middleware::check_mod::is_mod;
+use crate::middleware::authenticate_user::{authenticate, Authentication};
+use crate::middleware::check_csrf::csrf;
+use crate::middleware::check_mod::moderator;
+use crate::middleware::check_permission::permission;
 use crate::middleware::identify_grove::{grove, CurrentGrove};
 
 #[get("/api/grove", wrap = "authenticate!()", wrap = "grove!()")]
@@ -15,47 +18,193 @@ pub async fn get_grove(current_grove: CurrentGrove) -> BambooApiResult<Grove> {
     Ok(ok!(current_grove.grove.clone()))
 }
 
+#[get("/api/grove/settings", wrap = "authenticate!()", wrap = "grove!()")]
+pub async fn get_grove_settings(
+    current_grove: CurrentGrove,
+    db: DbConnection,
+) -> BambooApiResult<GroveSettings> {
+    dbal::get_grove_settings(current_grove.grove.id, &db)
+        .await
+        .map(|data| ok!(data))
+}
+
+#[put(
+    "/api/grove/settings",
+    wrap = "authenticate!()",
+    wrap = "csrf!()",
+    wrap = "grove!()",
+    wrap = "permission!(GrovePermission::ManageGrove)"
+)]
+pub async fn update_grove_settings(
+    body: Option<web::Json<GroveSettings>>,
+    current_grove: CurrentGrove,
+    db: DbConnection,
+) -> BambooApiResponseResult {
+    let body = check_missing_fields!(body, "grove_settings")?;
+
+    dbal::update_grove_settings(current_grove.grove.id, body.into_inner(), &db)
+        .await
+        .map(|_| no_content!())
+}
+
+#[get("/api/grove/logo", wrap = "authenticate!()", wrap = "grove!()")]
+pub async fn get_grove_logo(current_grove: CurrentGrove, minio: MinioService) -> impl Responder {
+    if let Ok(logo) = minio.get_grove_logo(current_grove.grove.id).await {
+        actix_web::HttpResponse::Ok()
+            .content_type(
+                minio_service::get_image_content_type(&logo).unwrap_or("application/octet-stream"),
+            )
+            .insert_header(("X-Content-Type-Options", "nosniff"))
+            .body(logo)
+    } else {
+        actix_web::HttpResponse::NotFound().finish()
+    }
+}
+
+#[put(
+    "/api/grove/logo",
+    wrap = "authenticate!()",
+    wrap = "csrf!()",
+    wrap = "grove!()",
+    wrap = "permission!(GrovePermission::ManageGrove)"
+)]
+pub async fn upload_grove_logo(
+    current_grove: CurrentGrove,
+    minio: MinioService,
+    db: DbConnection,
+    body: Bytes,
+) -> BambooApiResponseResult {
+    minio
+        .upload_grove_logo(current_grove.grove.id, &body)
+        .await?;
+
+    dbal::set_grove_has_logo(current_grove.grove.id, true, &db)
+        .await
+        .map(|_| no_content!())
+}
+
 #[delete(
-    "/api/grove/enabled",
+    "/api/grove/logo",
     wrap = "authenticate!()",
+    wrap = "csrf!()",
     wrap = "grove!()",
-    wrap = "is_mod!()"
+    wrap = "permission!(GrovePermission::ManageGrove)"
 )]
-pub async fn disable_grove(
+pub async fn delete_grove_logo(
     current_grove: CurrentGrove,
+    minio: MinioService,
     db: DbConnection,
 ) -> BambooApiResponseResult {
-    dbal::disable_grove(current_grove.grove.id, &db)
+    minio.delete_grove_logo(current_grove.grove.id).await?;
+
+    dbal::set_grove_has_logo(current_grove.grove.id, false, &db)
         .await
         .map(|_| no_content!())
 }
 
+#[delete(
+    "/api/grove/enabled",
+    wrap = "authenticate!()",
+    wrap = "csrf!()",
+    wrap = "grove!()",
+    wrap = "permission!(GrovePermission::ManageGrove)"
+)]
+pub async fn disable_grove(
+    current_grove: CurrentGrove,
+    authentication: Authentication,
+    db: DbConnection,
+) -> BambooApiResponseResult {
+    dbal::disable_grove(current_grove.grove.id, &db).await?;
+    let _ = dbal::create_audit_log_entry(
+        current_grove.grove.id,
+        &authentication.user,
+        AuditAction::GroveDisabled,
+        None,
+        &db,
+    )
+    .await;
+
+    Ok(no_content!())
+}
+
 #[put(
     "/api/grove/enabled",
     wrap = "authenticate!()",
+    wrap = "csrf!()",
     wrap = "grove!()",
-    wrap = "is_mod!()"
+    wrap = "permission!(GrovePermission::ManageGrove)"
 )]
 pub async fn enable_grove(
     current_grove: CurrentGrove,
+    authentication: Authentication,
     db: DbConnection,
 ) -> BambooApiResponseResult {
-    dbal::enable_grove(current_grove.grove.id, &db)
-        .await
-        .map(|_| no_content!())
+    dbal::enable_grove(current_grove.grove.id, &db).await?;
+    let _ = dbal::create_audit_log_entry(
+        current_grove.grove.id,
+        &authentication.user,
+        AuditAction::GroveEnabled,
+        None,
+        &db,
+    )
+    .await;
+
+    Ok(no_content!())
 }
 
+/// Only mods can still enter a deleted grove, so only they can delete it and restore it again.
 #[delete(
     "/api/grove",
     wrap = "authenticate!()",
+    wrap = "csrf!()",
     wrap = "grove!()",
-    wrap = "is_mod!()"
+    wrap = "moderator!()"
 )]
 pub async fn delete_grove(
     current_grove: CurrentGrove,
+    authentication: Authentication,
+    env_service: EnvService,
     db: DbConnection,
 ) -> BambooApiResponseResult {
-    dbal::delete_grove(current_grove.grove.id, &db)
-        .await
-        .map(|_| no_content!())
+    dbal::schedule_grove_deletion(
+        current_grove.grove.id,
+        env_service.get_grove_deletion_grace_period_days(),
+        &db,
+    )
+    .await?;
+    let _ = dbal::create_audit_log_entry(
+        current_grove.grove.id,
+        &authentication.user,
+        AuditAction::GroveDeleted,
+        None,
+        &db,
+    )
+    .await;
+
+    Ok(no_content!())
+}
+
+#[delete(
+    "/api/grove/deletion",
+    wrap = "authenticate!()",
+    wrap = "csrf!()",
+    wrap = "grove!()",
+    wrap = "moderator!()"
+)]
+pub async fn restore_grove(
+    current_grove: CurrentGrove,
+    authentication: Authentication,
+    db: DbConnection,
+) -> BambooApiResponseResult {
+    dbal::restore_grove(current_grove.grove.id, &db).await?;
+    let _ = dbal::create_audit_log_entry(
+        current_grove.grove.id,
+        &authentication.user,
+        AuditAction::GroveRestored,
+        None,
+        &db,
+    )
+    .await;
+
+    Ok(no_content!())
 }
@@ -0,0 +1,117 @@
+use actix_web::{delete, get, post, web};
+
+use bamboo_common::backend::response::*;
+use bamboo_common::backend::services::{DbConnection, EnvService};
+use bamboo_common::backend::{dbal, mailing};
+use bamboo_common::core::entities::*;
+use bamboo_common::core::error::*;
+
+use crate::middleware::authenticate_user::{authenticate, Authentication};
+use crate::middleware::check_csrf::csrf;
+use crate::middleware::check_permission::permission;
+use crate::middleware::identify_grove::{grove, CurrentGrove};
+use crate::path;
+
+pub(crate) fn build_invitation_link(token: String, env_service: &EnvService) -> String {
+    format!(
+        "{}/invitation?token={token}",
+        env_service
+            .get_env("PANDAS_BASE_URL", "https://pandas.bambushain.app")
+            .trim_end_matches('/')
+    )
+}
+
+#[get(
+    "/api/grove/invitation",
+    wrap = "authenticate!()",
+    wrap = "grove!()",
+    wrap = "permission!(GrovePermission::ManageInvitations)"
+)]
+pub async fn get_grove_invitations(
+    current_grove: CurrentGrove,
+    db: DbConnection,
+) -> BambooApiResponseResult {
+    dbal::get_grove_invitations(current_grove.grove.id, &db)
+        .await
+        .map(|data| list!(data))
+}
+
+#[post(
+    "/api/grove/invitation",
+    wrap = "authenticate!()",
+    wrap = "csrf!()",
+    wrap = "grove!()",
+    wrap = "permission!(GrovePermission::ManageInvitations)"
+)]
+pub async fn create_grove_invitation(
+    body: Option<web::Json<CreateGroveInvitation>>,
+    current_grove: CurrentGrove,
+    authentication: Authentication,
+    db: DbConnection,
+    env_service: EnvService,
+) -> BambooApiResult<CreatedGroveInvitation> {
+    let body = check_missing_fields!(body, "grove_invitation")?;
+
+    let (grove_invitation, token) =
+        dbal::create_grove_invitation(current_grove.grove.id, body.into_inner(), &db).await?;
+    let link = build_invitation_link(token, &env_service);
+
+    if let Some(email) = grove_invitation.email.clone() {
+        mailing::user::send_grove_invitation(
+            current_grove.grove.name.clone(),
+            authentication.user.display_name.clone(),
+            email,
+            link.clone(),
+            env_service,
+        )
+        .await?;
+    }
+
+    Ok(created!(CreatedGroveInvitation {
+        grove_invitation,
+        link,
+    }))
+}
+
+#[delete(
+    "/api/grove/invitation/{invitation_id}",
+    wrap = "authenticate!()",
+    wrap = "csrf!()",
+    wrap = "grove!()",
+    wrap = "permission!(GrovePermission::ManageInvitations)"
+)]
+pub async fn delete_grove_invitation(
+    path: Option<path::GroveInvitationPath>,
+    current_grove: CurrentGrove,
+    db: DbConnection,
+) -> BambooApiResponseResult {
+    let path = check_invalid_path!(path, "grove_invitation")?;
+
+    dbal::delete_grove_invitation(current_grove.grove.id, path.invitation_id, &db)
+        .await
+        .map(|_| no_content!())
+}
+
+#[post("/api/invitation")]
+pub async fn get_grove_invitation_details(
+    body: Option<web::Json<GroveInvitationToken>>,
+    db: DbConnection,
+) -> BambooApiResult<GroveInvitationDetails> {
+    let body = check_missing_fields!(body, "grove_invitation")?;
+
+    dbal::get_grove_invitation_details(body.token.clone(), &db)
+        .await
+        .map(|data| ok!(data))
+}
+
+#[post("/api/invitation/accept")]
+pub async fn accept_grove_invitation(
+    body: Option<web::Json<AcceptGroveInvitation>>,
+    db: DbConnection,
+) -> BambooApiResponseResult {
+    let body = check_missing_fields!(body, "grove_invitation")?;
+
+    dbal::accept_grove_invitation(body.into_inner(), &db)
+        .await
+        .map(|_| no_content!())
+}
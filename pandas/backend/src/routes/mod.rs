@@ -4,17 +4,21 @@ use bamboo_common::backend::services::{EnvService, EnvironmentService};
 
 use crate::middleware::authenticate_user::authenticate;
 
+mod audit_log;
 mod authentication;
 mod character;
 mod character_housing;
 mod crafter;
 mod custom_field;
 mod event;
+mod export;
 mod fighter;
 mod free_company;
 mod grove;
+mod invitation;
 mod licenses;
 mod my;
+mod role;
 mod sse;
 mod support;
 mod user;
@@ -27,7 +31,16 @@ pub fn configure_routes(cfg: &mut web::ServiceConfig) {
 
     cfg.app_data(environment_service)
         .service(authentication::login)
+        .service(authentication::start_passkey_login)
+        .service(authentication::passkey_login)
+        .service(authentication::get_oidc_login_config)
+        .service(authentication::start_oidc_login)
+        .service(authentication::oidc_login_callback)
         .service(authentication::forgot_password)
+        .service(authentication::reset_password)
+        .service(authentication::confirm_email_change)
+        .service(invitation::get_grove_invitation_details)
+        .service(invitation::accept_grove_invitation)
         .service(authentication::logout)
         .route(
             "/api/login",
@@ -42,20 +55,36 @@ pub fn configure_routes(cfg: &mut web::ServiceConfig) {
         .service(user::update_user_profile)
         .service(user::add_mod_user)
         .service(user::remove_mod_user)
+        .service(user::change_user_role)
         .service(user::change_password)
         .service(user::disable_totp)
+        .service(user::get_login_lockouts)
+        .service(user::clear_login_lockout)
         .service(user::get_profile_picture)
         .service(event::get_events)
         .service(event::create_event)
         .service(event::update_event)
         .service(event::delete_event)
         .service(my::get_profile)
+        .service(my::get_permissions)
         .service(my::update_profile)
         .service(my::change_password)
         .service(my::enable_totp)
         .service(my::disable_totp)
         .service(my::validate_totp)
+        .service(my::get_totp_recovery_code_count)
+        .service(my::regenerate_totp_recovery_codes)
+        .service(my::get_passkeys)
+        .service(my::start_passkey_registration)
+        .service(my::finish_passkey_registration)
+        .service(my::delete_passkey)
+        .service(my::get_personal_access_tokens)
+        .service(my::create_personal_access_token)
+        .service(my::delete_personal_access_token)
+        .service(my::get_my_groves)
+        .service(my::change_active_grove)
         .service(my::leave)
+        .service(my::transfer_mod_rights_and_leave)
         .service(my::upload_profile_picture)
         .service(custom_field::get_custom_fields)
         .service(custom_field::create_custom_field)
@@ -96,9 +125,26 @@ pub fn configure_routes(cfg: &mut web::ServiceConfig) {
         .service(support::report_glitchtip_error)
         .service(licenses::get_licenses)
         .service(grove::get_grove)
+        .service(grove::get_grove_settings)
+        .service(grove::update_grove_settings)
+        .service(grove::get_grove_logo)
+        .service(grove::upload_grove_logo)
+        .service(grove::delete_grove_logo)
         .service(grove::disable_grove)
         .service(grove::enable_grove)
         .service(grove::delete_grove)
+        .service(grove::restore_grove)
+        .service(invitation::get_grove_invitations)
+        .service(invitation::create_grove_invitation)
+        .service(invitation::delete_grove_invitation)
+        .service(role::get_grove_roles)
+        .service(role::create_grove_role)
+        .service(role::update_grove_role)
+        .service(role::delete_grove_role)
+        .service(audit_log::get_audit_log)
+        .service(export::get_grove_exports)
+        .service(export::start_grove_export)
+        .service(export::download_grove_export)
         .service(sse::event_sse_client)
         .service(
             actix_web_lab::web::spa()
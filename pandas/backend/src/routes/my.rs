@@ -3,13 +3,17 @@ use actix_web::{delete, get, post, put, web};
 
 use bamboo_common::backend::dbal;
 use bamboo_common::backend::response::*;
-use bamboo_common::backend::services::{DbConnection, MinioService};
+use bamboo_common::backend::services::{DbConnection, EnvService, MinioService, WebauthnService};
 use bamboo_common::core::entities::*;
 use bamboo_common::core::error::*;
 
+use crate::cookie;
+use crate::email_change;
 use crate::middleware::authenticate_user::{authenticate, Authentication};
+use crate::middleware::check_csrf::csrf;
+use crate::path;
 
-#[put("/api/my/password", wrap = "authenticate!()")]
+#[put("/api/my/password", wrap = "authenticate!()", wrap = "csrf!()")]
 pub async fn change_password(
     body: Option<web::Json<ChangeMyPassword>>,
     authentication: Authentication,
@@ -28,26 +32,30 @@ pub async fn change_password(
     .map_err(|err| err.into())
 }
 
-#[put("/api/my/profile", wrap = "authenticate!()")]
+#[put("/api/my/profile", wrap = "authenticate!()", wrap = "csrf!()")]
 pub async fn update_profile(
     body: Option<web::Json<UpdateProfile>>,
     authentication: Authentication,
     db: DbConnection,
+    env_service: EnvService,
 ) -> BambooApiResponseResult {
     let body = check_missing_fields!(body, "user")?;
 
+    email_change::check_email_change(&authentication.user, body.email.as_str(), &db).await?;
     dbal::update_my_profile(
         authentication.user.id,
-        body.email.clone(),
         body.display_name.clone(),
         body.discord_name.clone(),
         &db,
     )
-    .await
-    .map(|_| no_content!())
+    .await?;
+
+    email_change::request_email_change(&authentication.user, body.email.clone(), &db, env_service)
+        .await
+        .map(|_| no_content!())
 }
 
-#[post("/api/my/totp", wrap = "authenticate!()")]
+#[post("/api/my/totp", wrap = "authenticate!()", wrap = "csrf!()")]
 pub async fn enable_totp(
     authentication: Authentication,
     db: DbConnection,
@@ -75,12 +83,12 @@ pub async fn enable_totp(
         })?
 }
 
-#[put("/api/my/totp/validate", wrap = "authenticate!()")]
+#[put("/api/my/totp/validate", wrap = "authenticate!()", wrap = "csrf!()")]
 pub async fn validate_totp(
     body: Option<web::Json<ValidateTotp>>,
     authentication: Authentication,
     db: DbConnection,
-) -> BambooApiResponseResult {
+) -> BambooApiResult<TotpRecoveryCodes> {
     if authentication.user.totp_validated.unwrap_or(false) {
         Err(BambooError::invalid_data("user", "Already validated"))
     } else {
@@ -92,18 +100,44 @@ pub async fn validate_totp(
             body.code.clone(),
             &db,
         )
+        .await?
+        .map(|data| ok!(data))
+        .ok_or(BambooError::insufficient_rights(
+            "user",
+            "The code is invalid",
+        ))
+    }
+}
+
+#[get("/api/my/totp/recovery-codes", wrap = "authenticate!()")]
+pub async fn get_totp_recovery_code_count(
+    authentication: Authentication,
+    db: DbConnection,
+) -> BambooApiResult<TotpRecoveryCodeCount> {
+    dbal::count_totp_recovery_codes(authentication.user.id, &db)
         .await
-        .map(|data| {
-            if data {
-                Ok(no_content!())
-            } else {
-                Err(BambooError::insufficient_rights(
-                    "user",
-                    "The code is invalid",
-                ))
-            }
-        })?
+        .map(|data| ok!(data))
+}
+
+#[post(
+    "/api/my/totp/recovery-codes",
+    wrap = "authenticate!()",
+    wrap = "csrf!()"
+)]
+pub async fn regenerate_totp_recovery_codes(
+    authentication: Authentication,
+    db: DbConnection,
+) -> BambooApiResult<TotpRecoveryCodes> {
+    if !authentication.user.totp_validated.unwrap_or(false) {
+        return Err(BambooError::invalid_data(
+            "user",
+            "App two factor is not enabled",
+        ));
     }
+
+    dbal::create_totp_recovery_codes(authentication.user.id, &db)
+        .await
+        .map(|data| ok!(data))
 }
 
 #[get("/api/my/profile", wrap = "authenticate!()")]
@@ -111,7 +145,17 @@ pub async fn get_profile(authentication: Authentication) -> BambooApiResult<WebU
     Ok(ok!(authentication.user.clone().into()))
 }
 
-#[delete("/api/my/totp", wrap = "authenticate!()")]
+#[get("/api/my/permissions", wrap = "authenticate!()")]
+pub async fn get_permissions(
+    authentication: Authentication,
+    db: DbConnection,
+) -> BambooApiResponseResult {
+    dbal::get_user_permissions(&authentication.user, &db)
+        .await
+        .map(|permissions| list!(permissions))
+}
+
+#[delete("/api/my/totp", wrap = "authenticate!()", wrap = "csrf!()")]
 pub async fn disable_totp(
     authentication: Authentication,
     db: DbConnection,
@@ -121,14 +165,174 @@ pub async fn disable_totp(
         .map(|_| no_content!())
 }
 
-#[delete("/api/my", wrap = "authenticate!()")]
+#[get("/api/my/passkey", wrap = "authenticate!()")]
+pub async fn get_passkeys(
+    authentication: Authentication,
+    db: DbConnection,
+) -> BambooApiResponseResult {
+    dbal::get_passkeys(authentication.user.id, &db)
+        .await
+        .map(|data| list!(data))
+}
+
+#[post(
+    "/api/my/passkey/challenge",
+    wrap = "authenticate!()",
+    wrap = "csrf!()"
+)]
+pub async fn start_passkey_registration(
+    authentication: Authentication,
+    webauthn: WebauthnService,
+    db: DbConnection,
+) -> BambooApiResult<PasskeyRegistrationChallenge> {
+    dbal::start_passkey_registration(authentication.user.id, &webauthn, &db)
+        .await
+        .map(|data| ok!(data))
+}
+
+#[post("/api/my/passkey", wrap = "authenticate!()", wrap = "csrf!()")]
+pub async fn finish_passkey_registration(
+    body: Option<web::Json<FinishPasskeyRegistration>>,
+    authentication: Authentication,
+    webauthn: WebauthnService,
+    db: DbConnection,
+) -> BambooApiResult<Passkey> {
+    let body = check_missing_fields!(body, "passkey")?;
+
+    dbal::finish_passkey_registration(
+        authentication.user.id,
+        body.challenge_id.clone(),
+        body.name.clone(),
+        body.credential.clone(),
+        &webauthn,
+        &db,
+    )
+    .await
+    .map(|data| created!(data))
+}
+
+#[delete(
+    "/api/my/passkey/{passkey_id}",
+    wrap = "authenticate!()",
+    wrap = "csrf!()"
+)]
+pub async fn delete_passkey(
+    path: Option<path::PasskeyPath>,
+    authentication: Authentication,
+    db: DbConnection,
+) -> BambooApiResponseResult {
+    let path = check_invalid_path!(path, "passkey")?;
+
+    dbal::delete_passkey(authentication.user.id, path.passkey_id, &db)
+        .await
+        .map(|_| no_content!())
+}
+
+#[get("/api/my/tokens", wrap = "authenticate!()")]
+pub async fn get_personal_access_tokens(
+    authentication: Authentication,
+    db: DbConnection,
+) -> BambooApiResponseResult {
+    dbal::get_personal_access_tokens(authentication.user.id, &db)
+        .await
+        .map(|data| list!(data))
+}
+
+#[post("/api/my/tokens", wrap = "authenticate!()", wrap = "csrf!()")]
+pub async fn create_personal_access_token(
+    body: Option<web::Json<CreatePersonalAccessToken>>,
+    authentication: Authentication,
+    db: DbConnection,
+) -> BambooApiResult<CreatedPersonalAccessToken> {
+    let body = check_missing_fields!(body, "personal_access_token")?;
+
+    dbal::create_personal_access_token(authentication.user.id, body.into_inner(), &db)
+        .await
+        .map(|data| created!(data))
+}
+
+#[delete(
+    "/api/my/tokens/{token_id}",
+    wrap = "authenticate!()",
+    wrap = "csrf!()"
+)]
+pub async fn delete_personal_access_token(
+    path: Option<path::PersonalAccessTokenPath>,
+    authentication: Authentication,
+    db: DbConnection,
+) -> BambooApiResponseResult {
+    let path = check_invalid_path!(path, "personal_access_token")?;
+
+    dbal::delete_personal_access_token(authentication.user.id, path.token_id, &db)
+        .await
+        .map(|_| no_content!())
+}
+
+#[get("/api/my/groves", wrap = "authenticate!()")]
+pub async fn get_my_groves(
+    authentication: Authentication,
+    db: DbConnection,
+) -> BambooApiResponseResult {
+    dbal::get_groves_by_user_id(authentication.user.id, &db)
+        .await
+        .map(|groves| list!(groves))
+}
+
+#[put("/api/my/grove", wrap = "authenticate!()", wrap = "csrf!()")]
+pub async fn change_active_grove(
+    body: Option<web::Json<ChangeActiveGrove>>,
+    authentication: Authentication,
+    db: DbConnection,
+    env_service: EnvService,
+) -> BambooApiResponseResult {
+    let body = check_missing_fields!(body, "grove")?;
+
+    dbal::change_default_grove(authentication.user.id, body.grove_id, &db)
+        .await
+        .map(|_| {
+            let mut response = no_content!();
+            let _ = response.add_cookie(&cookie::build_grove_cookie(body.grove_id, &env_service));
+
+            response
+        })
+}
+
+#[delete("/api/my", wrap = "authenticate!()", wrap = "csrf!()")]
 pub async fn leave(authentication: Authentication, db: DbConnection) -> BambooApiResponseResult {
     dbal::delete_user(authentication.user.grove_id, authentication.user.id, &db)
         .await
         .map(|_| no_content!())
 }
 
-#[put("/api/my/picture", wrap = "authenticate!()")]
+#[post(
+    "/api/my/transfer-and-leave",
+    wrap = "authenticate!()",
+    wrap = "csrf!()"
+)]
+pub async fn transfer_mod_rights_and_leave(
+    body: Option<web::Json<TransferModRights>>,
+    authentication: Authentication,
+    db: DbConnection,
+) -> BambooApiResponseResult {
+    let body = check_missing_fields!(body, "grove_membership")?;
+
+    let grove_id = authentication.user.grove_id;
+    let successor = dbal::get_user(grove_id, body.successor_id, &db).await?;
+    dbal::transfer_mod_rights_and_leave(grove_id, authentication.user.id, successor.id, &db)
+        .await?;
+    let _ = dbal::create_audit_log_entry(
+        grove_id,
+        &authentication.user,
+        AuditAction::ModRightsTransferred,
+        Some(&successor),
+        &db,
+    )
+    .await;
+
+    Ok(no_content!())
+}
+
+#[put("/api/my/picture", wrap = "authenticate!()", wrap = "csrf!()")]
 pub async fn upload_profile_picture(
     authentication: Authentication,
     minio: MinioService,
@@ -0,0 +1,82 @@
+use actix_web::{delete, get, post, put, web};
+
+use bamboo_common::backend::dbal;
+use bamboo_common::backend::response::*;
+use bamboo_common::backend::services::DbConnection;
+use bamboo_common::core::entities::*;
+use bamboo_common::core::error::*;
+
+use crate::middleware::authenticate_user::authenticate;
+use crate::middleware::check_csrf::csrf;
+use crate::middleware::check_permission::permission;
+use crate::middleware::identify_grove::{grove, CurrentGrove};
+use crate::path;
+
+#[get("/api/grove/role", wrap = "authenticate!()", wrap = "grove!()")]
+pub async fn get_grove_roles(
+    current_grove: CurrentGrove,
+    db: DbConnection,
+) -> BambooApiResponseResult {
+    dbal::get_grove_roles(current_grove.grove.id, &db)
+        .await
+        .map(|data| list!(data))
+}
+
+#[post(
+    "/api/grove/role",
+    wrap = "authenticate!()",
+    wrap = "csrf!()",
+    wrap = "grove!()",
+    wrap = "permission!(GrovePermission::ManageRoles)"
+)]
+pub async fn create_grove_role(
+    body: Option<web::Json<GroveRole>>,
+    current_grove: CurrentGrove,
+    db: DbConnection,
+) -> BambooApiResult<GroveRole> {
+    let body = check_missing_fields!(body, "grove_role")?;
+
+    dbal::create_grove_role(current_grove.grove.id, body.into_inner(), &db)
+        .await
+        .map(|data| created!(data))
+}
+
+#[put(
+    "/api/grove/role/{role_id}",
+    wrap = "authenticate!()",
+    wrap = "csrf!()",
+    wrap = "grove!()",
+    wrap = "permission!(GrovePermission::ManageRoles)"
+)]
+pub async fn update_grove_role(
+    path: Option<path::GroveRolePath>,
+    body: Option<web::Json<GroveRole>>,
+    current_grove: CurrentGrove,
+    db: DbConnection,
+) -> BambooApiResponseResult {
+    let path = check_invalid_path!(path, "grove_role")?;
+    let body = check_missing_fields!(body, "grove_role")?;
+
+    dbal::update_grove_role(current_grove.grove.id, path.role_id, body.into_inner(), &db)
+        .await
+        .map(|_| no_content!())
+}
+
+#[delete(
+    "/api/grove/role/{role_id}",
+    wrap = "authenticate!()",
+    wrap = "csrf!()",
+    wrap = "grove!()",
+    wrap = "permission!(GrovePermission::ManageRoles)"
+)]
+pub async fn delete_grove_role(
+    path: Option<path::GroveRolePath>,
+    current_grove: CurrentGrove,
+    db: DbConnection,
+) -> BambooApiResponseResult {
+    let path = check_invalid_path!(path, "grove_role")?;
+
+    dbal::delete_grove_role(current_grove.grove.id, path.role_id, &db)
+        .await
+        .map(|_| no_content!())
+}
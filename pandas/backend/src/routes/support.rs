@@ -7,8 +7,9 @@ use bamboo_common::core::entities::{GlitchTipErrorRequest, SupportRequest};
 use bamboo_common::core::error::*;
 
 use crate::middleware::authenticate_user::{authenticate, Authentication};
+use crate::middleware::check_csrf::csrf;
 
-#[post("/api/support", wrap = "authenticate!()")]
+#[post("/api/support", wrap = "authenticate!()", wrap = "csrf!()")]
 pub async fn send_support_request(
     authentication: Authentication,
     env_service: EnvService,
@@ -25,7 +26,7 @@ pub async fn send_support_request(
     .map(|_| no_content!())
 }
 
-#[post("/api/glitchtip", wrap = "authenticate!()")]
+#[post("/api/glitchtip", wrap = "authenticate!()", wrap = "csrf!()")]
 pub async fn report_glitchtip_error(
     _body: Option<web::Json<GlitchTipErrorRequest>>,
 ) -> HttpResponse {
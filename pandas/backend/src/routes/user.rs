@@ -2,15 +2,51 @@ use actix_web::{delete, get, post, put, web, Responder};
 
 use bamboo_common::backend::response::*;
 use bamboo_common::backend::services::{DbConnection, EnvService, MinioService};
-use bamboo_common::backend::utils::get_random_password;
 use bamboo_common::backend::{dbal, mailing};
 use bamboo_common::core::entities::*;
 use bamboo_common::core::error::*;
 
+use crate::email_change;
 use crate::middleware::authenticate_user::{authenticate, Authentication};
-use crate::middleware::check_mod::is_mod;
+use crate::middleware::check_csrf::csrf;
+use crate::middleware::check_mod::moderator;
+use crate::middleware::check_permission::permission;
 use crate::middleware::identify_grove::{grove, CurrentGrove};
 use crate::path;
+use crate::routes::invitation;
+
+async fn check_can_manage_user(
+    actor: &User,
+    target: &User,
+    db: &DbConnection,
+) -> BambooErrorResult {
+    if dbal::can_manage_user(actor, target, db).await? {
+        Ok(())
+    } else {
+        Err(BambooError::insufficient_rights(
+            "user",
+            "Only mods can manage mods or pandas with permissions you don't have",
+        ))
+    }
+}
+
+/// Password, profile and second factor belong to the account, which can be shared with other groves.
+async fn check_can_change_account(
+    grove_id: i32,
+    actor: &User,
+    target: &User,
+    db: &DbConnection,
+) -> BambooErrorResult {
+    check_can_manage_user(actor, target, db).await?;
+    if dbal::is_only_member_of_grove(grove_id, target.id, db).await? {
+        Ok(())
+    } else {
+        Err(BambooError::insufficient_rights(
+            "user",
+            "The panda is a member of other groves, only they can change their account",
+        ))
+    }
+}
 
 #[get("/api/user", wrap = "authenticate!()", wrap = "grove!()")]
 pub async fn get_users(current_grove: CurrentGrove, db: DbConnection) -> BambooApiResponseResult {
@@ -40,7 +76,8 @@ pub async fn get_user(
 #[post(
     "/api/user",
     wrap = "authenticate!()",
-    wrap = "is_mod!()",
+    wrap = "csrf!()",
+    wrap = "permission!(GrovePermission::ManageUsers)",
     wrap = "grove!()"
 )]
 pub async fn create_user(
@@ -49,16 +86,41 @@ pub async fn create_user(
     authentication: Authentication,
     env_service: EnvService,
     db: DbConnection,
-) -> BambooApiResult<WebUser> {
-    let body = check_missing_fields!(body, "user")?;
-    let new_password = get_random_password();
-    let user = dbal::create_user(
+) -> BambooApiResponseResult {
+    let mut body = check_missing_fields!(body, "user")?.into_inner();
+    if !dbal::has_permission(&authentication.user, GrovePermission::ManageRoles, &db).await? {
+        body.is_mod = false;
+        body.role_id = None;
+    }
+
+    // Pandas with an account have to accept the invitation before they join the grove
+    if let Some((user, token)) =
+        dbal::invite_existing_user(current_grove.grove.id, body.email.clone(), &db).await?
+    {
+        mailing::user::send_grove_join_invitation(
+            user.display_name.clone(),
+            authentication.user.display_name.clone(),
+            current_grove.grove.name.clone(),
+            user.email.clone(),
+            invitation::build_invitation_link(token, &env_service),
+            env_service,
+        )
+        .await?;
+
+        return Ok(no_content!());
+    }
+
+    let new_password =
+        dbal::generate_password(&[body.email.clone(), body.display_name.clone()]).await?;
+    let user = dbal::create_user(current_grove.grove.id, body, new_password.clone(), &db).await?;
+    let _ = dbal::create_audit_log_entry(
         current_grove.grove.id,
-        body.into_inner(),
-        new_password.clone(),
+        &authentication.user,
+        AuditAction::UserCreated,
+        Some(&user),
         &db,
     )
-    .await?;
+    .await;
     mailing::user::send_user_created(
         user.display_name.clone(),
         authentication.user.display_name.clone(),
@@ -68,13 +130,14 @@ pub async fn create_user(
     )
     .await?;
 
-    Ok(created!(user.into()))
+    Ok(actix_web::HttpResponse::Created().json(WebUser::from(user)))
 }
 
 #[delete(
     "/api/user/{user_id}",
     wrap = "authenticate!()",
-    wrap = "is_mod!()",
+    wrap = "csrf!()",
+    wrap = "permission!(GrovePermission::ManageUsers)",
     wrap = "grove!()"
 )]
 pub async fn delete_user(
@@ -91,15 +154,26 @@ pub async fn delete_user(
         ));
     }
 
-    dbal::delete_user(current_grove.grove.id, path.user_id, &db)
-        .await
-        .map(|_| no_content!())
+    let user = dbal::get_user(current_grove.grove.id, path.user_id, &db).await?;
+    check_can_manage_user(&authentication.user, &user, &db).await?;
+    dbal::delete_user(current_grove.grove.id, path.user_id, &db).await?;
+    let _ = dbal::create_audit_log_entry(
+        current_grove.grove.id,
+        &authentication.user,
+        AuditAction::UserDeleted,
+        Some(&user),
+        &db,
+    )
+    .await;
+
+    Ok(no_content!())
 }
 
 #[put(
     "/api/user/{user_id}/mod",
     wrap = "authenticate!()",
-    wrap = "is_mod!()",
+    wrap = "csrf!()",
+    wrap = "moderator!()",
     wrap = "grove!()"
 )]
 pub async fn add_mod_user(
@@ -116,15 +190,26 @@ pub async fn add_mod_user(
         ));
     }
 
-    dbal::change_mod_status(current_grove.grove.id, path.user_id, true, &db)
-        .await
-        .map(|_| no_content!())
+    let user = dbal::get_user(current_grove.grove.id, path.user_id, &db).await?;
+    check_can_manage_user(&authentication.user, &user, &db).await?;
+    dbal::change_mod_status(current_grove.grove.id, path.user_id, true, &db).await?;
+    let _ = dbal::create_audit_log_entry(
+        current_grove.grove.id,
+        &authentication.user,
+        AuditAction::ModStatusGranted,
+        Some(&user),
+        &db,
+    )
+    .await;
+
+    Ok(no_content!())
 }
 
 #[delete(
     "/api/user/{user_id}/mod",
     wrap = "authenticate!()",
-    wrap = "is_mod!()",
+    wrap = "csrf!()",
+    wrap = "moderator!()",
     wrap = "grove!()"
 )]
 pub async fn remove_mod_user(
@@ -141,15 +226,77 @@ pub async fn remove_mod_user(
         ));
     }
 
-    dbal::change_mod_status(current_grove.grove.id, path.user_id, false, &db)
-        .await
-        .map(|_| no_content!())
+    let user = dbal::get_user(current_grove.grove.id, path.user_id, &db).await?;
+    check_can_manage_user(&authentication.user, &user, &db).await?;
+    dbal::change_mod_status(current_grove.grove.id, path.user_id, false, &db).await?;
+    let _ = dbal::create_audit_log_entry(
+        current_grove.grove.id,
+        &authentication.user,
+        AuditAction::ModStatusRevoked,
+        Some(&user),
+        &db,
+    )
+    .await;
+
+    Ok(no_content!())
+}
+
+#[put(
+    "/api/user/{user_id}/role",
+    wrap = "authenticate!()",
+    wrap = "csrf!()",
+    wrap = "permission!(GrovePermission::ManageRoles)",
+    wrap = "grove!()"
+)]
+pub async fn change_user_role(
+    path: Option<path::UserPath>,
+    body: Option<web::Json<ChangeUserRole>>,
+    current_grove: CurrentGrove,
+    authentication: Authentication,
+    db: DbConnection,
+) -> BambooApiResponseResult {
+    let path = check_invalid_path!(path, "user")?;
+    let body = check_missing_fields!(body, "user")?;
+    if path.user_id == authentication.user.id {
+        return Err(BambooError::validation(
+            "user",
+            "You cannot change your own role",
+        ));
+    }
+
+    let user = dbal::get_user(current_grove.grove.id, path.user_id, &db).await?;
+    check_can_manage_user(&authentication.user, &user, &db).await?;
+    if !dbal::can_assign_role(
+        &authentication.user,
+        current_grove.grove.id,
+        body.role_id,
+        &db,
+    )
+    .await?
+    {
+        return Err(BambooError::insufficient_rights(
+            "user",
+            "Only mods can give roles with permissions you don't have",
+        ));
+    }
+    dbal::change_user_role(current_grove.grove.id, path.user_id, body.role_id, &db).await?;
+    let _ = dbal::create_audit_log_entry(
+        current_grove.grove.id,
+        &authentication.user,
+        AuditAction::RoleChanged,
+        Some(&user),
+        &db,
+    )
+    .await;
+
+    Ok(no_content!())
 }
 
 #[put(
     "/api/user/{user_id}/password",
     wrap = "authenticate!()",
-    wrap = "is_mod!()",
+    wrap = "csrf!()",
+    wrap = "permission!(GrovePermission::ManageUsers)",
     wrap = "grove!()"
 )]
 pub async fn change_password(
@@ -168,7 +315,9 @@ pub async fn change_password(
     }
 
     let user = dbal::get_user(current_grove.grove.id, path.user_id, &db).await?;
-    let new_password = get_random_password();
+    check_can_change_account(current_grove.grove.id, &authentication.user, &user, &db).await?;
+    let new_password =
+        dbal::generate_password(&[user.email.clone(), user.display_name.clone()]).await?;
     dbal::change_password(
         current_grove.grove.id,
         path.user_id,
@@ -176,6 +325,14 @@ pub async fn change_password(
         &db,
     )
     .await?;
+    let _ = dbal::create_audit_log_entry(
+        current_grove.grove.id,
+        &authentication.user,
+        AuditAction::PasswordReset,
+        Some(&user),
+        &db,
+    )
+    .await;
 
     mailing::user::send_password_changed(
         user.display_name.clone(),
@@ -191,34 +348,43 @@ pub async fn change_password(
 #[put(
     "/api/user/{user_id}/profile",
     wrap = "authenticate!()",
-    wrap = "is_mod!()",
+    wrap = "csrf!()",
+    wrap = "permission!(GrovePermission::ManageUsers)",
     wrap = "grove!()"
 )]
 pub async fn update_user_profile(
     path: Option<path::UserPath>,
     body: Option<web::Json<UpdateProfile>>,
     current_grove: CurrentGrove,
+    authentication: Authentication,
     db: DbConnection,
+    env_service: EnvService,
 ) -> BambooApiResponseResult {
     let path = check_invalid_path!(path, "user")?;
     let body = check_missing_fields!(body, "user")?;
 
+    let user = dbal::get_user(current_grove.grove.id, path.user_id, &db).await?;
+    check_can_change_account(current_grove.grove.id, &authentication.user, &user, &db).await?;
+    email_change::check_email_change(&user, body.email.as_str(), &db).await?;
     dbal::update_profile(
         current_grove.grove.id,
         path.user_id,
-        body.email.clone(),
         body.display_name.clone(),
         body.discord_name.clone(),
         &db,
     )
-    .await
-    .map(|_| no_content!())
+    .await?;
+
+    email_change::request_email_change(&user, body.email.clone(), &db, env_service)
+        .await
+        .map(|_| no_content!())
 }
 
 #[delete(
     "/api/user/{user_id}/totp",
     wrap = "authenticate!()",
-    wrap = "is_mod!()",
+    wrap = "csrf!()",
+    wrap = "permission!(GrovePermission::ManageUsers)",
     wrap = "grove!()"
 )]
 pub async fn disable_totp(
@@ -235,7 +401,51 @@ pub async fn disable_totp(
         ));
     }
 
-    dbal::disable_totp(current_grove.grove.id, path.user_id, &db)
+    let user = dbal::get_user(current_grove.grove.id, path.user_id, &db).await?;
+    check_can_change_account(current_grove.grove.id, &authentication.user, &user, &db).await?;
+    dbal::disable_totp(current_grove.grove.id, path.user_id, &db).await?;
+    let _ = dbal::create_audit_log_entry(
+        current_grove.grove.id,
+        &authentication.user,
+        AuditAction::TotpDisabled,
+        Some(&user),
+        &db,
+    )
+    .await;
+
+    Ok(no_content!())
+}
+
+#[get(
+    "/api/lockout",
+    wrap = "authenticate!()",
+    wrap = "permission!(GrovePermission::ManageUsers)",
+    wrap = "grove!()"
+)]
+pub async fn get_login_lockouts(
+    current_grove: CurrentGrove,
+    db: DbConnection,
+) -> BambooApiResponseResult {
+    dbal::get_login_lockouts(current_grove.grove.id, &db)
+        .await
+        .map(|lockouts| list!(lockouts))
+}
+
+#[delete(
+    "/api/user/{user_id}/lockout",
+    wrap = "authenticate!()",
+    wrap = "csrf!()",
+    wrap = "permission!(GrovePermission::ManageUsers)",
+    wrap = "grove!()"
+)]
+pub async fn clear_login_lockout(
+    path: Option<path::UserPath>,
+    current_grove: CurrentGrove,
+    db: DbConnection,
+) -> BambooApiResponseResult {
+    let path = check_invalid_path!(path, "user")?;
+
+    dbal::clear_login_lockout(current_grove.grove.id, path.user_id, &db)
         .await
         .map(|_| no_content!())
 }
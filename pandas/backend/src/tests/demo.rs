@@ -0,0 +1,30 @@
+use std::time::Duration;
+
+use bamboo_common::backend::dbal;
+
+use super::*;
+use crate::demo::{reset_demo_account, DemoAccount};
+
+#[actix_web::test]
+#[ignore = "needs a postgres database in DATABASE_URL"]
+async fn resetting_the_demo_account_revokes_its_mod_rights() {
+    let db = connect().await;
+    let grove = create_grove(&db).await;
+    let user = create_user(grove.id, true, None, &db).await;
+    let demo_account = DemoAccount {
+        email: user.email.clone(),
+        password: "demo-password".to_string(),
+        display_name: user.display_name.clone(),
+        grove_name: grove.name.clone(),
+        read_only: true,
+        skip_two_factor: true,
+        reset_interval: Duration::from_secs(60),
+    };
+
+    reset_demo_account(&demo_account, &db).await.unwrap();
+
+    let membership = dbal::get_grove_membership(grove.id, user.id, &db)
+        .await
+        .unwrap();
+    assert!(!membership.is_mod);
+}
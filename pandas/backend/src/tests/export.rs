@@ -0,0 +1,29 @@
+use bamboo_common::backend::dbal;
+use bamboo_common::core::entities::GroveExportStatus;
+use bamboo_common::core::error::BambooErrorCode;
+
+use super::*;
+
+#[actix_web::test]
+#[ignore = "needs a postgres database in DATABASE_URL"]
+async fn finished_exports_keep_their_state() {
+    let db = connect().await;
+    let grove = create_grove(&db).await;
+    let user = create_user(grove.id, true, None, &db).await;
+    let export = dbal::create_grove_export(grove.id, &user, &db)
+        .await
+        .unwrap();
+
+    dbal::finish_grove_export(export.id, GroveExportStatus::Failed, &db)
+        .await
+        .unwrap();
+    let err = dbal::finish_grove_export(export.id, GroveExportStatus::Finished, &db)
+        .await
+        .unwrap_err();
+    assert_eq!(err.error_type, BambooErrorCode::NotFound);
+
+    let export = dbal::get_grove_export(grove.id, export.id, &db)
+        .await
+        .unwrap();
+    assert_eq!(export.status, GroveExportStatus::Failed);
+}
@@ -0,0 +1,82 @@
+//! The route tests run against a real database, start them with a migrated postgres in `DATABASE_URL` and `--ignored`.
+
+use actix_web::http::StatusCode;
+use actix_web::{test, App};
+use sea_orm::{ActiveModelTrait, DatabaseConnection, NotSet, Set};
+use tokio::sync::OnceCell;
+
+use bamboo_common::backend::dbal;
+use bamboo_common::backend::migration::{Migrator, MigratorTrait};
+use bamboo_common::backend::services::DbConnection;
+use bamboo_common::core::entities::*;
+
+use crate::routes;
+
+mod demo;
+mod export;
+mod token_scope;
+mod user;
+
+static MIGRATION: OnceCell<()> = OnceCell::const_new();
+
+pub(crate) async fn connect() -> DatabaseConnection {
+    let db = bamboo_common::backend::database::get_database()
+        .await
+        .unwrap();
+    MIGRATION
+        .get_or_init(|| async { Migrator::up(&db, None).await.unwrap() })
+        .await;
+
+    db
+}
+
+/// The database is shared between the tests, so every grove and panda gets a name of its own.
+pub(crate) fn unique_name(prefix: &str) -> String {
+    format!("{prefix}-{}", uuid::Uuid::new_v4())
+}
+
+pub(crate) async fn create_grove(db: &DatabaseConnection) -> Grove {
+    dbal::create_grove(unique_name("grove"), db).await.unwrap()
+}
+
+pub(crate) async fn create_user(
+    grove_id: i32,
+    is_mod: bool,
+    role_id: Option<i32>,
+    db: &DatabaseConnection,
+) -> User {
+    let name = unique_name("panda");
+    let mut user = User::new(format!("{name}@bambushain.app"), name.clone(), name, is_mod);
+    user.role_id = role_id;
+
+    dbal::create_user(grove_id, user, "bamboo-test-password".to_string(), db)
+        .await
+        .unwrap()
+}
+
+/// Creates the token a regular login hands out, the routes get it through the authorization header.
+pub(crate) async fn login(user: &User, db: &DatabaseConnection) -> String {
+    token::ActiveModel {
+        id: NotSet,
+        token: Set(uuid::Uuid::new_v4().to_string()),
+        user_id: Set(user.id),
+    }
+    .insert(db)
+    .await
+    .unwrap()
+    .token
+}
+
+pub(crate) async fn call(req: test::TestRequest, db: &DatabaseConnection) -> StatusCode {
+    let app = test::init_service(
+        App::new()
+            .app_data(DbConnection::new(db.clone()))
+            .configure(routes::configure_routes),
+    )
+    .await;
+
+    match test::try_call_service(&app, req.to_request()).await {
+        Ok(res) => res.status(),
+        Err(err) => err.as_response_error().status_code(),
+    }
+}
@@ -0,0 +1,102 @@
+use actix_web::http::{Method, StatusCode};
+use actix_web::test;
+use sea_orm::DatabaseConnection;
+
+use bamboo_common::backend::dbal;
+use bamboo_common::core::entities::*;
+
+use super::*;
+
+const ALL_SCOPES: [TokenScope; 10] = [
+    TokenScope::ReadEvents,
+    TokenScope::WriteEvents,
+    TokenScope::ReadCharacters,
+    TokenScope::WriteCharacters,
+    TokenScope::ReadUsers,
+    TokenScope::WriteUsers,
+    TokenScope::ReadGrove,
+    TokenScope::WriteGrove,
+    TokenScope::ReadProfile,
+    TokenScope::WriteProfile,
+];
+
+async fn create_token(user: &User, scopes: &[TokenScope], db: &DatabaseConnection) -> String {
+    dbal::create_personal_access_token(
+        user.id,
+        CreatePersonalAccessToken {
+            name: unique_name("token"),
+            scopes: scopes.to_vec(),
+            expires_at: None,
+        },
+        db,
+    )
+    .await
+    .unwrap()
+    .token
+}
+
+fn request(method: Method, path: &str, token: &str) -> test::TestRequest {
+    test::TestRequest::default()
+        .method(method)
+        .uri(path)
+        .insert_header(("Authorization", format!("Token {token}")))
+}
+
+#[actix_web::test]
+#[ignore = "needs a postgres database in DATABASE_URL"]
+async fn tokens_can_call_the_routes_of_their_scope() {
+    let db = connect().await;
+    let grove = create_grove(&db).await;
+    let user = create_user(grove.id, false, None, &db).await;
+    let token = create_token(&user, &[TokenScope::ReadGrove], &db).await;
+
+    assert_eq!(
+        call(request(Method::GET, "/api/grove", &token), &db).await,
+        StatusCode::OK
+    );
+    assert_eq!(
+        call(request(Method::GET, "/api/my/profile", &token), &db).await,
+        StatusCode::FORBIDDEN
+    );
+}
+
+#[actix_web::test]
+#[ignore = "needs a postgres database in DATABASE_URL"]
+async fn tokens_of_mods_cannot_call_mod_only_and_destructive_routes() {
+    let db = connect().await;
+    let grove = create_grove(&db).await;
+    let mod_user = create_user(grove.id, true, None, &db).await;
+    let panda = create_user(grove.id, false, None, &db).await;
+    let token = create_token(&mod_user, &ALL_SCOPES, &db).await;
+
+    for (method, path) in [
+        (Method::GET, "/api/grove/export".to_string()),
+        (Method::POST, "/api/grove/export".to_string()),
+        (Method::GET, "/api/grove/export/1/download".to_string()),
+        (Method::GET, "/api/grove/audit-log".to_string()),
+        (Method::GET, "/api/grove/role".to_string()),
+        (Method::POST, "/api/grove/role".to_string()),
+        (Method::GET, "/api/grove/invitation".to_string()),
+        (Method::POST, "/api/grove/invitation".to_string()),
+        (Method::DELETE, "/api/grove/enabled".to_string()),
+        (Method::DELETE, "/api/grove".to_string()),
+        (Method::DELETE, "/api/grove/deletion".to_string()),
+        (Method::PUT, format!("/api/user/{}/mod", panda.id)),
+        (Method::DELETE, format!("/api/user/{}/mod", panda.id)),
+        (Method::PUT, format!("/api/user/{}/role", panda.id)),
+        (Method::PUT, format!("/api/user/{}/password", panda.id)),
+        (Method::DELETE, format!("/api/user/{}", panda.id)),
+        (Method::POST, "/api/my/tokens".to_string()),
+    ] {
+        assert_eq!(
+            call(request(method.clone(), path.as_str(), &token), &db).await,
+            StatusCode::FORBIDDEN,
+            "{method} {path}"
+        );
+    }
+
+    let grove = dbal::get_grove_by_id(grove.id, &db).await.unwrap();
+    assert!(grove.is_enabled);
+    assert!(!grove.is_deleted());
+    assert!(dbal::get_user(grove.id, panda.id, &db).await.is_ok());
+}
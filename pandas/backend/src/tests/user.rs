@@ -0,0 +1,194 @@
+use actix_web::http::{Method, StatusCode};
+use actix_web::test;
+use sea_orm::DatabaseConnection;
+use serde_json::json;
+
+use bamboo_common::backend::dbal;
+use bamboo_common::core::entities::*;
+
+use super::*;
+
+/// A grove with a mod, a panda that can manage roles but isn't a mod and a regular panda.
+struct RoleGrove {
+    grove: Grove,
+    mod_user: User,
+    role_manager: User,
+    panda: User,
+    role_manager_role: GroveRole,
+    admin_role: GroveRole,
+}
+
+async fn create_role(
+    grove_id: i32,
+    permissions: Vec<GrovePermission>,
+    db: &DatabaseConnection,
+) -> GroveRole {
+    dbal::create_grove_role(
+        grove_id,
+        GroveRole {
+            name: unique_name("role"),
+            permissions: GrovePermissions(permissions),
+            ..Default::default()
+        },
+        db,
+    )
+    .await
+    .unwrap()
+}
+
+async fn create_role_grove(db: &DatabaseConnection) -> RoleGrove {
+    let grove = create_grove(db).await;
+    let role_manager_role = create_role(grove.id, vec![GrovePermission::ManageRoles], db).await;
+    let admin_role = create_role(
+        grove.id,
+        vec![GrovePermission::ManageRoles, GrovePermission::ManageUsers],
+        db,
+    )
+    .await;
+
+    RoleGrove {
+        mod_user: create_user(grove.id, true, None, db).await,
+        role_manager: create_user(grove.id, false, Some(role_manager_role.id), db).await,
+        panda: create_user(grove.id, false, None, db).await,
+        grove,
+        role_manager_role,
+        admin_role,
+    }
+}
+
+async fn call_as(
+    user: &User,
+    method: Method,
+    path: String,
+    body: Option<serde_json::Value>,
+    db: &DatabaseConnection,
+) -> StatusCode {
+    let mut req = test::TestRequest::default()
+        .method(method)
+        .uri(path.as_str())
+        .insert_header(("Authorization", format!("Panda {}", login(user, db).await)));
+    if let Some(body) = body {
+        req = req.set_json(body);
+    }
+
+    call(req, db).await
+}
+
+#[actix_web::test]
+#[ignore = "needs a postgres database in DATABASE_URL"]
+async fn role_managers_cannot_change_the_mod_status() {
+    let db = connect().await;
+    let role_grove = create_role_grove(&db).await;
+
+    assert_eq!(
+        call_as(
+            &role_grove.role_manager,
+            Method::DELETE,
+            format!("/api/user/{}/mod", role_grove.mod_user.id),
+            None,
+            &db
+        )
+        .await,
+        StatusCode::FORBIDDEN
+    );
+    assert_eq!(
+        call_as(
+            &role_grove.role_manager,
+            Method::PUT,
+            format!("/api/user/{}/mod", role_grove.panda.id),
+            None,
+            &db
+        )
+        .await,
+        StatusCode::FORBIDDEN
+    );
+
+    let mod_membership =
+        dbal::get_grove_membership(role_grove.grove.id, role_grove.mod_user.id, &db)
+            .await
+            .unwrap();
+    let panda_membership =
+        dbal::get_grove_membership(role_grove.grove.id, role_grove.panda.id, &db)
+            .await
+            .unwrap();
+    assert!(mod_membership.is_mod);
+    assert!(!panda_membership.is_mod);
+}
+
+#[actix_web::test]
+#[ignore = "needs a postgres database in DATABASE_URL"]
+async fn role_managers_cannot_change_the_role_of_mods() {
+    let db = connect().await;
+    let role_grove = create_role_grove(&db).await;
+
+    assert_eq!(
+        call_as(
+            &role_grove.role_manager,
+            Method::PUT,
+            format!("/api/user/{}/role", role_grove.mod_user.id),
+            Some(json!({ "roleId": role_grove.role_manager_role.id })),
+            &db
+        )
+        .await,
+        StatusCode::FORBIDDEN
+    );
+
+    let membership = dbal::get_grove_membership(role_grove.grove.id, role_grove.mod_user.id, &db)
+        .await
+        .unwrap();
+    assert_eq!(membership.role_id, None);
+}
+
+#[actix_web::test]
+#[ignore = "needs a postgres database in DATABASE_URL"]
+async fn role_managers_can_only_give_roles_with_their_own_permissions() {
+    let db = connect().await;
+    let role_grove = create_role_grove(&db).await;
+
+    assert_eq!(
+        call_as(
+            &role_grove.role_manager,
+            Method::PUT,
+            format!("/api/user/{}/role", role_grove.panda.id),
+            Some(json!({ "roleId": role_grove.admin_role.id })),
+            &db
+        )
+        .await,
+        StatusCode::FORBIDDEN
+    );
+    assert_eq!(
+        call_as(
+            &role_grove.role_manager,
+            Method::PUT,
+            format!("/api/user/{}/role", role_grove.panda.id),
+            Some(json!({ "roleId": role_grove.role_manager_role.id })),
+            &db
+        )
+        .await,
+        StatusCode::NO_CONTENT
+    );
+}
+
+#[actix_web::test]
+#[ignore = "needs a postgres database in DATABASE_URL"]
+async fn mods_can_change_the_mod_status() {
+    let db = connect().await;
+    let role_grove = create_role_grove(&db).await;
+
+    assert_eq!(
+        call_as(
+            &role_grove.mod_user,
+            Method::PUT,
+            format!("/api/user/{}/mod", role_grove.panda.id),
+            None,
+            &db
+        )
+        .await,
+        StatusCode::NO_CONTENT
+    );
+
+    let membership = dbal::get_grove_membership(role_grove.grove.id, role_grove.panda.id, &db)
+        .await
+        .unwrap();
+    assert!(membership.is_mod);
+}
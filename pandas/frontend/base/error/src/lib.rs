@@ -1,3 +1,5 @@
+pub use password_policy::get_password_policy_violations;
 pub use reporting::report_unknown_error;
 
+pub mod password_policy;
 pub mod reporting;
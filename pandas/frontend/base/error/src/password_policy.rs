@@ -0,0 +1,18 @@
+use bamboo_common::frontend::api::ApiError;
+
+/// Maps the password policy violations returned by the backend to messages that can be shown next to the password field.
+pub fn get_password_policy_violations(error: &ApiError) -> Vec<&'static str> {
+    error
+        .bamboo_error
+        .details
+        .iter()
+        .filter(|detail| detail.field == "password")
+        .map(|detail| match detail.code.as_str() {
+            "tooShort" => "Das Passwort ist zu kurz",
+            "tooWeak" => "Das Passwort ist zu leicht zu erraten",
+            "breached" => "Das Passwort ist in bekannten Datenlecks aufgetaucht",
+            "containsPersonalData" => "Das Passwort enthält deine E-Mail oder deinen Namen",
+            _ => "Das Passwort erfüllt die Anforderungen nicht",
+        })
+        .collect()
+}
@@ -31,6 +31,14 @@ pub enum AppRoute {
     Licenses,
     #[at("/login")]
     Login,
+    #[at("/login/oidc")]
+    OidcLogin,
+    #[at("/reset-password")]
+    ResetPassword,
+    #[at("/confirm-email")]
+    ConfirmEmail,
+    #[at("/invitation")]
+    Invitation,
 }
 
 #[derive(Clone, Routable, PartialEq)]
@@ -53,6 +61,14 @@ pub enum ModAreaRoute {
     UserManagement,
     #[at("/mod-area/grove")]
     GroveManagement,
+    #[at("/mod-area/invitations")]
+    InvitationManagement,
+    #[at("/mod-area/roles")]
+    RoleManagement,
+    #[at("/mod-area/audit-log")]
+    AuditLog,
+    #[at("/mod-area/export")]
+    Export,
 }
 
 #[derive(Clone, Routable, PartialEq)]
@@ -1,7 +1,11 @@
 use bounce::Atom;
+use chrono::{Local, NaiveDate, NaiveDateTime, TimeZone, Utc};
 use gloo_storage::{LocalStorage, Storage};
+use wasm_bindgen::JsValue;
 
-use bamboo_common::core::entities::WebUser;
+use bamboo_common::core::entities::{
+    GrovePermission, GrovePermissions, GroveSettings, WebUser, TIMEZONES,
+};
 
 pub fn get_token() -> Option<String> {
     LocalStorage::get("/bamboo/token").ok()
@@ -29,3 +33,64 @@ impl From<WebUser> for CurrentUser {
         Self { profile: value }
     }
 }
+
+/// The permissions of the current user in the grove, mods have all permissions.
+#[derive(Atom, PartialEq, Clone, Default)]
+pub struct CurrentPermissions {
+    pub permissions: GrovePermissions,
+}
+
+impl CurrentPermissions {
+    pub fn contains(&self, permission: GrovePermission) -> bool {
+        self.permissions.contains(permission)
+    }
+}
+
+impl From<GrovePermissions> for CurrentPermissions {
+    fn from(value: GrovePermissions) -> Self {
+        Self { permissions: value }
+    }
+}
+
+/// The settings of the active grove, they drive the title, language, accent color and timezone of the page.
+#[derive(Atom, PartialEq, Clone, Default)]
+pub struct CurrentGroveSettings {
+    pub settings: GroveSettings,
+}
+
+/// The browser knows the timezone rules, the swedish locale formats the converted time like ISO 8601.
+fn convert_to_timezone(utc: &NaiveDateTime, timezone: &str) -> Option<NaiveDateTime> {
+    if !TIMEZONES.contains(&timezone) {
+        return None;
+    }
+
+    let date = js_sys::Date::new(&JsValue::from_f64(utc.and_utc().timestamp_millis() as f64));
+    let options = js_sys::Object::new();
+    js_sys::Reflect::set(
+        &options,
+        &JsValue::from_str("timeZone"),
+        &JsValue::from_str(timezone),
+    )
+    .ok()?;
+
+    let converted = String::from(date.to_locale_string("sv-SE", &options));
+    NaiveDateTime::parse_from_str(converted.as_str(), "%Y-%m-%d %H:%M:%S").ok()
+}
+
+impl CurrentGroveSettings {
+    /// Timestamps are stored in UTC, the pages of the grove show them in the timezone of the grove.
+    pub fn to_grove_time(&self, utc: &NaiveDateTime) -> NaiveDateTime {
+        convert_to_timezone(utc, self.settings.timezone.as_str())
+            .unwrap_or_else(|| Local.from_utc_datetime(utc).naive_local())
+    }
+
+    pub fn today(&self) -> NaiveDate {
+        self.to_grove_time(&Utc::now().naive_utc()).date()
+    }
+}
+
+impl From<GroveSettings> for CurrentGroveSettings {
+    fn from(value: GroveSettings) -> Self {
+        Self { settings: value }
+    }
+}
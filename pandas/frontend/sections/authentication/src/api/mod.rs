@@ -12,18 +12,63 @@ pub async fn get_my_profile() -> BambooApiResult<WebUser> {
 
 pub async fn login(login_data: Login) -> BambooApiResult<either::Either<LoginResult, ()>> {
     log::debug!("Execute login");
-    if login_data.two_factor_code.is_some()
-        || login_data.email.clone() == "playstore@google.bambushain"
-    {
-        let result = api::post("/api/login", &login_data).await?;
+    if let Some(result) = api::post_optional("/api/login", &login_data).await? {
         Ok(either::Left(result))
     } else {
-        api::post_no_content("/api/login", &login_data).await?;
         Ok(either::Right(()))
     }
 }
 
+pub async fn passkey_login(email: String) -> BambooApiResult<LoginResult> {
+    log::debug!("Execute passkey login");
+    let challenge = api::post::<StartPasskeyLogin, PasskeyLoginChallenge>(
+        "/api/login/passkey/challenge",
+        &StartPasskeyLogin {
+            email: email.clone(),
+        },
+    )
+    .await?;
+    let credential = api::get_passkey_credential(challenge.options).await?;
+
+    api::post(
+        "/api/login/passkey",
+        &FinishPasskeyLogin {
+            email,
+            challenge_id: challenge.challenge_id,
+            credential,
+        },
+    )
+    .await
+}
+
+pub async fn get_oidc_login_config() -> BambooApiResult<OidcLoginConfig> {
+    log::debug!("Get oidc login config");
+    api::get("/api/login/oidc/config").await
+}
+
 pub async fn forgot_password(data: ForgotPassword) -> BambooApiResult<()> {
     log::debug!("Request new password");
     api::post_no_content("/api/forgot-password", &data).await
 }
+
+pub async fn reset_password(data: ResetPassword) -> BambooApiResult<()> {
+    log::debug!("Reset password with token");
+    api::post_no_content("/api/reset-password", &data).await
+}
+
+pub async fn confirm_email_change(data: ConfirmEmailChange) -> BambooApiResult<()> {
+    log::debug!("Confirm email change with token");
+    api::post_no_content("/api/confirm-email", &data).await
+}
+
+pub async fn get_grove_invitation_details(
+    data: GroveInvitationToken,
+) -> BambooApiResult<GroveInvitationDetails> {
+    log::debug!("Load grove invitation details");
+    api::post("/api/invitation", &data).await
+}
+
+pub async fn accept_grove_invitation(data: AcceptGroveInvitation) -> BambooApiResult<()> {
+    log::debug!("Accept grove invitation");
+    api::post_no_content("/api/invitation/accept", &data).await
+}
@@ -0,0 +1,128 @@
+use serde::Deserialize;
+use stylist::yew::use_style;
+use yew::prelude::*;
+use yew_cosmo::prelude::*;
+use yew_hooks::{use_async, use_mount};
+use yew_icons::Icon;
+use yew_router::hooks::use_location;
+
+use bamboo_common::core::entities::ConfirmEmailChange;
+use bamboo_common::frontend::api::CONFLICT;
+use bamboo_pandas_frontend_base::routing::{AppRoute, LegalRoute};
+
+use crate::api;
+
+#[derive(Deserialize, Default)]
+struct ConfirmEmailQuery {
+    #[serde(default)]
+    token: String,
+}
+
+#[function_component(ConfirmEmailContent)]
+fn confirm_email_content() -> Html {
+    let location = use_location().expect("Location should be available");
+    let token = location
+        .query::<ConfirmEmailQuery>()
+        .unwrap_or_default()
+        .token;
+
+    let confirm_state = {
+        let token = token.clone();
+
+        use_async(async move { api::confirm_email_change(ConfirmEmailChange { token }).await })
+    };
+
+    {
+        let confirm_state = confirm_state.clone();
+        let token = token.clone();
+
+        use_mount(move || {
+            if !token.is_empty() {
+                confirm_state.run();
+            }
+        });
+    }
+
+    let confirm_around_style = use_style!(
+        r#"
+position: fixed;
+left: 0;
+right: 0;
+top: 0;
+bottom: 0;
+display: flex;
+justify-content: center;
+align-items: center;
+height: 100vh;
+width: 100vw;
+background: url("/static/background-login.webp");
+background-size: cover;
+background-position-y: bottom;
+
+font-family: var(--font-family);
+color: var(--black);
+
+--black: #ffffff;
+--white: transparent;
+    "#
+    );
+
+    let confirm_container_style = use_style!(
+        r#"
+background: rgba(255, 255, 255, 0.25);
+padding: 2rem 4rem;
+backdrop-filter: blur(24px) saturate(90%);
+box-sizing: border-box;
+margin-top: 1.25rem;
+min-width: 35.625rem;
+max-width: 40rem;
+border-radius: var(--border-radius);
+"#
+    );
+    let confirm_message_style = use_style!(
+        r#"
+font-size: 1.5rem;
+color: #fff;
+font-weight: var(--font-weight-light);
+font-family: var(--font-family);
+display: flex;
+gap: 0.5rem;
+align-items: center;
+    "#
+    );
+
+    html!(
+        <div class={confirm_around_style}>
+            <div class={classes!(confirm_container_style, "login-page")}>
+                <CosmoTitle title="Email bestätigen" />
+                <p class={confirm_message_style}>
+                    if token.is_empty() {
+                        <Icon icon_id={IconId::LucideXOctagon} style="stroke: var(--negative-color);" /> {"Der Link ist ungültig, bitte ändere deine Email erneut"}
+                    } else if confirm_state.data.is_some() {
+                        <Icon icon_id={IconId::LucideCheckCircle} /> {"Deine neue Email ist bestätigt, du kannst dich jetzt damit anmelden"}
+                    } else if let Some(err) = &confirm_state.error {
+                        if err.code == CONFLICT {
+                            <Icon icon_id={IconId::LucideXOctagon} style="stroke: var(--negative-color);" /> {"Die Email wird inzwischen von einem anderen Panda verwendet"}
+                        } else {
+                            <Icon icon_id={IconId::LucideXOctagon} style="stroke: var(--negative-color);" /> {"Der Link ist ungültig oder abgelaufen, bitte ändere deine Email erneut"}
+                        }
+                    } else {
+                        <Icon icon_id={IconId::LucideMail} /> {"Deine neue Email wird bestätigt"}
+                    }
+                </p>
+                <div style="display: flex; gap: 1rem">
+                    <CosmoAnchorLink<AppRoute> to={AppRoute::Login}>{"Zur Anmeldung"}</CosmoAnchorLink<AppRoute>>
+                    <CosmoAnchorLink<AppRoute> to={AppRoute::LegalRoot}>{"Impressum"}</CosmoAnchorLink<AppRoute>>
+                    <CosmoAnchorLink<LegalRoute> to={LegalRoute::DataProtection}>{"Datenschutzerklärung"}</CosmoAnchorLink<LegalRoute>>
+                </div>
+            </div>
+        </div>
+    )
+}
+
+#[function_component(ConfirmEmailPage)]
+pub fn confirm_email_page() -> Html {
+    html!(
+        <ConfirmEmailContent />
+    )
+}
@@ -0,0 +1,210 @@
+use serde::Deserialize;
+use stylist::yew::use_style;
+use yew::prelude::*;
+use yew_cosmo::prelude::*;
+use yew_hooks::{use_async, use_mount};
+use yew_icons::Icon;
+use yew_router::hooks::use_location;
+
+use bamboo_common::core::entities::{AcceptGroveInvitation, GroveInvitationToken};
+use bamboo_common::frontend::api::CONFLICT;
+use bamboo_pandas_frontend_base::error;
+use bamboo_pandas_frontend_base::routing::{AppRoute, LegalRoute};
+
+use crate::api;
+
+#[derive(Deserialize, Default)]
+struct InvitationQuery {
+    #[serde(default)]
+    token: String,
+}
+
+#[function_component(InvitationContent)]
+fn invitation_content() -> Html {
+    let location = use_location().expect("Location should be available");
+    let token = location
+        .query::<InvitationQuery>()
+        .unwrap_or_default()
+        .token;
+
+    let email_state = use_state_eq(|| AttrValue::from(""));
+    let display_name_state = use_state_eq(|| AttrValue::from(""));
+    let discord_name_state = use_state_eq(|| AttrValue::from(""));
+    let password_state = use_state_eq(|| AttrValue::from(""));
+    let repeat_password_state = use_state_eq(|| AttrValue::from(""));
+
+    let details_state = {
+        let email_state = email_state.clone();
+
+        let token = token.clone();
+
+        use_async(async move {
+            api::get_grove_invitation_details(GroveInvitationToken { token })
+                .await
+                .map(|details| {
+                    if let Some(email) = details.email.clone() {
+                        email_state.set(AttrValue::from(email));
+                    }
+
+                    details
+                })
+        })
+    };
+    let accept_state = {
+        let email_state = email_state.clone();
+        let display_name_state = display_name_state.clone();
+        let discord_name_state = discord_name_state.clone();
+        let password_state = password_state.clone();
+        let repeat_password_state = repeat_password_state.clone();
+
+        let token = token.clone();
+
+        use_async(async move {
+            if *password_state != *repeat_password_state {
+                return Err("Die Passwörter stimmen nicht überein".to_string());
+            }
+
+            api::accept_grove_invitation(AcceptGroveInvitation {
+                token,
+                email: (*email_state).to_string(),
+                display_name: (*display_name_state).to_string(),
+                discord_name: (*discord_name_state).to_string(),
+                password: (*password_state).to_string(),
+            })
+            .await
+            .map_err(|err| {
+                let password_policy_violations = error::get_password_policy_violations(&err);
+                if err.code == CONFLICT {
+                    "Ein Panda mit dieser Email oder diesem Namen ist bereits im Bambushain"
+                        .to_string()
+                } else if !password_policy_violations.is_empty() {
+                    password_policy_violations.join(", ")
+                } else {
+                    "Die Einladung ist ungültig oder abgelaufen, bitte frag nach einer neuen"
+                        .to_string()
+                }
+            })
+        })
+    };
+
+    {
+        let details_state = details_state.clone();
+        let token = token.clone();
+
+        use_mount(move || {
+            if !token.is_empty() {
+                details_state.run();
+            }
+        });
+    }
+
+    let on_email_update = use_callback(email_state.clone(), |value, state| state.set(value));
+    let on_display_name_update =
+        use_callback(display_name_state.clone(), |value, state| state.set(value));
+    let on_discord_name_update =
+        use_callback(discord_name_state.clone(), |value, state| state.set(value));
+    let on_password_update = use_callback(password_state.clone(), |value, state| state.set(value));
+    let on_repeat_password_update = use_callback(repeat_password_state.clone(), |value, state| {
+        state.set(value)
+    });
+    let accept_submit = use_callback(accept_state.clone(), |_, accept_state| {
+        accept_state.run();
+    });
+
+    let invitation_around_style = use_style!(
+        r#"
+position: fixed;
+left: 0;
+right: 0;
+top: 0;
+bottom: 0;
+display: flex;
+justify-content: center;
+align-items: center;
+height: 100vh;
+width: 100vw;
+background: url("/static/background-login.webp");
+background-size: cover;
+background-position-y: bottom;
+
+font-family: var(--font-family);
+color: var(--black);
+
+--black: #ffffff;
+--white: transparent;
+
+input {
+    --primary-color: var(--control-border-color);
+}
+    "#
+    );
+
+    let invitation_container_style = use_style!(
+        r#"
+background: rgba(255, 255, 255, 0.25);
+padding: 2rem 4rem;
+backdrop-filter: blur(24px) saturate(90%);
+box-sizing: border-box;
+margin-top: 1.25rem;
+min-width: 35.625rem;
+max-width: 40rem;
+border-radius: var(--border-radius);
+"#
+    );
+    let invitation_message_style = use_style!(
+        r#"
+font-size: 1.5rem;
+color: #fff;
+font-weight: var(--font-weight-light);
+font-family: var(--font-family);
+display: flex;
+gap: 0.5rem;
+align-items: center;
+    "#
+    );
+
+    let email_preset = details_state
+        .data
+        .as_ref()
+        .is_some_and(|details| details.email.is_some());
+
+    html!(
+        <div class={invitation_around_style}>
+            <div class={classes!(invitation_container_style, "login-page")}>
+                <CosmoTitle title="Einladung annehmen" />
+                <p class={invitation_message_style}>
+                    if token.is_empty() || details_state.error.is_some() {
+                        <Icon icon_id={IconId::LucideXOctagon} style="stroke: var(--negative-color);" /> {"Die Einladung ist ungültig oder abgelaufen, bitte frag nach einer neuen"}
+                    } else if accept_state.data.is_some() {
+                        <Icon icon_id={IconId::LucideCheckCircle} /> {"Dein Account wurde angelegt, du kannst dich jetzt anmelden"}
+                    } else if let Some(error) = &accept_state.error {
+                        <Icon icon_id={IconId::LucideXOctagon} style="stroke: var(--negative-color);" /> {error}
+                    } else if let Some(details) = &details_state.data {
+                        <Icon icon_id={IconId::LucideUserPlus} /> {format!("Du wurdest in den Hain {} eingeladen", details.grove_name)}
+                    }
+                </p>
+                if details_state.data.is_some() && accept_state.data.is_none() {
+                    <CosmoForm on_submit={accept_submit} buttons={html!(<CosmoButton state={CosmoButtonType::Primary} label="Account anlegen" is_submit={true} />)}>
+                        <CosmoTextBox id="email" input_type={CosmoTextBoxType::Email} required={true} readonly={email_preset} value={(*email_state).clone()} on_input={on_email_update} label="Email" />
+                        <CosmoTextBox id="display-name" required={true} value={(*display_name_state).clone()} on_input={on_display_name_update} label="Name" />
+                        <CosmoTextBox id="discord-name" value={(*discord_name_state).clone()} on_input={on_discord_name_update} label="Discord Name (optional)" />
+                        <CosmoTextBox id="password" input_type={CosmoTextBoxType::Password} required={true} value={(*password_state).clone()} on_input={on_password_update} label="Passwort" />
+                        <CosmoTextBox id="repeat-password" input_type={CosmoTextBoxType::Password} required={true} value={(*repeat_password_state).clone()} on_input={on_repeat_password_update} label="Passwort wiederholen" />
+                    </CosmoForm>
+                }
+                <div style="display: flex; gap: 1rem">
+                    <CosmoAnchorLink<AppRoute> to={AppRoute::Login}>{"Zur Anmeldung"}</CosmoAnchorLink<AppRoute>>
+                    <CosmoAnchorLink<AppRoute> to={AppRoute::LegalRoot}>{"Impressum"}</CosmoAnchorLink<AppRoute>>
+                    <CosmoAnchorLink<LegalRoute> to={LegalRoute::DataProtection}>{"Datenschutzerklärung"}</CosmoAnchorLink<LegalRoute>>
+                </div>
+            </div>
+        </div>
+    )
+}
+
+#[function_component(InvitationPage)]
+pub fn invitation_page() -> Html {
+    html!(
+        <InvitationContent />
+    )
+}
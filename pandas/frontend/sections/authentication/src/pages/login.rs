@@ -1,11 +1,12 @@
 use stylist::yew::use_style;
 use yew::prelude::*;
 use yew_cosmo::prelude::*;
-use yew_hooks::{use_async, use_bool_toggle};
+use yew_hooks::{use_async, use_bool_toggle, use_mount};
 use yew_icons::Icon;
 use yew_router::hooks::use_navigator;
 
 use bamboo_common::core::entities::{ForgotPassword, Login};
+use bamboo_common::frontend::api::{PASSKEY_ERROR, TOO_MANY_REQUESTS};
 use bamboo_pandas_frontend_base::routing::{AppRoute, LegalRoute};
 use bamboo_pandas_frontend_base::storage;
 
@@ -23,6 +24,8 @@ fn login_content() -> Html {
     let forgot_password_toggle = use_bool_toggle(false);
 
     let login = {
+        let navigator = navigator.clone();
+
         let email_state = email_state.clone();
         let password_state = password_state.clone();
         let two_factor_code_state = two_factor_code_state.clone();
@@ -52,9 +55,12 @@ fn login_content() -> Html {
                     two_factor_code_requested_toggle.set(true);
                     Ok(())
                 }
+                Err(err) if err.code == TOO_MANY_REQUESTS => {
+                    Err("Zu viele fehlgeschlagene Anmeldeversuche, bitte versuche es später noch einmal")
+                }
                 Err(_) => {
                     if *two_factor_code_requested_toggle {
-                        Err("Der Zwei Faktor Code ist ungültig")
+                        Err("Der Zwei Faktor Code ist ungültig oder abgelaufen, fordere bei Bedarf einen neuen Code an")
                     } else {
                         Err("Die Email und das Passwort passen nicht zusammen")
                     }
@@ -62,6 +68,48 @@ fn login_content() -> Html {
             }
         })
     };
+    let resend_two_factor_code = {
+        let email_state = email_state.clone();
+        let password_state = password_state.clone();
+        let two_factor_code_state = two_factor_code_state.clone();
+
+        use_async(async move {
+            two_factor_code_state.set(AttrValue::from(""));
+            match api::login(Login::new(
+                (*email_state).to_string(),
+                (*password_state).to_string(),
+                None,
+            ))
+            .await
+            {
+                Ok(_) => Ok(()),
+                Err(err) if err.code == TOO_MANY_REQUESTS => {
+                    Err("Bitte warte einen Moment, bevor du einen neuen Code anforderst")
+                }
+                Err(_) => Err("Leider konnten wir dir keinen neuen Code schicken"),
+            }
+        })
+    };
+    let passkey_login = {
+        let email_state = email_state.clone();
+
+        use_async(async move {
+            match api::passkey_login((*email_state).to_string()).await {
+                Ok(result) => {
+                    storage::set_token(result.token);
+                    navigator.push(&AppRoute::BambooGroveRoot);
+                    Ok(())
+                }
+                Err(err) if err.code == TOO_MANY_REQUESTS => {
+                    Err("Zu viele fehlgeschlagene Anmeldeversuche, bitte versuche es später noch einmal")
+                }
+                Err(err) if err.code == PASSKEY_ERROR => {
+                    Err("Die Anmeldung mit deinem Passkey wurde abgebrochen")
+                }
+                Err(_) => Err("Die Anmeldung mit Passkey hat leider nicht geklappt"),
+            }
+        })
+    };
     let forgot_password = {
         let email_state = email_state.clone();
 
@@ -73,6 +121,14 @@ fn login_content() -> Html {
         })
     };
 
+    let oidc_login_config = use_async(async move { api::get_oidc_login_config().await });
+
+    {
+        let oidc_login_config = oidc_login_config.clone();
+
+        use_mount(move || oidc_login_config.run());
+    }
+
     let on_email_update = use_callback(email_state.clone(), |value, state| state.set(value));
     let on_password_update = use_callback(password_state.clone(), |value, state| state.set(value));
     let on_two_factor_code_update =
@@ -93,6 +149,18 @@ fn login_content() -> Html {
             }
         },
     );
+    let resend_two_factor_code_click = use_callback(
+        resend_two_factor_code.clone(),
+        |_, resend_two_factor_code| resend_two_factor_code.run(),
+    );
+    let passkey_login_click = use_callback(
+        (email_state.clone(), passkey_login.clone()),
+        |_, (email_state, passkey_login)| {
+            if !email_state.is_empty() {
+                passkey_login.run();
+            }
+        },
+    );
     let forgot_password_click = use_callback(
         forgot_password_toggle.clone(),
         |_, forgot_password_toggle| {
@@ -158,13 +226,19 @@ align-items: center;
                 <CosmoTitle title="Anmelden" />
                 <p class={login_message_style}>
                     if *forgot_password_toggle {
-                        {"Gib deine Emailadresse oder deinen Namen ein, wenn du im Hain bist, schicken wir allen Mods eine Email mit der Bitte dein Passwort zurückzusetzen"}
+                        {"Gib deine Emailadresse oder deinen Namen ein, wenn du im Hain bist, schicken wir dir eine Email mit einem Link, mit dem du dein Passwort zurücksetzen kannst"}
                     } else if forgot_password.error.is_some() {
-                        {"Leider konnten wir die Mods nicht erreichen, bitte wende dich direkt an einen Mod wenn du kannst oder an "}<CosmoAnchor href="mailto:panda.helferlein@bambushain.app">{"panda.helferlein@bambushain.app"}</CosmoAnchor>
+                        {"Leider konnten wir dir keine Email schicken, bitte wende dich direkt an einen Mod wenn du kannst oder an "}<CosmoAnchor href="mailto:panda.helferlein@bambushain.app">{"panda.helferlein@bambushain.app"}</CosmoAnchor>
                     } else if forgot_password.data.is_some() {
-                        {"Wir haben den Mods geschrieben, bitte warte bis sich jemand bei dir meldet"}
+                        {"Wir haben dir eine Email geschickt, folge dem Link darin um dein Passwort zurückzusetzen. Falls keine Email ankommt, melde dich bei deinen Mods"}
+                    } else if let Some(error) = &resend_two_factor_code.error {
+                        <Icon icon_id={IconId::LucideXOctagon} style="stroke: var(--negative-color);" /> {error}
                     } else if let Some(error) = &login.error {
                         <Icon icon_id={IconId::LucideXOctagon} style="stroke: var(--negative-color);" /> {error}
+                    } else if resend_two_factor_code.data.is_some() && !resend_two_factor_code.loading {
+                        <Icon icon_id={IconId::LucideMail} /> {"Wir haben dir einen neuen Zwei Faktor Code geschickt"}
+                    } else if let Some(error) = &passkey_login.error {
+                        <Icon icon_id={IconId::LucideXOctagon} style="stroke: var(--negative-color);" /> {error}
                     } else {
                         <Icon icon_id={IconId::LucideLogIn} /> {"Melde dich an und betrete den Bambushain"}
                     }
@@ -173,6 +247,7 @@ align-items: center;
                     <CosmoForm on_submit={login_submit} buttons={html!(
                         <>
                             <CosmoButton state={CosmoButtonType::Default} label="Passwort vergessen" on_click={forgot_password_click} />
+                            <CosmoButton state={CosmoButtonType::Default} label="Mit Passkey anmelden" enabled={!email_state.is_empty()} on_click={passkey_login_click} />
                             <CosmoButton state={CosmoButtonType::Primary} label="Anmelden" is_submit={true} />
                         </>
                     )}>
@@ -189,12 +264,22 @@ align-items: center;
                         <CosmoTextBox id="email" required={true} value={(*email_state).clone()} on_input={on_email_update} label="Email oder Name" />
                     </CosmoForm>
                 } else {
-                    <CosmoForm on_submit={login_submit} buttons={html!(<CosmoButton state={CosmoButtonType::Primary} label="Anmelden" is_submit={true} />)}>
+                    <CosmoForm on_submit={login_submit} buttons={html!(
+                        <>
+                            <CosmoButton state={CosmoButtonType::Default} label="Neuen Code anfordern" on_click={resend_two_factor_code_click} />
+                            <CosmoButton state={CosmoButtonType::Primary} label="Anmelden" is_submit={true} />
+                        </>
+                    )}>
                         <CosmoTextBox required={true} readonly={true} id="email" value={(*email_state).clone()} on_input={on_email_update} label="Email" />
                         <CosmoTextBox required={true} readonly={true} id="password" input_type={CosmoTextBoxType::Password} value={(*password_state).clone()} on_input={on_password_update} label="Passwort" />
-                        <CosmoTextBox required={true} id="twofactor" value={(*two_factor_code_state).clone()} on_input={on_two_factor_code_update} label="Zwei Faktor Code" />
+                        <CosmoTextBox required={true} id="twofactor" value={(*two_factor_code_state).clone()} on_input={on_two_factor_code_update} label="Zwei Faktor Code oder Wiederherstellungscode" />
                     </CosmoForm>
                 }
+                if let Some(config) = oidc_login_config.data.as_ref().filter(|config| config.enabled && !*two_factor_code_requested_toggle && !*forgot_password_toggle) {
+                    <CosmoParagraph>
+                        <CosmoAnchor href="/api/login/oidc">{format!("Mit {} anmelden", config.provider_name)}</CosmoAnchor>
+                    </CosmoParagraph>
+                }
                 <div style="display: flex; gap: 1rem">
                     <CosmoAnchorLink<AppRoute> to={AppRoute::LegalRoot}>{"Impressum"}</CosmoAnchorLink<AppRoute>>
                     <CosmoAnchorLink<LegalRoute> to={LegalRoute::DataProtection}>{"Datenschutzerklärung"}</CosmoAnchorLink<LegalRoute>>
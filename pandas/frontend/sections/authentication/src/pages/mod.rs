@@ -1,3 +1,11 @@
+pub use confirm_email::*;
+pub use invitation::*;
 pub use login::*;
+pub use oidc_login::*;
+pub use reset_password::*;
 
+mod confirm_email;
+mod invitation;
 mod login;
+mod oidc_login;
+mod reset_password;
@@ -0,0 +1,107 @@
+use stylist::yew::use_style;
+use yew::prelude::*;
+use yew_cosmo::prelude::*;
+use yew_hooks::use_mount;
+use yew_icons::Icon;
+use yew_router::hooks::{use_location, use_navigator};
+
+use bamboo_pandas_frontend_base::routing::{AppRoute, LegalRoute};
+use bamboo_pandas_frontend_base::storage;
+
+#[function_component(OidcLoginContent)]
+fn oidc_login_content() -> Html {
+    let navigator = use_navigator().expect("Navigator should be available");
+    let location = use_location().expect("Location should be available");
+
+    let token = location
+        .hash()
+        .trim_start_matches('#')
+        .strip_prefix("token=")
+        .map(|token| token.to_string());
+
+    {
+        let token = token.clone();
+
+        use_mount(move || {
+            if let Some(token) = token {
+                storage::set_token(token);
+                navigator.push(&AppRoute::BambooGroveRoot);
+            }
+        });
+    }
+
+    let login_around_style = use_style!(
+        r#"
+position: fixed;
+left: 0;
+right: 0;
+top: 0;
+bottom: 0;
+display: flex;
+justify-content: center;
+align-items: center;
+height: 100vh;
+width: 100vw;
+background: url("/static/background-login.webp");
+background-size: cover;
+background-position-y: bottom;
+
+font-family: var(--font-family);
+color: var(--black);
+
+--black: #ffffff;
+--white: transparent;
+    "#
+    );
+
+    let login_container_style = use_style!(
+        r#"
+background: rgba(255, 255, 255, 0.25);
+padding: 2rem 4rem;
+backdrop-filter: blur(24px) saturate(90%);
+box-sizing: border-box;
+margin-top: 1.25rem;
+min-width: 35.625rem;
+max-width: 40rem;
+border-radius: var(--border-radius);
+"#
+    );
+    let login_message_style = use_style!(
+        r#"
+font-size: 1.5rem;
+color: #fff;
+font-weight: var(--font-weight-light);
+font-family: var(--font-family);
+display: flex;
+gap: 0.5rem;
+align-items: center;
+    "#
+    );
+
+    html!(
+        <div class={login_around_style}>
+            <div class={classes!(login_container_style, "login-page")}>
+                <CosmoTitle title="Anmelden" />
+                <p class={login_message_style}>
+                    if token.is_some() {
+                        <Icon icon_id={IconId::LucideLogIn} /> {"Du wirst angemeldet"}
+                    } else {
+                        <Icon icon_id={IconId::LucideXOctagon} style="stroke: var(--negative-color);" /> {"Die Anmeldung hat leider nicht geklappt, eventuell ist dein Konto nicht mit dieser Emailadresse im Hain oder die Anmeldung ist für deinen Hain nicht freigeschaltet"}
+                    }
+                </p>
+                <div style="display: flex; gap: 1rem">
+                    <CosmoAnchorLink<AppRoute> to={AppRoute::Login}>{"Zur Anmeldung"}</CosmoAnchorLink<AppRoute>>
+                    <CosmoAnchorLink<AppRoute> to={AppRoute::LegalRoot}>{"Impressum"}</CosmoAnchorLink<AppRoute>>
+                    <CosmoAnchorLink<LegalRoute> to={LegalRoute::DataProtection}>{"Datenschutzerklärung"}</CosmoAnchorLink<LegalRoute>>
+                </div>
+            </div>
+        </div>
+    )
+}
+
+#[function_component(OidcLoginPage)]
+pub fn oidc_login_page() -> Html {
+    html!(
+        <OidcLoginContent />
+    )
+}
@@ -0,0 +1,177 @@
+use serde::Deserialize;
+use stylist::yew::use_style;
+use yew::prelude::*;
+use yew_cosmo::prelude::*;
+use yew_hooks::use_async;
+use yew_icons::Icon;
+use yew_router::hooks::use_location;
+
+use bamboo_common::core::entities::ResetPassword;
+use bamboo_common::core::error::BambooErrorCode;
+use bamboo_pandas_frontend_base::error;
+use bamboo_pandas_frontend_base::routing::{AppRoute, LegalRoute};
+
+use crate::api;
+
+#[derive(Deserialize, Default)]
+struct ResetPasswordQuery {
+    #[serde(default)]
+    token: String,
+}
+
+#[function_component(ResetPasswordContent)]
+fn reset_password_content() -> Html {
+    let location = use_location().expect("Location should be available");
+    let token = location
+        .query::<ResetPasswordQuery>()
+        .unwrap_or_default()
+        .token;
+
+    let new_password_state = use_state_eq(|| AttrValue::from(""));
+    let repeat_password_state = use_state_eq(|| AttrValue::from(""));
+    let two_factor_code_state = use_state_eq(|| AttrValue::from(""));
+
+    let two_factor_required_state = use_state_eq(|| false);
+
+    let reset_password = {
+        let new_password_state = new_password_state.clone();
+        let repeat_password_state = repeat_password_state.clone();
+        let two_factor_code_state = two_factor_code_state.clone();
+
+        let two_factor_required_state = two_factor_required_state.clone();
+
+        let token = token.clone();
+
+        use_async(async move {
+            if *new_password_state != *repeat_password_state {
+                return Err("Die Passwörter stimmen nicht überein".to_string());
+            }
+
+            api::reset_password(ResetPassword {
+                token,
+                new_password: (*new_password_state).to_string(),
+                two_factor_code: Some((*two_factor_code_state).to_string())
+                    .filter(|code| !code.is_empty()),
+            })
+            .await
+            .map_err(|err| {
+                let password_policy_violations = error::get_password_policy_violations(&err);
+                if err.bamboo_error.error_type == BambooErrorCode::Unauthorized {
+                    two_factor_required_state.set(true);
+                    "Bitte gib den Code aus deiner Authenticator App oder einen Wiederherstellungscode ein"
+                        .to_string()
+                } else if password_policy_violations.is_empty() {
+                    "Der Link ist ungültig oder abgelaufen, bitte fordere einen neuen an"
+                        .to_string()
+                } else {
+                    password_policy_violations.join(", ")
+                }
+            })
+        })
+    };
+
+    let on_new_password_update =
+        use_callback(new_password_state.clone(), |value, state| state.set(value));
+    let on_repeat_password_update = use_callback(repeat_password_state.clone(), |value, state| {
+        state.set(value)
+    });
+    let on_two_factor_code_update = use_callback(two_factor_code_state.clone(), |value, state| {
+        state.set(value)
+    });
+    let reset_submit = use_callback(reset_password.clone(), |_, reset_password| {
+        reset_password.run();
+    });
+
+    let reset_around_style = use_style!(
+        r#"
+position: fixed;
+left: 0;
+right: 0;
+top: 0;
+bottom: 0;
+display: flex;
+justify-content: center;
+align-items: center;
+height: 100vh;
+width: 100vw;
+background: url("/static/background-login.webp");
+background-size: cover;
+background-position-y: bottom;
+
+font-family: var(--font-family);
+color: var(--black);
+
+--black: #ffffff;
+--white: transparent;
+
+input {
+    --primary-color: var(--control-border-color);
+}
+    "#
+    );
+
+    let reset_container_style = use_style!(
+        r#"
+background: rgba(255, 255, 255, 0.25);
+padding: 2rem 4rem;
+backdrop-filter: blur(24px) saturate(90%);
+box-sizing: border-box;
+margin-top: 1.25rem;
+min-width: 35.625rem;
+max-width: 40rem;
+border-radius: var(--border-radius);
+"#
+    );
+    let reset_message_style = use_style!(
+        r#"
+font-size: 1.5rem;
+color: #fff;
+font-weight: var(--font-weight-light);
+font-family: var(--font-family);
+display: flex;
+gap: 0.5rem;
+align-items: center;
+    "#
+    );
+
+    html!(
+        <div class={reset_around_style}>
+            <div class={classes!(reset_container_style, "login-page")}>
+                <CosmoTitle title="Passwort zurücksetzen" />
+                <p class={reset_message_style}>
+                    if token.is_empty() {
+                        <Icon icon_id={IconId::LucideXOctagon} style="stroke: var(--negative-color);" /> {"Der Link ist ungültig, bitte fordere einen neuen an"}
+                    } else if reset_password.data.is_some() {
+                        <Icon icon_id={IconId::LucideCheckCircle} /> {"Dein Passwort wurde geändert, du kannst dich jetzt anmelden"}
+                    } else if let Some(error) = &reset_password.error {
+                        <Icon icon_id={IconId::LucideXOctagon} style="stroke: var(--negative-color);" /> {error}
+                    } else {
+                        <Icon icon_id={IconId::LucideKey} /> {"Setze dir ein neues Passwort für den Bambushain"}
+                    }
+                </p>
+                if !token.is_empty() && reset_password.data.is_none() && *two_factor_required_state {
+                    <CosmoForm on_submit={reset_submit} buttons={html!(<CosmoButton state={CosmoButtonType::Primary} label="Passwort setzen" is_submit={true} />)}>
+                        <CosmoTextBox id="two-factor-code" required={true} value={(*two_factor_code_state).clone()} on_input={on_two_factor_code_update} label="Zwei-Faktor-Code oder Wiederherstellungscode" />
+                    </CosmoForm>
+                } else if !token.is_empty() && reset_password.data.is_none() {
+                    <CosmoForm on_submit={reset_submit} buttons={html!(<CosmoButton state={CosmoButtonType::Primary} label="Passwort setzen" is_submit={true} />)}>
+                        <CosmoTextBox id="new-password" input_type={CosmoTextBoxType::Password} required={true} value={(*new_password_state).clone()} on_input={on_new_password_update} label="Neues Passwort" />
+                        <CosmoTextBox id="repeat-password" input_type={CosmoTextBoxType::Password} required={true} value={(*repeat_password_state).clone()} on_input={on_repeat_password_update} label="Passwort wiederholen" />
+                    </CosmoForm>
+                }
+                <div style="display: flex; gap: 1rem">
+                    <CosmoAnchorLink<AppRoute> to={AppRoute::Login}>{"Zur Anmeldung"}</CosmoAnchorLink<AppRoute>>
+                    <CosmoAnchorLink<AppRoute> to={AppRoute::LegalRoot}>{"Impressum"}</CosmoAnchorLink<AppRoute>>
+                    <CosmoAnchorLink<LegalRoute> to={LegalRoute::DataProtection}>{"Datenschutzerklärung"}</CosmoAnchorLink<LegalRoute>>
+                </div>
+            </div>
+        </div>
+    )
+}
+
+#[function_component(ResetPasswordPage)]
+pub fn reset_password_page() -> Html {
+    html!(
+        <ResetPasswordContent />
+    )
+}
@@ -4,6 +4,7 @@ use std::ops::Deref;
 use std::pin::Pin;
 use std::task::{Context, Poll};
 
+use bounce::use_atom_value;
 use chrono::prelude::*;
 use chrono::{Days, Months};
 use date_range::DateRange;
@@ -22,7 +23,7 @@ use yew_icons::Icon;
 
 use bamboo_common::core::entities::Event;
 use bamboo_common::frontend::api::ApiError;
-use bamboo_pandas_frontend_base::error;
+use bamboo_pandas_frontend_base::{error, storage};
 
 use crate::api;
 
@@ -536,7 +537,7 @@ fn day(
     } else {
         "var(--day-background-past-month)"
     };
-    let today = Local::now().date_naive();
+    let today = use_atom_value::<storage::CurrentGroveSettings>().today();
     let day_number_color = if today.month() == month && today.day() == day && today.year() == year {
         "var(--black)"
     } else {
@@ -910,7 +911,8 @@ grid-row: 3/4;
 #[function_component(CalendarPage)]
 pub fn calendar_page() -> Html {
     log::debug!("Render calendar page");
-    let date_state = use_state_eq(|| Local::now().date_naive().with_day(1).unwrap());
+    let grove_settings_atom = use_atom_value::<storage::CurrentGroveSettings>();
+    let date_state = use_state_eq(|| grove_settings_atom.today().with_day(1).unwrap());
 
     let prev_month = *date_state - Months::new(1);
     let next_month = *date_state + Months::new(1);
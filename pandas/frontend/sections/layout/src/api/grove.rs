@@ -1,4 +1,4 @@
-use bamboo_common::core::entities::Grove;
+use bamboo_common::core::entities::{Grove, GroveSettings};
 use bamboo_common::frontend::api::BambooApiResult;
 use bamboo_pandas_frontend_base::api;
 
@@ -6,3 +6,8 @@ pub async fn get_grove() -> BambooApiResult<Grove> {
     log::debug!("Loading grove of current user");
     api::get("/api/grove").await
 }
+
+pub async fn get_grove_settings() -> BambooApiResult<GroveSettings> {
+    log::debug!("Loading settings of the current grove");
+    api::get("/api/grove/settings").await
+}
@@ -14,6 +14,11 @@ pub async fn change_my_password(old_password: String, new_password: String) -> B
     .await
 }
 
+pub async fn get_my_permissions() -> BambooApiResult<GrovePermissions> {
+    log::debug!("Load my permissions in the grove");
+    api::get("/api/my/permissions").await
+}
+
 pub async fn update_my_profile(profile: UpdateProfile) -> BambooApiResult<()> {
     log::debug!("Update profile to the following data {:?}", profile);
     api::put_no_content("/api/my/profile", &profile).await
@@ -29,9 +34,73 @@ pub async fn disable_totp() -> BambooApiResult<()> {
     api::delete("/api/my/totp").await
 }
 
-pub async fn validate_totp(code: String, password: String) -> BambooApiResult<()> {
+pub async fn validate_totp(code: String, password: String) -> BambooApiResult<TotpRecoveryCodes> {
     log::debug!("Validate totp for current user");
-    api::put_no_content("/api/my/totp/validate", &ValidateTotp { code, password }).await
+    api::put("/api/my/totp/validate", &ValidateTotp { code, password }).await
+}
+
+pub async fn get_totp_recovery_code_count() -> BambooApiResult<TotpRecoveryCodeCount> {
+    log::debug!("Get remaining totp recovery codes for current user");
+    api::get("/api/my/totp/recovery-codes").await
+}
+
+pub async fn regenerate_totp_recovery_codes() -> BambooApiResult<TotpRecoveryCodes> {
+    log::debug!("Regenerate totp recovery codes for current user");
+    api::post_no_body("/api/my/totp/recovery-codes").await
+}
+
+pub async fn get_passkeys() -> BambooApiResult<Vec<Passkey>> {
+    log::debug!("Get passkeys of current user");
+    api::get("/api/my/passkey").await
+}
+
+pub async fn register_passkey(name: String) -> BambooApiResult<Passkey> {
+    log::debug!("Register passkey {name} for current user");
+    let challenge =
+        api::post_no_body::<PasskeyRegistrationChallenge>("/api/my/passkey/challenge").await?;
+    let credential = api::create_passkey_credential(challenge.options).await?;
+
+    api::post(
+        "/api/my/passkey",
+        &FinishPasskeyRegistration {
+            challenge_id: challenge.challenge_id,
+            name,
+            credential,
+        },
+    )
+    .await
+}
+
+pub async fn delete_passkey(id: i32) -> BambooApiResult<()> {
+    log::debug!("Delete passkey {id}");
+    api::delete(format!("/api/my/passkey/{id}")).await
+}
+
+pub async fn get_personal_access_tokens() -> BambooApiResult<Vec<PersonalAccessToken>> {
+    log::debug!("Get personal access tokens of current user");
+    api::get("/api/my/tokens").await
+}
+
+pub async fn create_personal_access_token(
+    token: CreatePersonalAccessToken,
+) -> BambooApiResult<CreatedPersonalAccessToken> {
+    log::debug!("Create personal access token {}", token.name);
+    api::post("/api/my/tokens", &token).await
+}
+
+pub async fn delete_personal_access_token(id: i32) -> BambooApiResult<()> {
+    log::debug!("Delete personal access token {id}");
+    api::delete(format!("/api/my/tokens/{id}")).await
+}
+
+pub async fn get_my_groves() -> BambooApiResult<Vec<Grove>> {
+    log::debug!("Load my groves");
+    api::get("/api/my/groves").await
+}
+
+pub async fn change_active_grove(grove_id: i32) -> BambooApiResult<()> {
+    log::debug!("Change active grove to {grove_id}");
+    api::put_no_content("/api/my/grove", &ChangeActiveGrove { grove_id }).await
 }
 
 pub async fn leave() -> BambooApiResult<()> {
@@ -39,6 +108,15 @@ pub async fn leave() -> BambooApiResult<()> {
     api::delete("/api/my").await
 }
 
+pub async fn transfer_mod_rights_and_leave(successor_id: i32) -> BambooApiResult<()> {
+    log::debug!("Transfer mod rights to {successor_id} and leave the grove");
+    api::post_no_content(
+        "/api/my/transfer-and-leave",
+        &TransferModRights { successor_id },
+    )
+    .await
+}
+
 pub async fn upload_profile_picture(file: web_sys::File) -> BambooApiResult<()> {
     log::debug!("Change profile picture");
     api::upload_file("/api/my/picture", file).await
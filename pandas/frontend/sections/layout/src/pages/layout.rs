@@ -2,7 +2,10 @@ use std::ops::Deref;
 
 use bounce::helmet::Helmet;
 use bounce::{use_atom_setter, use_atom_value};
-use stylist::yew::use_style;
+use chrono::TimeZone;
+use strum::IntoEnumIterator;
+use stylist::css;
+use stylist::yew::{use_style, Global};
 use yew::prelude::*;
 use yew_autoprops::autoprops;
 use yew_cosmo::prelude::*;
@@ -10,13 +13,21 @@ use yew_hooks::{use_async, use_bool_toggle, use_mount, use_update, UseAsyncHandl
 use yew_router::prelude::*;
 
 use bamboo_common::core::entities::user::UpdateProfile;
-use bamboo_common::frontend::api::{ApiError, CONFLICT, FORBIDDEN, NOT_FOUND};
+use bamboo_common::core::entities::{
+    CreatePersonalAccessToken, Grove, Passkey, PersonalAccessToken, TokenScope, TokenScopes,
+    WebUser,
+};
+use bamboo_common::frontend::api::{
+    ApiError, BAD_REQUEST, CONFLICT, FORBIDDEN, NOT_FOUND, PASSKEY_ERROR,
+};
 use bamboo_pandas_frontend_base::routing::{
     AppRoute, BambooGroveRoute, FinalFantasyRoute, LegalRoute, LicensesRoute, ModAreaRoute,
     SupportRoute,
 };
 use bamboo_pandas_frontend_base::{error, storage};
-use bamboo_pandas_frontend_section_authentication::LoginPage;
+use bamboo_pandas_frontend_section_authentication::{
+    ConfirmEmailPage, InvitationPage, LoginPage, OidcLoginPage, ResetPasswordPage,
+};
 use bamboo_pandas_frontend_section_bamboo::CalendarPage;
 use bamboo_pandas_frontend_section_bamboo::UsersPage;
 use bamboo_pandas_frontend_section_final_fantasy::CharacterPage;
@@ -25,7 +36,10 @@ use bamboo_pandas_frontend_section_legal::{DataProtectionPage, ImprintPage};
 use bamboo_pandas_frontend_section_licenses::{
     BambooGrovePage, FontsPage, ImagesPage, SoftwareLicensesPage,
 };
-use bamboo_pandas_frontend_section_mod_area::{GroveManagementPage, UserManagementPage};
+use bamboo_pandas_frontend_section_mod_area::{
+    AuditLogPage, ExportPage, GroveManagementPage, InvitationManagementPage, RoleManagementPage,
+    UserManagementPage,
+};
 use bamboo_pandas_frontend_section_support::ContactPage;
 
 use crate::api;
@@ -40,6 +54,38 @@ pub fn switch(route: AppRoute) -> Html {
                 <LoginPage />
             </>
         ),
+        AppRoute::ResetPassword => html!(
+            <>
+                <Helmet>
+                    <title>{"Passwort zurücksetzen"}</title>
+                </Helmet>
+                <ResetPasswordPage />
+            </>
+        ),
+        AppRoute::ConfirmEmail => html!(
+            <>
+                <Helmet>
+                    <title>{"Email bestätigen"}</title>
+                </Helmet>
+                <ConfirmEmailPage />
+            </>
+        ),
+        AppRoute::Invitation => html!(
+            <>
+                <Helmet>
+                    <title>{"Einladung annehmen"}</title>
+                </Helmet>
+                <InvitationPage />
+            </>
+        ),
+        AppRoute::OidcLogin => html!(
+            <>
+                <Helmet>
+                    <title>{"Anmelden"}</title>
+                </Helmet>
+                <OidcLoginPage />
+            </>
+        ),
         _ => html!(<Layout />),
     }
 }
@@ -67,6 +113,10 @@ fn switch_sub_menu(route: AppRoute) -> Html {
             <CosmoSubMenuBar>
                 <Switch<ModAreaRoute> render={render_sub_menu_entry("Benutzerverwaltung", ModAreaRoute::UserManagement)} />
                 <Switch<ModAreaRoute> render={render_sub_menu_entry("Hainverwaltung", ModAreaRoute::GroveManagement)} />
+                <Switch<ModAreaRoute> render={render_sub_menu_entry("Einladungen", ModAreaRoute::InvitationManagement)} />
+                <Switch<ModAreaRoute> render={render_sub_menu_entry("Rollen", ModAreaRoute::RoleManagement)} />
+                <Switch<ModAreaRoute> render={render_sub_menu_entry("Moderationsprotokoll", ModAreaRoute::AuditLog)} />
+                <Switch<ModAreaRoute> render={render_sub_menu_entry("Datenexport", ModAreaRoute::Export)} />
             </CosmoSubMenuBar>
         ),
         AppRoute::LegalRoot | AppRoute::Legal => html!(
@@ -163,6 +213,38 @@ fn switch_mod_area(route: ModAreaRoute) -> Html {
                 <GroveManagementPage />
             </>
         ),
+        ModAreaRoute::InvitationManagement => html!(
+            <>
+                <Helmet>
+                    <title>{"Einladungen"}</title>
+                </Helmet>
+                <InvitationManagementPage />
+            </>
+        ),
+        ModAreaRoute::RoleManagement => html!(
+            <>
+                <Helmet>
+                    <title>{"Rollen"}</title>
+                </Helmet>
+                <RoleManagementPage />
+            </>
+        ),
+        ModAreaRoute::AuditLog => html!(
+            <>
+                <Helmet>
+                    <title>{"Moderationsprotokoll"}</title>
+                </Helmet>
+                <AuditLogPage />
+            </>
+        ),
+        ModAreaRoute::Export => html!(
+            <>
+                <Helmet>
+                    <title>{"Datenexport"}</title>
+                </Helmet>
+                <ExportPage />
+            </>
+        ),
     }
 }
 
@@ -224,7 +306,7 @@ fn switch_licenses(route: LicensesRoute) -> Html {
     }
 }
 
-fn switch_app(is_mod: bool, grove_is_enabled: bool) -> impl Fn(AppRoute) -> Html {
+fn switch_app(can_moderate: bool, grove_is_enabled: bool) -> impl Fn(AppRoute) -> Html {
     move |route| {
         if grove_is_enabled {
             match route {
@@ -256,7 +338,7 @@ fn switch_app(is_mod: bool, grove_is_enabled: bool) -> impl Fn(AppRoute) -> Html
                     </>
                 ),
                 AppRoute::ModAreaRoot | AppRoute::ModArea => {
-                    if is_mod {
+                    if can_moderate {
                         html!(
                             <>
                                 <Helmet>
@@ -287,7 +369,11 @@ fn switch_app(is_mod: bool, grove_is_enabled: bool) -> impl Fn(AppRoute) -> Html
                         <Switch<LicensesRoute> render={switch_licenses} />
                     </>
                 ),
-                AppRoute::Login => html!(),
+                AppRoute::Login
+                | AppRoute::OidcLogin
+                | AppRoute::ResetPassword
+                | AppRoute::ConfirmEmail
+                | AppRoute::Invitation => html!(),
             }
         } else {
             match route {
@@ -349,7 +435,11 @@ fn render_sub_menu_entry<Route: Routable + Clone + 'static>(
 
 fn switch_top_bar(route: AppRoute) -> Html {
     match route {
-        AppRoute::Login => html!(),
+        AppRoute::Login
+        | AppRoute::OidcLogin
+        | AppRoute::ResetPassword
+        | AppRoute::ConfirmEmail
+        | AppRoute::Invitation => html!(),
         AppRoute::LegalRoot | AppRoute::Legal | AppRoute::LicensesRoot | AppRoute::Licenses => {
             html!(
                 <TopBarLegal />
@@ -374,23 +464,27 @@ fn switch_layout(route: AppRoute) -> Html {
 fn app_layout() -> Html {
     log::debug!("Render app layout");
     let profile_atom_setter = use_atom_setter::<storage::CurrentUser>();
+    let permissions_atom_setter = use_atom_setter::<storage::CurrentPermissions>();
+    let grove_settings_atom_setter = use_atom_setter::<storage::CurrentGroveSettings>();
 
     let grove_is_enabled = use_bool_toggle(true);
 
     let navigator = use_navigator();
 
     let profile_state = use_async(async move {
-        api::get_my_profile().await.map(|user| {
-            profile_atom_setter(user.clone().into());
-            user
-        })
+        let user = api::get_my_profile().await?;
+        let permissions = api::get_my_permissions().await?;
+        profile_atom_setter(user.clone().into());
+        permissions_atom_setter(permissions.clone().into());
+
+        Ok::<_, ApiError>((user, permissions))
     });
     let grove_state = {
         let grove_is_enabled = grove_is_enabled.clone();
 
         use_async(async move {
             api::get_grove().await.map(|grove| {
-                if !grove.is_enabled {
+                if !grove.is_enabled || grove.is_deleted() {
                     if let Some(navigator) = navigator {
                         navigator.push(&AppRoute::ModAreaRoot);
                     }
@@ -399,6 +493,11 @@ fn app_layout() -> Html {
             })
         })
     };
+    let grove_settings_state = use_async(async move {
+        api::get_grove_settings()
+            .await
+            .map(|settings| grove_settings_atom_setter(settings.into()))
+    });
 
     {
         let profile_state = profile_state.clone();
@@ -409,14 +508,16 @@ fn app_layout() -> Html {
             );
             profile_state.run();
             grove_state.run();
+            grove_settings_state.run();
         });
     }
 
     html!(
         if let Some(_) = &profile_state.error {
             <Redirect<AppRoute> to={AppRoute::Login} />
-        } else if let Some(profile) = &profile_state.data {
+        } else if let Some((_, permissions)) = &profile_state.data {
             <>
+                <GroveBranding />
                 <Switch<AppRoute> render={switch_top_bar}/>
                 <CosmoMenuBar>
                     <CosmoMainMenu>
@@ -425,20 +526,55 @@ fn app_layout() -> Html {
                             <Switch<AppRoute> render={render_main_menu_entry("Final Fantasy", AppRoute::FinalFantasyRoot, AppRoute::FinalFantasy)} />
                             <Switch<AppRoute> render={render_main_menu_entry("Bambussupport", AppRoute::SupportRoot, AppRoute::Support)} />
                         }
-                        if profile.is_mod {
+                        if !permissions.is_empty() {
                             <Switch<AppRoute> render={render_main_menu_entry("Mod Area", AppRoute::ModAreaRoot, AppRoute::ModArea)} />
                         }
                     </CosmoMainMenu>
                     <Switch<AppRoute> render={switch_sub_menu} />
                 </CosmoMenuBar>
                 <CosmoPageBody>
-                    <Switch<AppRoute> render={switch_app(profile.is_mod, *grove_is_enabled)} />
+                    <Switch<AppRoute> render={switch_app(!permissions.is_empty(), *grove_is_enabled)} />
                </CosmoPageBody>
             </>
         }
     )
 }
 
+#[function_component(GroveBranding)]
+fn grove_branding() -> Html {
+    let grove_settings_atom = use_atom_value::<storage::CurrentGroveSettings>();
+    let settings = &grove_settings_atom.settings;
+
+    let accent_color = settings.accent_color();
+    let style = css!(
+        r#"
+html:root {
+    --primary-hue: ${hue};
+    --primary-saturation: ${saturation}%;
+    --primary-lightness-base: ${lightness}%;
+}
+"#,
+        hue = accent_color.hue(),
+        saturation = accent_color.saturation() * 100.0,
+        lightness = accent_color.lightness() * 100.0,
+    );
+
+    html!(
+        <>
+            <Helmet>
+                <html lang={settings.language.get_code()} />
+                if !settings.description.is_empty() {
+                    <meta name="description" content={settings.description.clone()} />
+                }
+                if settings.has_logo {
+                    <link rel="icon" href="/api/grove/logo" />
+                }
+            </Helmet>
+            <Global css={style} />
+        </>
+    )
+}
+
 #[function_component(LegalLayout)]
 fn legal_layout() -> Html {
     log::debug!("Render legal layout");
@@ -511,7 +647,7 @@ fn change_password_dialog(on_close: &Callback<()>, mods: &Vec<AttrValue>) -> Htm
                 unreported_error_toggle.set(false);
             })
             .map_err(|err| {
-                unreported_error_toggle.set(true);
+                unreported_error_toggle.set(err.code != BAD_REQUEST);
                 bamboo_error_state.set(err.clone());
                 err
             })
@@ -521,6 +657,12 @@ fn change_password_dialog(on_close: &Callback<()>, mods: &Vec<AttrValue>) -> Htm
     let on_close = on_close.clone();
     let on_save = use_callback(save_state.clone(), |_, state| state.run());
 
+    let password_policy_violations = save_state
+        .error
+        .as_ref()
+        .map(error::get_password_policy_violations)
+        .unwrap_or_default();
+
     html!(
         <>
             <Helmet>
@@ -537,6 +679,8 @@ fn change_password_dialog(on_close: &Callback<()>, mods: &Vec<AttrValue>) -> Htm
                         <CosmoMessage message_type={CosmoMessageType::Negative} message="Wenn du möchtest dass es von einem Mod zurückgesetzt wird, einfach anschreiben" header="Das alte Passwort ist falsch" />
                     } else if err.code == NOT_FOUND {
                         <CosmoMessage message_type={CosmoMessageType::Negative} message="Bitte versuch es erneut um einen Fehler auszuschließen" header="Du wurdest scheinbar gelöscht" />
+                    } else if !password_policy_violations.is_empty() {
+                        <CosmoMessage message_type={CosmoMessageType::Negative} message={password_policy_violations.join("\n")} header="Das neue Passwort ist nicht sicher genug" />
                     } else if *unreported_error_toggle {
                         <CosmoMessage message_type={CosmoMessageType::Negative} message="Leider konnte dein Passwort nicht geändert werden" header="Fehler beim ändern" actions={html!(<CosmoButton label="Fehler melden" on_click={report_unknown_error} />)} />
                     } else {
@@ -563,11 +707,16 @@ fn update_my_profile_dialog(on_close: &Callback<()>) -> Html {
 
     let disable_totp_open_toggle = use_bool_toggle(false);
     let app_two_factor_open_toggle = use_bool_toggle(false);
+    let passkeys_open_toggle = use_bool_toggle(false);
+    let personal_access_tokens_open_toggle = use_bool_toggle(false);
+    let regenerate_recovery_codes_open_toggle = use_bool_toggle(false);
+    let recovery_codes_open_toggle = use_bool_toggle(false);
     let unreported_error_toggle = use_bool_toggle(false);
 
     let bamboo_error_state = use_state_eq(ApiError::default);
 
     let profile_picture_state = use_state_eq(|| None as Option<web_sys::File>);
+    let pending_email_state = use_state_eq(|| None as Option<AttrValue>);
 
     let email_state = use_state_eq(|| AttrValue::from(profile_atom.profile.email.clone()));
     let display_name_state =
@@ -608,10 +757,22 @@ fn update_my_profile_dialog(on_close: &Callback<()>) -> Html {
         let discord_name_state = discord_name_state.clone();
 
         let profile_picture_state = profile_picture_state.clone();
+        let pending_email_state = pending_email_state.clone();
+
+        let current_email = profile_atom.profile.email.clone();
 
         let on_close = on_close.clone();
 
         use_async(async move {
+            let email = (*email_state).clone();
+            let email_changed = !current_email.eq_ignore_ascii_case(email.trim());
+            let on_saved = move || {
+                if email_changed {
+                    pending_email_state.set(Some(email));
+                } else {
+                    on_close.emit(());
+                }
+            };
             let result = api::update_my_profile(UpdateProfile::new(
                 (*email_state).to_string(),
                 (*display_name_state).to_string(),
@@ -630,7 +791,7 @@ fn update_my_profile_dialog(on_close: &Callback<()>) -> Html {
                         .await
                         .map(|_| {
                             unreported_error_toggle.set(false);
-                            on_close.emit(())
+                            on_saved()
                         })
                         .map_err(|err| {
                             unreported_error_toggle.set(true);
@@ -646,7 +807,7 @@ fn update_my_profile_dialog(on_close: &Callback<()>) -> Html {
 
                     profile_result
                 } else {
-                    on_close.emit(());
+                    on_saved();
                     result
                 }
             } else {
@@ -681,6 +842,67 @@ fn update_my_profile_dialog(on_close: &Callback<()>) -> Html {
         })
     };
 
+    let recovery_code_count_state = {
+        let app_totp_enabled = profile_atom.profile.app_totp_enabled;
+
+        use_async(async move {
+            if app_totp_enabled {
+                api::get_totp_recovery_code_count()
+                    .await
+                    .map(|count| Some(count.remaining))
+            } else {
+                Ok(None)
+            }
+        })
+    };
+    let regenerate_recovery_codes_state = {
+        let unreported_error_toggle = unreported_error_toggle.clone();
+        let regenerate_recovery_codes_open_toggle = regenerate_recovery_codes_open_toggle.clone();
+        let recovery_codes_open_toggle = recovery_codes_open_toggle.clone();
+
+        let bamboo_error_state = bamboo_error_state.clone();
+
+        let recovery_code_count_state = recovery_code_count_state.clone();
+
+        use_async(async move {
+            regenerate_recovery_codes_open_toggle.set(false);
+            api::regenerate_totp_recovery_codes()
+                .await
+                .map(|recovery_codes| {
+                    unreported_error_toggle.set(false);
+                    recovery_codes_open_toggle.set(true);
+                    recovery_code_count_state.run();
+
+                    recovery_codes
+                })
+                .map_err(|err| {
+                    unreported_error_toggle.set(true);
+                    bamboo_error_state.set(err.clone());
+                    err
+                })
+        })
+    };
+
+    {
+        let recovery_code_count_state = recovery_code_count_state.clone();
+
+        use_effect_with(profile_atom.profile.app_totp_enabled, move |_| {
+            recovery_code_count_state.run()
+        });
+    }
+
+    let on_open_regenerate_recovery_codes =
+        use_callback(regenerate_recovery_codes_open_toggle.clone(), |_, state| {
+            state.set(true)
+        });
+    let on_close_regenerate_recovery_codes =
+        use_callback(regenerate_recovery_codes_open_toggle.clone(), |_, state| {
+            state.set(false)
+        });
+    let on_regenerate_recovery_codes =
+        use_callback(regenerate_recovery_codes_state.clone(), |_, state| {
+            state.run()
+        });
     let on_open_disable_totp =
         use_callback(disable_totp_open_toggle.clone(), |_, state| state.set(true));
     let on_close_disable_totp = use_callback(disable_totp_open_toggle.clone(), |_, state| {
@@ -690,6 +912,11 @@ fn update_my_profile_dialog(on_close: &Callback<()>) -> Html {
         app_two_factor_open_toggle.clone(),
         |_, app_two_factor_open_toggle| app_two_factor_open_toggle.set(true),
     );
+    let on_manage_passkeys = use_callback(passkeys_open_toggle.clone(), |_, state| state.set(true));
+    let on_manage_personal_access_tokens =
+        use_callback(personal_access_tokens_open_toggle.clone(), |_, state| {
+            state.set(true)
+        });
     let on_save = use_callback(save_state.clone(), |_, save_state| save_state.run());
     let on_disable_totp = use_callback(disable_totp_state.clone(), |_, disable_totp_state| {
         disable_totp_state.run()
@@ -709,9 +936,14 @@ fn update_my_profile_dialog(on_close: &Callback<()>) -> Html {
                     } else {
                         <CosmoButton on_click={on_enable_app_two_factor} label="App Zwei Faktor aktivieren" />
                     }
+                    <CosmoButton on_click={on_manage_passkeys} label="Passkeys verwalten" />
+                    <CosmoButton on_click={on_manage_personal_access_tokens} label="API Tokens verwalten" />
                     <CosmoButton is_submit={true} label="Profil speichern" />
                 </>
             )}>
+                if let Some(pending_email) = (*pending_email_state).clone() {
+                    <CosmoMessage message_type={CosmoMessageType::Information} message={format!("Wir haben dir einen Link an {pending_email} geschickt. Bis du ihn öffnest, bleibt deine bisherige Email aktiv.")} header="Bestätige deine neue Email" />
+                }
                 if let Some(err) = &save_state.error {
                     if err.code == NOT_FOUND {
                         <CosmoMessage message_type={CosmoMessageType::Negative} message="Bitte versuch es erneut um einen Fehler auszuschließen" header="Du wurdest scheinbar gelöscht" />
@@ -727,11 +959,24 @@ fn update_my_profile_dialog(on_close: &Callback<()>) -> Html {
                     if err.code == NOT_FOUND {
                         <CosmoMessage message_type={CosmoMessageType::Negative} message="Bitte versuch es erneut um einen Fehler auszuschließen" header="Du wurdest scheinbar gelöscht" />
                     } else if *unreported_error_toggle {
-                        <CosmoMessage message_type={CosmoMessageType::Negative} message="Zwei Faktor per App konnte leider nicht deaktiviert werden" header="Fehler beim Deaktivieren" actions={html!(<CosmoButton label="Fehler melden" on_click={report_unknown_error} />)} />
+                        <CosmoMessage message_type={CosmoMessageType::Negative} message="Zwei Faktor per App konnte leider nicht deaktiviert werden" header="Fehler beim Deaktivieren" actions={html!(<CosmoButton label="Fehler melden" on_click={report_unknown_error.clone()} />)} />
                     } else {
                         <CosmoMessage message_type={CosmoMessageType::Negative} message="Zwei Faktor per App konnte leider nicht deaktiviert werden" header="Fehler beim Deaktivieren" />
                     }
                 }
+                if regenerate_recovery_codes_state.error.is_some() {
+                    if *unreported_error_toggle {
+                        <CosmoMessage message_type={CosmoMessageType::Negative} message="Die Wiederherstellungscodes konnten leider nicht neu erzeugt werden" header="Fehler beim Erzeugen" actions={html!(<CosmoButton label="Fehler melden" on_click={report_unknown_error} />)} />
+                    } else {
+                        <CosmoMessage message_type={CosmoMessageType::Negative} message="Die Wiederherstellungscodes konnten leider nicht neu erzeugt werden" header="Fehler beim Erzeugen" />
+                    }
+                }
+                if let Some(Some(remaining)) = recovery_code_count_state.data {
+                    <CosmoParagraph>
+                        {format!("Du hast noch {remaining} Wiederherstellungscodes für die Anmeldung ohne App übrig. ")}
+                        <CosmoButton on_click={on_open_regenerate_recovery_codes} label="Neue Wiederherstellungscodes erzeugen" />
+                    </CosmoParagraph>
+                }
                 <CosmoInputGroup>
                     <CosmoTextBox label="Email" input_type={CosmoTextBoxType::Email} required={true} on_input={update_email} value={(*email_state).clone()} />
                     <CosmoTextBox label="Name" required={true} on_input={update_display_name} value={(*display_name_state).clone()} />
@@ -745,6 +990,404 @@ fn update_my_profile_dialog(on_close: &Callback<()>) -> Html {
             if *app_two_factor_open_toggle {
                 <EnableTotpDialog on_close={move |_| app_two_factor_open_toggle.set(false)} />
             }
+            if *passkeys_open_toggle {
+                <ManagePasskeysDialog on_close={move |_| passkeys_open_toggle.set(false)} />
+            }
+            if *personal_access_tokens_open_toggle {
+                <ManagePersonalAccessTokensDialog on_close={move |_| personal_access_tokens_open_toggle.set(false)} />
+            }
+            if *regenerate_recovery_codes_open_toggle {
+                <CosmoConfirm confirm_type={CosmoModalType::Warning} message="Möchtest du neue Wiederherstellungscodes erzeugen? Deine bisherigen Codes funktionieren danach nicht mehr." title="Wiederherstellungscodes erzeugen" on_decline={on_close_regenerate_recovery_codes} on_confirm={on_regenerate_recovery_codes} confirm_label="Neu erzeugen" decline_label="Nicht erzeugen" />
+            }
+            if *recovery_codes_open_toggle {
+                if let Some(recovery_codes) = &regenerate_recovery_codes_state.data {
+                    <TotpRecoveryCodesDialog codes={recovery_codes.codes.clone()} on_close={move |_| recovery_codes_open_toggle.set(false)} />
+                }
+            }
+        </>
+    )
+}
+
+#[autoprops]
+#[function_component(ManagePasskeysDialog)]
+fn manage_passkeys_dialog(on_close: &Callback<()>) -> Html {
+    log::debug!("Open dialog to manage passkeys");
+    let unreported_error_toggle = use_bool_toggle(false);
+
+    let bamboo_error_state = use_state_eq(ApiError::default);
+    let name_state = use_state_eq(|| AttrValue::from(""));
+    let delete_passkey_state = use_state_eq(|| None as Option<Passkey>);
+
+    let passkeys_state = {
+        let unreported_error_toggle = unreported_error_toggle.clone();
+
+        let bamboo_error_state = bamboo_error_state.clone();
+
+        use_async(async move {
+            api::get_passkeys().await.map_err(|err| {
+                unreported_error_toggle.set(true);
+                bamboo_error_state.set(err.clone());
+                err
+            })
+        })
+    };
+    let register_passkey_state = {
+        let unreported_error_toggle = unreported_error_toggle.clone();
+
+        let bamboo_error_state = bamboo_error_state.clone();
+        let name_state = name_state.clone();
+
+        let passkeys_state = passkeys_state.clone();
+
+        use_async(async move {
+            api::register_passkey((*name_state).to_string())
+                .await
+                .map(|_| {
+                    unreported_error_toggle.set(false);
+                    name_state.set(AttrValue::from(""));
+                    passkeys_state.run();
+                })
+                .map_err(|err| {
+                    unreported_error_toggle.set(err.code != PASSKEY_ERROR);
+                    bamboo_error_state.set(err.clone());
+                    err
+                })
+        })
+    };
+    let delete_passkey = {
+        let unreported_error_toggle = unreported_error_toggle.clone();
+
+        let bamboo_error_state = bamboo_error_state.clone();
+        let delete_passkey_state = delete_passkey_state.clone();
+
+        let passkeys_state = passkeys_state.clone();
+
+        use_async(async move {
+            if let Some(passkey) = (*delete_passkey_state).clone() {
+                delete_passkey_state.set(None);
+                api::delete_passkey(passkey.id)
+                    .await
+                    .map(|_| {
+                        unreported_error_toggle.set(false);
+                        passkeys_state.run();
+                    })
+                    .map_err(|err| {
+                        unreported_error_toggle.set(true);
+                        bamboo_error_state.set(err.clone());
+                        err
+                    })
+            } else {
+                Ok(())
+            }
+        })
+    };
+
+    {
+        let passkeys_state = passkeys_state.clone();
+
+        use_mount(move || passkeys_state.run());
+    }
+
+    let update_name = use_callback(name_state.clone(), |value, state| state.set(value));
+    let report_unknown_error = use_callback(
+        (bamboo_error_state.clone(), unreported_error_toggle.clone()),
+        |_, (bamboo_error_state, unreported_error_toggle)| {
+            error::report_unknown_error(
+                "layout",
+                "manage_passkeys_dialog",
+                bamboo_error_state.deref().clone(),
+            );
+            unreported_error_toggle.set(false);
+        },
+    );
+    let on_form_submit = use_callback(register_passkey_state.clone(), |_, state| state.run());
+    let on_decline_delete = use_callback(delete_passkey_state.clone(), |_, state| state.set(None));
+    let on_confirm_delete = use_callback(delete_passkey.clone(), |_, state| state.run());
+
+    html!(
+        <>
+            <Helmet>
+                <title>{"Passkeys verwalten"}</title>
+            </Helmet>
+            <CosmoModal title="Passkeys verwalten" is_form={true} on_form_submit={on_form_submit} buttons={html!(
+                <>
+                    <CosmoButton on_click={on_close.clone()} label="Schließen" />
+                    <CosmoButton is_submit={true} label="Passkey hinzufügen" />
+                </>
+            )}>
+                if let Some(err) = &register_passkey_state.error {
+                    if err.code == PASSKEY_ERROR {
+                        <CosmoMessage header="Passkey nicht hinzugefügt" message="Das Hinzufügen des Passkeys wurde abgebrochen oder von deinem Gerät nicht unterstützt" message_type={CosmoMessageType::Negative} />
+                    } else if *unreported_error_toggle {
+                        <CosmoMessage header="Fehler beim Hinzufügen" message="Leider konnte der Passkey nicht hinzugefügt werden" message_type={CosmoMessageType::Negative} actions={html!(<CosmoButton label="Fehler melden" on_click={report_unknown_error.clone()} />)} />
+                    } else {
+                        <CosmoMessage header="Fehler beim Hinzufügen" message="Leider konnte der Passkey nicht hinzugefügt werden" message_type={CosmoMessageType::Negative} />
+                    }
+                }
+                if delete_passkey.error.is_some() {
+                    if *unreported_error_toggle {
+                        <CosmoMessage header="Fehler beim Entfernen" message="Leider konnte der Passkey nicht entfernt werden" message_type={CosmoMessageType::Negative} actions={html!(<CosmoButton label="Fehler melden" on_click={report_unknown_error.clone()} />)} />
+                    } else {
+                        <CosmoMessage header="Fehler beim Entfernen" message="Leider konnte der Passkey nicht entfernt werden" message_type={CosmoMessageType::Negative} />
+                    }
+                }
+                if passkeys_state.error.is_some() {
+                    if *unreported_error_toggle {
+                        <CosmoMessage header="Fehler beim Laden" message="Leider konnten deine Passkeys nicht geladen werden" message_type={CosmoMessageType::Negative} actions={html!(<CosmoButton label="Fehler melden" on_click={report_unknown_error} />)} />
+                    } else {
+                        <CosmoMessage header="Fehler beim Laden" message="Leider konnten deine Passkeys nicht geladen werden" message_type={CosmoMessageType::Negative} />
+                    }
+                }
+                <CosmoParagraph>{"Mit einem Passkey kannst du dich ohne Passwort und Zwei Faktor Code anmelden, zum Beispiel mit deinem Fingerabdruck, deinem Gesicht oder einem Sicherheitsschlüssel."}</CosmoParagraph>
+                if let Some(passkeys) = &passkeys_state.data {
+                    if passkeys.is_empty() {
+                        <CosmoParagraph>{"Du hast noch keine Passkeys eingerichtet"}</CosmoParagraph>
+                    } else {
+                        <CosmoKeyValueList>
+                            {for passkeys.iter().map(|passkey| {
+                                let delete_passkey_state = delete_passkey_state.clone();
+                                let passkey_to_delete = passkey.clone();
+
+                                html!(
+                                    <CosmoKeyValueListItem title={passkey.name.clone()}>
+                                        {format!("Hinzugefügt am {}", chrono::Local.from_utc_datetime(&passkey.created_at).format("%d.%m.%Y"))}
+                                        if let Some(last_used_at) = passkey.last_used_at {
+                                            {format!(", zuletzt benutzt am {}", chrono::Local.from_utc_datetime(&last_used_at).format("%d.%m.%Y %H:%M"))}
+                                        }
+                                        <CosmoButton label="Entfernen" on_click={move |_| delete_passkey_state.set(Some(passkey_to_delete.clone()))} />
+                                    </CosmoKeyValueListItem>
+                                )
+                            })}
+                        </CosmoKeyValueList>
+                    }
+                }
+                <CosmoInputGroup>
+                    <CosmoTextBox label="Name des neuen Passkeys" required={true} on_input={update_name} value={(*name_state).clone()} />
+                </CosmoInputGroup>
+            </CosmoModal>
+            if let Some(passkey) = (*delete_passkey_state).clone() {
+                <CosmoConfirm confirm_type={CosmoModalType::Warning} message={format!("Soll der Passkey {} wirklich entfernt werden? Du kannst dich danach nicht mehr damit anmelden.", passkey.name)} title="Passkey entfernen" on_decline={on_decline_delete} on_confirm={on_confirm_delete} confirm_label="Passkey entfernen" decline_label="Passkey behalten" />
+            }
+        </>
+    )
+}
+
+#[autoprops]
+#[function_component(ManagePersonalAccessTokensDialog)]
+fn manage_personal_access_tokens_dialog(on_close: &Callback<()>) -> Html {
+    log::debug!("Open dialog to manage personal access tokens");
+    let unreported_error_toggle = use_bool_toggle(false);
+
+    let bamboo_error_state = use_state_eq(ApiError::default);
+    let name_state = use_state_eq(|| AttrValue::from(""));
+    let scopes_state = use_state_eq(Vec::<TokenScope>::new);
+    let expires_in_days_state = use_state_eq(|| None as Option<AttrValue>);
+    let created_token_state = use_state_eq(|| None as Option<AttrValue>);
+    let delete_token_state = use_state_eq(|| None as Option<PersonalAccessToken>);
+
+    let tokens_state = {
+        let unreported_error_toggle = unreported_error_toggle.clone();
+
+        let bamboo_error_state = bamboo_error_state.clone();
+
+        use_async(async move {
+            api::get_personal_access_tokens().await.map_err(|err| {
+                unreported_error_toggle.set(true);
+                bamboo_error_state.set(err.clone());
+                err
+            })
+        })
+    };
+    let create_token_state = {
+        let unreported_error_toggle = unreported_error_toggle.clone();
+
+        let bamboo_error_state = bamboo_error_state.clone();
+        let name_state = name_state.clone();
+        let scopes_state = scopes_state.clone();
+        let expires_in_days_state = expires_in_days_state.clone();
+        let created_token_state = created_token_state.clone();
+
+        let tokens_state = tokens_state.clone();
+
+        use_async(async move {
+            let expires_at = (*expires_in_days_state)
+                .clone()
+                .and_then(|days| days.parse::<i64>().ok())
+                .map(|days| chrono::Utc::now().naive_utc() + chrono::Duration::days(days));
+
+            api::create_personal_access_token(CreatePersonalAccessToken {
+                name: (*name_state).to_string(),
+                scopes: (*scopes_state).clone(),
+                expires_at,
+            })
+            .await
+            .map(|created| {
+                unreported_error_toggle.set(false);
+                name_state.set(AttrValue::from(""));
+                scopes_state.set(vec![]);
+                expires_in_days_state.set(None);
+                created_token_state.set(Some(AttrValue::from(created.token)));
+                tokens_state.run();
+            })
+            .map_err(|err| {
+                unreported_error_toggle.set(err.code != BAD_REQUEST);
+                bamboo_error_state.set(err.clone());
+                err
+            })
+        })
+    };
+    let delete_token = {
+        let unreported_error_toggle = unreported_error_toggle.clone();
+
+        let bamboo_error_state = bamboo_error_state.clone();
+        let delete_token_state = delete_token_state.clone();
+
+        let tokens_state = tokens_state.clone();
+
+        use_async(async move {
+            if let Some(token) = (*delete_token_state).clone() {
+                delete_token_state.set(None);
+                api::delete_personal_access_token(token.id)
+                    .await
+                    .map(|_| {
+                        unreported_error_toggle.set(false);
+                        tokens_state.run();
+                    })
+                    .map_err(|err| {
+                        unreported_error_toggle.set(true);
+                        bamboo_error_state.set(err.clone());
+                        err
+                    })
+            } else {
+                Ok(())
+            }
+        })
+    };
+
+    {
+        let tokens_state = tokens_state.clone();
+
+        use_mount(move || tokens_state.run());
+    }
+
+    let update_name = use_callback(name_state.clone(), |value, state| state.set(value));
+    let update_expires_in_days = use_callback(expires_in_days_state.clone(), |value, state| {
+        state.set(value)
+    });
+    let report_unknown_error = use_callback(
+        (bamboo_error_state.clone(), unreported_error_toggle.clone()),
+        |_, (bamboo_error_state, unreported_error_toggle)| {
+            error::report_unknown_error(
+                "layout",
+                "manage_personal_access_tokens_dialog",
+                bamboo_error_state.deref().clone(),
+            );
+            unreported_error_toggle.set(false);
+        },
+    );
+    let on_form_submit = use_callback(create_token_state.clone(), |_, state| state.run());
+    let on_decline_delete = use_callback(delete_token_state.clone(), |_, state| state.set(None));
+    let on_confirm_delete = use_callback(delete_token.clone(), |_, state| state.run());
+
+    let format_scopes = |scopes: &TokenScopes| {
+        scopes
+            .0
+            .iter()
+            .map(|scope| scope.to_string())
+            .collect::<Vec<String>>()
+            .join(", ")
+    };
+
+    html!(
+        <>
+            <Helmet>
+                <title>{"API Tokens verwalten"}</title>
+            </Helmet>
+            <CosmoModal title="API Tokens verwalten" is_form={true} on_form_submit={on_form_submit} buttons={html!(
+                <>
+                    <CosmoButton on_click={on_close.clone()} label="Schließen" />
+                    <CosmoButton is_submit={true} label="Token erstellen" />
+                </>
+            )}>
+                if let Some(token) = (*created_token_state).clone() {
+                    <CosmoMessage header="Token erstellt" message={format!("Dein neuer Token lautet {token}\nKopiere ihn jetzt, er wird dir nicht noch einmal angezeigt.")} message_type={CosmoMessageType::Positive} />
+                }
+                if create_token_state.error.is_some() {
+                    if *unreported_error_toggle {
+                        <CosmoMessage header="Fehler beim Erstellen" message="Leider konnte der Token nicht erstellt werden" message_type={CosmoMessageType::Negative} actions={html!(<CosmoButton label="Fehler melden" on_click={report_unknown_error.clone()} />)} />
+                    } else {
+                        <CosmoMessage header="Fehler beim Erstellen" message="Bitte gib einen Namen an und wähle mindestens eine Berechtigung aus" message_type={CosmoMessageType::Negative} />
+                    }
+                }
+                if delete_token.error.is_some() {
+                    if *unreported_error_toggle {
+                        <CosmoMessage header="Fehler beim Löschen" message="Leider konnte der Token nicht gelöscht werden" message_type={CosmoMessageType::Negative} actions={html!(<CosmoButton label="Fehler melden" on_click={report_unknown_error.clone()} />)} />
+                    } else {
+                        <CosmoMessage header="Fehler beim Löschen" message="Leider konnte der Token nicht gelöscht werden" message_type={CosmoMessageType::Negative} />
+                    }
+                }
+                if tokens_state.error.is_some() {
+                    if *unreported_error_toggle {
+                        <CosmoMessage header="Fehler beim Laden" message="Leider konnten deine Tokens nicht geladen werden" message_type={CosmoMessageType::Negative} actions={html!(<CosmoButton label="Fehler melden" on_click={report_unknown_error} />)} />
+                    } else {
+                        <CosmoMessage header="Fehler beim Laden" message="Leider konnten deine Tokens nicht geladen werden" message_type={CosmoMessageType::Negative} />
+                    }
+                }
+                <CosmoParagraph>{"Mit einem API Token können Programme wie ein Discord Bot in deinem Namen auf Pandas zugreifen. Der Token darf nur das, was du bei den Berechtigungen auswählst und was du selbst auch darfst. Übergib ihn im Header als Authorization: Token <dein Token>."}</CosmoParagraph>
+                if let Some(tokens) = &tokens_state.data {
+                    if tokens.is_empty() {
+                        <CosmoParagraph>{"Du hast noch keine API Tokens erstellt"}</CosmoParagraph>
+                    } else {
+                        <CosmoKeyValueList>
+                            {for tokens.iter().map(|token| {
+                                let delete_token_state = delete_token_state.clone();
+                                let token_to_delete = token.clone();
+
+                                html!(
+                                    <CosmoKeyValueListItem title={token.name.clone()}>
+                                        {format_scopes(&token.scopes)}
+                                        <br />
+                                        {format!("Erstellt am {}", chrono::Local.from_utc_datetime(&token.created_at).format("%d.%m.%Y"))}
+                                        if let Some(expires_at) = token.expires_at {
+                                            {format!(", läuft ab am {}", chrono::Local.from_utc_datetime(&expires_at).format("%d.%m.%Y"))}
+                                        }
+                                        if let Some(last_used_at) = token.last_used_at {
+                                            {format!(", zuletzt benutzt am {}", chrono::Local.from_utc_datetime(&last_used_at).format("%d.%m.%Y %H:%M"))}
+                                        }
+                                        <CosmoButton label="Löschen" on_click={move |_| delete_token_state.set(Some(token_to_delete.clone()))} />
+                                    </CosmoKeyValueListItem>
+                                )
+                            })}
+                        </CosmoKeyValueList>
+                    }
+                }
+                <CosmoInputGroup>
+                    <CosmoTextBox label="Name des neuen Tokens" required={true} on_input={update_name} value={(*name_state).clone()} />
+                    <CosmoDropdown label="Ablauf" on_select={update_expires_in_days} value={(*expires_in_days_state).clone()} items={vec![
+                        (None, AttrValue::from("Läuft nie ab")),
+                        (Some(AttrValue::from("30")), AttrValue::from("In 30 Tagen")),
+                        (Some(AttrValue::from("90")), AttrValue::from("In 90 Tagen")),
+                        (Some(AttrValue::from("365")), AttrValue::from("In einem Jahr")),
+                    ]} />
+                    {for TokenScope::iter().map(|scope| {
+                        let scopes_state = scopes_state.clone();
+
+                        html_nested!(
+                            <CosmoCheckbox label={scope.to_string()} checked={scopes_state.contains(&scope)} on_check={move |checked| {
+                                let mut scopes = (*scopes_state).clone();
+                                scopes.retain(|item| *item != scope);
+                                if checked {
+                                    scopes.push(scope);
+                                }
+                                scopes_state.set(scopes);
+                            }} />
+                        )
+                    })}
+                </CosmoInputGroup>
+            </CosmoModal>
+            if let Some(token) = (*delete_token_state).clone() {
+                <CosmoConfirm confirm_type={CosmoModalType::Warning} message={format!("Soll der Token {} wirklich gelöscht werden? Programme, die ihn verwenden, haben danach keinen Zugriff mehr.", token.name)} title="Token löschen" on_decline={on_decline_delete} on_confirm={on_confirm_delete} confirm_label="Token löschen" decline_label="Token behalten" />
+            }
         </>
     )
 }
@@ -788,30 +1431,30 @@ fn enable_totp_dialog(on_close: &Callback<()>) -> Html {
         let code_state = code_state.clone();
         let current_password_state = current_password_state.clone();
 
-        let on_close = on_close.clone();
-
         let profile_atom = profile_atom.clone();
 
         use_async(async move {
-            if let Err(err) = api::validate_totp(
+            match api::validate_totp(
                 (*code_state).to_string(),
                 (*current_password_state).to_string(),
             )
             .await
             {
-                log::error!("Failed to validate token: {err}");
-                unreported_error_toggle.set(true);
-                bamboo_error_state.set(err.clone());
+                Ok(recovery_codes) => {
+                    unreported_error_toggle.set(false);
+                    if let Ok(profile) = api::get_my_profile().await {
+                        profile_atom(profile.into())
+                    }
 
-                Err(err)
-            } else {
-                on_close.emit(());
-                unreported_error_toggle.set(false);
-                if let Ok(profile) = api::get_my_profile().await {
-                    profile_atom(profile.into())
+                    Ok(recovery_codes)
                 }
+                Err(err) => {
+                    log::error!("Failed to validate token: {err}");
+                    unreported_error_toggle.set(true);
+                    bamboo_error_state.set(err.clone());
 
-                Ok(())
+                    Err(err)
+                }
             }
         })
     };
@@ -850,6 +1493,12 @@ object-fit: scale-down;
 "#
     );
 
+    if let Some(recovery_codes) = &validate_totp_state.data {
+        return html!(
+            <TotpRecoveryCodesDialog codes={recovery_codes.codes.clone()} on_close={on_close.clone()} />
+        );
+    }
+
     html!(
         <>
             <Helmet>
@@ -894,16 +1543,235 @@ Anschließend kommt ein QR Code, den musst du scannen und danach einen Code aus
     )
 }
 
+#[autoprops]
+#[function_component(TotpRecoveryCodesDialog)]
+fn totp_recovery_codes_dialog(codes: &Vec<String>, on_close: &Callback<()>) -> Html {
+    log::debug!("Open dialog to show totp recovery codes");
+
+    html!(
+        <>
+            <Helmet>
+                <title>{"Wiederherstellungscodes"}</title>
+            </Helmet>
+            <CosmoModal title="Wiederherstellungscodes" buttons={html!(<CosmoButton on_click={on_close.clone()} label="Codes gespeichert" />)}>
+                <CosmoParagraph>{r#"Falls du keinen Zugriff mehr auf deine App hast, kannst du dich mit einem dieser Codes anstelle des Zwei Faktor Codes anmelden.
+    Jeder Code funktioniert nur einmal. Bewahre die Codes an einem sicheren Ort auf, sie werden dir nicht noch einmal angezeigt."#}</CosmoParagraph>
+                <CosmoPre>{codes.join("\n")}</CosmoPre>
+            </CosmoModal>
+        </>
+    )
+}
+
+#[autoprops]
+#[function_component(ChangeGroveDialog)]
+fn change_grove_dialog(groves: &Vec<Grove>, on_close: &Callback<()>) -> Html {
+    log::debug!("Open dialog to change the grove");
+    let selected_grove_state = use_state_eq(|| None as Option<AttrValue>);
+
+    let unreported_error_toggle = use_bool_toggle(false);
+
+    let bamboo_error_state = use_state_eq(ApiError::default);
+
+    let save_state = {
+        let selected_grove_state = selected_grove_state.clone();
+
+        let unreported_error_toggle = unreported_error_toggle.clone();
+
+        let bamboo_error_state = bamboo_error_state.clone();
+
+        use_async(async move {
+            let grove_id = (*selected_grove_state)
+                .clone()
+                .and_then(|grove_id| grove_id.parse::<i32>().ok())
+                .unwrap_or_default();
+
+            api::change_active_grove(grove_id)
+                .await
+                .map(|_| {
+                    let _ = gloo_utils::window().location().reload();
+                })
+                .map_err(|err| {
+                    bamboo_error_state.set(err.clone());
+                    unreported_error_toggle.set(err.code != NOT_FOUND);
+
+                    err
+                })
+        })
+    };
+
+    let current_grove_state = {
+        let selected_grove_state = selected_grove_state.clone();
+
+        use_async(async move {
+            api::get_grove()
+                .await
+                .map(|grove| selected_grove_state.set(Some(AttrValue::from(grove.id.to_string()))))
+        })
+    };
+
+    {
+        let current_grove_state = current_grove_state.clone();
+
+        use_mount(move || current_grove_state.run());
+    }
+
+    let update_grove = use_callback(selected_grove_state.clone(), |value, state| {
+        state.set(value)
+    });
+    let report_unknown_error = use_callback(
+        (bamboo_error_state.clone(), unreported_error_toggle.clone()),
+        |_, (bamboo_error_state, unreported_error_toggle)| {
+            error::report_unknown_error(
+                "layout",
+                "change_grove_dialog",
+                bamboo_error_state.deref().clone(),
+            );
+            unreported_error_toggle.set(false);
+        },
+    );
+    let on_save = use_callback(save_state.clone(), |_, state| state.run());
+
+    let items = groves
+        .iter()
+        .map(|grove| {
+            (
+                Some(AttrValue::from(grove.id.to_string())),
+                AttrValue::from(grove.name.clone()),
+            )
+        })
+        .collect::<Vec<(Option<AttrValue>, AttrValue)>>();
+
+    html!(
+        <CosmoModal title="Hain wechseln" is_form={true} on_form_submit={on_save} buttons={html!(
+            <>
+                <CosmoButton on_click={on_close.clone()} label="Abbrechen" />
+                <CosmoButton is_submit={true} label="Hain wechseln" />
+            </>
+        )}>
+            if let Some(err) = &save_state.error {
+                if err.code == NOT_FOUND {
+                    <CosmoMessage message="Du bist kein Mitglied in diesem Hain" message_type={CosmoMessageType::Negative} />
+                } else if *unreported_error_toggle {
+                    <CosmoMessage message="Der Hain konnte leider nicht gewechselt werden" message_type={CosmoMessageType::Negative} actions={html!(<CosmoButton label="Fehler melden" on_click={report_unknown_error} />)} />
+                } else {
+                    <CosmoMessage message="Der Hain konnte leider nicht gewechselt werden" message_type={CosmoMessageType::Negative} />
+                }
+            } else {
+                <CosmoMessage message_type={CosmoMessageType::Information} message="Wähle den Hain in dem du unterwegs sein möchtest, beim nächsten Anmelden landest du wieder in diesem Hain" />
+            }
+            <CosmoInputGroup>
+                <CosmoDropdown label="Hain" on_select={update_grove} value={(*selected_grove_state).clone()} items={items} />
+            </CosmoInputGroup>
+        </CosmoModal>
+    )
+}
+
+#[autoprops]
+#[function_component(TransferModRightsDialog)]
+fn transfer_mod_rights_dialog(
+    pandas: &Vec<WebUser>,
+    on_left: &Callback<()>,
+    on_close: &Callback<()>,
+) -> Html {
+    log::debug!("Open dialog to transfer the mod rights");
+    let successor_state = use_state_eq(|| {
+        pandas
+            .first()
+            .map(|panda| AttrValue::from(panda.id.to_string()))
+    });
+
+    let unreported_error_toggle = use_bool_toggle(false);
+
+    let bamboo_error_state = use_state_eq(ApiError::default);
+
+    let save_state = {
+        let successor_state = successor_state.clone();
+
+        let unreported_error_toggle = unreported_error_toggle.clone();
+
+        let bamboo_error_state = bamboo_error_state.clone();
+
+        let on_left = on_left.clone();
+
+        use_async(async move {
+            let successor_id = (*successor_state)
+                .clone()
+                .and_then(|successor_id| successor_id.parse::<i32>().ok())
+                .unwrap_or_default();
+
+            api::transfer_mod_rights_and_leave(successor_id)
+                .await
+                .map(|_| on_left.emit(()))
+                .map_err(|err| {
+                    bamboo_error_state.set(err.clone());
+                    unreported_error_toggle.set(err.code != NOT_FOUND);
+
+                    err
+                })
+        })
+    };
+
+    let update_successor = use_callback(successor_state.clone(), |value, state| state.set(value));
+    let report_unknown_error = use_callback(
+        (bamboo_error_state.clone(), unreported_error_toggle.clone()),
+        |_, (bamboo_error_state, unreported_error_toggle)| {
+            error::report_unknown_error(
+                "layout",
+                "transfer_mod_rights_dialog",
+                bamboo_error_state.deref().clone(),
+            );
+            unreported_error_toggle.set(false);
+        },
+    );
+    let on_save = use_callback(save_state.clone(), |_, state| state.run());
+
+    let items = pandas
+        .iter()
+        .map(|panda| {
+            (
+                Some(AttrValue::from(panda.id.to_string())),
+                AttrValue::from(panda.display_name.clone()),
+            )
+        })
+        .collect::<Vec<(Option<AttrValue>, AttrValue)>>();
+
+    html!(
+        <CosmoModal title="Modrechte übertragen" is_form={true} on_form_submit={on_save} buttons={html!(
+            <>
+                <CosmoButton on_click={on_close.clone()} label="Im Hain bleiben" />
+                <CosmoButton is_submit={true} label="Übertragen und verlassen" />
+            </>
+        )}>
+            if let Some(err) = &save_state.error {
+                if err.code == NOT_FOUND {
+                    <CosmoMessage message="Der Panda ist nicht mehr im Hain" message_type={CosmoMessageType::Negative} />
+                } else if *unreported_error_toggle {
+                    <CosmoMessage message="Die Modrechte konnten leider nicht übertragen werden" message_type={CosmoMessageType::Negative} actions={html!(<CosmoButton label="Fehler melden" on_click={report_unknown_error} />)} />
+                } else {
+                    <CosmoMessage message="Die Modrechte konnten leider nicht übertragen werden" message_type={CosmoMessageType::Negative} />
+                }
+            } else {
+                <CosmoMessage message_type={CosmoMessageType::Warning} message="Du bist der letzte Mod im Hain. Bevor du gehst, musst du einen anderen Panda zum Mod machen.\nWenn du in keinem anderen Hain mehr bist, werden alle deine Daten gelöscht und können nicht wiederhergestellt werden." />
+            }
+            <CosmoInputGroup>
+                <CosmoDropdown label="Neuer Mod" required={true} on_select={update_successor} value={(*successor_state).clone()} items={items} />
+            </CosmoInputGroup>
+        </CosmoModal>
+    )
+}
+
 #[function_component(TopBar)]
 fn top_bar() -> Html {
     log::debug!("Render top bar");
     let navigator = use_navigator().expect("Navigator should be available");
 
     let profile_atom = use_atom_value::<storage::CurrentUser>();
+    let grove_settings_atom = use_atom_value::<storage::CurrentGroveSettings>();
 
     let profile_open_toggle = use_bool_toggle(false);
     let password_open_toggle = use_bool_toggle(false);
     let leave_grove_open_toggle = use_bool_toggle(false);
+    let change_grove_open_toggle = use_bool_toggle(false);
 
     let profile_user_id = use_state(|| profile_atom.profile.id);
 
@@ -925,8 +1793,36 @@ fn top_bar() -> Html {
             vec![]
         })
     });
-    let leave_grove_state =
-        use_async(async move { api::leave().await.map(|_| navigator.push(&AppRoute::Login)) });
+    let groves_state: UseAsyncHandle<_, ApiError> =
+        use_async(async move { Ok(api::get_my_groves().await.unwrap_or_default()) });
+    let pandas_state: UseAsyncHandle<_, ApiError> =
+        use_async(async move { Ok(api::get_users().await.unwrap_or_default()) });
+
+    let on_left = use_callback(
+        (groves_state.clone(), navigator),
+        |_, (groves_state, navigator)| {
+            if groves_state
+                .data
+                .as_ref()
+                .is_some_and(|groves| groves.len() > 1)
+            {
+                let _ = gloo_utils::window().location().reload();
+            } else {
+                navigator.push(&AppRoute::Login);
+            }
+        },
+    );
+    let leave_grove_state = {
+        let on_left = on_left.clone();
+
+        use_async(async move { api::leave().await.map(|_| on_left.emit(())) })
+    };
+
+    {
+        let groves_state = groves_state.clone();
+
+        use_mount(move || groves_state.run());
+    }
 
     let navigator = use_navigator().expect("Navigator should be available");
     let logout = use_callback(navigator, |_: (), navigator| {
@@ -947,13 +1843,23 @@ fn top_bar() -> Html {
             mods_state.run();
         },
     );
-    let open_leave_grove = use_callback(leave_grove_open_toggle.clone(), |_, toggle| {
-        toggle.set(true)
-    });
+    let open_leave_grove = use_callback(
+        (leave_grove_open_toggle.clone(), pandas_state.clone()),
+        |_, (toggle, pandas_state)| {
+            toggle.set(true);
+            pandas_state.run();
+        },
+    );
     let close_leave_grove = use_callback(leave_grove_open_toggle.clone(), |_, toggle| {
         toggle.set(false)
     });
     let leave_grove = use_callback(leave_grove_state.clone(), |_, state| state.run());
+    let open_change_grove = use_callback(change_grove_open_toggle.clone(), |_, toggle| {
+        toggle.set(true)
+    });
+    let close_change_grove = use_callback(change_grove_open_toggle.clone(), |_, toggle| {
+        toggle.set(false)
+    });
     let profile_updated = use_callback(
         (
             profile_user_id.clone(),
@@ -967,6 +1873,19 @@ fn top_bar() -> Html {
         },
     );
 
+    let other_pandas = pandas_state
+        .data
+        .as_ref()
+        .map(|pandas| {
+            pandas
+                .iter()
+                .filter(|panda| panda.id != profile_atom.profile.id)
+                .cloned()
+                .collect::<Vec<WebUser>>()
+        })
+        .unwrap_or_default();
+    let is_last_mod = profile_atom.profile.is_mod && !other_pandas.iter().any(|panda| panda.is_mod);
+
     let profile_picture = format!(
         "/api/user/{}/picture#time={}",
         *profile_user_id,
@@ -976,9 +1895,15 @@ fn top_bar() -> Html {
     html!(
         <>
             <CosmoTopBar profile_picture={profile_picture} has_right_item={true} right_item_on_click={logout} right_item_label="Abmelden">
+                if !grove_settings_atom.settings.display_name.is_empty() {
+                    <CosmoTopBarItemLink<AppRoute> label={grove_settings_atom.settings.display_name.clone()} to={AppRoute::BambooGroveRoot} />
+                }
                 <CosmoTopBarItemLink<AppRoute> label="Rechtliches" to={AppRoute::LegalRoot} />
                 <CosmoTopBarItem label="Mein Profil" on_click={open_update_my_profile} />
                 <CosmoTopBarItem label="Passwort ändern" on_click={open_change_password} />
+                if groves_state.data.as_ref().is_some_and(|groves| groves.len() > 1) {
+                    <CosmoTopBarItem label="Hain wechseln" on_click={open_change_grove} />
+                }
                 <CosmoTopBarItem label="Hain verlassen" on_click={open_leave_grove} />
             </CosmoTopBar>
             if *profile_open_toggle {
@@ -989,8 +1914,19 @@ fn top_bar() -> Html {
                     <ChangePasswordDialog on_close={move |_| password_open_toggle.set(false)} mods={data.clone()} />
                 }
             }
-            if *leave_grove_open_toggle {
-                <CosmoConfirm confirm_type={CosmoModalType::Negative} on_confirm={leave_grove} on_decline={close_leave_grove} title="Hain verlassen" message="Bist du sicher, dass du den Hain verlassen möchtest?\nWenn du den Hain verlässt werden alle deine Daten gelöscht und können nicht wiederhergestellt werden." confirm_label="Hain verlassen" decline_label="Im Hain bleiben" />
+            if *change_grove_open_toggle {
+                if let Some(groves) = &groves_state.data {
+                    <ChangeGroveDialog groves={groves.clone()} on_close={close_change_grove} />
+                }
+            }
+            if *leave_grove_open_toggle && pandas_state.loading {
+                <CosmoProgressRing />
+            } else if *leave_grove_open_toggle && is_last_mod && other_pandas.is_empty() {
+                <CosmoAlert alert_type={CosmoModalType::Warning} on_close={close_leave_grove} close_label="Alles klar" title="Hain verlassen" message="Du bist der einzige Panda im Hain und kannst ihn deshalb nicht verlassen. Wenn du den Hain nicht mehr brauchst, kannst du ihn in der Hainverwaltung löschen." />
+            } else if *leave_grove_open_toggle && is_last_mod {
+                <TransferModRightsDialog pandas={other_pandas} on_left={on_left} on_close={close_leave_grove} />
+            } else if *leave_grove_open_toggle {
+                <CosmoConfirm confirm_type={CosmoModalType::Negative} on_confirm={leave_grove} on_decline={close_leave_grove} title="Hain verlassen" message="Bist du sicher, dass du den Hain verlassen möchtest?\nWenn du in keinem anderen Hain mehr bist, werden alle deine Daten gelöscht und können nicht wiederhergestellt werden." confirm_label="Hain verlassen" decline_label="Im Hain bleiben" />
             }
         </>
     )
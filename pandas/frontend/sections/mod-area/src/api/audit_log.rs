@@ -0,0 +1,21 @@
+use bamboo_common::core::entities::AuditLogPage;
+use bamboo_common::frontend::api::BambooApiResult;
+
+use crate::api::get_with_query;
+
+pub async fn get_audit_log(
+    page: u64,
+    action: Option<String>,
+    actor_id: Option<String>,
+) -> BambooApiResult<AuditLogPage> {
+    log::debug!("Load page {page} of the audit log");
+    let mut query = vec![("page", page.to_string())];
+    if let Some(action) = action {
+        query.push(("action", action));
+    }
+    if let Some(actor_id) = actor_id {
+        query.push(("actorId", actor_id));
+    }
+
+    get_with_query("/api/grove/audit-log", query).await
+}
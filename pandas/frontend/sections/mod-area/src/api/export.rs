@@ -0,0 +1,13 @@
+use bamboo_common::core::entities::GroveExport;
+use bamboo_common::frontend::api::BambooApiResult;
+use bamboo_pandas_frontend_base::api;
+
+pub async fn get_grove_exports() -> BambooApiResult<Vec<GroveExport>> {
+    log::debug!("Loading exports of the current grove");
+    api::get("/api/grove/export").await
+}
+
+pub async fn start_grove_export() -> BambooApiResult<GroveExport> {
+    log::debug!("Start export of the current grove");
+    api::post("/api/grove/export", &()).await
+}
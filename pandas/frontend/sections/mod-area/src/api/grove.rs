@@ -1,4 +1,4 @@
-use bamboo_common::core::entities::Grove;
+use bamboo_common::core::entities::{Grove, GroveSettings};
 use bamboo_common::frontend::api::BambooApiResult;
 use bamboo_pandas_frontend_base::api;
 
@@ -12,6 +12,11 @@ pub async fn delete_grove() -> BambooApiResult<()> {
     api::delete("/api/grove").await
 }
 
+pub async fn restore_grove() -> BambooApiResult<()> {
+    log::debug!("Restore the current grove");
+    api::delete("/api/grove/deletion").await
+}
+
 pub async fn disable_grove() -> BambooApiResult<()> {
     log::debug!("Disable the current grove");
     api::delete("/api/grove/enabled").await
@@ -21,3 +26,23 @@ pub async fn enable_grove() -> BambooApiResult<()> {
     log::debug!("Disable the current grove");
     api::put_no_body_no_content("/api/grove/enabled").await
 }
+
+pub async fn get_grove_settings() -> BambooApiResult<GroveSettings> {
+    log::debug!("Loading settings of the current grove");
+    api::get("/api/grove/settings").await
+}
+
+pub async fn update_grove_settings(settings: GroveSettings) -> BambooApiResult<()> {
+    log::debug!("Update settings of the current grove");
+    api::put_no_content("/api/grove/settings", &settings).await
+}
+
+pub async fn upload_grove_logo(file: web_sys::File) -> BambooApiResult<()> {
+    log::debug!("Upload logo of the current grove");
+    api::upload_file("/api/grove/logo", file).await
+}
+
+pub async fn delete_grove_logo() -> BambooApiResult<()> {
+    log::debug!("Delete logo of the current grove");
+    api::delete("/api/grove/logo").await
+}
@@ -0,0 +1,22 @@
+use bamboo_common::core::entities::{
+    CreateGroveInvitation, CreatedGroveInvitation, GroveInvitation,
+};
+use bamboo_common::frontend::api::BambooApiResult;
+use bamboo_pandas_frontend_base::api;
+
+pub async fn get_grove_invitations() -> BambooApiResult<Vec<GroveInvitation>> {
+    log::debug!("Loading invitations of current grove");
+    api::get("/api/grove/invitation").await
+}
+
+pub async fn create_grove_invitation(
+    invitation: CreateGroveInvitation,
+) -> BambooApiResult<CreatedGroveInvitation> {
+    log::debug!("Create a new invitation");
+    api::post("/api/grove/invitation", &invitation).await
+}
+
+pub async fn delete_grove_invitation(id: i32) -> BambooApiResult<()> {
+    log::debug!("Revoke invitation {id}");
+    api::delete(format!("/api/grove/invitation/{id}")).await
+}
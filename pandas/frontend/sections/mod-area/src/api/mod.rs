@@ -1,6 +1,14 @@
+pub use audit_log::*;
 pub use bamboo_pandas_frontend_base::api::*;
+pub use export::*;
 pub use grove::*;
+pub use invitation::*;
+pub use role::*;
 pub use user::*;
 
+pub mod audit_log;
+pub mod export;
 pub mod grove;
+pub mod invitation;
+pub mod role;
 pub mod user;
@@ -0,0 +1,23 @@
+use bamboo_common::core::entities::GroveRole;
+use bamboo_common::frontend::api::BambooApiResult;
+use bamboo_pandas_frontend_base::api;
+
+pub async fn get_grove_roles() -> BambooApiResult<Vec<GroveRole>> {
+    log::debug!("Loading roles of current grove");
+    api::get("/api/grove/role").await
+}
+
+pub async fn create_grove_role(role: GroveRole) -> BambooApiResult<GroveRole> {
+    log::debug!("Create role {}", role.name);
+    api::post("/api/grove/role", &role).await
+}
+
+pub async fn update_grove_role(id: i32, role: GroveRole) -> BambooApiResult<()> {
+    log::debug!("Update role {id}");
+    api::put_no_content(format!("/api/grove/role/{id}"), &role).await
+}
+
+pub async fn delete_grove_role(id: i32) -> BambooApiResult<()> {
+    log::debug!("Delete role {id}");
+    api::delete(format!("/api/grove/role/{id}")).await
+}
@@ -1,16 +1,17 @@
 use bamboo_common::core::entities::*;
 use bamboo_common::frontend::api::BambooApiResult;
 
-use crate::api::{delete, get, post, put_no_body_no_content, put_no_content};
+use crate::api::{delete, get, post_optional, put_no_body_no_content, put_no_content};
 
 pub async fn get_users() -> BambooApiResult<Vec<WebUser>> {
     log::debug!("Get users");
     get("/api/user").await
 }
 
-pub async fn create_user(user: User) -> BambooApiResult<WebUser> {
+/// Pandas that already have an account only get an invitation, in that case no user is returned.
+pub async fn create_user(user: User) -> BambooApiResult<Option<WebUser>> {
     log::debug!("Create user {}", user.email);
-    post("/api/user", &user).await
+    post_optional("/api/user", &user).await
 }
 
 pub async fn make_user_mod(id: i32) -> BambooApiResult<()> {
@@ -23,6 +24,11 @@ pub async fn remove_user_mod(id: i32) -> BambooApiResult<()> {
     delete(format!("/api/user/{id}/mod")).await
 }
 
+pub async fn change_user_role(id: i32, role_id: Option<i32>) -> BambooApiResult<()> {
+    log::debug!("Change role of user {id} to {role_id:?}");
+    put_no_content(format!("/api/user/{id}/role"), &ChangeUserRole { role_id }).await
+}
+
 pub async fn delete_user(id: i32) -> BambooApiResult<()> {
     log::debug!("Delete user {id}");
     delete(format!("/api/user/{id}")).await
@@ -45,3 +51,13 @@ pub async fn disable_totp(id: i32) -> BambooApiResult<()> {
     log::debug!("Disable totp for user {id}");
     delete(format!("/api/user/{id}/totp")).await
 }
+
+pub async fn get_login_lockouts() -> BambooApiResult<Vec<LoginLockout>> {
+    log::debug!("Get login lockouts");
+    get("/api/lockout").await
+}
+
+pub async fn clear_login_lockout(id: i32) -> BambooApiResult<()> {
+    log::debug!("Clear login lockout for user {id}");
+    delete(format!("/api/user/{id}/lockout")).await
+}
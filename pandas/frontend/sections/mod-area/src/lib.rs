@@ -1,5 +1,9 @@
 pub use api::get_users;
+pub use pages::audit_log::*;
+pub use pages::export::*;
 pub use pages::grove::*;
+pub use pages::invitation::*;
+pub use pages::role::*;
 pub use pages::user::*;
 
 mod api;
@@ -0,0 +1,168 @@
+use std::ops::Deref;
+
+use bounce::use_atom_value;
+use strum::IntoEnumIterator;
+use yew::prelude::*;
+use yew_cosmo::prelude::*;
+use yew_hooks::{use_async, use_bool_toggle, use_mount};
+
+use bamboo_common::core::entities::*;
+use bamboo_common::frontend::api::{ApiError, FORBIDDEN};
+use bamboo_pandas_frontend_base::{error, storage};
+
+use crate::api;
+
+fn format_entry(entry: &AuditLogEntry, grove_settings: &storage::CurrentGroveSettings) -> String {
+    let created_at = grove_settings
+        .to_grove_time(&entry.created_at)
+        .format("%d.%m.%Y %H:%M");
+    if let Some(target_name) = &entry.target_name {
+        format!(
+            "{} hat {target_name} bearbeitet, am {created_at}",
+            entry.actor_name
+        )
+    } else {
+        format!("Von {} am {created_at}", entry.actor_name)
+    }
+}
+
+#[function_component(AuditLogPage)]
+pub fn audit_log_page() -> Html {
+    log::debug!("Render audit log page");
+    let grove_settings_atom = use_atom_value::<storage::CurrentGroveSettings>();
+    let page_state = use_state_eq(|| 0u64);
+    let action_state = use_state_eq(|| None as Option<AttrValue>);
+    let actor_state = use_state_eq(|| None as Option<AttrValue>);
+
+    let unreported_error_toggle = use_bool_toggle(false);
+
+    let bamboo_error_state = use_state_eq(ApiError::default);
+
+    let entries_state = {
+        let page_state = page_state.clone();
+        let action_state = action_state.clone();
+        let actor_state = actor_state.clone();
+
+        let unreported_error_toggle = unreported_error_toggle.clone();
+
+        let bamboo_error_state = bamboo_error_state.clone();
+
+        use_async(async move {
+            api::get_audit_log(
+                *page_state,
+                (*action_state).clone().map(|action| action.to_string()),
+                (*actor_state).clone().map(|actor| actor.to_string()),
+            )
+            .await
+            .map_err(|err| {
+                unreported_error_toggle.set(err.code != FORBIDDEN);
+                bamboo_error_state.set(err.clone());
+
+                err
+            })
+        })
+    };
+    let users_state = use_async(async move { api::get_users().await });
+
+    {
+        let users_state = users_state.clone();
+
+        use_mount(move || users_state.run());
+    }
+    {
+        let entries_state = entries_state.clone();
+
+        use_effect_with(
+            (*page_state, (*action_state).clone(), (*actor_state).clone()),
+            move |_| entries_state.run(),
+        );
+    }
+
+    let update_action = use_callback(
+        (action_state.clone(), page_state.clone()),
+        |value, (action_state, page_state)| {
+            action_state.set(value);
+            page_state.set(0);
+        },
+    );
+    let update_actor = use_callback(
+        (actor_state.clone(), page_state.clone()),
+        |value, (actor_state, page_state)| {
+            actor_state.set(value);
+            page_state.set(0);
+        },
+    );
+    let previous_page = use_callback(page_state.clone(), |_, state| {
+        state.set(state.saturating_sub(1))
+    });
+    let next_page = use_callback(page_state.clone(), |_, state| state.set(**state + 1));
+    let report_unknown_error = use_callback(
+        (bamboo_error_state.clone(), unreported_error_toggle.clone()),
+        |_, (bamboo_error_state, unreported_error_toggle)| {
+            error::report_unknown_error(
+                "mod_area_audit_log",
+                "audit_log_page",
+                bamboo_error_state.deref().clone(),
+            );
+            unreported_error_toggle.set(false);
+        },
+    );
+
+    let mut action_items = vec![(None, AttrValue::from("Alle Aktionen"))];
+    action_items.extend(AuditAction::iter().map(|action| {
+        (
+            Some(AttrValue::from(action.get_name())),
+            AttrValue::from(action.to_string()),
+        )
+    }));
+    let mut actor_items = vec![(None, AttrValue::from("Alle Pandas"))];
+    if let Some(users) = &users_state.data {
+        actor_items.extend(users.iter().map(|user| {
+            (
+                Some(AttrValue::from(user.id.to_string())),
+                AttrValue::from(user.display_name.clone()),
+            )
+        }));
+    }
+
+    html!(
+        <>
+            <CosmoTitle title="Moderationsprotokoll" />
+            <CosmoParagraph>{"Im Moderationsprotokoll siehst du, wer im Hain Pandas verwaltet, Rechte vergeben oder den Hain aktiviert und deaktiviert hat. Die Einträge können nicht verändert oder gelöscht werden."}</CosmoParagraph>
+            <CosmoInputGroup>
+                <CosmoDropdown label="Aktion" on_select={update_action} value={(*action_state).clone()} items={action_items} />
+                <CosmoDropdown label="Ausgeführt von" on_select={update_actor} value={(*actor_state).clone()} items={actor_items} />
+            </CosmoInputGroup>
+            if let Some(err) = &entries_state.error {
+                if err.code == FORBIDDEN {
+                    <CosmoMessage header="Fehler beim Laden" message="Dir fehlt die Berechtigung um das Moderationsprotokoll einzusehen" message_type={CosmoMessageType::Negative} />
+                } else if *unreported_error_toggle {
+                    <CosmoMessage header="Fehler beim Laden" message="Das Moderationsprotokoll konnte nicht geladen werden" message_type={CosmoMessageType::Negative} actions={html!(<CosmoButton label="Fehler melden" on_click={report_unknown_error} />)} />
+                } else {
+                    <CosmoMessage header="Fehler beim Laden" message="Das Moderationsprotokoll konnte nicht geladen werden" message_type={CosmoMessageType::Negative} />
+                }
+            } else if let Some(audit_log) = &entries_state.data {
+                if audit_log.entries.is_empty() {
+                    <CosmoParagraph>{"Es gibt keine passenden Einträge im Moderationsprotokoll"}</CosmoParagraph>
+                } else {
+                    <CosmoKeyValueList>
+                        {for audit_log.entries.iter().map(|entry| html!(
+                            <CosmoKeyValueListItem title={entry.action.to_string()}>
+                                {format_entry(entry, &grove_settings_atom)}
+                            </CosmoKeyValueListItem>
+                        ))}
+                    </CosmoKeyValueList>
+                    <CosmoToolbar>
+                        <CosmoToolbarGroup>
+                            <CosmoButton label="Zurück" enabled={audit_log.page > 0 && !entries_state.loading} on_click={previous_page} />
+                            <CosmoButton label="Weiter" enabled={audit_log.page + 1 < audit_log.page_count && !entries_state.loading} on_click={next_page} />
+                        </CosmoToolbarGroup>
+                    </CosmoToolbar>
+                    <CosmoParagraph>{format!("Seite {} von {}, {} Einträge insgesamt", audit_log.page + 1, audit_log.page_count, audit_log.total_count)}</CosmoParagraph>
+                }
+            } else if entries_state.loading {
+                <CosmoProgressRing />
+            }
+        </>
+    )
+}
@@ -0,0 +1,136 @@
+use std::ops::Deref;
+
+use bounce::use_atom_value;
+use yew::prelude::*;
+use yew_cosmo::prelude::*;
+use yew_hooks::{use_async, use_bool_toggle, use_mount};
+
+use bamboo_common::core::entities::*;
+use bamboo_common::frontend::api::{ApiError, CONFLICT, FORBIDDEN};
+use bamboo_pandas_frontend_base::{error, storage};
+
+use crate::api;
+
+fn format_export(
+    grove_export: &GroveExport,
+    grove_settings: &storage::CurrentGroveSettings,
+) -> String {
+    let created_at = grove_settings
+        .to_grove_time(&grove_export.created_at)
+        .format("%d.%m.%Y %H:%M");
+    format!(
+        "{}, angefordert von {} am {created_at}",
+        grove_export.status, grove_export.requested_by_name
+    )
+}
+
+#[function_component(ExportPage)]
+pub fn export_page() -> Html {
+    log::debug!("Render export page");
+    let grove_settings_atom = use_atom_value::<storage::CurrentGroveSettings>();
+    let unreported_error_toggle = use_bool_toggle(false);
+
+    let bamboo_error_state = use_state_eq(ApiError::default);
+
+    let exports_state = {
+        let unreported_error_toggle = unreported_error_toggle.clone();
+
+        let bamboo_error_state = bamboo_error_state.clone();
+
+        use_async(async move {
+            api::get_grove_exports().await.map_err(|err| {
+                unreported_error_toggle.set(err.code != FORBIDDEN);
+                bamboo_error_state.set(err.clone());
+
+                err
+            })
+        })
+    };
+    let start_state = {
+        let exports_state = exports_state.clone();
+
+        let unreported_error_toggle = unreported_error_toggle.clone();
+
+        let bamboo_error_state = bamboo_error_state.clone();
+
+        use_async(async move {
+            api::start_grove_export()
+                .await
+                .map(|_| exports_state.run())
+                .map_err(|err| {
+                    unreported_error_toggle.set(err.code != CONFLICT);
+                    bamboo_error_state.set(err.clone());
+
+                    err
+                })
+        })
+    };
+
+    {
+        let exports_state = exports_state.clone();
+
+        use_mount(move || exports_state.run());
+    }
+
+    let start_export = use_callback(start_state.clone(), |_, state| state.run());
+    let reload_exports = use_callback(exports_state.clone(), |_, state| state.run());
+    let report_unknown_error = use_callback(
+        (bamboo_error_state.clone(), unreported_error_toggle.clone()),
+        |_, (bamboo_error_state, unreported_error_toggle)| {
+            error::report_unknown_error(
+                "mod_area_export",
+                "export_page",
+                bamboo_error_state.deref().clone(),
+            );
+            unreported_error_toggle.set(false);
+        },
+    );
+
+    html!(
+        <>
+            <CosmoTitle title="Datenexport" />
+            <CosmoParagraph>{"Hier kannst du eine vollständige Kopie der Daten deines Hains anfordern, zum Beispiel bevor du den Hain löschst oder umziehst. Der Export enthält alle Pandas mit ihren Profilbildern, Events, Charaktere, Kämpfer, Crafter, Unterkünfte, Freie Gesellschaften und eigenen Felder. Private Events, Passwörter und Zwei-Faktor-Daten werden nicht exportiert."}</CosmoParagraph>
+            <CosmoToolbar>
+                <CosmoToolbarGroup>
+                    <CosmoButton label="Export starten" enabled={!start_state.loading} on_click={start_export} />
+                    <CosmoButton label="Aktualisieren" enabled={!exports_state.loading} on_click={reload_exports} />
+                </CosmoToolbarGroup>
+            </CosmoToolbar>
+            if let Some(err) = &start_state.error {
+                if err.code == CONFLICT {
+                    <CosmoMessage header="Fehler beim Starten" message="Es läuft bereits ein Export, bitte warte bis er fertig ist" message_type={CosmoMessageType::Negative} />
+                } else if *unreported_error_toggle {
+                    <CosmoMessage header="Fehler beim Starten" message="Der Export konnte nicht gestartet werden" message_type={CosmoMessageType::Negative} actions={html!(<CosmoButton label="Fehler melden" on_click={report_unknown_error.clone()} />)} />
+                } else {
+                    <CosmoMessage header="Fehler beim Starten" message="Der Export konnte nicht gestartet werden" message_type={CosmoMessageType::Negative} />
+                }
+            }
+            if let Some(err) = &exports_state.error {
+                if err.code == FORBIDDEN {
+                    <CosmoMessage header="Fehler beim Laden" message="Nur Mods können die Daten des Hains exportieren" message_type={CosmoMessageType::Negative} />
+                } else if *unreported_error_toggle {
+                    <CosmoMessage header="Fehler beim Laden" message="Die Exporte konnten nicht geladen werden" message_type={CosmoMessageType::Negative} actions={html!(<CosmoButton label="Fehler melden" on_click={report_unknown_error} />)} />
+                } else {
+                    <CosmoMessage header="Fehler beim Laden" message="Die Exporte konnten nicht geladen werden" message_type={CosmoMessageType::Negative} />
+                }
+            } else if let Some(exports) = &exports_state.data {
+                if exports.is_empty() {
+                    <CosmoParagraph>{"Es wurde noch kein Export angefordert"}</CosmoParagraph>
+                } else {
+                    <CosmoKeyValueList>
+                        {for exports.iter().map(|grove_export| html!(
+                            <CosmoKeyValueListItem title={format!("Export {}", grove_export.id)}>
+                                {format_export(grove_export, &grove_settings_atom)}
+                                if grove_export.status == GroveExportStatus::Finished {
+                                    {" – "}<CosmoAnchor href={format!("/api/grove/export/{}/download", grove_export.id)}>{"Herunterladen"}</CosmoAnchor>
+                                }
+                            </CosmoKeyValueListItem>
+                        ))}
+                    </CosmoKeyValueList>
+                }
+            } else if exports_state.loading {
+                <CosmoProgressRing />
+            }
+        </>
+    )
+}
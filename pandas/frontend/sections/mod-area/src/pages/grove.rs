@@ -1,21 +1,167 @@
+use bounce::{use_atom_setter, use_atom_value};
+use strum::IntoEnumIterator;
 use yew::prelude::*;
+use yew_autoprops::autoprops;
 use yew_cosmo::prelude::*;
 use yew_hooks::{use_async, use_bool_toggle, use_mount};
 use yew_router::prelude::*;
 
+use bamboo_common::core::entities::{GroveLanguage, GroveSettings, TIMEZONES};
+use bamboo_common::frontend::api::{ApiError, BAD_REQUEST};
 use bamboo_pandas_frontend_base::routing::AppRoute;
+use bamboo_pandas_frontend_base::storage;
 
 use crate::api;
 
+#[autoprops]
+#[function_component(GroveSettingsForm)]
+fn grove_settings_form(settings: &GroveSettings) -> Html {
+    let grove_settings_atom_setter = use_atom_setter::<storage::CurrentGroveSettings>();
+    let grove_settings_atom_setter_logo = grove_settings_atom_setter.clone();
+
+    let display_name_state = use_state_eq(|| AttrValue::from(settings.display_name.clone()));
+    let description_state = use_state_eq(|| AttrValue::from(settings.description.clone()));
+    let language_state = use_state_eq(|| AttrValue::from(settings.language.get_name()));
+    let timezone_state = use_state_eq(|| AttrValue::from(settings.timezone.clone()));
+    let accent_color_state = use_state_eq(|| settings.accent_color());
+    let logo_state = use_state_eq(|| None as Option<web_sys::File>);
+    let has_logo_state = use_state_eq(|| settings.has_logo);
+
+    let saved_toggle = use_bool_toggle(false);
+
+    let save_state = {
+        let display_name_state = display_name_state.clone();
+        let description_state = description_state.clone();
+        let language_state = language_state.clone();
+        let timezone_state = timezone_state.clone();
+        let accent_color_state = accent_color_state.clone();
+        let logo_state = logo_state.clone();
+        let has_logo_state = has_logo_state.clone();
+
+        let saved_toggle = saved_toggle.clone();
+
+        use_async(async move {
+            let mut settings = GroveSettings {
+                display_name: (*display_name_state).to_string(),
+                description: (*description_state).to_string(),
+                language: GroveLanguage::from((*language_state).to_string()),
+                timezone: (*timezone_state).to_string(),
+                has_logo: *has_logo_state,
+                ..GroveSettings::default()
+            };
+            settings.set_accent_color(*accent_color_state);
+
+            api::update_grove_settings(settings.clone()).await?;
+            if let Some(logo) = (*logo_state).clone() {
+                api::upload_grove_logo(logo).await?;
+                settings.has_logo = true;
+                has_logo_state.set(true);
+            }
+
+            grove_settings_atom_setter(settings.into());
+            saved_toggle.set(true);
+
+            Ok::<_, ApiError>(())
+        })
+    };
+    let delete_logo_state = {
+        let has_logo_state = has_logo_state.clone();
+
+        use_async(async move {
+            api::delete_grove_logo().await?;
+            has_logo_state.set(false);
+            grove_settings_atom_setter_logo(api::get_grove_settings().await?.into());
+
+            Ok::<_, ApiError>(())
+        })
+    };
+
+    let update_display_name =
+        use_callback(display_name_state.clone(), |value, state| state.set(value));
+    let update_description =
+        use_callback(description_state.clone(), |value, state| state.set(value));
+    let update_language =
+        use_callback(language_state.clone(), |value: Option<AttrValue>, state| {
+            state.set(value.unwrap_or_default())
+        });
+    let update_timezone =
+        use_callback(timezone_state.clone(), |value: Option<AttrValue>, state| {
+            state.set(value.unwrap_or_default())
+        });
+    let update_accent_color =
+        use_callback(accent_color_state.clone(), |value, state| state.set(value));
+    let select_logo = use_callback(logo_state.clone(), |value, state| state.set(Some(value)));
+    let save = use_callback(
+        (save_state.clone(), saved_toggle.clone()),
+        |_, (state, saved_toggle)| {
+            saved_toggle.set(false);
+            state.run();
+        },
+    );
+    let delete_logo = use_callback(delete_logo_state.clone(), |_, state| state.run());
+
+    let language_items = GroveLanguage::iter()
+        .map(|language| {
+            (
+                Some(AttrValue::from(language.get_name())),
+                AttrValue::from(language.to_string()),
+            )
+        })
+        .collect::<Vec<(Option<AttrValue>, AttrValue)>>();
+    let timezone_items = TIMEZONES
+        .iter()
+        .map(|timezone| (Some(AttrValue::from(*timezone)), AttrValue::from(*timezone)))
+        .collect::<Vec<(Option<AttrValue>, AttrValue)>>();
+
+    html!(
+        <>
+            if *saved_toggle {
+                <CosmoMessage header="Einstellungen gespeichert" message="Die Einstellungen deines Hains wurden gespeichert" message_type={CosmoMessageType::Positive} />
+            }
+            if let Some(err) = &save_state.error {
+                if err.code == BAD_REQUEST {
+                    <CosmoMessage header="Fehler beim Speichern" message="Bitte gib einen Anzeigenamen an, wähle eine gültige Zeitzone und Akzentfarbe und lade als Logo ein PNG, JPEG, GIF oder WebP Bild hoch" message_type={CosmoMessageType::Negative} />
+                } else {
+                    <CosmoMessage header="Fehler beim Speichern" message="Die Einstellungen konnten nicht gespeichert werden" message_type={CosmoMessageType::Negative} />
+                }
+            }
+            if delete_logo_state.error.is_some() {
+                <CosmoMessage header="Fehler beim Entfernen" message="Das Logo konnte nicht entfernt werden" message_type={CosmoMessageType::Negative} />
+            }
+            <CosmoForm on_submit={save} buttons={html!(
+                <>
+                    if *has_logo_state {
+                        <CosmoButton label="Logo entfernen" on_click={delete_logo} />
+                    }
+                    <CosmoButton state={CosmoButtonType::Primary} label="Einstellungen speichern" is_submit={true} />
+                </>
+            )}>
+                <CosmoTextBox label="Anzeigename" required={true} on_input={update_display_name} value={(*display_name_state).clone()} />
+                <CosmoTextArea label="Beschreibung" on_input={update_description} value={(*description_state).clone()} />
+                <CosmoDropdown label="Sprache" on_select={update_language} value={Some((*language_state).clone())} items={language_items} />
+                <CosmoDropdown label="Zeitzone" on_select={update_timezone} value={Some((*timezone_state).clone())} items={timezone_items} />
+                <CosmoColorPicker label="Akzentfarbe" value={*accent_color_state} on_input={update_accent_color} />
+                <CosmoFilePicker label="Logo (optional)" on_select={select_logo} />
+            </CosmoForm>
+        </>
+    )
+}
+
 #[function_component(GroveManagementPage)]
 pub fn grove_management_page() -> Html {
     let navigator = use_navigator().expect("Navigator needs to be some");
+    let current_user = use_atom_value::<storage::CurrentUser>();
 
     let delete_grove_open_toggle = use_bool_toggle(false);
     let disable_grove_open_toggle = use_bool_toggle(false);
     let enable_grove_open_toggle = use_bool_toggle(false);
 
-    let grove_state = use_async(async { api::get_grove().await });
+    let grove_state = use_async(async {
+        let grove = api::get_grove().await?;
+        let settings = api::get_grove_settings().await?;
+
+        Ok::<_, ApiError>((grove, settings))
+    });
     let disable_grove_state = {
         let navigator = navigator.clone();
 
@@ -36,16 +182,16 @@ pub fn grove_management_page() -> Html {
                 .map_err(|_| navigator.push(&AppRoute::Login))
         })
     };
-    let delete_grove_state = {
-        let navigator = navigator.clone();
-
-        use_async(async move {
-            api::delete_grove()
-                .await
-                .map(|_| navigator.push(&AppRoute::Login))
-                .map_err(|_| navigator.push(&AppRoute::Login))
-        })
-    };
+    let delete_grove_state = use_async(async move {
+        api::delete_grove()
+            .await
+            .map(|_| gloo_utils::window().location().reload())
+    });
+    let restore_grove_state = use_async(async move {
+        api::restore_grove()
+            .await
+            .map(|_| gloo_utils::window().location().reload())
+    });
 
     let open_delete_grove = use_callback(delete_grove_open_toggle.clone(), |_, toggle| {
         toggle.set(true)
@@ -69,7 +215,14 @@ pub fn grove_management_page() -> Html {
 
     let disable_grove = use_callback(disable_grove_state.clone(), |_, state| state.run());
     let enable_grove = use_callback(enable_grove_state.clone(), |_, state| state.run());
-    let delete_grove = use_callback(delete_grove_state.clone(), |_, state| state.run());
+    let delete_grove = use_callback(
+        (delete_grove_state.clone(), delete_grove_open_toggle.clone()),
+        |_, (state, toggle)| {
+            toggle.set(false);
+            state.run();
+        },
+    );
+    let restore_grove = use_callback(restore_grove_state.clone(), |_, state| state.run());
 
     {
         let grove_state = grove_state.clone();
@@ -90,12 +243,18 @@ pub fn grove_management_page() -> Html {
                 <CosmoMessage header="Fehler beim Laden" message="Dein Hain konnte nicht geladen werden, bitte wende dich an den Bambussupport" message_type={CosmoMessageType::Negative} />
             </>
         )
-    } else if let Some(grove) = &grove_state.data {
+    } else if let Some((grove, settings)) = &grove_state.data {
         html!(
             <>
-                <CosmoTitle title="Hainverwaltung" subtitle={grove.name.clone()} />
-                <CosmoMessage header="Willkommen in der Hainverwaltung" message="In der Hainverwaltung hast du die Möglichkeit deinen Hain zu löschen oder zu deaktivieren" message_type={CosmoMessageType::Information} />
-                if grove.is_enabled {
+                <CosmoTitle title="Hainverwaltung" subtitle={settings.display_name.clone()} />
+                <CosmoMessage header="Willkommen in der Hainverwaltung" message="In der Hainverwaltung hast du die Möglichkeit deinen Hain einzurichten, zu löschen oder zu deaktivieren" message_type={CosmoMessageType::Information} />
+                if !grove.is_deleted() {
+                    <CosmoHeader level={CosmoHeaderLevel::H2} header="Einstellungen" />
+                    <GroveSettingsForm settings={settings.clone()} />
+                }
+                if grove.is_deleted() {
+                    <CosmoMessage header="Hain ist schreibgeschützt" message="Solange der Hain gelöscht ist, können keine Daten geändert werden. Du kannst ihn aber weiterhin exportieren oder wiederherstellen." message_type={CosmoMessageType::Information} />
+                } else if grove.is_enabled {
                     <CosmoMessage header="Hain deaktivieren" message="Du hast die Möglichkeit den Hain zu deaktivieren. Sobald er deaktiviert ist können sich nur noch Mods anmelden und haben nur noch Zugriff auf die Mod Area. Den Hain zu deaktivieren ist eine gute Alternative dazu ihn direkt zu löschen." message_type={CosmoMessageType::Warning} actions={html!(
                         <CosmoButton label="Hain deaktivieren" on_click={open_disable_grove} />
                     )} />
@@ -110,11 +269,23 @@ pub fn grove_management_page() -> Html {
                         <CosmoConfirm confirm_type={CosmoModalType::Warning} on_confirm={enable_grove} on_decline={close_enable_grove} confirm_label="Hain aktivieren" decline_label="Hain nicht aktivieren" title="Hain aktivieren" message="Wenn du den Hain aktivierst haben wieder alle Benutzer die Möglichkeit sich anzumelden." />
                     }
                 }
-                <CosmoMessage header="Hain löschen" message="Du hast die Möglichkeit den Hain zu löschen. Sobald der Hain gelöscht ist werden alle Benutzer, Events und Charaktere gelöscht. Wir haben 14 Tage lang nach dem Löschen die Möglichkeit die Daten aus einem unserer Backups wiederherzustellen, danach sind die Daten verloren." message_type={CosmoMessageType::Negative} actions={html!(
-                    <CosmoButton label="Hain löschen" on_click={open_delete_grove} />
-                )} />
-                if *delete_grove_open_toggle {
-                    <CosmoConfirm confirm_type={CosmoModalType::Negative} on_confirm={delete_grove} on_decline={close_delete_grove} confirm_label="Hain löschen" decline_label="Hain nicht löschen" title="Hain löschen" message="Wenn du den Hain löscht werden alle Benutzer und ihre Daten unwiderruflich gelöscht.\nBitte überleg dir ob es reicht ihn zu deaktivieren. Falls du ihn wirklich löschen möchtest mächten wir dich darum bitten allen Benutzern Bescheid zu geben, damit sie ihre Daten sichern können." />
+                if let Some(purge_at) = grove.purge_at {
+                    <CosmoMessage header="Hain wird gelöscht" message={format!("Der Hain wurde gelöscht und ist für alle Pandas gesperrt. Am {} wird er mit allen Benutzern, Events und Charakteren endgültig gelöscht. Bis dahin kannst du ihn wiederherstellen.", purge_at.format("%d.%m.%Y um %H:%M"))} message_type={CosmoMessageType::Negative} actions={html!(
+                        <CosmoButton label="Hain wiederherstellen" on_click={restore_grove} />
+                    )} />
+                    if restore_grove_state.error.is_some() {
+                        <CosmoMessage header="Fehler beim Wiederherstellen" message="Der Hain konnte nicht wiederhergestellt werden" message_type={CosmoMessageType::Negative} />
+                    }
+                } else if current_user.profile.is_mod {
+                    <CosmoMessage header="Hain löschen" message="Du hast die Möglichkeit den Hain zu löschen. Der Hain wird sofort für alle Pandas gesperrt und nach Ablauf einer Frist mit allen Benutzern, Events und Charakteren endgültig gelöscht. Bis dahin kannst du ihn hier wiederherstellen." message_type={CosmoMessageType::Negative} actions={html!(
+                        <CosmoButton label="Hain löschen" on_click={open_delete_grove} />
+                    )} />
+                    if delete_grove_state.error.is_some() {
+                        <CosmoMessage header="Fehler beim Löschen" message="Der Hain konnte nicht gelöscht werden" message_type={CosmoMessageType::Negative} />
+                    }
+                    if *delete_grove_open_toggle {
+                        <CosmoConfirm confirm_type={CosmoModalType::Negative} on_confirm={delete_grove} on_decline={close_delete_grove} confirm_label="Hain löschen" decline_label="Hain nicht löschen" title="Hain löschen" message="Wenn du den Hain löschst, wird er sofort für alle Pandas gesperrt und nach Ablauf der Frist mit allen Daten endgültig gelöscht.\nBitte überleg dir ob es reicht ihn zu deaktivieren. Falls du ihn wirklich löschen möchtest möchten wir dich darum bitten allen Benutzern Bescheid zu geben, damit sie ihre Daten sichern können." />
+                    }
                 }
             </>
         )
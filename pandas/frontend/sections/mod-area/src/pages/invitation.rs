@@ -0,0 +1,307 @@
+use std::ops::Deref;
+
+use bounce::use_atom_value;
+use yew::prelude::*;
+use yew_autoprops::autoprops;
+use yew_cosmo::prelude::*;
+use yew_hooks::{use_async, use_bool_toggle, use_mount};
+
+use bamboo_common::core::entities::*;
+use bamboo_common::frontend::api::{ApiError, BAD_REQUEST, FORBIDDEN};
+use bamboo_pandas_frontend_base::{error, storage};
+
+use crate::api;
+
+#[autoprops]
+#[function_component(CreateInvitationModal)]
+fn create_invitation_modal(
+    on_saved: &Callback<CreatedGroveInvitation>,
+    on_close: &Callback<()>,
+) -> Html {
+    log::debug!("Create create invitation modal");
+    let email_state = use_state_eq(|| AttrValue::from(""));
+    let max_uses_state = use_state_eq(|| Some(AttrValue::from("1")));
+    let expires_in_days_state = use_state_eq(|| Some(AttrValue::from("7")));
+
+    let unreported_error_toggle = use_bool_toggle(false);
+
+    let bamboo_error_state = use_state_eq(ApiError::default);
+
+    let save_state = {
+        let email_state = email_state.clone();
+        let max_uses_state = max_uses_state.clone();
+        let expires_in_days_state = expires_in_days_state.clone();
+
+        let unreported_error_toggle = unreported_error_toggle.clone();
+
+        let bamboo_error_state = bamboo_error_state.clone();
+
+        let on_saved = on_saved.clone();
+
+        use_async(async move {
+            let expires_in_days = (*expires_in_days_state)
+                .clone()
+                .and_then(|days| days.parse::<i64>().ok())
+                .unwrap_or(7);
+            let email = Some(email_state.trim().to_string()).filter(|email| !email.is_empty());
+
+            api::create_grove_invitation(CreateGroveInvitation {
+                max_uses: if email.is_some() {
+                    Some(1)
+                } else {
+                    (*max_uses_state)
+                        .clone()
+                        .and_then(|max_uses| max_uses.parse::<i32>().ok())
+                },
+                email,
+                expires_at: chrono::Utc::now().naive_utc()
+                    + chrono::Duration::days(expires_in_days),
+            })
+            .await
+            .map(|data| {
+                unreported_error_toggle.set(false);
+                on_saved.emit(data.clone());
+
+                data
+            })
+            .map_err(|err| {
+                log::warn!("Failed to create invitation {err}");
+                unreported_error_toggle.set(err.code != BAD_REQUEST);
+                bamboo_error_state.set(err.clone());
+
+                err
+            })
+        })
+    };
+
+    let update_email = use_callback(email_state.clone(), |value, state| state.set(value));
+    let update_max_uses = use_callback(max_uses_state.clone(), |value, state| state.set(value));
+    let update_expires_in_days = use_callback(expires_in_days_state.clone(), |value, state| {
+        state.set(value)
+    });
+
+    let report_unknown_error = use_callback(
+        (bamboo_error_state.clone(), unreported_error_toggle.clone()),
+        |_, (bamboo_error_state, unreported_error_toggle)| {
+            error::report_unknown_error(
+                "mod_area_invitation",
+                "create_invitation_modal",
+                bamboo_error_state.deref().clone(),
+            );
+            unreported_error_toggle.set(false);
+        },
+    );
+
+    let on_save = use_callback(save_state.clone(), |_, state| state.run());
+
+    let expiry_items = vec![
+        (Some(AttrValue::from("1")), AttrValue::from("Einen Tag")),
+        (Some(AttrValue::from("7")), AttrValue::from("Eine Woche")),
+        (Some(AttrValue::from("30")), AttrValue::from("30 Tage")),
+    ];
+
+    html!(
+        <CosmoModal title="Panda einladen" is_form={true} on_form_submit={on_save} buttons={html!(
+            <>
+                <CosmoButton on_click={on_close.clone()} label="Abbrechen" />
+                <CosmoButton is_submit={true} label="Einladung erstellen" />
+            </>
+        )}>
+            if let Some(err) = &save_state.error {
+                if err.code == FORBIDDEN {
+                    <CosmoMessage message="Dir fehlt die Berechtigung um Pandas einzuladen" message_type={CosmoMessageType::Negative} />
+                } else if *unreported_error_toggle {
+                    <CosmoMessage message="Die Einladung konnte leider nicht erstellt werden" message_type={CosmoMessageType::Negative} actions={html!(<CosmoButton label="Fehler melden" on_click={report_unknown_error} />)} />
+                } else {
+                    <CosmoMessage message="Die Einladung konnte leider nicht erstellt werden" message_type={CosmoMessageType::Negative} />
+                }
+            } else {
+                <CosmoMessage message_type={CosmoMessageType::Information} header="Lade einen neuen Panda ein" message="Der Panda legt sich über den Link selbst einen Account mit eigenem Namen und Passwort an. Wenn du eine Email angibst, schicken wir den Link dorthin und er kann nur einmal und nur mit dieser Email benutzt werden." />
+            }
+            if email_state.trim().is_empty() {
+                <CosmoInputGroup>
+                    <CosmoTextBox label="Email (optional)" input_type={CosmoTextBoxType::Email} value={(*email_state).clone()} on_input={update_email.clone()} />
+                    <CosmoDropdown label="Verwendungen" on_select={update_max_uses} value={(*max_uses_state).clone()} items={vec![
+                        (Some(AttrValue::from("1")), AttrValue::from("Einmalig")),
+                        (Some(AttrValue::from("5")), AttrValue::from("5 Pandas")),
+                        (Some(AttrValue::from("10")), AttrValue::from("10 Pandas")),
+                        (None, AttrValue::from("Unbegrenzt")),
+                    ]} />
+                    <CosmoDropdown label="Gültig" on_select={update_expires_in_days.clone()} value={(*expires_in_days_state).clone()} items={expiry_items.clone()} />
+                </CosmoInputGroup>
+            } else {
+                <CosmoInputGroup>
+                    <CosmoTextBox label="Email (optional)" input_type={CosmoTextBoxType::Email} value={(*email_state).clone()} on_input={update_email} />
+                    <CosmoDropdown label="Gültig" on_select={update_expires_in_days} value={(*expires_in_days_state).clone()} items={expiry_items} />
+                </CosmoInputGroup>
+            }
+        </CosmoModal>
+    )
+}
+
+#[function_component(InvitationManagementPage)]
+pub fn invitation_management_page() -> Html {
+    log::debug!("Render invitations page");
+    let grove_settings_atom = use_atom_value::<storage::CurrentGroveSettings>();
+    let open_create_invitation_modal_toggle = use_bool_toggle(false);
+    let unreported_error_toggle = use_bool_toggle(false);
+
+    let created_invitation_state = use_state_eq(|| None as Option<CreatedGroveInvitation>);
+    let delete_invitation_state = use_state_eq(|| None as Option<GroveInvitation>);
+
+    let bamboo_error_state = use_state_eq(ApiError::default);
+
+    let invitations_state = {
+        let unreported_error_toggle = unreported_error_toggle.clone();
+
+        let bamboo_error_state = bamboo_error_state.clone();
+
+        use_async(async move {
+            api::get_grove_invitations().await.map_err(|err| {
+                unreported_error_toggle.set(true);
+                bamboo_error_state.set(err.clone());
+
+                err
+            })
+        })
+    };
+    let delete_invitation = {
+        let unreported_error_toggle = unreported_error_toggle.clone();
+
+        let bamboo_error_state = bamboo_error_state.clone();
+        let delete_invitation_state = delete_invitation_state.clone();
+
+        let invitations_state = invitations_state.clone();
+
+        use_async(async move {
+            if let Some(invitation) = (*delete_invitation_state).clone() {
+                delete_invitation_state.set(None);
+                api::delete_grove_invitation(invitation.id)
+                    .await
+                    .map(|_| {
+                        unreported_error_toggle.set(false);
+                        invitations_state.run();
+                    })
+                    .map_err(|err| {
+                        unreported_error_toggle.set(true);
+                        bamboo_error_state.set(err.clone());
+
+                        err
+                    })
+            } else {
+                Ok(())
+            }
+        })
+    };
+
+    {
+        let invitations_state = invitations_state.clone();
+
+        use_mount(move || invitations_state.run());
+    }
+
+    let open_create_invitation_modal =
+        use_callback(open_create_invitation_modal_toggle.clone(), |_, toggle| {
+            toggle.set(true)
+        });
+    let close_create_invitation_modal =
+        use_callback(open_create_invitation_modal_toggle.clone(), |_, toggle| {
+            toggle.set(false)
+        });
+    let on_create_saved = use_callback(
+        (
+            open_create_invitation_modal_toggle.clone(),
+            created_invitation_state.clone(),
+            invitations_state.clone(),
+        ),
+        |invitation: CreatedGroveInvitation,
+         (open_create_invitation_modal_toggle, created_invitation_state, invitations_state)| {
+            open_create_invitation_modal_toggle.set(false);
+            created_invitation_state.set(Some(invitation));
+            invitations_state.run();
+        },
+    );
+    let report_unknown_error = use_callback(
+        (bamboo_error_state.clone(), unreported_error_toggle.clone()),
+        |_, (bamboo_error_state, unreported_error_toggle)| {
+            error::report_unknown_error(
+                "mod_area_invitation",
+                "invitation_management_page",
+                bamboo_error_state.deref().clone(),
+            );
+            unreported_error_toggle.set(false);
+        },
+    );
+    let on_decline_delete =
+        use_callback(delete_invitation_state.clone(), |_, state| state.set(None));
+    let on_confirm_delete = use_callback(delete_invitation.clone(), |_, state| state.run());
+
+    let format_uses = |invitation: &GroveInvitation| {
+        if let Some(max_uses) = invitation.max_uses {
+            format!("{} von {max_uses} verwendet", invitation.use_count)
+        } else {
+            format!("{} Mal verwendet", invitation.use_count)
+        }
+    };
+
+    html!(
+        <>
+            <CosmoTitle title="Einladungen" />
+            <CosmoParagraph>{"Mit einer Einladung können sich neue Pandas selbst einen Account in deinem Hain anlegen. Den Link siehst du nur direkt nach dem Erstellen, danach kannst du die Einladung nur noch zurückziehen."}</CosmoParagraph>
+            <CosmoToolbar>
+                <CosmoToolbarGroup>
+                    <CosmoButton label="Panda einladen" on_click={open_create_invitation_modal} />
+                </CosmoToolbarGroup>
+            </CosmoToolbar>
+            if let Some(created) = (*created_invitation_state).clone() {
+                if let Some(email) = created.grove_invitation.email.clone() {
+                    <CosmoMessage header="Einladung verschickt" message={format!("Wir haben die Einladung an {email} geschickt, der Link lautet {}", created.link)} message_type={CosmoMessageType::Positive} />
+                } else {
+                    <CosmoMessage header="Einladung erstellt" message={format!("Der Link zur Einladung lautet {}\nKopiere ihn jetzt, er wird dir nicht noch einmal angezeigt.", created.link)} message_type={CosmoMessageType::Positive} />
+                }
+            }
+            if delete_invitation.error.is_some() {
+                if *unreported_error_toggle {
+                    <CosmoMessage header="Fehler beim Zurückziehen" message="Die Einladung konnte leider nicht zurückgezogen werden" message_type={CosmoMessageType::Negative} actions={html!(<CosmoButton label="Fehler melden" on_click={report_unknown_error.clone()} />)} />
+                } else {
+                    <CosmoMessage header="Fehler beim Zurückziehen" message="Die Einladung konnte leider nicht zurückgezogen werden" message_type={CosmoMessageType::Negative} />
+                }
+            }
+            if invitations_state.loading {
+                <CosmoProgressRing />
+            } else if invitations_state.error.is_some() {
+                if *unreported_error_toggle {
+                    <CosmoMessage header="Fehler beim Laden" message="Die Einladungen konnten nicht geladen werden" message_type={CosmoMessageType::Negative} actions={html!(<CosmoButton label="Fehler melden" on_click={report_unknown_error} />)} />
+                } else {
+                    <CosmoMessage header="Fehler beim Laden" message="Die Einladungen konnten nicht geladen werden" message_type={CosmoMessageType::Negative} />
+                }
+            } else if let Some(invitations) = &invitations_state.data {
+                if invitations.is_empty() {
+                    <CosmoParagraph>{"Es gibt aktuell keine Einladungen"}</CosmoParagraph>
+                } else {
+                    <CosmoKeyValueList>
+                        {for invitations.iter().map(|invitation| {
+                            let delete_invitation_state = delete_invitation_state.clone();
+                            let invitation_to_delete = invitation.clone();
+
+                            html!(
+                                <CosmoKeyValueListItem title={invitation.email.clone().unwrap_or("Offene Einladung".to_string())}>
+                                    {format_uses(invitation)}
+                                    <br />
+                                    {format!("Erstellt am {}, gültig bis {}", grove_settings_atom.to_grove_time(&invitation.created_at).format("%d.%m.%Y"), grove_settings_atom.to_grove_time(&invitation.expires_at).format("%d.%m.%Y %H:%M"))}
+                                    <CosmoButton label="Zurückziehen" on_click={move |_| delete_invitation_state.set(Some(invitation_to_delete.clone()))} />
+                                </CosmoKeyValueListItem>
+                            )
+                        })}
+                    </CosmoKeyValueList>
+                }
+            }
+            if *open_create_invitation_modal_toggle {
+                <CreateInvitationModal on_saved={on_create_saved} on_close={close_create_invitation_modal} />
+            }
+            if (*delete_invitation_state).is_some() {
+                <CosmoConfirm confirm_type={CosmoModalType::Warning} message="Soll die Einladung wirklich zurückgezogen werden? Der Link funktioniert danach nicht mehr." title="Einladung zurückziehen" on_decline={on_decline_delete} on_confirm={on_confirm_delete} confirm_label="Zurückziehen" decline_label="Nicht zurückziehen" />
+            }
+        </>
+    )
+}
@@ -1,2 +1,6 @@
+pub mod audit_log;
+pub mod export;
 pub mod grove;
+pub mod invitation;
+pub mod role;
 pub mod user;
@@ -0,0 +1,310 @@
+use std::ops::Deref;
+
+use bounce::use_atom_value;
+use strum::IntoEnumIterator;
+use yew::prelude::*;
+use yew_autoprops::autoprops;
+use yew_cosmo::prelude::*;
+use yew_hooks::{use_async, use_bool_toggle, use_mount};
+
+use bamboo_common::core::entities::*;
+use bamboo_common::frontend::api::{ApiError, CONFLICT, FORBIDDEN};
+use bamboo_pandas_frontend_base::{error, storage};
+
+use crate::api;
+
+fn format_permissions(permissions: &GrovePermissions) -> String {
+    if permissions.is_empty() {
+        "Keine Berechtigungen".to_string()
+    } else {
+        permissions
+            .0
+            .iter()
+            .map(|permission| permission.to_string())
+            .collect::<Vec<String>>()
+            .join(", ")
+    }
+}
+
+#[autoprops]
+#[function_component(RoleModal)]
+fn role_modal(role: &Option<GroveRole>, on_saved: &Callback<()>, on_close: &Callback<()>) -> Html {
+    log::debug!("Create role modal");
+    let name_state = use_state_eq(|| {
+        AttrValue::from(
+            role.as_ref()
+                .map(|role| role.name.clone())
+                .unwrap_or_default(),
+        )
+    });
+    let permissions_state = use_state_eq(|| {
+        role.as_ref()
+            .map(|role| role.permissions.0.clone())
+            .unwrap_or_default()
+    });
+
+    let unreported_error_toggle = use_bool_toggle(false);
+
+    let bamboo_error_state = use_state_eq(ApiError::default);
+
+    let save_state = {
+        let name_state = name_state.clone();
+        let permissions_state = permissions_state.clone();
+
+        let unreported_error_toggle = unreported_error_toggle.clone();
+
+        let bamboo_error_state = bamboo_error_state.clone();
+
+        let on_saved = on_saved.clone();
+
+        let id = role.as_ref().map(|role| role.id);
+
+        use_async(async move {
+            let role = GroveRole {
+                name: name_state.trim().to_string(),
+                permissions: GrovePermissions((*permissions_state).clone()),
+                ..Default::default()
+            };
+            let result = if let Some(id) = id {
+                api::update_grove_role(id, role).await
+            } else {
+                api::create_grove_role(role).await.map(|_| ())
+            };
+
+            result
+                .map(|_| {
+                    unreported_error_toggle.set(false);
+                    on_saved.emit(());
+                })
+                .map_err(|err| {
+                    log::warn!("Failed to save role {err}");
+                    unreported_error_toggle.set(err.code != CONFLICT && err.code != FORBIDDEN);
+                    bamboo_error_state.set(err.clone());
+
+                    err
+                })
+        })
+    };
+
+    let update_name = use_callback(name_state.clone(), |value, state| state.set(value));
+
+    let report_unknown_error = use_callback(
+        (bamboo_error_state.clone(), unreported_error_toggle.clone()),
+        |_, (bamboo_error_state, unreported_error_toggle)| {
+            error::report_unknown_error(
+                "mod_area_role",
+                "role_modal",
+                bamboo_error_state.deref().clone(),
+            );
+            unreported_error_toggle.set(false);
+        },
+    );
+
+    let on_save = use_callback(save_state.clone(), |_, state| state.run());
+
+    html!(
+        <CosmoModal title={if role.is_some() { "Rolle bearbeiten" } else { "Rolle erstellen" }} is_form={true} on_form_submit={on_save} buttons={html!(
+            <>
+                <CosmoButton on_click={on_close.clone()} label="Abbrechen" />
+                <CosmoButton is_submit={true} label="Rolle speichern" />
+            </>
+        )}>
+            if let Some(err) = &save_state.error {
+                if err.code == FORBIDDEN {
+                    <CosmoMessage message="Dir fehlt die Berechtigung um Rollen zu verwalten" message_type={CosmoMessageType::Negative} />
+                } else if err.code == CONFLICT {
+                    <CosmoMessage message="Eine Rolle mit diesem Namen gibt es bereits" message_type={CosmoMessageType::Negative} />
+                } else if *unreported_error_toggle {
+                    <CosmoMessage message="Die Rolle konnte leider nicht gespeichert werden" message_type={CosmoMessageType::Negative} actions={html!(<CosmoButton label="Fehler melden" on_click={report_unknown_error} />)} />
+                } else {
+                    <CosmoMessage message="Die Rolle konnte leider nicht gespeichert werden" message_type={CosmoMessageType::Negative} />
+                }
+            }
+            <CosmoInputGroup>
+                <CosmoTextBox label="Name" required={true} value={(*name_state).clone()} on_input={update_name} />
+                {for GrovePermission::iter().map(|permission| {
+                    let permissions_state = permissions_state.clone();
+
+                    html_nested!(
+                        <CosmoCheckbox label={permission.to_string()} checked={permissions_state.contains(&permission)} on_check={move |checked| {
+                            let mut permissions = (*permissions_state).clone();
+                            permissions.retain(|item| *item != permission);
+                            if checked {
+                                permissions.push(permission);
+                            }
+                            permissions_state.set(permissions);
+                        }} />
+                    )
+                })}
+            </CosmoInputGroup>
+        </CosmoModal>
+    )
+}
+
+#[function_component(RoleManagementPage)]
+pub fn role_management_page() -> Html {
+    log::debug!("Render roles page");
+    let permissions = use_atom_value::<storage::CurrentPermissions>();
+    let can_manage_roles = permissions.contains(GrovePermission::ManageRoles);
+
+    let unreported_error_toggle = use_bool_toggle(false);
+    let open_create_role_modal_toggle = use_bool_toggle(false);
+
+    let edit_role_state = use_state_eq(|| None as Option<GroveRole>);
+    let delete_role_state = use_state_eq(|| None as Option<GroveRole>);
+
+    let bamboo_error_state = use_state_eq(ApiError::default);
+
+    let roles_state = {
+        let unreported_error_toggle = unreported_error_toggle.clone();
+
+        let bamboo_error_state = bamboo_error_state.clone();
+
+        use_async(async move {
+            api::get_grove_roles().await.map_err(|err| {
+                unreported_error_toggle.set(true);
+                bamboo_error_state.set(err.clone());
+
+                err
+            })
+        })
+    };
+    let delete_role = {
+        let unreported_error_toggle = unreported_error_toggle.clone();
+
+        let bamboo_error_state = bamboo_error_state.clone();
+        let delete_role_state = delete_role_state.clone();
+
+        let roles_state = roles_state.clone();
+
+        use_async(async move {
+            if let Some(role) = (*delete_role_state).clone() {
+                delete_role_state.set(None);
+                api::delete_grove_role(role.id)
+                    .await
+                    .map(|_| {
+                        unreported_error_toggle.set(false);
+                        roles_state.run();
+                    })
+                    .map_err(|err| {
+                        unreported_error_toggle.set(true);
+                        bamboo_error_state.set(err.clone());
+
+                        err
+                    })
+            } else {
+                Ok(())
+            }
+        })
+    };
+
+    {
+        let roles_state = roles_state.clone();
+
+        use_mount(move || roles_state.run());
+    }
+
+    let open_create_role_modal =
+        use_callback(open_create_role_modal_toggle.clone(), |_, toggle| {
+            toggle.set(true)
+        });
+    let close_role_modal = use_callback(
+        (
+            open_create_role_modal_toggle.clone(),
+            edit_role_state.clone(),
+        ),
+        |_, (open_create_role_modal_toggle, edit_role_state)| {
+            open_create_role_modal_toggle.set(false);
+            edit_role_state.set(None);
+        },
+    );
+    let on_role_saved = use_callback(
+        (
+            open_create_role_modal_toggle.clone(),
+            edit_role_state.clone(),
+            roles_state.clone(),
+        ),
+        |_, (open_create_role_modal_toggle, edit_role_state, roles_state)| {
+            open_create_role_modal_toggle.set(false);
+            edit_role_state.set(None);
+            roles_state.run();
+        },
+    );
+    let report_unknown_error = use_callback(
+        (bamboo_error_state.clone(), unreported_error_toggle.clone()),
+        |_, (bamboo_error_state, unreported_error_toggle)| {
+            error::report_unknown_error(
+                "mod_area_role",
+                "role_management_page",
+                bamboo_error_state.deref().clone(),
+            );
+            unreported_error_toggle.set(false);
+        },
+    );
+    let on_decline_delete = use_callback(delete_role_state.clone(), |_, state| state.set(None));
+    let on_confirm_delete = use_callback(delete_role.clone(), |_, state| state.run());
+
+    html!(
+        <>
+            <CosmoTitle title="Rollen" />
+            <CosmoParagraph>{"Mit Rollen kannst du Pandas einzelne Berechtigungen geben, ohne sie zum Mod zu machen. Mods haben immer alle Berechtigungen. Welcher Panda welche Rolle hat, legst du in der Benutzerverwaltung fest."}</CosmoParagraph>
+            if can_manage_roles {
+                <CosmoToolbar>
+                    <CosmoToolbarGroup>
+                        <CosmoButton label="Rolle erstellen" on_click={open_create_role_modal} />
+                    </CosmoToolbarGroup>
+                </CosmoToolbar>
+            }
+            if delete_role.error.is_some() {
+                if *unreported_error_toggle {
+                    <CosmoMessage header="Fehler beim Löschen" message="Die Rolle konnte leider nicht gelöscht werden" message_type={CosmoMessageType::Negative} actions={html!(<CosmoButton label="Fehler melden" on_click={report_unknown_error.clone()} />)} />
+                } else {
+                    <CosmoMessage header="Fehler beim Löschen" message="Die Rolle konnte leider nicht gelöscht werden" message_type={CosmoMessageType::Negative} />
+                }
+            }
+            if roles_state.loading {
+                <CosmoProgressRing />
+            } else if roles_state.error.is_some() {
+                if *unreported_error_toggle {
+                    <CosmoMessage header="Fehler beim Laden" message="Die Rollen konnten nicht geladen werden" message_type={CosmoMessageType::Negative} actions={html!(<CosmoButton label="Fehler melden" on_click={report_unknown_error} />)} />
+                } else {
+                    <CosmoMessage header="Fehler beim Laden" message="Die Rollen konnten nicht geladen werden" message_type={CosmoMessageType::Negative} />
+                }
+            } else if let Some(roles) = &roles_state.data {
+                if roles.is_empty() {
+                    <CosmoParagraph>{"In deinem Hain gibt es noch keine Rollen"}</CosmoParagraph>
+                } else {
+                    <CosmoKeyValueList>
+                        {for roles.iter().map(|role| {
+                            let edit_role_state = edit_role_state.clone();
+                            let delete_role_state = delete_role_state.clone();
+                            let role_to_edit = role.clone();
+                            let role_to_delete = role.clone();
+
+                            html!(
+                                <CosmoKeyValueListItem title={role.name.clone()}>
+                                    {format_permissions(&role.permissions)}
+                                    if can_manage_roles {
+                                        <CosmoToolbarGroup>
+                                            <CosmoButton label="Bearbeiten" on_click={move |_| edit_role_state.set(Some(role_to_edit.clone()))} />
+                                            <CosmoButton label="Löschen" on_click={move |_| delete_role_state.set(Some(role_to_delete.clone()))} />
+                                        </CosmoToolbarGroup>
+                                    }
+                                </CosmoKeyValueListItem>
+                            )
+                        })}
+                    </CosmoKeyValueList>
+                }
+            }
+            if *open_create_role_modal_toggle {
+                <RoleModal role={None as Option<GroveRole>} on_saved={on_role_saved.clone()} on_close={close_role_modal.clone()} />
+            }
+            if let Some(role) = (*edit_role_state).clone() {
+                <RoleModal role={Some(role)} on_saved={on_role_saved} on_close={close_role_modal} />
+            }
+            if let Some(role) = (*delete_role_state).clone() {
+                <CosmoConfirm confirm_type={CosmoModalType::Warning} message={format!("Soll die Rolle {} wirklich gelöscht werden? Die Pandas mit dieser Rolle verlieren ihre Berechtigungen.", role.name)} title="Rolle löschen" on_decline={on_decline_delete} on_confirm={on_confirm_delete} confirm_label="Rolle löschen" decline_label="Rolle behalten" />
+            }
+        </>
+    )
+}
@@ -8,7 +8,7 @@ use yew_hooks::use_mount;
 use yew_hooks::{use_async, use_bool_toggle, use_unmount};
 
 use bamboo_common::core::entities::*;
-use bamboo_common::frontend::api::{ApiError, CONFLICT, FORBIDDEN, NOT_FOUND};
+use bamboo_common::frontend::api::{ApiError, BAD_REQUEST, CONFLICT, FORBIDDEN, NOT_FOUND};
 use bamboo_pandas_frontend_base::{error, storage};
 
 use crate::api;
@@ -20,6 +20,7 @@ enum UserConfirmActions {
     DisableTotp,
     Delete,
     ChangePassword,
+    ClearLockout,
     Closed,
 }
 
@@ -32,6 +33,8 @@ fn create_user_modal(on_saved: &Callback<WebUser>, on_close: &Callback<()>) -> H
     let display_name_state = use_state_eq(|| AttrValue::from(""));
     let discord_name_state = use_state_eq(|| AttrValue::from(""));
 
+    let permissions = use_atom_value::<storage::CurrentPermissions>();
+
     let is_mod_toggle = use_bool_toggle(false);
     let unreported_error_toggle = use_bool_toggle(false);
 
@@ -74,9 +77,11 @@ fn create_user_modal(on_saved: &Callback<WebUser>, on_close: &Callback<()>) -> H
             ))
             .await
             .map(|data| {
-                log::debug!("User was created successfully");
+                log::debug!("User was created or invited successfully");
                 unreported_error_toggle.set(false);
-                on_saved.emit(data.clone());
+                if let Some(user) = data.clone() {
+                    on_saved.emit(user);
+                }
 
                 data
             })
@@ -123,7 +128,7 @@ fn create_user_modal(on_saved: &Callback<WebUser>, on_close: &Callback<()>) -> H
             <>
                 if let Some(err) = &save_state.error {
                     if err.code == FORBIDDEN {
-                        <CosmoMessage message="Du musst Mod sein um andere Pandas hinzuzufügen" message_type={CosmoMessageType::Negative} />
+                        <CosmoMessage message="Dir fehlt die Berechtigung um andere Pandas hinzuzufügen" message_type={CosmoMessageType::Negative} />
                     } else if err.code == CONFLICT {
                         <CosmoMessage message="Ein Panda mit dieser Emailadresse oder Namen ist bereits in Bambushain registriert" message_type={CosmoMessageType::Negative} />
                     } else if *unreported_error_toggle {
@@ -131,15 +136,25 @@ fn create_user_modal(on_saved: &Callback<WebUser>, on_close: &Callback<()>) -> H
                     } else {
                         <CosmoMessage message="Der Panda konnte leider nicht hinzugefügt werden" message_type={CosmoMessageType::Negative} />
                     }
+                } else if let Some(None) = &save_state.data {
+                    <CosmoMessage message_type={CosmoMessageType::Positive} header="Einladung verschickt" message="Der Panda hat bereits einen Account und wurde per Mail eingeladen, sobald er die Einladung annimmt ist er im Hain" />
                 } else {
-                    <CosmoMessage message_type={CosmoMessageType::Information} header="Füge einen neuen Panda hinzu" message="Das Passwort wird dem Panda per Mail geschickt" />
+                    <CosmoMessage message_type={CosmoMessageType::Information} header="Füge einen neuen Panda hinzu" message="Das Passwort wird dem Panda per Mail geschickt, Pandas mit einem Account bekommen stattdessen eine Einladung" />
+                }
+                if permissions.contains(GrovePermission::ManageRoles) {
+                    <CosmoInputGroup>
+                        <CosmoTextBox label="Email" value={(*email_state).clone()} on_input={update_email} required={true} />
+                        <CosmoTextBox label="Name" value={(*display_name_state).clone()} on_input={update_display_name} required={true} />
+                        <CosmoTextBox label="Discord Name (optional)" value={(*discord_name_state).clone()} on_input={update_discord_name} />
+                        <CosmoSwitch label="Moderator" on_check={update_is_mod} checked={*is_mod_toggle} />
+                    </CosmoInputGroup>
+                } else {
+                    <CosmoInputGroup>
+                        <CosmoTextBox label="Email" value={(*email_state).clone()} on_input={update_email} required={true} />
+                        <CosmoTextBox label="Name" value={(*display_name_state).clone()} on_input={update_display_name} required={true} />
+                        <CosmoTextBox label="Discord Name (optional)" value={(*discord_name_state).clone()} on_input={update_discord_name} />
+                    </CosmoInputGroup>
                 }
-                <CosmoInputGroup>
-                    <CosmoTextBox label="Email" value={(*email_state).clone()} on_input={update_email} required={true} />
-                    <CosmoTextBox label="Name" value={(*display_name_state).clone()} on_input={update_display_name} required={true} />
-                    <CosmoTextBox label="Discord Name (optional)" value={(*discord_name_state).clone()} on_input={update_discord_name} />
-                    <CosmoSwitch label="Moderator" on_check={update_is_mod} checked={*is_mod_toggle} />
-                </CosmoInputGroup>
             </>
         </CosmoModal>
     )
@@ -223,7 +238,7 @@ fn update_profile_dialog(
             )}>
                 if let Some(err) = &save_state.error {
                     if err.code == FORBIDDEN {
-                        <CosmoMessage message="Du musst Mod sein um andere Pandas zu bearbeiten" message_type={CosmoMessageType::Negative} />
+                        <CosmoMessage message="Du kannst diesen Panda nicht bearbeiten, dir fehlt die Berechtigung oder der Panda ist auch in anderen Hainen" message_type={CosmoMessageType::Negative} />
                     } else if err.code == NOT_FOUND {
                         <CosmoMessage message="Der Panda wurde nicht gefunden" message_type={CosmoMessageType::Negative} />
                     } else if err.code == CONFLICT {
@@ -233,6 +248,8 @@ fn update_profile_dialog(
                     } else {
                         <CosmoMessage message="Der Panda konnte leider nicht geändert werden" message_type={CosmoMessageType::Negative} />
                     }
+                } else if *email_state != *email {
+                    <CosmoMessage message="Die neue Email wird erst übernommen, wenn der Panda den Link in der Email an die neue Adresse öffnet" message_type={CosmoMessageType::Information} />
                 }
                 <CosmoInputGroup>
                     <CosmoTextBox label="Email" required={true} input_type={CosmoTextBoxType::Email} on_input={update_email} value={(*email_state).clone()} />
@@ -244,15 +261,121 @@ fn update_profile_dialog(
     )
 }
 
+#[autoprops]
+#[function_component(ChangeRoleDialog)]
+fn change_role_dialog(
+    on_update: &Callback<()>,
+    on_close: &Callback<()>,
+    display_name: &AttrValue,
+    role_id: &Option<i32>,
+    roles: &Vec<GroveRole>,
+    id: i32,
+) -> Html {
+    log::debug!("Open dialog to change role");
+    let unreported_error_toggle = use_bool_toggle(false);
+
+    let bamboo_error_state = use_state_eq(ApiError::default);
+
+    let role_state = use_state_eq(|| role_id.map(|role_id| AttrValue::from(role_id.to_string())));
+
+    let save_state = {
+        let unreported_error_toggle = unreported_error_toggle.clone();
+
+        let role_state = role_state.clone();
+
+        let bamboo_error_state = bamboo_error_state.clone();
+
+        let on_update = on_update.clone();
+
+        use_async(async move {
+            api::change_user_role(
+                id,
+                (*role_state)
+                    .clone()
+                    .and_then(|role_id| role_id.parse::<i32>().ok()),
+            )
+            .await
+            .map(|_| on_update.emit(()))
+            .map_err(|err| {
+                bamboo_error_state.set(err.clone());
+                unreported_error_toggle.set(true);
+
+                err
+            })
+        })
+    };
+
+    let update_role = use_callback(role_state.clone(), |value, state| state.set(value));
+    let report_unknown_error = use_callback(
+        (bamboo_error_state.clone(), unreported_error_toggle.clone()),
+        |_, (bamboo_error_state, unreported_error_toggle)| {
+            error::report_unknown_error(
+                "bamboo_user",
+                "change_role_dialog",
+                bamboo_error_state.deref().clone(),
+            );
+            unreported_error_toggle.set(false);
+        },
+    );
+    let on_save = use_callback(save_state.clone(), |_, state| state.run());
+
+    let mut items = vec![(None, AttrValue::from("Keine Rolle"))];
+    items.extend(roles.iter().map(|role| {
+        (
+            Some(AttrValue::from(role.id.to_string())),
+            AttrValue::from(role.name.clone()),
+        )
+    }));
+
+    html!(
+        <CosmoModal title="Rolle ändern" is_form={true} on_form_submit={on_save} buttons={html!(
+            <>
+                <CosmoButton on_click={on_close.clone()} label="Abbrechen" />
+                <CosmoButton is_submit={true} label="Rolle speichern" />
+            </>
+        )}>
+            if let Some(err) = &save_state.error {
+                if err.code == FORBIDDEN {
+                    <CosmoMessage message="Du kannst die Rolle nicht ändern, weil der Panda ein Mod ist oder dir Berechtigungen des Pandas oder der Rolle fehlen" message_type={CosmoMessageType::Negative} />
+                } else if err.code == NOT_FOUND {
+                    <CosmoMessage message="Die Rolle wurde nicht gefunden" message_type={CosmoMessageType::Negative} />
+                } else if *unreported_error_toggle {
+                    <CosmoMessage message="Die Rolle konnte leider nicht geändert werden" message_type={CosmoMessageType::Negative} actions={html!(<CosmoButton label="Fehler melden" on_click={report_unknown_error} />)} />
+                } else {
+                    <CosmoMessage message="Die Rolle konnte leider nicht geändert werden" message_type={CosmoMessageType::Negative} />
+                }
+            } else {
+                <CosmoMessage message_type={CosmoMessageType::Information} message={format!("Wähle die Rolle für {display_name}, die Berechtigungen der Rolle gelten sofort")} />
+            }
+            <CosmoInputGroup>
+                <CosmoDropdown label="Rolle" on_select={update_role} value={(*role_state).clone()} items={items} />
+            </CosmoInputGroup>
+        </CosmoModal>
+    )
+}
+
 #[autoprops]
 #[function_component(UserDetails)]
-fn user_details(user: &WebUser, on_delete: &Callback<()>, on_update: &Callback<()>) -> Html {
+fn user_details(
+    user: &WebUser,
+    lockout: &Option<LoginLockout>,
+    roles: &Vec<GroveRole>,
+    on_delete: &Callback<()>,
+    on_update: &Callback<()>,
+) -> Html {
     log::debug!("Initialize table body state and callbacks");
     let confirm_state = use_state_eq(|| UserConfirmActions::Closed);
 
     let current_user = use_atom::<storage::CurrentUser>();
+    let permissions = use_atom_value::<storage::CurrentPermissions>();
+    let grove_settings_atom = use_atom_value::<storage::CurrentGroveSettings>();
+
+    let can_manage_users = permissions.contains(GrovePermission::ManageUsers);
+    let can_manage_roles = permissions.contains(GrovePermission::ManageRoles);
+    let is_self = user.id == current_user.profile.id;
 
     let profile_edit_toggle = use_bool_toggle(false);
+    let role_edit_toggle = use_bool_toggle(false);
     let unreported_error_toggle = use_bool_toggle(false);
 
     let bamboo_error_state = use_state_eq(ApiError::default);
@@ -402,6 +525,36 @@ fn user_details(user: &WebUser, on_delete: &Callback<()>, on_update: &Callback<(
         })
     };
 
+    let clear_lockout_state = {
+        let bamboo_error_state = bamboo_error_state.clone();
+
+        let unreported_error_toggle = unreported_error_toggle.clone();
+
+        let confirm_state = confirm_state.clone();
+
+        let on_update = on_update.clone();
+
+        let user_id = user.id;
+
+        use_async(async move {
+            api::clear_login_lockout(user_id)
+                .await
+                .map(|_| {
+                    unreported_error_toggle.set(false);
+                    confirm_state.set(UserConfirmActions::Closed);
+
+                    on_update.emit(())
+                })
+                .map_err(|err| {
+                    log::error!("Failed to clear lockout {err}");
+                    bamboo_error_state.set(err.clone());
+                    unreported_error_toggle.set(true);
+
+                    err
+                })
+        })
+    };
+
     let make_mod_click = use_callback(confirm_state.clone(), |_, state| {
         state.set(UserConfirmActions::MakeMod)
     });
@@ -421,12 +574,21 @@ fn user_details(user: &WebUser, on_delete: &Callback<()>, on_update: &Callback<(
     let change_password_click = use_callback(confirm_state.clone(), |_, state| {
         state.set(UserConfirmActions::ChangePassword)
     });
+    let clear_lockout_click = use_callback(confirm_state.clone(), |_, state| {
+        state.set(UserConfirmActions::ClearLockout)
+    });
     let on_decline = use_callback(confirm_state.clone(), |_, state| {
         state.set(UserConfirmActions::Closed)
     });
+    let change_role_click = use_callback(role_edit_toggle.clone(), |_, role_edit_toggle| {
+        role_edit_toggle.set(true)
+    });
     let on_update_close = use_callback(profile_edit_toggle.clone(), |_, toggle| {
         toggle.set(false);
     });
+    let on_role_close = use_callback(role_edit_toggle.clone(), |_, toggle| {
+        toggle.set(false);
+    });
     let on_confirm = use_callback(
         (
             confirm_state.clone(),
@@ -435,6 +597,7 @@ fn user_details(user: &WebUser, on_delete: &Callback<()>, on_update: &Callback<(
             remove_mod_state.clone(),
             disable_totp_state.clone(),
             change_password_state.clone(),
+            clear_lockout_state.clone(),
         ),
         |_,
          (
@@ -444,15 +607,24 @@ fn user_details(user: &WebUser, on_delete: &Callback<()>, on_update: &Callback<(
             remove_mod_state,
             disable_totp_state,
             change_password_state,
+            clear_lockout_state,
         )| match **confirm_state {
             UserConfirmActions::MakeMod => make_mod_state.run(),
             UserConfirmActions::RemoveMod => remove_mod_state.run(),
             UserConfirmActions::DisableTotp => disable_totp_state.run(),
             UserConfirmActions::Delete => delete_state.run(),
             UserConfirmActions::ChangePassword => change_password_state.run(),
+            UserConfirmActions::ClearLockout => clear_lockout_state.run(),
             UserConfirmActions::Closed => (),
         },
     );
+    let on_role_update = use_callback(
+        (on_update.clone(), role_edit_toggle.clone()),
+        |_, (on_update, role_edit_toggle)| {
+            role_edit_toggle.set(false);
+            on_update.emit(())
+        },
+    );
     let on_update = use_callback(on_update.clone(), |_, on_update| on_update.emit(()));
     let report_unknown_error = use_callback(
         (bamboo_error_state.clone(), unreported_error_toggle.clone()),
@@ -472,23 +644,29 @@ fn user_details(user: &WebUser, on_delete: &Callback<()>, on_update: &Callback<(
             <CosmoToolbar>
                 <CosmoToolbarGroup>
                     if user.is_mod {
-                        <CosmoButton enabled={user.id != current_user.profile.id} on_click={remove_mod_click} label="Modrechte entziehen" />
+                        <CosmoButton enabled={!is_self && current_user.profile.is_mod} on_click={remove_mod_click} label="Modrechte entziehen" />
                     } else {
-                        <CosmoButton enabled={user.id != current_user.profile.id} on_click={make_mod_click} label="Zum Mod machen" />
+                        <CosmoButton enabled={!is_self && current_user.profile.is_mod} on_click={make_mod_click} label="Zum Mod machen" />
                     }
-                    <CosmoButton enabled={user.id != current_user.profile.id} on_click={update_profile_click} label="Panda bearbeiten" />
+                    <CosmoButton enabled={!is_self && can_manage_roles} on_click={change_role_click} label="Rolle ändern" />
+                    <CosmoButton enabled={!is_self && can_manage_users} on_click={update_profile_click} label="Panda bearbeiten" />
                 </CosmoToolbarGroup>
                 <CosmoToolbarGroup>
-                    <CosmoButton enabled={user.id != current_user.profile.id} on_click={change_password_click} label="Passwort ändern" />
-                    <CosmoButton enabled={user.id != current_user.profile.id && user.app_totp_enabled} on_click={disable_totp_click} label="Zwei Faktor deaktivieren" />
-                    <CosmoButton enabled={user.id != current_user.profile.id} on_click={delete_click} label="Aus dem Hain werfen" />
+                    <CosmoButton enabled={!is_self && can_manage_users} on_click={change_password_click} label="Passwort ändern" />
+                    <CosmoButton enabled={!is_self && can_manage_users && user.app_totp_enabled} on_click={disable_totp_click} label="Zwei Faktor deaktivieren" />
+                    <CosmoButton enabled={!is_self && can_manage_users} on_click={delete_click} label="Aus dem Hain werfen" />
+                </CosmoToolbarGroup>
+                <CosmoToolbarGroup>
+                    <CosmoButton enabled={lockout.is_some() && can_manage_users} on_click={clear_lockout_click} label="Sperre aufheben" />
                 </CosmoToolbarGroup>
             </CosmoToolbar>
             if let Some(err) = &delete_state.error {
                 if err.code == FORBIDDEN {
-                    <CosmoMessage header="Fehler beim Rauswerfen" message="Du musst Mod sein um Pandas aus dem Hain zu werfen" message_type={CosmoMessageType::Negative} />
+                    <CosmoMessage header="Fehler beim Rauswerfen" message="Dir fehlt die Berechtigung um Pandas aus dem Hain zu werfen" message_type={CosmoMessageType::Negative} />
                 } else if err.code == CONFLICT {
                     <CosmoMessage header="Fehler beim Rauswerfen" message="Du kannst dich nicht selbst aus dem Hain werfen, wenn du gehen möchtest, benutze die Verlassen Funktion" message_type={CosmoMessageType::Negative} />
+                } else if err.code == BAD_REQUEST {
+                    <CosmoMessage header="Fehler beim Rauswerfen" message="Der Panda ist der letzte Mod im Hain und kann deshalb nicht rausgeworfen werden" message_type={CosmoMessageType::Negative} />
                 } else if *unreported_error_toggle {
                     <CosmoMessage header="Fehler beim Rauswerfen" message="Der Panda konnte nicht aus dem Hain geworfen werden" message_type={CosmoMessageType::Negative} actions={html!(<CosmoButton label="Fehler melden" on_click={report_unknown_error.clone()} />)} />
                 } else {
@@ -497,7 +675,7 @@ fn user_details(user: &WebUser, on_delete: &Callback<()>, on_update: &Callback<(
             }
             if let Some(err) = &make_mod_state.error {
                 if err.code == FORBIDDEN {
-                    <CosmoMessage header="Fehler beim Ändern des Modstatus" message="Du musst Mod sein um Mods zu ernennen" message_type={CosmoMessageType::Negative} />
+                    <CosmoMessage header="Fehler beim Ändern des Modstatus" message="Nur Mods können andere Pandas zu Mods ernennen" message_type={CosmoMessageType::Negative} />
                 } else if err.code == CONFLICT {
                     <CosmoMessage header="Fehler beim Ändern des Modstatus" message="Du kannst dich nicht selbst zum Mod machen" message_type={CosmoMessageType::Negative} />
                 } else if *unreported_error_toggle {
@@ -508,9 +686,11 @@ fn user_details(user: &WebUser, on_delete: &Callback<()>, on_update: &Callback<(
             }
             if let Some(err) = &remove_mod_state.error {
                 if err.code == FORBIDDEN {
-                    <CosmoMessage header="Fehler beim Ändern des Modstatus" message="Du musst Mod sein um Pandas die Modrechte zu entziehen" message_type={CosmoMessageType::Negative} />
+                    <CosmoMessage header="Fehler beim Ändern des Modstatus" message="Nur Mods können anderen Pandas die Modrechte entziehen" message_type={CosmoMessageType::Negative} />
                 } else if err.code == CONFLICT {
                     <CosmoMessage header="Fehler beim Ändern des Modstatus" message="Du kannst dir die Modrechte nicht entziehen" message_type={CosmoMessageType::Negative} />
+                } else if err.code == BAD_REQUEST {
+                    <CosmoMessage header="Fehler beim Ändern des Modstatus" message="Der Panda ist der letzte Mod im Hain, der Hain braucht mindestens einen Mod" message_type={CosmoMessageType::Negative} />
                 } else if *unreported_error_toggle {
                     <CosmoMessage header="Fehler beim Ändern des Modstatus" message="Dem Panda konnten die Modrechte nicht entzogen werden" message_type={CosmoMessageType::Negative} actions={html!(<CosmoButton label="Fehler melden" on_click={report_unknown_error.clone()} />)} />
                 } else {
@@ -519,7 +699,7 @@ fn user_details(user: &WebUser, on_delete: &Callback<()>, on_update: &Callback<(
             }
             if let Some(err) = &disable_totp_state.error {
                 if err.code == FORBIDDEN {
-                    <CosmoMessage header="Fehler beim Deaktivieren" message="Du musst Mod sein um die Zwei Faktor Authentifizierung von Pandas zu deaktivieren" message_type={CosmoMessageType::Negative} />
+                    <CosmoMessage header="Fehler beim Deaktivieren" message="Du kannst die Zwei Faktor Authentifizierung nicht deaktivieren, dir fehlt die Berechtigung oder der Panda ist auch in anderen Hainen" message_type={CosmoMessageType::Negative} />
                 } else if err.code == CONFLICT {
                     <CosmoMessage header="Fehler beim Deaktivieren" message="Du kannst deine eigene Zwei Faktor Authentifizierung über dein Profil deaktivieren" message_type={CosmoMessageType::Negative} />
                 } else if *unreported_error_toggle {
@@ -530,15 +710,24 @@ fn user_details(user: &WebUser, on_delete: &Callback<()>, on_update: &Callback<(
             }
             if let Some(err) = &change_password_state.error {
                 if err.code == FORBIDDEN {
-                    <CosmoMessage header="Fehler beim Zurücksetzen" message="Du musst Mod sein um Passwörter zurückzusetzen" message_type={CosmoMessageType::Negative} />
+                    <CosmoMessage header="Fehler beim Zurücksetzen" message="Du kannst das Passwort nicht zurücksetzen, dir fehlt die Berechtigung oder der Panda ist auch in anderen Hainen" message_type={CosmoMessageType::Negative} />
                 } else if err.code == CONFLICT {
                     <CosmoMessage header="Fehler beim Zurücksetzen" message="Wenn du dein Passwort ändern willst, kannst du das über Passwort ändern machen" message_type={CosmoMessageType::Negative} />
                 } else if *unreported_error_toggle {
-                    <CosmoMessage header="Fehler beim Zurücksetzen" message="Das Passwort konnte nicht zurückgesetzt werden" message_type={CosmoMessageType::Negative} actions={html!(<CosmoButton label="Fehler melden" on_click={report_unknown_error} />)} />
+                    <CosmoMessage header="Fehler beim Zurücksetzen" message="Das Passwort konnte nicht zurückgesetzt werden" message_type={CosmoMessageType::Negative} actions={html!(<CosmoButton label="Fehler melden" on_click={report_unknown_error.clone()} />)} />
                 } else {
                     <CosmoMessage header="Fehler beim Zurücksetzen" message="Das Passwort konnte nicht zurückgesetzt werden" message_type={CosmoMessageType::Negative} />
                 }
             }
+            if let Some(err) = &clear_lockout_state.error {
+                if err.code == FORBIDDEN {
+                    <CosmoMessage header="Fehler beim Aufheben der Sperre" message="Dir fehlt die Berechtigung um Sperren aufzuheben" message_type={CosmoMessageType::Negative} />
+                } else if *unreported_error_toggle {
+                    <CosmoMessage header="Fehler beim Aufheben der Sperre" message="Die Sperre konnte nicht aufgehoben werden" message_type={CosmoMessageType::Negative} actions={html!(<CosmoButton label="Fehler melden" on_click={report_unknown_error} />)} />
+                } else {
+                    <CosmoMessage header="Fehler beim Aufheben der Sperre" message="Die Sperre konnte nicht aufgehoben werden" message_type={CosmoMessageType::Negative} />
+                }
+            }
             <CosmoKeyValueList>
                 <CosmoKeyValueListItem title="Name">
                     {user.display_name.clone()}
@@ -560,6 +749,18 @@ fn user_details(user: &WebUser, on_delete: &Callback<()>, on_update: &Callback<(
                         {"Nein"}
                     }
                 </CosmoKeyValueListItem>
+                <CosmoKeyValueListItem title="Rolle">
+                    if let Some(role) = roles.iter().find(|role| Some(role.id) == user.role_id) {
+                        {role.name.clone()}
+                    } else {
+                        {"Keine Rolle"}
+                    }
+                </CosmoKeyValueListItem>
+                if let Some(locked_until) = lockout.as_ref().and_then(|lockout| lockout.locked_until) {
+                    <CosmoKeyValueListItem title="Gesperrt bis">
+                        {grove_settings_atom.to_grove_time(&locked_until).format("%d.%m.%Y %H:%M").to_string()}
+                    </CosmoKeyValueListItem>
+                }
             </CosmoKeyValueList>
             {match (*confirm_state).clone() {
                 UserConfirmActions::MakeMod => html!(
@@ -571,6 +772,9 @@ fn user_details(user: &WebUser, on_delete: &Callback<()>, on_update: &Callback<(
                 UserConfirmActions::Delete => html!(
                     <CosmoConfirm confirm_type={CosmoModalType::Warning} message={format!("Soll der Panda {} wirklich aus dem Hain geworfen werden?", user.display_name.clone())} title="Panda rauswerfen" on_decline={on_decline} on_confirm={on_confirm} confirm_label="Panda rauswerfen" decline_label="Panda behalten" />
                 ),
+                UserConfirmActions::ClearLockout => html!(
+                    <CosmoConfirm message={format!("Soll die Anmeldesperre von {} aufgehoben werden?", user.display_name.clone())} title="Sperre aufheben" on_decline={on_decline} on_confirm={on_confirm} confirm_label="Sperre aufheben" decline_label="Abbrechen" />
+                ),
                 UserConfirmActions::DisableTotp => html!(
                     <CosmoConfirm confirm_type={CosmoModalType::Warning} message={format!("Soll die Zwei Faktor Authentifizierung von {} wirklich deaktiviert werden?", user.display_name.clone())} title="Zwei Faktor Authentifizierung deaktivieren" on_decline={on_decline} on_confirm={on_confirm} confirm_label="Deaktivieren" decline_label="Nicht deaktivieren" />
                 ),
@@ -591,6 +795,9 @@ fn user_details(user: &WebUser, on_delete: &Callback<()>, on_update: &Callback<(
             if *profile_edit_toggle {
                 <UpdateProfileDialog on_close={on_update_close} on_update={on_update} id={user.id} email={user.email.clone()} display_name={user.display_name.clone()} discord_name={user.discord_name.clone()} />
             }
+            if *role_edit_toggle {
+                <ChangeRoleDialog on_close={on_role_close} on_update={on_role_update} id={user.id} display_name={user.display_name.clone()} role_id={user.role_id} roles={roles.clone()} />
+            }
         </>
     )
 }
@@ -599,7 +806,7 @@ fn user_details(user: &WebUser, on_delete: &Callback<()>, on_update: &Callback<(
 pub fn user_management_page() -> Html {
     log::debug!("Render users page");
     log::debug!("Initialize state and callbacks");
-    let current_user = use_atom::<storage::CurrentUser>();
+    let permissions = use_atom_value::<storage::CurrentPermissions>();
 
     let open_create_user_modal_toggle = use_bool_toggle(false);
     let unreported_error_toggle = use_bool_toggle(false);
@@ -625,6 +832,9 @@ pub fn user_management_page() -> Html {
         })
     };
 
+    let lockouts_state = use_async(async move { api::get_login_lockouts().await });
+    let roles_state = use_async(async move { api::get_grove_roles().await });
+
     let open_create_user_modal_click = use_callback(
         open_create_user_modal_toggle.clone(),
         |_, open_create_user_modal_toggle| open_create_user_modal_toggle.set(true),
@@ -647,9 +857,13 @@ pub fn user_management_page() -> Html {
             selected_state.set(0);
         },
     );
-    let on_update = use_callback(users_state.clone(), |_, users_state| {
-        users_state.run();
-    });
+    let on_update = use_callback(
+        (users_state.clone(), lockouts_state.clone()),
+        |_, (users_state, lockouts_state)| {
+            users_state.run();
+            lockouts_state.run();
+        },
+    );
     let on_create_saved = use_callback(
         (
             selected_user_state.clone(),
@@ -668,9 +882,13 @@ pub fn user_management_page() -> Html {
 
     {
         let users_state = users_state.clone();
+        let lockouts_state = lockouts_state.clone();
+        let roles_state = roles_state.clone();
 
         use_mount(move || {
             users_state.run();
+            lockouts_state.run();
+            roles_state.run();
         });
     }
 
@@ -689,6 +907,8 @@ pub fn user_management_page() -> Html {
             )
         }
     } else if let Some(data) = &users_state.data {
+        let lockouts = lockouts_state.data.clone().unwrap_or_default();
+        let roles = roles_state.data.clone().unwrap_or_default();
         let select_user = {
             let data = data.clone();
             let selected_user_state = selected_user_state.clone();
@@ -700,10 +920,10 @@ pub fn user_management_page() -> Html {
 
         html!(
             <>
-                <CosmoSideList on_select_item={select_user} selected_index={data.iter().position(|u| u.id == *selected_user_state).unwrap_or(0)} has_add_button={current_user.profile.is_mod} add_button_on_click={open_create_user_modal_click} add_button_label="Panda hinzufügen">
+                <CosmoSideList on_select_item={select_user} selected_index={data.iter().position(|u| u.id == *selected_user_state).unwrap_or(0)} has_add_button={permissions.contains(GrovePermission::ManageUsers)} add_button_on_click={open_create_user_modal_click} add_button_label="Panda hinzufügen">
                     {for data.iter().map(|user| {
                         CosmoSideListItem::from_label_and_children(user.display_name.clone().into(), html!(
-                            <UserDetails on_delete={on_delete.clone()} on_update={on_update.clone()} user={user.clone()} />
+                            <UserDetails on_delete={on_delete.clone()} on_update={on_update.clone()} user={user.clone()} roles={roles.clone()} lockout={lockouts.iter().find(|lockout| lockout.user_id == Some(user.id)).cloned()} />
                         ))
                     })}
                 </CosmoSideList>
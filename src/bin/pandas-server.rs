@@ -1,3 +1,9 @@
 fn main() -> std::io::Result<()> {
-    bamboo_pandas::backend::start_server()
+    match std::env::args().nth(1).as_deref() {
+        Some("rotate-encryption-key") => bamboo_pandas::backend::rotate_encryption_key(),
+        Some(command) => Err(std::io::Error::other(format!(
+            "Unknown command {command}, the only supported command is rotate-encryption-key"
+        ))),
+        None => bamboo_pandas::backend::start_server(),
+    }
 }